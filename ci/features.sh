@@ -0,0 +1,6 @@
+set -ex
+
+cd src/elastic
+cargo test --features simd-json
+cargo test --features preserve_order
+cargo test --features test-support
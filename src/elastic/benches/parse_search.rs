@@ -0,0 +1,118 @@
+/*!
+Benchmarks for parsing large search responses.
+
+Run with and without the `simd-json` feature to compare parsers:
+
+```text
+cargo bench -p elastic --bench parse_search
+cargo bench -p elastic --bench parse_search --features simd-json
+```
+
+The `serde_json` timings are a baseline that doesn't change with the feature.
+The `parse` timings use whichever parser the crate was built with.
+*/
+
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use elastic::{
+    client::responses::SearchResponse,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+use serde_json::{
+    json,
+    Value,
+};
+
+const ITERATIONS: u32 = 20;
+
+fn search_response(hits: usize) -> Vec<u8> {
+    let hits: Vec<Value> = (0..hits)
+        .map(|i| {
+            json!({
+                "_index": "accounts",
+                "_type": "_doc",
+                "_id": i.to_string(),
+                "_score": 1.0,
+                "_source": {
+                    "account_number": i,
+                    "balance": i * 31 % 50_000,
+                    "firstname": "Virginia",
+                    "lastname": "Ayala",
+                    "age": i % 80,
+                    "active": i % 2 == 0,
+                    "address": "171 Putnam Avenue",
+                    "email": format!("account{}@example.com", i),
+                    "city": "Nicholson",
+                    "tags": ["gold", "verified", "newsletter"],
+                    "location": { "lat": 40.7, "lon": -74.0 }
+                }
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&json!({
+        "took": 12,
+        "timed_out": false,
+        "_shards": { "total": 5, "successful": 5, "skipped": 0, "failed": 0 },
+        "hits": {
+            "total": { "value": hits.len(), "relation": "eq" },
+            "max_score": 1.0,
+            "hits": hits
+        }
+    }))
+    .unwrap()
+}
+
+fn bench(name: &str, body: &[u8], f: impl Fn(&[u8]) -> usize) {
+    // Warm up
+    f(body);
+
+    let mut elapsed = Duration::default();
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        let hits = f(body);
+        elapsed += start.elapsed();
+
+        assert!(hits > 0);
+    }
+
+    let per_iter = elapsed / ITERATIONS;
+    let throughput = body.len() as f64 / per_iter.as_secs_f64() / (1024.0 * 1024.0);
+
+    println!(
+        "{:<24} {:>10.2?}/iter {:>8.1} MiB/s",
+        name, per_iter, throughput
+    );
+}
+
+fn main() {
+    let parser = if cfg!(feature = "simd-json") {
+        "simd-json"
+    } else {
+        "serde_json"
+    };
+
+    for &hits in &[100, 1_000, 10_000] {
+        let body = search_response(hits);
+
+        println!("{} hits ({} KiB):", hits, body.len() / 1024);
+
+        bench("serde_json", &body, |body| {
+            let res: SearchResponse<Value> = serde_json::from_slice(body).unwrap();
+            res.hits().count()
+        });
+
+        bench(&format!("parse ({})", parser), &body, |body| {
+            let res = parse::<SearchResponse<Value>>()
+                .from_slice(StatusCode::OK, body)
+                .unwrap();
+            res.hits().count()
+        });
+    }
+}
@@ -0,0 +1,350 @@
+/*!
+Hooks for changing how documents are serialised before they're sent to Elasticsearch.
+*/
+
+use serde::ser::{
+    Serialize,
+    SerializeMap,
+    Serializer,
+};
+use serde_json::{
+    value::RawValue,
+    Value,
+};
+use std::{
+    fmt,
+    io::{
+        self,
+        Write,
+    },
+};
+
+/**
+A serialiser for the documents in index and bulk requests.
+
+By default, documents are written straight to the request body with `serde_json`.
+Set a `DocumentSerializer` on the client builder to use a different JSON serialiser or to normalise documents before they're sent.
+The serialiser is used for the document in an [index request][index] and every document in a [bulk request][bulk].
+For bulk update operations only the partial document is given to the serialiser, not the `doc` object around it.
+
+A document is given to the serialiser as a [`Document`][Document] that can be written directly or converted into a `serde_json::Value`.
+The serialiser must write it as a single line of JSON, because bulk requests are newline delimited.
+A document that's written over more than one line is rejected.
+
+# Examples
+
+Write documents with `serde_json`, dropping any `null` fields:
+
+```no_run
+# use std::io::{self, Write};
+# use elastic::prelude::*;
+# use elastic::client::{Document, DocumentSerializer};
+# use serde_json::Value;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+struct SkipNulls;
+
+impl DocumentSerializer for SkipNulls {
+    fn serialize(&self, doc: Document, writer: &mut dyn Write) -> io::Result<()> {
+        let doc = match doc.to_value()? {
+            Value::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .filter(|(_, value)| !value.is_null())
+                    .collect(),
+            ),
+            doc => doc,
+        };
+
+        serde_json::to_writer(writer, &doc)?;
+
+        Ok(())
+    }
+}
+
+let client = SyncClientBuilder::new()
+    .document_serializer(SkipNulls)
+    .build()?;
+# Ok(())
+# }
+```
+
+[index]: requests/document_index/index.html
+[bulk]: requests/bulk/index.html
+[Document]: struct.Document.html
+*/
+pub trait DocumentSerializer: Send + Sync {
+    /** Write a document to the request body as a single line of JSON. */
+    fn serialize(&self, doc: Document, writer: &mut dyn Write) -> io::Result<()>;
+}
+
+/**
+A document that's being written by a `DocumentSerializer`.
+
+Writing the document with `write_json` doesn't build an intermediate `serde_json::Value`.
+Converting it with `to_value` is slower, but lets the serialiser change the document before writing it.
+*/
+#[derive(Clone, Copy)]
+pub struct Document<'a> {
+    inner: DocumentInner<'a>,
+}
+
+#[derive(Clone, Copy)]
+enum DocumentInner<'a> {
+    Typed(&'a dyn ErasedDocument),
+    Raw(&'a RawValue),
+}
+
+impl<'a> Document<'a> {
+    pub(crate) fn new<TDocument>(doc: &'a TDocument) -> Self
+    where
+        TDocument: Serialize,
+    {
+        Document {
+            inner: DocumentInner::Typed(doc),
+        }
+    }
+
+    pub(crate) fn raw(doc: &'a RawValue) -> Self {
+        Document {
+            inner: DocumentInner::Raw(doc),
+        }
+    }
+
+    /** Write the document to `writer` with `serde_json`. */
+    pub fn write_json(&self, writer: &mut dyn Write) -> io::Result<()> {
+        match self.inner {
+            DocumentInner::Typed(doc) => Ok(doc.write_json(writer)?),
+            DocumentInner::Raw(doc) => writer.write_all(doc.get().as_bytes()),
+        }
+    }
+
+    /** Write the document to `writer` with `simd-json`. */
+    #[cfg(feature = "simd-json")]
+    pub fn write_simd_json(&self, writer: &mut dyn Write) -> io::Result<()> {
+        match self.inner {
+            DocumentInner::Typed(doc) => Ok(doc.write_simd_json(writer)?),
+            DocumentInner::Raw(doc) => writer.write_all(doc.get().as_bytes()),
+        }
+    }
+
+    /** Convert the document into a `serde_json::Value`. */
+    pub fn to_value(&self) -> io::Result<Value> {
+        match self.inner {
+            DocumentInner::Typed(doc) => Ok(doc.to_value()?),
+            DocumentInner::Raw(doc) => Ok(serde_json::from_str(doc.get())?),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for Document<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            DocumentInner::Typed(_) => f.debug_struct("Document").finish(),
+            DocumentInner::Raw(doc) => f.debug_tuple("Document").field(&doc).finish(),
+        }
+    }
+}
+
+/** An object-safe version of `Serialize` for the serialisers a `Document` can be written with. */
+trait ErasedDocument {
+    fn write_json(&self, writer: &mut dyn Write) -> serde_json::Result<()>;
+
+    #[cfg(feature = "simd-json")]
+    fn write_simd_json(&self, writer: &mut dyn Write) -> simd_json::Result<()>;
+
+    fn to_value(&self) -> serde_json::Result<Value>;
+}
+
+impl<TDocument> ErasedDocument for TDocument
+where
+    TDocument: Serialize,
+{
+    fn write_json(&self, writer: &mut dyn Write) -> serde_json::Result<()> {
+        serde_json::to_writer(writer, self)
+    }
+
+    #[cfg(feature = "simd-json")]
+    fn write_simd_json(&self, writer: &mut dyn Write) -> simd_json::Result<()> {
+        simd_json::serde::to_writer(writer, self)
+    }
+
+    fn to_value(&self) -> serde_json::Result<Value> {
+        serde_json::to_value(self)
+    }
+}
+
+/**
+A serialiser that writes the fields of every object in a document sorted by key.
+
+Documents with the same fields always serialise to the same bytes, regardless of the order the fields were added in.
+That's useful for comparing request bodies or hashing documents, especially when the `preserve_order` feature is enabled.
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SortedKeysSerializer;
+
+impl DocumentSerializer for SortedKeysSerializer {
+    fn serialize(&self, doc: Document, writer: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer(writer, &SortedKeys(&doc.to_value()?))?;
+
+        Ok(())
+    }
+}
+
+struct SortedKeys<'a>(&'a Value);
+
+impl<'a> Serialize for SortedKeys<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self.0 {
+            Value::Object(ref fields) => {
+                let mut fields: Vec<_> = fields.iter().collect();
+                fields.sort_by_key(|(key, _)| *key);
+
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (key, value) in fields {
+                    map.serialize_entry(key, &SortedKeys(value))?;
+                }
+
+                map.end()
+            }
+            Value::Array(ref values) => serializer.collect_seq(values.iter().map(SortedKeys)),
+            ref value => value.serialize(serializer),
+        }
+    }
+}
+
+/** Serialise a document using the given serialiser, or `serde_json` if there isn't one. */
+pub(crate) fn to_vec<TDocument>(
+    doc: &TDocument,
+    serializer: Option<&dyn DocumentSerializer>,
+) -> io::Result<Vec<u8>>
+where
+    TDocument: Serialize,
+{
+    match serializer {
+        Some(serializer) => write_line(Document::new(doc), serializer),
+        None => Ok(serde_json::to_vec(doc)?),
+    }
+}
+
+/**
+Serialise a document using the given serialiser into a raw JSON value.
+
+The raw value is written unchanged when it's serialised again, so it can be embedded in a larger body.
+*/
+pub(crate) fn to_raw_value(
+    doc: Document,
+    serializer: &dyn DocumentSerializer,
+) -> io::Result<Box<RawValue>> {
+    let body = write_line(doc, serializer)?;
+    let body =
+        String::from_utf8(body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(RawValue::from_string(body)?)
+}
+
+fn write_line(doc: Document, serializer: &dyn DocumentSerializer) -> io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    serializer.serialize(doc, &mut body)?;
+
+    if body.contains(&b'\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the document serializer wrote a document over more than one line",
+        ));
+    }
+
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_vec_default() {
+        let body = to_vec(&json!({ "b": 1, "a": 2 }), None).unwrap();
+
+        assert_eq!(
+            serde_json::to_vec(&json!({ "b": 1, "a": 2 })).unwrap(),
+            body
+        );
+    }
+
+    #[test]
+    fn sorted_keys() {
+        let mut doc = serde_json::Map::new();
+        doc.insert("c".to_owned(), json!([{ "z": 1, "y": 2 }]));
+        doc.insert("a".to_owned(), json!({ "x": null, "w": "text" }));
+        doc.insert("b".to_owned(), json!(1.5));
+
+        let body = to_vec(&doc, Some(&SortedKeysSerializer)).unwrap();
+
+        assert_eq!(
+            r#"{"a":{"w":"text","x":null},"b":1.5,"c":[{"y":2,"z":1}]}"#,
+            String::from_utf8(body).unwrap()
+        );
+    }
+
+    #[test]
+    fn raw_value_is_written_unchanged() {
+        struct Spaced;
+
+        impl DocumentSerializer for Spaced {
+            fn serialize(&self, doc: Document, writer: &mut dyn Write) -> io::Result<()> {
+                write!(writer, "{{ \"doc\": ")?;
+                doc.write_json(writer)?;
+                write!(writer, " }}")
+            }
+        }
+
+        let raw = to_raw_value(Document::new(&json!({ "a": 1 })), &Spaced).unwrap();
+
+        assert_eq!(
+            r#"{ "doc": {"a":1} }"#,
+            serde_json::to_string(&raw).unwrap()
+        );
+    }
+
+    #[test]
+    fn raw_value_invalid_json() {
+        struct Invalid;
+
+        impl DocumentSerializer for Invalid {
+            fn serialize(&self, _: Document, writer: &mut dyn Write) -> io::Result<()> {
+                write!(writer, "{{")
+            }
+        }
+
+        assert!(to_raw_value(Document::new(&json!({ "a": 1 })), &Invalid).is_err());
+    }
+
+    #[test]
+    fn raw_value_over_many_lines() {
+        struct Pretty;
+
+        impl DocumentSerializer for Pretty {
+            fn serialize(&self, doc: Document, writer: &mut dyn Write) -> io::Result<()> {
+                serde_json::to_writer_pretty(writer, &doc.to_value()?)?;
+
+                Ok(())
+            }
+        }
+
+        let err = to_raw_value(Document::new(&json!({ "a": 1 })), &Pretty).unwrap_err();
+
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert!(to_vec(&json!({ "a": 1 }), Some(&Pretty)).is_err());
+    }
+
+    #[test]
+    fn raw_document() {
+        let raw = RawValue::from_string(r#"{"b":1,"a":2}"#.to_owned()).unwrap();
+
+        let body = write_line(Document::raw(&raw), &SortedKeysSerializer).unwrap();
+
+        assert_eq!(r#"{"a":2,"b":1}"#, String::from_utf8(body).unwrap());
+    }
+}
@@ -0,0 +1,1319 @@
+/*!
+Changing the settings of an index that can only be changed while it's closed.
+*/
+
+use futures::{
+    future::{
+        self,
+        Either,
+    },
+    Future,
+    IntoFuture,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use std::{
+    collections::BTreeMap,
+    time::Duration,
+};
+
+use crate::{
+    client::{
+        responses::{
+            ClusterHealthResponse,
+            ClusterHealthStatus,
+            CommandResponse,
+        },
+        Client,
+        IndexClient,
+    },
+    endpoints::{
+        CatIndicesRequest,
+        IndicesGetSettingsRequest,
+        IndicesPutSettingsRequest,
+    },
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        receiver::IsOkOnSuccess,
+        sender::{
+            AsyncSender,
+            Sender,
+            SyncSender,
+        },
+    },
+    params::Index,
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/**
+A change to the settings of an index that closes the index, applies the settings, and reopens it.
+
+Some index settings, like analyzers, can't be changed while an index is open.
+`IndexMaintenance` closes the index, updates its settings, reopens it, and then waits for the health of the index to return to green.
+If the settings can't be applied, or the index doesn't become healthy in time, the previous values of the settings are restored and the index is reopened before the error is returned.
+Settings that weren't set on the index before are restored to their defaults.
+When the index is an alias, the previous values are kept for each index it resolves to and restored separately.
+
+The state of each index is read before anything is changed.
+Indices that are already closed are left closed: only the indices that were open are closed and reopened.
+If every index is already closed then the settings are applied without opening them, and the health of the index is returned without waiting for it.
+
+The index must be a single index or alias.
+Wildcards, `_all` and comma-separated lists of indices are rejected so a typo can't close more indices than expected.
+Sending fails without changing anything if there are no settings to apply.
+
+Call [`Client.index.maintenance`][Client.index.maintenance] to get an `IndexMaintenance`.
+
+The index can't be searched or written to while it's closed.
+Freezing an index isn't supported, because the freeze API isn't one of the endpoints available in `elastic`.
+
+# Examples
+
+Add an analyzer to an existing index:
+
+```no_run
+# #[macro_use] extern crate serde_json;
+# use elastic::prelude::*;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+# let client = SyncClientBuilder::new().build()?;
+client.index("myindex")
+      .maintenance()
+      .settings(json!({
+          "analysis": {
+              "analyzer": {
+                  "my_analyzer": {
+                      "type": "custom",
+                      "tokenizer": "standard",
+                      "filter": ["lowercase"]
+                  }
+              }
+          }
+      }))
+      .send()?;
+# Ok(())
+# }
+```
+
+[Client.index.maintenance]: struct.IndexClient.html#method.maintenance
+*/
+#[derive(Clone)]
+pub struct IndexMaintenance<TSender> {
+    client: Client<TSender>,
+    index: Index<'static>,
+    settings: Map<String, Value>,
+    invalid_settings: Option<String>,
+    wait_for_status: ClusterHealthStatus,
+    timeout: Duration,
+}
+
+impl<TSender> IndexClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Change settings that can only be changed while the index is closed.
+
+    Nothing is sent to Elasticsearch until `send` is called.
+    See [`IndexMaintenance`][IndexMaintenance] for more details.
+
+    [IndexMaintenance]: struct.IndexMaintenance.html
+    */
+    pub fn maintenance(self) -> IndexMaintenance<TSender> {
+        IndexMaintenance {
+            client: self.inner,
+            index: self.index,
+            settings: Map::new(),
+            invalid_settings: None,
+            wait_for_status: ClusterHealthStatus::Green,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl<TSender> IndexMaintenance<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Set the index settings to apply while the index is closed.
+
+    Settings can be nested objects or use dotted names, with or without the `index.` prefix.
+    Calling `settings` multiple times merges the settings together.
+    Sending fails without changing the index if the settings aren't an object, or contain an empty object.
+    */
+    pub fn settings(mut self, settings: Value) -> Self {
+        let flattened = match settings {
+            Value::Object(_) => flatten_settings("", settings, &mut self.settings),
+            settings => Err(format!(
+                "index settings must be an object, but they were '{}'",
+                settings
+            )),
+        };
+
+        if let Err(invalid) = flattened {
+            self.invalid_settings.get_or_insert(invalid);
+        }
+
+        self
+    }
+
+    /**
+    Set the health the index must reach after being reopened.
+
+    If this isn't specified then the index must become green.
+    */
+    pub fn wait_for_status(mut self, status: ClusterHealthStatus) -> Self {
+        self.wait_for_status = status;
+        self
+    }
+
+    /**
+    Set how long to wait for the index to become healthy after being reopened.
+
+    If this isn't specified then the timeout is 30 seconds.
+    */
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    // Only change a single index or alias, and fail before sending anything if there's nothing to change
+    fn check(&self) -> Result<(), Error> {
+        self.index.validate().map_err(error::request)?;
+
+        let expression = if &self.index[..] == "_all" {
+            Some("`_all`")
+        } else if self.index.contains('*') {
+            Some("a wildcard")
+        } else if self.index.contains(',') {
+            Some("a list of indices")
+        } else {
+            None
+        };
+
+        if let Some(expression) = expression {
+            return Err(error::request(error::message(format!(
+                "index maintenance needs a single index or alias, but '{}' is {}",
+                self.index, expression
+            ))));
+        }
+
+        if let Some(ref invalid) = self.invalid_settings {
+            return Err(error::request(error::message(format!(
+                "{} for index '{}'",
+                invalid, self.index
+            ))));
+        }
+
+        if self.settings.is_empty() {
+            return Err(error::request(error::message(format!(
+                "there are no settings to change for index '{}'",
+                self.index
+            ))));
+        }
+
+        Ok(())
+    }
+
+    fn index_states_request(&self) -> CatIndicesRequest<'static> {
+        CatIndicesRequest::for_index(self.index.clone())
+    }
+
+    fn get_settings_request(&self) -> IndicesGetSettingsRequest<'static> {
+        IndicesGetSettingsRequest::for_index(self.index.clone())
+    }
+
+    fn put_settings_request(
+        &self,
+        index: Index<'static>,
+        settings: &Map<String, Value>,
+    ) -> IndicesPutSettingsRequest<'static, String> {
+        IndicesPutSettingsRequest::for_index(index, Value::from(settings.clone()).to_string())
+    }
+
+    // The current values of the settings that are about to be changed for each index
+    // Settings that aren't set yet are restored to their defaults
+    // Settings without a default, like custom analyzers, are removed by setting them to `null`
+    fn previous_settings(&self, current: IndexSettings) -> PreviousSettings {
+        current
+            .0
+            .into_iter()
+            .map(|(index, current)| {
+                let IndexSettingsInner {
+                    settings: mut current,
+                    mut defaults,
+                } = current;
+
+                let previous = self
+                    .settings
+                    .keys()
+                    .map(|key| {
+                        let previous = current
+                            .remove(key)
+                            .or_else(|| defaults.remove(key))
+                            .unwrap_or(Value::Null);
+
+                        (key.clone(), previous)
+                    })
+                    .collect();
+
+                (index, previous)
+            })
+            .collect()
+    }
+
+    fn check_health(&self, health: ClusterHealthResponse) -> Result<ClusterHealthResponse, Error> {
+        if health.timed_out() || health.status() < self.wait_for_status {
+            return Err(error::request(error::message(format!(
+                "index '{}' was {:?} instead of {:?} after {:?}",
+                self.index,
+                health.status(),
+                self.wait_for_status,
+                self.timeout
+            ))));
+        }
+
+        Ok(health)
+    }
+
+    fn rolled_back(&self, err: Error, rollback: Result<(), Error>) -> Error {
+        if let Err(rollback_err) = rollback {
+            warn!(
+                "Elasticsearch Index Maintenance: failed to roll back settings for index '{}': '{:?}'",
+                self.index, rollback_err
+            );
+        }
+
+        err
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexMaintenance<SyncSender> {
+    /**
+    Close the index, apply the settings, and reopen it.
+
+    This will block the current thread until the index is healthy again or the changes have been rolled back.
+    Returns the health of the index after it's been reopened.
+
+    # Examples
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let health = client.index("myindex")
+                       .maintenance()
+                       .settings(json!({ "index.codec": "best_compression" }))
+                       .timeout(Duration::from_secs(60))
+                       .send()?;
+
+    println!("status: {:?}", health.status());
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn send(self) -> Result<ClusterHealthResponse, Error> {
+        self.check()?;
+
+        let open = self
+            .client
+            .request(self.index_states_request())
+            .params_fluent(|params| {
+                params
+                    .url_param("format", "json")
+                    .url_param("h", "index,status")
+            })
+            .send()?
+            .into_response::<IndexStates>()?
+            .open();
+
+        let previous = self.previous_settings(
+            self.client
+                .request(self.get_settings_request())
+                .params_fluent(|params| {
+                    params
+                        .url_param("flat_settings", true)
+                        .url_param("include_defaults", true)
+                })
+                .send()?
+                .into_response::<IndexSettings>()?,
+        );
+
+        let open = match open {
+            Some(open) => open,
+            None => {
+                self.put_settings(self.index.clone(), &self.settings)?;
+
+                return self.health();
+            }
+        };
+
+        self.close(open.clone())?;
+
+        if let Err(e) = self.put_settings(self.index.clone(), &self.settings) {
+            let rollback = self.open(open);
+            return Err(self.rolled_back(e, rollback));
+        }
+
+        match self
+            .open(open.clone())
+            .and_then(|_| self.wait_for_health(open.clone()))
+        {
+            Ok(health) => Ok(health),
+            Err(e) => {
+                let rollback = self
+                    .close(open.clone())
+                    .and_then(|_| self.restore(previous))
+                    .and_then(|_| self.open(open));
+
+                Err(self.rolled_back(e, rollback))
+            }
+        }
+    }
+
+    fn close(&self, index: Index<'static>) -> Result<(), Error> {
+        self.client.index(index).close().send()?;
+
+        Ok(())
+    }
+
+    fn open(&self, index: Index<'static>) -> Result<(), Error> {
+        self.client.index(index).open().send()?;
+
+        Ok(())
+    }
+
+    fn put_settings(
+        &self,
+        index: Index<'static>,
+        settings: &Map<String, Value>,
+    ) -> Result<(), Error> {
+        self.client
+            .request(self.put_settings_request(index, settings))
+            .send()?
+            .into_response::<CommandResponse>()?;
+
+        Ok(())
+    }
+
+    fn restore(&self, previous: PreviousSettings) -> Result<(), Error> {
+        for (index, settings) in previous {
+            self.put_settings(index.into(), &settings)?;
+        }
+
+        Ok(())
+    }
+
+    fn wait_for_health(&self, open: Index<'static>) -> Result<ClusterHealthResponse, Error> {
+        let health = self
+            .client
+            .cluster_health()
+            .index(open)
+            .wait_for_status(self.wait_for_status)
+            .timeout(self.timeout)
+            .send()?;
+
+        self.check_health(health)
+    }
+
+    fn health(&self) -> Result<ClusterHealthResponse, Error> {
+        self.client.cluster_health().index(self.index.clone()).send()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexMaintenance<AsyncSender> {
+    /**
+    Close the index, apply the settings, and reopen it.
+
+    The future resolves to the health of the index after it's been reopened, or an error after the changes have been rolled back.
+
+    # Examples
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index("myindex")
+                       .maintenance()
+                       .settings(json!({ "index.codec": "best_compression" }))
+                       .send();
+
+    future.and_then(|health| {
+        println!("status: {:?}", health.status());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn send(self) -> impl Future<Item = ClusterHealthResponse, Error = Error> + Send {
+        let this = self.clone();
+
+        self.check()
+            .into_future()
+            .and_then(move |_| {
+                let get_settings = this.clone();
+
+                this.client
+                    .request(this.index_states_request())
+                    .params_fluent(|params| {
+                        params
+                            .url_param("format", "json")
+                            .url_param("h", "index,status")
+                    })
+                    .send()
+                    .and_then(|res| res.into_response::<IndexStates>())
+                    .map(move |states| (get_settings, states.open()))
+            })
+            .and_then(|(this, open)| {
+                this.client
+                    .request(this.get_settings_request())
+                    .params_fluent(|params| {
+                        params
+                            .url_param("flat_settings", true)
+                            .url_param("include_defaults", true)
+                    })
+                    .send()
+                    .and_then(|res| res.into_response::<IndexSettings>())
+                    .map(move |current| {
+                        let previous = this.previous_settings(current);
+
+                        (this, open, previous)
+                    })
+            })
+            .and_then(|(this, open, previous)| match open {
+                Some(open) => Either::A(this.change(open, previous)),
+                None => {
+                    let health = this.clone();
+
+                    Either::B(
+                        this.put_settings(this.index.clone(), &this.settings)
+                            .and_then(move |_| health.health()),
+                    )
+                }
+            })
+    }
+
+    // Close the open indices, apply the settings, and reopen them
+    fn change(
+        self,
+        open: Index<'static>,
+        previous: PreviousSettings,
+    ) -> impl Future<Item = ClusterHealthResponse, Error = Error> + Send {
+        self.close(open.clone())
+            .map(move |_| self)
+            .and_then(move |this| {
+                this.put_settings(this.index.clone(), &this.settings)
+                    .then(move |res| match res {
+                        Ok(_) => Either::A(this.reopen(open, previous)),
+                        Err(e) => {
+                            let rollback = this.open(open);
+
+                            Either::B(rollback.then(move |rollback| {
+                                Err::<ClusterHealthResponse, _>(this.rolled_back(e, rollback))
+                            }))
+                        }
+                    })
+            })
+    }
+
+    // Reopen the indices and wait for them to become healthy, restoring the previous settings if they don't
+    fn reopen(
+        self,
+        open: Index<'static>,
+        previous: PreviousSettings,
+    ) -> impl Future<Item = ClusterHealthResponse, Error = Error> + Send {
+        let (this, wait) = (self.clone(), open.clone());
+
+        self.open(open.clone())
+            .and_then(move |_| this.wait_for_health(wait))
+            .or_else(move |e| {
+                let rollback = self.roll_back(open, previous);
+
+                rollback.then(move |rollback| Err(self.rolled_back(e, rollback)))
+            })
+    }
+
+    fn roll_back(
+        &self,
+        open: Index<'static>,
+        previous: PreviousSettings,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        let (put, reopen) = (self.clone(), self.clone());
+
+        self.close(open.clone())
+            .and_then(move |_| {
+                future::join_all(
+                    previous
+                        .into_iter()
+                        .map(move |(index, settings)| put.put_settings(index.into(), &settings)),
+                )
+            })
+            .and_then(move |_| reopen.open(open))
+    }
+
+    fn close(&self, index: Index<'static>) -> impl Future<Item = (), Error = Error> + Send {
+        self.client.index(index).close().send().map(|_| ())
+    }
+
+    fn open(&self, index: Index<'static>) -> impl Future<Item = (), Error = Error> + Send {
+        self.client.index(index).open().send().map(|_| ())
+    }
+
+    fn put_settings(
+        &self,
+        index: Index<'static>,
+        settings: &Map<String, Value>,
+    ) -> impl Future<Item = (), Error = Error> + Send {
+        self.client
+            .request(self.put_settings_request(index, settings))
+            .send()
+            .and_then(|res| res.into_response::<CommandResponse>())
+            .map(|_| ())
+    }
+
+    fn wait_for_health(
+        &self,
+        open: Index<'static>,
+    ) -> impl Future<Item = ClusterHealthResponse, Error = Error> + Send {
+        let this = self.clone();
+
+        self.client
+            .cluster_health()
+            .index(open)
+            .wait_for_status(self.wait_for_status)
+            .timeout(self.timeout)
+            .send()
+            .and_then(move |health| this.check_health(health))
+    }
+
+    fn health(&self) -> impl Future<Item = ClusterHealthResponse, Error = Error> + Send {
+        self.client.cluster_health().index(self.index.clone()).send()
+    }
+}
+
+// Flatten nested settings into dotted names that start with `index.`
+// Empty nested objects don't have any settings to flatten, so they're rejected instead of dropped
+fn flatten_settings(
+    prefix: &str,
+    settings: Value,
+    flat: &mut Map<String, Value>,
+) -> Result<(), String> {
+    let key = || {
+        if prefix.starts_with("index.") {
+            prefix.to_owned()
+        } else {
+            format!("index.{}", prefix)
+        }
+    };
+
+    match settings {
+        Value::Object(ref settings) if settings.is_empty() && !prefix.is_empty() => Err(format!(
+            "the index setting '{}' is an empty object",
+            key()
+        )),
+        Value::Object(settings) => {
+            for (key, value) in settings {
+                let key = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+
+                flatten_settings(&key, value, flat)?;
+            }
+
+            Ok(())
+        }
+        value => {
+            flat.insert(key(), value);
+
+            Ok(())
+        }
+    }
+}
+
+// The state of each index an index or alias resolves to
+#[derive(Deserialize, Debug)]
+struct IndexStates(Vec<IndexState>);
+
+#[derive(Deserialize, Debug)]
+struct IndexState {
+    index: String,
+    status: String,
+}
+
+impl IndexStates {
+    // The indices that are open, or `None` if they're all closed
+    fn open(self) -> Option<Index<'static>> {
+        let open: Vec<String> = self
+            .0
+            .into_iter()
+            .filter(|state| state.status == "open")
+            .map(|state| state.index)
+            .collect();
+
+        if open.is_empty() {
+            None
+        } else {
+            Some(Index::from(open.join(",")))
+        }
+    }
+}
+
+impl IsOkOnSuccess for IndexStates {}
+
+// The previous values of the settings that are changed for each index
+type PreviousSettings = BTreeMap<String, Map<String, Value>>;
+
+// The flattened settings and defaults for each index
+#[derive(Deserialize, Debug)]
+struct IndexSettings(BTreeMap<String, IndexSettingsInner>);
+
+#[derive(Deserialize, Debug, Default)]
+struct IndexSettingsInner {
+    #[serde(default)]
+    settings: Map<String, Value>,
+    #[serde(default)]
+    defaults: Map<String, Value>,
+}
+
+impl IsOkOnSuccess for IndexSettings {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{
+            receiver::parse,
+            sender::recording::test_recording,
+            StatusCode,
+        },
+        prelude::*,
+        tests::*,
+    };
+    use futures::IntoFuture;
+    use std::{
+        fs,
+        sync::{
+            Arc,
+            Mutex,
+        },
+    };
+
+    const ACKNOWLEDGED: &str = r#"{"acknowledged":true}"#;
+
+    fn interaction(
+        method: &str,
+        path: &str,
+        query: Option<&str>,
+        body: Option<Value>,
+        status: u16,
+        response: &str,
+    ) -> Value {
+        json!({
+            "request": {
+                "method": method,
+                "path": path,
+                "query": query,
+                "body": body.map(|body| body.to_string())
+            },
+            "response": { "status": status, "body": response }
+        })
+    }
+
+    fn index_states(index: &str, states: &[(&str, &str)]) -> Value {
+        let states: Vec<Value> = states
+            .iter()
+            .map(|&(index, status)| json!({ "index": index, "status": status }))
+            .collect();
+
+        interaction(
+            "GET",
+            &format!("/_cat/indices/{}", index),
+            Some("format=json&h=index%2Cstatus"),
+            None,
+            200,
+            &Value::from(states).to_string(),
+        )
+    }
+
+    fn get_settings(index: &str, response: Value) -> Value {
+        interaction(
+            "GET",
+            &format!("/{}/_settings", index),
+            Some("flat_settings=true&include_defaults=true"),
+            None,
+            200,
+            &response.to_string(),
+        )
+    }
+
+    fn put_settings(index: &str, settings: Value, status: u16, response: &str) -> Value {
+        interaction(
+            "PUT",
+            &format!("/{}/_settings", index),
+            None,
+            Some(settings),
+            status,
+            response,
+        )
+    }
+
+    fn close(index: &str) -> Value {
+        interaction(
+            "POST",
+            &format!("/{}/_close", index),
+            None,
+            None,
+            200,
+            ACKNOWLEDGED,
+        )
+    }
+
+    fn open(index: &str) -> Value {
+        interaction(
+            "POST",
+            &format!("/{}/_open", index),
+            None,
+            None,
+            200,
+            ACKNOWLEDGED,
+        )
+    }
+
+    fn health(index: &str, status: &str, timed_out: bool) -> Value {
+        health_with_query(
+            index,
+            Some("timeout=30s&wait_for_status=green"),
+            status,
+            timed_out,
+        )
+    }
+
+    fn health_with_query(index: &str, query: Option<&str>, status: &str, timed_out: bool) -> Value {
+        let response = json!({
+            "cluster_name": "elasticsearch",
+            "status": status,
+            "timed_out": timed_out,
+            "number_of_nodes": 1,
+            "number_of_data_nodes": 1,
+            "active_primary_shards": 1,
+            "active_shards": 1,
+            "relocating_shards": 0,
+            "initializing_shards": 0,
+            "unassigned_shards": 0
+        });
+
+        interaction(
+            "GET",
+            &format!("/_cluster/health/{}", index),
+            query,
+            None,
+            200,
+            &response.to_string(),
+        )
+    }
+
+    // The interactions for an alias over two indices that doesn't become healthy after the settings are changed
+    fn unhealthy_alias() -> Vec<Value> {
+        vec![
+            index_states("logs", &[("logs-1", "open"), ("logs-2", "open")]),
+            get_settings(
+                "logs",
+                json!({
+                    "logs-1": { "settings": { "index.codec": "default" } },
+                    "logs-2": { "defaults": { "index.codec": "LZ4" } }
+                }),
+            ),
+            close("logs-1,logs-2"),
+            put_settings(
+                "logs",
+                json!({ "index.codec": "best_compression" }),
+                200,
+                ACKNOWLEDGED,
+            ),
+            open("logs-1,logs-2"),
+            health("logs-1,logs-2", "red", true),
+            close("logs-1,logs-2"),
+            put_settings(
+                "logs-1",
+                json!({ "index.codec": "default" }),
+                200,
+                ACKNOWLEDGED,
+            ),
+            put_settings("logs-2", json!({ "index.codec": "LZ4" }), 200, ACKNOWLEDGED),
+            open("logs-1,logs-2"),
+        ]
+    }
+
+    const UNHEALTHY_ALIAS_SENT: &[&str] = &[
+        "GET /_cat/indices/logs",
+        "GET /logs/_settings",
+        "POST /logs-1,logs-2/_close",
+        "PUT /logs/_settings",
+        "POST /logs-1,logs-2/_open",
+        "GET /_cluster/health/logs-1,logs-2",
+        "POST /logs-1,logs-2/_close",
+        "PUT /logs-1/_settings",
+        "PUT /logs-2/_settings",
+        "POST /logs-1,logs-2/_open",
+    ];
+
+    // The method and path of each request that's sent
+    type Sent = Arc<Mutex<Vec<String>>>;
+
+    fn replay_client(interactions: Vec<Value>) -> (SyncClient, Sent) {
+        let path = test_recording(interactions);
+        let sent = Sent::default();
+
+        let captured = sent.clone();
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .pre_send_raw(move |req| {
+                captured
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", req.method, req.url.path()));
+                Ok(())
+            })
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        (client, sent)
+    }
+
+    fn async_replay_client(interactions: Vec<Value>) -> (AsyncClient, Sent) {
+        let path = test_recording(interactions);
+        let sent = Sent::default();
+
+        let captured = sent.clone();
+        let client = AsyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .pre_send_raw(move |req| {
+                captured
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", req.method, req.url.path()));
+                Box::new(Ok(()).into_future())
+            })
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        (client, sent)
+    }
+
+    fn maintenance() -> IndexMaintenance<SyncSender> {
+        SyncClientBuilder::new()
+            .build()
+            .unwrap()
+            .index("myindex")
+            .maintenance()
+    }
+
+    #[test]
+    fn settings_are_flattened() {
+        let maintenance = maintenance()
+            .settings(json!({
+                "analysis": {
+                    "analyzer": {
+                        "my_analyzer": { "tokenizer": "standard", "filter": ["lowercase"] }
+                    }
+                }
+            }))
+            .settings(json!({ "index.codec": "best_compression" }))
+            .settings(json!({ "index": { "refresh_interval": "30s" } }));
+
+        let expected = json!({
+            "index.analysis.analyzer.my_analyzer.tokenizer": "standard",
+            "index.analysis.analyzer.my_analyzer.filter": ["lowercase"],
+            "index.codec": "best_compression",
+            "index.refresh_interval": "30s"
+        });
+
+        assert_eq!(expected, Value::from(maintenance.settings));
+    }
+
+    #[test]
+    fn previous_settings_restore_missing_as_defaults() {
+        let maintenance = maintenance().settings(json!({
+            "index.codec": "best_compression",
+            "index.refresh_interval": "30s",
+            "index.analysis.analyzer.my_analyzer.tokenizer": "standard"
+        }));
+
+        let current = parse::<IndexSettings>()
+            .from_slice(
+                StatusCode::OK,
+                br#"{
+                    "myindex": {
+                        "settings": { "index.codec": "default", "index.number_of_shards": "1" },
+                        "defaults": { "index.codec": "LZ4", "index.refresh_interval": "1s" }
+                    }
+                }"# as &[_],
+            )
+            .unwrap();
+
+        let expected = json!({
+            "myindex": {
+                "index.codec": "default",
+                "index.refresh_interval": "1s",
+                "index.analysis.analyzer.my_analyzer.tokenizer": null
+            }
+        });
+
+        assert_eq!(
+            expected,
+            serde_json::to_value(maintenance.previous_settings(current)).unwrap()
+        );
+    }
+
+    #[test]
+    fn put_settings_request() {
+        let maintenance = maintenance().settings(json!({ "codec": "best_compression" }));
+
+        let req = maintenance.put_settings_request("myindex".into(), &maintenance.settings);
+
+        assert_eq!("/myindex/_settings", req.url.as_ref());
+        assert_eq!(
+            json!({ "index.codec": "best_compression" }),
+            serde_json::from_str::<Value>(&req.body).unwrap()
+        );
+    }
+
+    #[test]
+    fn send_applies_settings() {
+        let (client, sent) = replay_client(vec![
+            index_states("myindex", &[("myindex", "open")]),
+            get_settings(
+                "myindex",
+                json!({ "myindex": { "settings": { "index.codec": "default" } } }),
+            ),
+            close("myindex"),
+            put_settings(
+                "myindex",
+                json!({ "index.codec": "best_compression" }),
+                200,
+                ACKNOWLEDGED,
+            ),
+            open("myindex"),
+            health("myindex", "green", false),
+        ]);
+
+        let health = client
+            .index("myindex")
+            .maintenance()
+            .settings(json!({ "index.codec": "best_compression" }))
+            .send()
+            .unwrap();
+
+        assert_eq!(ClusterHealthStatus::Green, health.status());
+        assert_eq!(
+            vec![
+                "GET /_cat/indices/myindex",
+                "GET /myindex/_settings",
+                "POST /myindex/_close",
+                "PUT /myindex/_settings",
+                "POST /myindex/_open",
+                "GET /_cluster/health/myindex",
+            ],
+            *sent.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn failed_put_settings_reopens_index() {
+        let (client, sent) = replay_client(vec![
+            index_states("myindex", &[("myindex", "open")]),
+            get_settings(
+                "myindex",
+                json!({ "myindex": { "settings": { "index.codec": "default" } } }),
+            ),
+            close("myindex"),
+            put_settings(
+                "myindex",
+                json!({ "index.codec": "best_compression" }),
+                400,
+                r#"{"error":{"type":"illegal_argument_exception","reason":"unknown setting"},"status":400}"#,
+            ),
+            open("myindex"),
+        ]);
+
+        let err = client
+            .index("myindex")
+            .maintenance()
+            .settings(json!({ "index.codec": "best_compression" }))
+            .send()
+            .unwrap_err();
+
+        assert!(format!("{:?}", err).contains("unknown setting"));
+        assert_eq!(
+            vec![
+                "GET /_cat/indices/myindex",
+                "GET /myindex/_settings",
+                "POST /myindex/_close",
+                "PUT /myindex/_settings",
+                "POST /myindex/_open",
+            ],
+            *sent.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn unhealthy_alias_restores_settings_of_each_index() {
+        let (client, sent) = replay_client(unhealthy_alias());
+
+        let err = client
+            .index("logs")
+            .maintenance()
+            .settings(json!({ "index.codec": "best_compression" }))
+            .send()
+            .unwrap_err();
+
+        assert!(format!("{:?}", err).contains("index 'logs' was Red instead of Green"));
+        assert_eq!(UNHEALTHY_ALIAS_SENT, &sent.lock().unwrap()[..]);
+    }
+
+    #[test]
+    fn async_unhealthy_alias_restores_settings_of_each_index() {
+        let (client, sent) = async_replay_client(unhealthy_alias());
+
+        let err = client
+            .index("logs")
+            .maintenance()
+            .settings(json!({ "index.codec": "best_compression" }))
+            .send()
+            .wait()
+            .unwrap_err();
+
+        assert!(format!("{:?}", err).contains("index 'logs' was Red instead of Green"));
+
+        // The previous settings of each index are restored concurrently
+        let mut sent = sent.lock().unwrap().clone();
+        sent[7..9].sort();
+        assert_eq!(UNHEALTHY_ALIAS_SENT, &sent[..]);
+    }
+
+    #[test]
+    fn closed_indices_stay_closed() {
+        let (client, sent) = replay_client(vec![
+            index_states("logs", &[("logs-1", "close"), ("logs-2", "open")]),
+            get_settings(
+                "logs",
+                json!({
+                    "logs-1": { "settings": { "index.codec": "default" } },
+                    "logs-2": { "settings": { "index.codec": "default" } }
+                }),
+            ),
+            close("logs-2"),
+            put_settings(
+                "logs",
+                json!({ "index.codec": "best_compression" }),
+                200,
+                ACKNOWLEDGED,
+            ),
+            open("logs-2"),
+            health("logs-2", "green", false),
+        ]);
+
+        let health = client
+            .index("logs")
+            .maintenance()
+            .settings(json!({ "index.codec": "best_compression" }))
+            .send()
+            .unwrap();
+
+        assert_eq!(ClusterHealthStatus::Green, health.status());
+        assert_eq!(
+            vec![
+                "GET /_cat/indices/logs",
+                "GET /logs/_settings",
+                "POST /logs-2/_close",
+                "PUT /logs/_settings",
+                "POST /logs-2/_open",
+                "GET /_cluster/health/logs-2",
+            ],
+            *sent.lock().unwrap()
+        );
+    }
+
+    fn closed_index() -> Vec<Value> {
+        vec![
+            index_states("myindex", &[("myindex", "close")]),
+            get_settings(
+                "myindex",
+                json!({ "myindex": { "settings": { "index.codec": "default" } } }),
+            ),
+            put_settings(
+                "myindex",
+                json!({ "index.codec": "best_compression" }),
+                200,
+                ACKNOWLEDGED,
+            ),
+            health_with_query("myindex", None, "green", false),
+        ]
+    }
+
+    const CLOSED_INDEX_SENT: &[&str] = &[
+        "GET /_cat/indices/myindex",
+        "GET /myindex/_settings",
+        "PUT /myindex/_settings",
+        "GET /_cluster/health/myindex",
+    ];
+
+    #[test]
+    fn closed_index_is_not_opened() {
+        let (client, sent) = replay_client(closed_index());
+
+        client
+            .index("myindex")
+            .maintenance()
+            .settings(json!({ "index.codec": "best_compression" }))
+            .send()
+            .unwrap();
+
+        assert_eq!(CLOSED_INDEX_SENT, &sent.lock().unwrap()[..]);
+    }
+
+    #[test]
+    fn async_closed_index_is_not_opened() {
+        let (client, sent) = async_replay_client(closed_index());
+
+        client
+            .index("myindex")
+            .maintenance()
+            .settings(json!({ "index.codec": "best_compression" }))
+            .send()
+            .wait()
+            .unwrap();
+
+        assert_eq!(CLOSED_INDEX_SENT, &sent.lock().unwrap()[..]);
+    }
+
+    #[test]
+    fn index_expressions_fail_without_sending() {
+        let client = SyncClientBuilder::new()
+            .pre_send_raw(|_| panic!("the request shouldn't be sent"))
+            .build()
+            .unwrap();
+
+        let send = |index: &'static str| {
+            let err = client
+                .index(index)
+                .maintenance()
+                .settings(json!({ "index.codec": "best_compression" }))
+                .send()
+                .unwrap_err();
+
+            format!("{:?}", err)
+        };
+
+        assert!(send("_all").contains("'_all' is `_all`"));
+        assert!(send("logs-*").contains("'logs-*' is a wildcard"));
+        assert!(send("logs-1,logs-2").contains("'logs-1,logs-2' is a list of indices"));
+    }
+
+    #[test]
+    fn empty_settings_fail_without_sending() {
+        let client = SyncClientBuilder::new()
+            .pre_send_raw(|_| panic!("the request shouldn't be sent"))
+            .build()
+            .unwrap();
+
+        let err = client
+            .index("myindex")
+            .maintenance()
+            .settings(json!({}))
+            .send()
+            .unwrap_err();
+
+        assert!(
+            format!("{:?}", err).contains("there are no settings to change for index 'myindex'")
+        );
+    }
+
+    #[test]
+    fn invalid_settings_fail_without_sending() {
+        let client = SyncClientBuilder::new()
+            .pre_send_raw(|_| panic!("the request shouldn't be sent"))
+            .build()
+            .unwrap();
+
+        let send = |settings: Value| {
+            let err = client
+                .index("myindex")
+                .maintenance()
+                .settings(json!({ "index.codec": "best_compression" }))
+                .settings(settings)
+                .send()
+                .unwrap_err();
+
+            format!("{:?}", err)
+        };
+
+        assert!(send(json!(null))
+            .contains("index settings must be an object, but they were 'null' for index 'myindex'"));
+        assert!(send(json!("best_compression")).contains("index settings must be an object"));
+        assert!(send(json!({ "analysis": {} }))
+            .contains("the index setting 'index.analysis' is an empty object for index 'myindex'"));
+    }
+
+    #[test]
+    fn invalid_index_fails_without_sending() {
+        let client = SyncClientBuilder::new()
+            .pre_send_raw(|_| panic!("the request shouldn't be sent"))
+            .build()
+            .unwrap();
+
+        let err = client
+            .index("My-Index")
+            .maintenance()
+            .settings(json!({ "index.codec": "best_compression" }))
+            .send()
+            .unwrap_err();
+
+        assert!(format!("{:?}", err).contains("isn't lowercase"));
+    }
+
+    #[test]
+    fn unavailable_node_is_err() {
+        // Nothing is listening on this port, so the settings can't be fetched and the index is never closed
+        let res = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .build()
+            .unwrap()
+            .index("myindex")
+            .maintenance()
+            .settings(json!({ "index.codec": "best_compression" }))
+            .send();
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn is_send() {
+        assert_send::<IndexMaintenance<SyncSender>>();
+        assert_send::<IndexMaintenance<AsyncSender>>();
+    }
+}
@@ -138,8 +138,15 @@ These request methods are called directly on a [`Client`][`Client`].
 Client method                                                 | Elasticsearch API                  | Raw request type                                        | Response type
 ------------------------------------------------------------- | ---------------------------------- | ------------------------------------------------------- | ------------------------------------
 [`search`][Client.search]                                     | [Search][docs-search]              | [`SearchRequest`][SearchRequest]                        | [`SearchResponse`][SearchResponse]
+[`search_template`][Client.search_template]                   | [Search Template][docs-template]   | [`SearchTemplateRequest`][SearchTemplateRequest]        | [`SearchResponse`][SearchResponse]
+[`put_search_template`][Client.put_search_template]           | [Search Template][docs-template]   | [`PutScriptRequest`][PutScriptRequest]                  | [`CommandResponse`][CommandResponse]
+[`get_search_template`][Client.get_search_template]           | [Search Template][docs-template]   | [`GetScriptRequest`][GetScriptRequest]                  | [`GetSearchTemplateResponse`][GetSearchTemplateResponse]
+[`delete_search_template`][Client.delete_search_template]     | [Search Template][docs-template]   | [`DeleteScriptRequest`][DeleteScriptRequest]            | [`CommandResponse`][CommandResponse]
+[`render_search_template`][Client.render_search_template]     | [Search Template][docs-template]   | [`RenderSearchTemplateRequest`][RenderSearchTemplateRequest] | [`RenderSearchTemplateResponse`][RenderSearchTemplateResponse]
 [`bulk`][Client.bulk]                                         | [Bulk][docs-bulk]                  | [`BulkRequest`][BulkRequest]                            | [`BulkResponse`][BulkResponse]
 [`ping`][Client.ping]                                         | -                                  | [`PingRequest`][PingRequest]                            | [`PingResponse`][PingResponse]
+[`info`][Client.info]                                         | -                                  | [`PingRequest`][PingRequest]                            | [`InfoResponse`][InfoResponse]
+[`cluster_health`][Client.cluster_health]                     | [Cluster Health][docs-health]      | [`ClusterHealthRequest`][ClusterHealthRequest]          | [`ClusterHealthResponse`][ClusterHealthResponse]
 [`sql`][Client.sql]                                           | [SQL][docs-sql]                    | [`SqlQueryRequest`][SqlQueryRequest]                    | [`SqlQueryResponse`][SqlQueryResponse]
 
 ## Document requests
@@ -166,6 +173,8 @@ Client method                                                 | Elasticsearch AP
 [`index.close`][Client.index.close]                           | [Close Index][docs-close-index]    | [`IndicesCloseRequest`][IndicesCloseRequest]            | [`CommandResponse`][CommandResponse]
 [`index.delete`][Client.index.delete]                         | [Delete Index][docs-delete-index]  | [`IndicesDeleteRequest`][IndicesDeleteRequest]          | [`CommandResponse`][CommandResponse]
 [`index.exists`][Client.index.exists]                         | [Index Exists][docs-index-exists]  | [`IndicesExistsRequest`][IndicesExistsRequest]          | [`IndicesExistsResponse`][IndicesExistsResponse]
+[`index.stats`][Client.index.stats]                           | [Index Stats][docs-index-stats]    | [`IndicesStatsRequest`][IndicesStatsRequest]            | [`IndicesStatsResponse`][IndicesStatsResponse]
+[`index.segments`][Client.index.segments]                     | [Index Segments][docs-index-segments] | [`IndicesSegmentsRequest`][IndicesSegmentsRequest]   | [`IndicesSegmentsResponse`][IndicesSegmentsResponse]
 
 All builders follow a standard pattern:
 
@@ -459,7 +468,9 @@ For more details see the [`responses`][responses-mod] module.
 
 [docs-bulk]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html
 [docs-search]: http://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html
+[docs-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html
 [docs-sql]: https://www.elastic.co/guide/en/elasticsearch/reference/current/sql-spec.html
+[docs-health]: https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-health.html
 [docs-get]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-get.html
 [docs-update]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-update.html
 [docs-delete]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-delete.html
@@ -469,6 +480,8 @@ For more details see the [`responses`][responses-mod] module.
 [docs-close-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-open-close.html
 [docs-open-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-open-close.html
 [docs-index-exists]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-exists.html
+[docs-index-stats]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-stats.html
+[docs-index-segments]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-segments.html
 [docs-delete-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-delete-index.html
 
 [tokio]: https://tokio.rs
@@ -485,6 +498,11 @@ For more details see the [`responses`][responses-mod] module.
 [Client.request]: struct.Client.html#method.request
 [Client.bulk]: struct.Client.html#bulk-request
 [Client.search]: struct.Client.html#search-request
+[Client.search_template]: struct.Client.html#search-template-request
+[Client.put_search_template]: struct.Client.html#put-search-template-request
+[Client.get_search_template]: struct.Client.html#get-search-template-request
+[Client.delete_search_template]: struct.Client.html#delete-search-template-request
+[Client.render_search_template]: struct.Client.html#render-search-template-request
 [Client.sql]: struct.Client.html#sql-request
 [Client.document.search]: struct.DocumentClient.html#search-request
 [Client.document.get]: struct.DocumentClient.html#get-document-request
@@ -497,12 +515,21 @@ For more details see the [`responses`][responses-mod] module.
 [Client.index.close]: struct.IndexClient.html#close-index-request
 [Client.index.delete]: struct.IndexClient.html#delete-index-request
 [Client.index.exists]: struct.IndexClient.html#index-exists-request
+[Client.index.stats]: struct.IndexClient.html#index-stats-request
+[Client.index.segments]: struct.IndexClient.html#index-segments-request
 [Client.ping]: struct.Client.html#ping-request
+[Client.info]: struct.Client.html#info-request
+[Client.cluster_health]: struct.Client.html#cluster-health-request
 
 [RequestBuilder]: requests/struct.RequestBuilder.html
 [RequestBuilder.params]: requests/struct.RequestBuilder.html#method.params
 [RawRequestBuilder]: requests/type.RawRequestBuilder.html
 [SearchRequest]: ../endpoints/struct.SearchRequest.html
+[SearchTemplateRequest]: ../endpoints/struct.SearchTemplateRequest.html
+[PutScriptRequest]: ../endpoints/struct.PutScriptRequest.html
+[GetScriptRequest]: ../endpoints/struct.GetScriptRequest.html
+[DeleteScriptRequest]: ../endpoints/struct.DeleteScriptRequest.html
+[RenderSearchTemplateRequest]: ../endpoints/struct.RenderSearchTemplateRequest.html
 [SqlQueryRequest]: ../endpoints/struct.SqlQueryRequest.html
 [BulkRequest]: ../endpoints/struct.BulkRequest.html
 [GetRequest]: ../endpoints/struct.GetRequest.html
@@ -515,7 +542,10 @@ For more details see the [`responses`][responses-mod] module.
 [IndicesCloseRequest]: ../endpoints/struct.IndicesCloseRequest.html
 [IndicesDeleteRequest]: ../endpoints/struct.IndicesDeleteRequest.html
 [IndicesExistsRequest]: ../endpoints/struct.IndicesExistsRequest.html
+[IndicesStatsRequest]: ../endpoints/struct.IndicesStatsRequest.html
+[IndicesSegmentsRequest]: ../endpoints/struct.IndicesSegmentsRequest.html
 [PingRequest]: ../endpoints/struct.PingRequest.html
+[ClusterHealthRequest]: ../endpoints/struct.ClusterHealthRequest.html
 
 [responses-mod]: responses/index.html
 [SyncResponseBuilder]: ../http/receiver/struct.SyncResponseBuilder.html
@@ -525,6 +555,8 @@ For more details see the [`responses`][responses-mod] module.
 [AsyncResponseBuilder.into_response]: ../http/receiver/struct.AsyncResponseBuilder.html#method.into_response
 [AsyncResponseBuilder.into_raw]: ../http/receiver/struct.AsyncResponseBuilder.html#method.into_raw
 [SearchResponse]: responses/struct.SearchResponse.html
+[GetSearchTemplateResponse]: responses/struct.GetSearchTemplateResponse.html
+[RenderSearchTemplateResponse]: responses/struct.RenderSearchTemplateResponse.html
 [SqlQueryResponse]: responses/struct.SqlQueryResponse.html
 [BulkResponse]: responses/struct.BulkResponse.html
 [GetResponse]: responses/struct.GetResponse.html
@@ -532,7 +564,11 @@ For more details see the [`responses`][responses-mod] module.
 [DeleteResponse]: responses/struct.DeleteResponse.html
 [IndexResponse]: responses/struct.IndexResponse.html
 [IndicesExistsResponse]: responses/struct.IndicesExistsResponse.html
+[IndicesStatsResponse]: responses/struct.IndicesStatsResponse.html
+[IndicesSegmentsResponse]: responses/struct.IndicesSegmentsResponse.html
 [PingResponse]: responses/struct.PingResponse.html
+[InfoResponse]: responses/struct.InfoResponse.html
+[ClusterHealthResponse]: responses/struct.ClusterHealthResponse.html
 [CommandResponse]: responses/struct.CommandResponse.html
 [SyncHttpResponse]: ../http/receiver/struct.SyncHttpResponse.html
 [AsyncHttpResponse]: ../http/receiver/struct.AsyncHttpResponse.html
@@ -544,11 +580,29 @@ pub mod requests;
 pub mod responses;
 
 mod asynchronous;
+mod document_serializer;
+mod index_maintenance;
 mod synchronous;
+mod time_based_index;
+mod version;
 
 pub use self::{
     asynchronous::*,
+    document_serializer::{
+        Document,
+        DocumentSerializer,
+        SortedKeysSerializer,
+    },
+    index_maintenance::IndexMaintenance,
     synchronous::*,
+    time_based_index::{
+        IndexPeriod,
+        TimeBasedIndex,
+    },
+    version::{
+        ParseVersionError,
+        Version,
+    },
 };
 
 #[doc(inline)]
@@ -557,15 +611,27 @@ pub use crate::http::sender::{
     RequestParams,
 };
 
+use self::{
+    requests::common::{
+        IdStrategies,
+        IdStrategy,
+    },
+    version::VersionCell,
+};
 use crate::{
     http::sender::{
+        index_scope::IndexScope,
         NodeAddresses,
         Sender,
     },
     params::Index,
 };
 
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    sync::Arc,
+    time::Duration,
+};
 
 /**
 A HTTP client for the Elasticsearch REST API.
@@ -609,19 +675,174 @@ tokio::runtime::current_thread::block_on_all(response_future)?;
 # }
 ```
 
+# Cloning a `Client`
+
+Cloning a `Client` is cheap, and all clones share the same underlying state:
+
+- the http connection pool
+- the set of node addresses and the position of the load balancing strategy
+- the cluster sniffer, if nodes are being sniffed
+- the circuit breaker, if one is configured
+- the captured cluster version.
+
+So it's fine to clone a client for each request or each thread instead of sharing a reference to it.
+Both `SyncClient` and `AsyncClient` are `Send` and `Sync`.
+
+Changes made to a request builder, like [`serde_pool`][RequestBuilder.serde_pool], only apply to that request and don't affect other clones.
+
+```no_run
+# use std::thread;
+# use elastic::prelude::*;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+let client = SyncClient::builder().build()?;
+
+let handles: Vec<_> = (0..4)
+    .map(|_| {
+        let client = client.clone();
+        thread::spawn(move || client.ping().send())
+    })
+    .collect();
+
+for handle in handles {
+    handle.join().unwrap()?;
+}
+# Ok(())
+# }
+```
+
 [SyncClient]: type.SyncClient.html
 [AsyncClient]: type.AsyncClient.html
+[RequestBuilder.serde_pool]: requests/struct.RequestBuilder.html#method.serde_pool
 */
 #[derive(Clone)]
 pub struct Client<TSender> {
     sender: TSender,
     addresses: NodeAddresses<TSender>,
+    version: VersionCell,
+    document_serializer: Option<Arc<dyn DocumentSerializer>>,
+    document_index: Option<Arc<DocumentIndexFn>>,
+    id_strategies: Arc<IdStrategies>,
+    index_scope: Option<IndexScope>,
+    deadline: Option<Duration>,
 }
 
+pub(crate) type DocumentIndexFn = dyn Fn(&str) -> String + Send + Sync;
+
 impl<TSender> Client<TSender>
 where
     TSender: Sender,
 {
+    /**
+    Get the version of the Elasticsearch cluster this client is connected to.
+
+    The version is captured the first time a [`ping`][Client.ping] or [`info`][Client.info] request is sent,
+    or when a request for an API that's only available in some versions needs it.
+    Requests that use APIs or options that aren't available in the cluster version fail without being sent.
+    The checked APIs and options are:
+
+    - the SQL API
+    - getting a document source without a type
+    - the typed `track_total_hits` option, `knn` queries and the `fixed_interval` and `calendar_interval` of date histograms on search requests.
+
+    Options set in a raw search body aren't checked.
+
+    Returns `None` if the version hasn't been captured yet.
+
+    [Client.ping]: #ping-request
+    [Client.info]: #info-request
+    */
+    pub fn cluster_version(&self) -> Option<Version> {
+        self.version.get()
+    }
+
+    /**
+    Get the index to use for a document type.
+
+    The index is passed through the function set with `document_index_name` on the client builder, if there is one.
+    */
+    pub(crate) fn document_index(&self, index: Index<'static>) -> Index<'static> {
+        match self.document_index {
+            Some(ref document_index) => Index::from(document_index(&index)),
+            None => index,
+        }
+    }
+
+    /** Get the id strategy set with `document_id_strategy` on the client builder for a document type, if there is one. */
+    pub(crate) fn id_strategy<TDocument>(&self) -> Option<&IdStrategy<TDocument>>
+    where
+        TDocument: 'static,
+    {
+        self.id_strategies.get()
+    }
+
+    /**
+    Get the name of an index within the client's index scope.
+
+    The index is returned unchanged if the client isn't [`scoped`](#method.scoped).
+    */
+    pub(crate) fn scoped_index(&self, index: Index<'static>) -> Index<'static> {
+        match self.index_scope {
+            Some(ref index_scope) => Index::from(index_scope.index(&index)),
+            None => index,
+        }
+    }
+
+    /**
+    Get a view of this client that keeps every request within a set of indices.
+
+    The `scope` function is given each index a request targets and returns the name of that index within the scope.
+    The index in the path of every request sent through the returned client is rewritten by `scope`, including searches, document requests and index management requests.
+    The indices of the operations in bulk requests are rewritten too.
+    Index lists like `a,b` and exclusions like `-a` are rewritten index by index, and `_all` is rewritten as `scope("*")`.
+
+    Requests that could reach indices outside the scope fail without being sent.
+    That includes requests that don't target an index in their path, like SQL or cluster health requests,
+    and raw requests with a body that can name other indices, like `_bulk`, `_mget` and `_msearch`.
+    Ping, info and scroll requests are still allowed.
+
+    The bodies of other requests aren't inspected, so queries that look up documents in other indices, like a `terms` lookup, aren't rewritten.
+
+    Calling `scoped` on a scoped client rewrites indices with the new scope first and then the existing one.
+    The scoped client shares the same underlying state as this client.
+
+    # Examples
+
+    Keep all requests for a tenant within indices prefixed with their name:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let tenant = client.scoped(|index| format!("tenant1_{}", index));
+
+    // Searches the `tenant1_logs` index
+    let response = tenant
+        .search::<Value>()
+        .index("logs")
+        .body(json!({ "query": { "match_all": {} } }))
+        .send()?;
+
+    // Searches the `tenant1_*` indices instead of `_all`
+    let response = tenant.search::<Value>().send()?;
+
+    // Fails without being sent, because it could reach any index
+    assert!(tenant.cluster_health().send().is_err());
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn scoped(
+        &self,
+        scope: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Client<TSender> {
+        let mut client = (*self).clone();
+        client.index_scope = Some(IndexScope::new(scope).within(self.index_scope.clone()));
+
+        client
+    }
+
     /**
     Get a client for working with specific document type.
 
@@ -686,7 +907,14 @@ pub mod prelude {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::*;
+    use crate::{
+        error::Error,
+        http::empty_body,
+        prelude::*,
+        tests::*,
+    };
+    use serde_json::Value;
+    use std::sync::Mutex;
 
     #[test]
     fn client_is_send_sync() {
@@ -696,4 +924,187 @@ mod tests {
         assert_send::<AsyncClient>();
         assert_sync::<AsyncClient>();
     }
+
+    #[derive(Serialize, Deserialize, ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    struct TestDoc {}
+
+    type Sent = Arc<Mutex<Vec<(String, String)>>>;
+
+    /** A scoped client that captures the path and body of requests instead of sending them. */
+    fn scoped_client() -> (SyncClient, Sent) {
+        let sent = Sent::default();
+
+        let captured = sent.clone();
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:9200")
+            .pre_send_raw(move |req| {
+                let body = req
+                    .body
+                    .as_mut()
+                    .and_then(|body| body.buffered_bytes())
+                    .map(|body| String::from_utf8_lossy(&body).into_owned())
+                    .unwrap_or_default();
+
+                captured
+                    .lock()
+                    .unwrap()
+                    .push((req.url.path().to_owned(), body));
+
+                Err("captured".into())
+            })
+            .build()
+            .unwrap();
+
+        (client.scoped(|index| format!("tenant1_{}", index)), sent)
+    }
+
+    fn sent_paths(sent: &Sent) -> Vec<String> {
+        sent.lock()
+            .unwrap()
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    #[test]
+    fn scoped_client_rewrites_requests() {
+        let (client, sent) = scoped_client();
+
+        let _ = client.search::<Value>().index("a,-b").send();
+        let _ = client.search::<Value>().send();
+        let _ = client.document::<TestDoc>().search().send();
+        let _ = client.document::<TestDoc>().get("1").send();
+        let _ = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .id("1")
+            .send();
+        let _ = client
+            .document::<TestDoc>()
+            .update("1")
+            .doc(json!({ "a": 1 }))
+            .send();
+        let _ = client.document::<TestDoc>().delete("1").send();
+        let _ = client.document::<TestDoc>().put_mapping().send();
+        let _ = client.index("myindex").create().send();
+        let _ = client.index("myindex").exists().send();
+        let _ = client.index("myindex").close().send();
+        let _ = client.index("_all").delete().send();
+        let _ = client.ping().send();
+
+        assert_eq!(
+            vec![
+                "/tenant1_a,-tenant1_b/_search",
+                "/tenant1_*/_search",
+                "/tenant1_testdoc/_doc/_search",
+                "/tenant1_testdoc/_doc/1",
+                "/tenant1_testdoc/_doc/1",
+                "/tenant1_testdoc/_doc/1/_update",
+                "/tenant1_testdoc/_doc/1",
+                "/tenant1_testdoc/_mapping",
+                "/tenant1_myindex",
+                "/tenant1_myindex",
+                "/tenant1_myindex/_close",
+                "/tenant1_*",
+                "/",
+            ],
+            sent_paths(&sent)
+        );
+    }
+
+    #[test]
+    fn scoped_client_rejects_unscoped_requests() {
+        let (client, sent) = scoped_client();
+
+        let health = client.cluster_health().send().map(|_| ());
+        let raw_search = client
+            .request(SearchRequest::new(empty_body()))
+            .send()
+            .map(|_| ());
+        let index_health = client
+            .request(ClusterHealthRequest::for_index("myindex"))
+            .send()
+            .map(|_| ());
+        let raw_bulk = client
+            .request(BulkRequest::for_index(
+                "myindex",
+                "{\"index\":{\"_index\":\"other\"}}\n{}\n",
+            ))
+            .send()
+            .map(|_| ());
+
+        for res in vec![health, index_health, raw_search, raw_bulk] {
+            match res {
+                Err(Error::Client(ref e)) => {
+                    assert!(format!("{:?}", e).contains("index scope"), "{:?}", e)
+                }
+                _ => panic!("expected a client error"),
+            }
+        }
+
+        assert!(sent_paths(&sent).is_empty());
+    }
+
+    #[test]
+    fn scoped_client_rewrites_bulk_operations() {
+        let (client, sent) = scoped_client();
+
+        let _ = client
+            .bulk()
+            .push(bulk::<TestDoc>().index(TestDoc {}).id("1"))
+            .push(bulk_raw().index(json!({})).index("other").id("2"))
+            .push(bulk_raw().delete().id("3"))
+            .send();
+
+        let _ = client
+            .bulk()
+            .index("myindex")
+            .push(bulk_raw().delete().id("1"))
+            .send();
+
+        let sent = sent.lock().unwrap();
+
+        assert_eq!("/_bulk", sent[0].0);
+        assert_eq!(
+            concat!(
+                r#"{"index":{"_index":"tenant1_testdoc","_type":"_doc","_id":"1"}}"#,
+                "\n{}\n",
+                r#"{"index":{"_index":"tenant1_other","_id":"2"}}"#,
+                "\n{}\n",
+                r#"{"delete":{"_id":"3"}}"#,
+                "\n",
+            ),
+            sent[0].1
+        );
+        assert_eq!("/tenant1_myindex/_bulk", sent[1].0);
+    }
+
+    #[test]
+    fn scoped_client_within_scope() {
+        let (client, sent) = scoped_client();
+        let client = client.scoped(|index| format!("{}_v2", index));
+
+        let _ = client.index("myindex").exists().send();
+
+        assert_eq!(vec!["/tenant1_myindex_v2"], sent_paths(&sent));
+    }
+
+    #[test]
+    fn scoped_client_leaves_original_unscoped() {
+        let client = SyncClientBuilder::new().build().unwrap();
+        let _ = client.scoped(|index| format!("tenant1_{}", index));
+
+        assert!(client.index_scope.is_none());
+    }
+
+    #[test]
+    fn cloned_client_shares_version() {
+        let client = SyncClientBuilder::new().build().unwrap();
+        let cloned = client.clone();
+
+        client.version.set(Version::new(7, 1, 0));
+
+        assert_eq!(Some(Version::new(7, 1, 0)), cloned.cluster_version());
+    }
 }
@@ -0,0 +1,1267 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{
+        self,
+        BufRead,
+        BufReader,
+        Lines,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Arc,
+    thread,
+};
+
+use channel;
+use futures::{
+    sync::mpsc,
+    Future,
+    IntoFuture,
+    Sink,
+    Stream,
+};
+use serde::de::{
+    Deserializer,
+    IgnoredAny,
+    SeqAccess,
+    Visitor,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+
+use super::{
+    bulk_raw,
+    BulkOperation,
+    Pending,
+    DEFAULT_BODY_SIZE,
+};
+use crate::{
+    client::{
+        responses::{
+            bulk::ErrorItem,
+            BulkResponse,
+        },
+        AsyncClient,
+        Client,
+        SyncClient,
+    },
+    endpoints::BulkRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Index,
+};
+
+/** The number of bulk requests sent at the same time by default. */
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/** The number of documents in a JSON array that are read ahead of the bulk requests they're sent in. */
+const ARRAY_READ_AHEAD: usize = 1024;
+
+/** The action line sent before each document, without an id. */
+const INDEX_ACTION: &str = "{\"index\":{}}\n";
+
+/**
+A builder for loading a file of documents with bulk requests.
+
+Call [`Client.bulk_file`][Client.bulk_file] to get a `BulkFileRequestBuilder`.
+The `send` method will either load the file [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+The file is split into bulk requests that are roughly [`body_size_bytes`](#method.body_size_bytes) in size.
+A file can contain:
+
+- newline delimited documents, with one JSON document per line.
+- a JSON array of documents.
+- newline delimited [bulk operations][docs-bulk], where each action line is followed by its source, if it has one.
+  Call [`operations`](#method.operations) to load a file like this.
+
+[docs-bulk]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.bulk_file]: ../../struct.Client.html#bulk-file-request
+*/
+pub struct BulkFileRequestBuilder<TSender>
+where
+    TSender: Sender,
+{
+    client: Client<TSender>,
+    path: PathBuf,
+    inner: BulkFileOptions,
+}
+
+#[derive(Debug, Clone)]
+struct BulkFileOptions {
+    index: Option<Index<'static>>,
+    id_field: Option<String>,
+    operations: bool,
+    body_size: usize,
+    concurrency: usize,
+}
+
+/**
+A summary of the documents loaded from a file.
+
+Failed documents don't stop the file from being loaded, so they're collected in the summary instead.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct BulkFileSummary {
+    requests: usize,
+    succeeded: usize,
+    errors: Vec<ErrorItem>,
+}
+
+impl BulkFileSummary {
+    /** The number of bulk requests that were sent. */
+    pub fn requests(&self) -> usize {
+        self.requests
+    }
+
+    /** The number of operations that succeeded. */
+    pub fn succeeded(&self) -> usize {
+        self.succeeded
+    }
+
+    /** The number of operations that failed. */
+    pub fn failed(&self) -> usize {
+        self.errors.len()
+    }
+
+    /** Returns `true` if every operation succeeded. */
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /**
+    The operations that failed.
+
+    The [`position`][ErrorItem.position] of each item is its position in the file, rather than in the bulk request it was sent in.
+    That's the line the document or operation starts on for a newline delimited file, counting from `1`,
+    or the index of the document for a file with a JSON array, counting from `0`.
+
+    [ErrorItem.position]: ../../responses/bulk/struct.ErrorItem.html#method.position
+    */
+    pub fn errors(&self) -> &[ErrorItem] {
+        &self.errors
+    }
+
+    /** Add the response to a bulk request, where `positions` are the positions in the file of its operations. */
+    fn add(&mut self, response: BulkResponse, positions: &[usize]) {
+        self.requests += 1;
+
+        for item in response {
+            match item {
+                Ok(_) => self.succeeded += 1,
+                Err(mut item) => {
+                    if let Some(&position) = positions.get(item.position()) {
+                        item.set_position(position);
+                    }
+
+                    self.errors.push(item)
+                }
+            }
+        }
+    }
+
+    /** Add the response to a bulk request, keeping the first request that failed. */
+    fn add_result(
+        &mut self,
+        (positions, res): (Vec<usize>, Result<BulkResponse, Error>),
+        failure: &mut Option<Error>,
+    ) {
+        match res {
+            Ok(res) => self.add(res, &positions),
+            Err(e) => {
+                failure.get_or_insert(e);
+            }
+        }
+    }
+}
+
+/**
+# Bulk file request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`BulkFileRequestBuilder`][BulkFileRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Load a file with one document per line into `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let summary = client.bulk_file("data/accounts.ndjson")
+                        .index("accounts")
+                        .id_field("account_number")
+                        .send()?;
+
+    println!("loaded {} documents, {} failed", summary.succeeded(), summary.failed());
+    # Ok(())
+    # }
+    ```
+
+    [BulkFileRequestBuilder]: requests/bulk/struct.BulkFileRequestBuilder.html
+    [builder-methods]: requests/bulk/struct.BulkFileRequestBuilder.html#builder-methods
+    [send-sync]: requests/bulk/struct.BulkFileRequestBuilder.html#send-synchronously
+    [send-async]: requests/bulk/struct.BulkFileRequestBuilder.html#send-asynchronously
+    */
+    pub fn bulk_file(&self, path: impl AsRef<Path>) -> BulkFileRequestBuilder<TSender> {
+        BulkFileRequestBuilder {
+            client: self.clone(),
+            path: path.as_ref().to_owned(),
+            inner: BulkFileOptions {
+                index: None,
+                id_field: None,
+                operations: false,
+                body_size: DEFAULT_BODY_SIZE,
+                concurrency: DEFAULT_CONCURRENCY,
+            },
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `BulkFileRequestBuilder` before sending it.
+*/
+impl<TSender> BulkFileRequestBuilder<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Set the default index for the bulk requests.
+
+    If an operation doesn't specify an index, then it will default to the supplied value here.
+    */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = Some(index.into());
+        self
+    }
+
+    /**
+    Use the value of a field in each document as its id.
+
+    The field must be a string or a number.
+    Documents without the field are indexed with an id generated by Elasticsearch.
+    This only applies to files of documents, not bulk operations.
+    */
+    pub fn id_field(mut self, field: impl Into<String>) -> Self {
+        self.inner.id_field = Some(field.into());
+        self
+    }
+
+    /**
+    Load a file of newline delimited bulk operations instead of documents.
+
+    Each operation is sent as it appears in the file, like the body of a bulk request.
+    A client with an [index scope][Client.scoped] rejects these requests, because the operations could target indices outside the scope.
+
+    [Client.scoped]: ../../struct.Client.html#method.scoped
+    */
+    pub fn operations(mut self) -> Self {
+        self.inner.operations = true;
+        self
+    }
+
+    /**
+    Set the approximate maximum size of the body of each bulk request.
+
+    An operation that's larger than this size is sent in a request on its own.
+    If this isn't specified then requests are up to 5MiB.
+    */
+    pub fn body_size_bytes(mut self, body_size: usize) -> Self {
+        self.inner.body_size = body_size;
+        self
+    }
+
+    /**
+    Set the maximum number of bulk requests sent at the same time.
+
+    If this isn't specified then up to `4` requests are sent at a time.
+    */
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.inner.concurrency = concurrency.max(1);
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl BulkFileRequestBuilder<SyncSender> {
+    /**
+    Load a file synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until every bulk request has been sent.
+    Requests are sent on a thread for each concurrent request.
+
+    If the file can't be read, or a bulk request fails, then an error is returned.
+    Documents sent in earlier requests will already have been loaded.
+
+    # Examples
+
+    Load the sample accounts dataset, which is a file of bulk operations:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let summary = client.bulk_file("data/accounts.json")
+                        .index("bank")
+                        .operations()
+                        .send()?;
+
+    for error in summary.errors() {
+        println!("err: {:?}", error);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<BulkFileSummary, Error> {
+        let BulkFileRequestBuilder {
+            client,
+            path,
+            inner,
+        } = self;
+
+        let chunks = Chunks::open(&path, &inner)?;
+
+        let options = Arc::new(inner);
+        let (chunk_tx, chunk_rx) = channel::bounded::<Chunk>(options.concurrency);
+        let (res_tx, res_rx) = channel::unbounded();
+
+        let workers: Vec<_> = (0..options.concurrency)
+            .map(|_| {
+                let client = client.clone();
+                let options = options.clone();
+                let chunk_rx = chunk_rx.clone();
+                let res_tx = res_tx.clone();
+
+                thread::spawn(move || {
+                    for chunk in chunk_rx {
+                        let res = options.send_sync(&client, chunk.body);
+
+                        if res_tx.send((chunk.positions, res)).is_err() {
+                            break;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        drop(res_tx);
+
+        let mut summary = BulkFileSummary::default();
+        let mut failure = None;
+
+        for chunk in chunks {
+            for res in res_rx.try_iter() {
+                summary.add_result(res, &mut failure);
+            }
+
+            let sent = chunk.and_then(|chunk| {
+                chunk_tx
+                    .send(chunk)
+                    .map_err(|_| error::request(error::message("the bulk file workers stopped")))
+            });
+
+            if let Err(e) = sent {
+                failure.get_or_insert(e);
+            }
+
+            // Stop reading the file once a request has failed
+            if failure.is_some() {
+                break;
+            }
+        }
+
+        drop(chunk_tx);
+
+        for res in res_rx {
+            summary.add_result(res, &mut failure);
+        }
+
+        for worker in workers {
+            if worker.join().is_err() {
+                failure.get_or_insert(error::request(error::message(
+                    "a bulk file worker panicked",
+                )));
+            }
+        }
+
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(summary),
+        }
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl BulkFileRequestBuilder<AsyncSender> {
+    /**
+    Load a file asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to a summary of the loaded documents once every bulk request has been sent.
+    The file is read on another thread so it doesn't block the executor the future runs on.
+    Only a few bulk requests are read ahead of the ones being sent.
+
+    If the file can't be read, or a bulk request fails, then the future resolves to an error.
+    Documents sent in earlier requests will already have been loaded.
+
+    # Examples
+
+    Load a file with a JSON array of documents into `myindex`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.bulk_file("data/documents.json")
+                       .index("myindex")
+                       .send();
+
+    future.and_then(|summary| {
+        println!("loaded {} documents", summary.succeeded());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending<BulkFileSummary> {
+        let (client, options) = (self.client, self.inner);
+        let concurrency = options.concurrency;
+
+        let chunks = match read_chunks(self.path, options.clone()) {
+            Ok(chunks) => chunks,
+            Err(e) => return Pending::new(Err(e).into_future()),
+        };
+
+        let summary = chunks
+            .map(move |chunk| {
+                let positions = chunk.positions;
+
+                options
+                    .send_async(&client, chunk.body)
+                    .map(move |res| (res, positions))
+            })
+            .buffer_unordered(concurrency)
+            .fold(
+                BulkFileSummary::default(),
+                |mut summary, (res, positions)| {
+                    summary.add(res, &positions);
+
+                    Ok::<_, Error>(summary)
+                },
+            );
+
+        Pending::new(summary)
+    }
+}
+
+/**
+Read the chunks of a file on another thread.
+
+Reading the file blocks, so it's kept off the executor that sends the requests.
+The channel is bounded by the number of concurrent requests, so the file is only read a few chunks ahead of them.
+*/
+fn read_chunks(
+    path: PathBuf,
+    options: BulkFileOptions,
+) -> Result<impl Stream<Item = Chunk, Error = Error> + Send, Error> {
+    let (tx, rx) = mpsc::channel(options.concurrency);
+
+    thread::Builder::new()
+        .name("elastic-bulk-file".to_owned())
+        .spawn(move || {
+            let chunks = match Chunks::open(&path, &options) {
+                Ok(chunks) => chunks,
+                Err(e) => {
+                    let _ = tx.send(Err(e)).wait();
+                    return;
+                }
+            };
+
+            let mut tx = tx;
+            for chunk in chunks {
+                let failed = chunk.is_err();
+
+                // The receiver is gone if loading the file has stopped
+                tx = match tx.send(chunk).wait() {
+                    Ok(tx) => tx,
+                    Err(_) => break,
+                };
+
+                if failed {
+                    break;
+                }
+            }
+        })
+        .map_err(error::request)?;
+
+    Ok(rx
+        .map_err(|()| error::request(error::message("the bulk file reader stopped")))
+        .and_then(|chunk| chunk))
+}
+
+impl BulkFileOptions {
+    fn send_sync(&self, client: &SyncClient, body: ChunkBody) -> Result<BulkResponse, Error> {
+        match body {
+            ChunkBody::Documents(docs) => {
+                let mut req = client.bulk();
+                if let Some(ref index) = self.index {
+                    req = req.index(index.clone());
+                }
+
+                req.extend(docs.into_iter().map(|doc| self.operation(doc)))
+                    .send()
+            }
+            ChunkBody::Operations(body) => client
+                .request(self.operations_request(body))
+                .send()?
+                .into_response(),
+        }
+    }
+
+    fn send_async(&self, client: &AsyncClient, body: ChunkBody) -> Pending<BulkResponse> {
+        match body {
+            ChunkBody::Documents(docs) => {
+                let mut req = client.bulk();
+                if let Some(ref index) = self.index {
+                    req = req.index(index.clone());
+                }
+
+                req.extend(docs.into_iter().map(|doc| self.operation(doc)))
+                    .send()
+            }
+            ChunkBody::Operations(body) => Pending::new(
+                client
+                    .request(self.operations_request(body))
+                    .send()
+                    .and_then(|res| res.into_response()),
+            ),
+        }
+    }
+
+    /** The bulk operation for a document. */
+    fn operation(&self, doc: Value) -> BulkOperation<Value> {
+        let id = document_id(self.id_field.as_ref(), &doc);
+
+        let op = bulk_raw().index(doc);
+
+        match id {
+            Some(id) => op.id(id),
+            None => op,
+        }
+    }
+
+    fn operations_request(&self, body: Vec<u8>) -> BulkRequest<'static, Vec<u8>> {
+        match self.index {
+            Some(ref index) => BulkRequest::for_index(index.clone(), body),
+            None => BulkRequest::new(body),
+        }
+    }
+}
+
+/** The id of a document from the value of its id field. */
+fn document_id(id_field: Option<&String>, doc: &Value) -> Option<String> {
+    id_field.and_then(|field| match doc.get(field) {
+        Some(Value::String(id)) => Some(id.clone()),
+        Some(Value::Number(id)) => Some(id.to_string()),
+        _ => None,
+    })
+}
+
+/** A set of operations to send in a single bulk request. */
+#[derive(Debug, PartialEq)]
+struct Chunk {
+    body: ChunkBody,
+    /** The position in the file of each operation in the body. */
+    positions: Vec<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+enum ChunkBody {
+    Documents(Vec<Value>),
+    Operations(Vec<u8>),
+}
+
+/** A single document or operation read from a file. */
+enum Entry {
+    /** A document and the size of its JSON in bytes. */
+    Document(Value, usize),
+    Operation(Vec<u8>),
+}
+
+/** The entries in a file, along with their positions. */
+enum Entries {
+    Lines {
+        lines: Lines<BufReader<File>>,
+        line: usize,
+        operations: bool,
+    },
+    Array {
+        docs: channel::Receiver<Result<Value, Error>>,
+        index: usize,
+    },
+}
+
+impl Entries {
+    fn open(path: &Path, operations: bool) -> Result<Self, Error> {
+        let mut reader = BufReader::new(File::open(path).map_err(error::request)?);
+
+        if starts_with_array(&mut reader).map_err(error::request)? {
+            if operations {
+                return Err(error::request(error::message(
+                    "a file of bulk operations must be newline delimited, not a JSON array",
+                )));
+            }
+
+            Ok(Entries::Array {
+                docs: read_array(reader)?,
+                index: 0,
+            })
+        } else {
+            Ok(Entries::Lines {
+                lines: reader.lines(),
+                line: 0,
+                operations,
+            })
+        }
+    }
+}
+
+/** Whether the first character in a file that isn't whitespace starts a JSON array. */
+fn starts_with_array(reader: &mut impl BufRead) -> io::Result<bool> {
+    loop {
+        let (skip, found) = {
+            let buf = reader.fill_buf()?;
+
+            if buf.is_empty() {
+                return Ok(false);
+            }
+
+            match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+                Some(i) => (i, Some(buf[i] == b'[')),
+                None => (buf.len(), None),
+            }
+        };
+
+        reader.consume(skip);
+
+        if let Some(found) = found {
+            return Ok(found);
+        }
+    }
+}
+
+/**
+Read the documents in a JSON array on another thread.
+
+The array is deserialised one document at a time, so the whole file is never held in memory.
+*/
+fn read_array(reader: BufReader<File>) -> Result<channel::Receiver<Result<Value, Error>>, Error> {
+    struct Documents<'a>(&'a channel::Sender<Result<Value, Error>>);
+
+    impl<'de, 'a> Visitor<'de> for Documents<'a> {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a JSON array of documents")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            while let Some(doc) = seq.next_element()? {
+                // The receiver is gone if loading the file has stopped
+                if self.0.send(Ok(doc)).is_err() {
+                    break;
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    let (tx, rx) = channel::bounded(ARRAY_READ_AHEAD);
+
+    thread::Builder::new()
+        .name("elastic-bulk-file".to_owned())
+        .spawn(move || {
+            let mut de = serde_json::Deserializer::from_reader(reader);
+
+            if let Err(e) = de.deserialize_seq(Documents(&tx)).and_then(|_| de.end()) {
+                let _ = tx.send(Err(error::request(error::message(format!(
+                    "the JSON array in the bulk file isn't valid: {}",
+                    e
+                )))));
+            }
+        })
+        .map_err(error::request)?;
+
+    Ok(rx)
+}
+
+impl Iterator for Entries {
+    type Item = Result<(usize, Entry), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match *self {
+            Entries::Array {
+                ref docs,
+                ref mut index,
+            } => {
+                let doc = docs.recv().ok()?;
+
+                let position = *index;
+                *index += 1;
+
+                Some(doc.and_then(|doc| {
+                    let size = serde_json::to_vec(&doc).map_err(error::request)?.len();
+
+                    Ok((position, Entry::Document(doc, size)))
+                }))
+            }
+            Entries::Lines {
+                ref mut lines,
+                ref mut line,
+                operations,
+            } => {
+                let mut next_line = || -> Option<Result<(usize, String), Error>> {
+                    for next in lines.by_ref() {
+                        *line += 1;
+
+                        match next {
+                            Ok(ref next) if next.trim().is_empty() => continue,
+                            Ok(next) => return Some(Ok((*line, next))),
+                            Err(e) => return Some(Err(error::request(e))),
+                        }
+                    }
+
+                    None
+                };
+
+                let (number, first) = match next_line()? {
+                    Ok(first) => first,
+                    Err(e) => return Some(Err(e)),
+                };
+
+                if operations {
+                    Some(operation(number, first, next_line).map(|op| (number, op)))
+                } else {
+                    Some(
+                        parse_line::<Value>(number, &first)
+                            .map(|doc| (number, Entry::Document(doc, first.len()))),
+                    )
+                }
+            }
+        }
+    }
+}
+
+/** Read a bulk operation, which is an action line followed by a source line unless it's a delete. */
+fn operation(
+    number: usize,
+    action: String,
+    mut next_line: impl FnMut() -> Option<Result<(usize, String), Error>>,
+) -> Result<Entry, Error> {
+    let header = parse_line::<Map<String, Value>>(number, &action)?;
+
+    let mut op = action.into_bytes();
+    op.push(b'\n');
+
+    if header.contains_key("delete") {
+        return Ok(Entry::Operation(op));
+    }
+
+    let (number, source) = next_line().unwrap_or_else(|| {
+        Err(error::request(error::message(format!(
+            "the bulk operation on line {} is missing its source",
+            number
+        ))))
+    })?;
+
+    parse_line::<IgnoredAny>(number, &source)?;
+
+    op.extend(source.into_bytes());
+    op.push(b'\n');
+
+    Ok(Entry::Operation(op))
+}
+
+fn parse_line<T>(number: usize, line: &str) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_str(line).map_err(|e| {
+        error::request(error::message(format!(
+            "line {} of the bulk file isn't valid: {}",
+            number, e
+        )))
+    })
+}
+
+/** The entries in a file grouped into bulk requests. */
+struct Chunks {
+    entries: Entries,
+    next: Option<(usize, Entry)>,
+    id_field: Option<String>,
+    body_size: usize,
+}
+
+impl Chunks {
+    fn open(path: &Path, options: &BulkFileOptions) -> Result<Self, Error> {
+        Ok(Chunks {
+            entries: Entries::open(path, options.operations)?,
+            next: None,
+            id_field: options.id_field.clone(),
+            body_size: options.body_size,
+        })
+    }
+
+    /**
+    The number of bytes an entry adds to the body of a bulk request.
+
+    Documents are sent after an action line, and each line ends with a newline.
+    */
+    fn size(&self, entry: &Entry) -> usize {
+        match *entry {
+            Entry::Document(ref doc, size) => {
+                let id = document_id(self.id_field.as_ref(), doc).map_or(0, |id| {
+                    "\"_id\":".len() + Value::String(id).to_string().len()
+                });
+
+                INDEX_ACTION.len() + id + size + 1
+            }
+            Entry::Operation(ref op) => op.len(),
+        }
+    }
+}
+
+impl Iterator for Chunks {
+    type Item = Result<Chunk, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut docs = Vec::new();
+        let mut ops = Vec::new();
+        let mut positions = Vec::new();
+        let mut size = 0;
+
+        loop {
+            let (position, entry) = match self.next.take().map(Ok).or_else(|| self.entries.next()) {
+                Some(Ok(entry)) => entry,
+                Some(Err(e)) => return Some(Err(e)),
+                None => break,
+            };
+
+            let entry_size = self.size(&entry);
+            if !positions.is_empty() && size + entry_size > self.body_size {
+                self.next = Some((position, entry));
+                break;
+            }
+
+            size += entry_size;
+            positions.push(position);
+
+            match entry {
+                Entry::Document(doc, _) => docs.push(doc),
+                Entry::Operation(op) => ops.extend(op),
+            }
+        }
+
+        let body = if !docs.is_empty() {
+            ChunkBody::Documents(docs)
+        } else if !ops.is_empty() {
+            ChunkBody::Operations(ops)
+        } else {
+            return None;
+        };
+
+        Some(Ok(Chunk { body, positions }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::sender::recording::test_recording,
+        prelude::*,
+        tests::*,
+    };
+    use std::{
+        env,
+        fs,
+    };
+    use uuid::Uuid;
+
+    fn temp_file(contents: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("elastic-bulk-file-{}.json", Uuid::new_v4()));
+        fs::write(&path, contents).unwrap();
+
+        path
+    }
+
+    fn options(operations: bool, body_size: usize) -> BulkFileOptions {
+        BulkFileOptions {
+            index: None,
+            id_field: None,
+            operations,
+            body_size,
+            concurrency: 1,
+        }
+    }
+
+    fn chunks(contents: &str, operations: bool, body_size: usize) -> Result<Vec<Chunk>, Error> {
+        chunks_with(contents, &options(operations, body_size))
+    }
+
+    fn chunks_with(contents: &str, options: &BulkFileOptions) -> Result<Vec<Chunk>, Error> {
+        let path = temp_file(contents);
+        let chunks =
+            Chunks::open(&path, options).and_then(|chunks| chunks.collect::<Result<Vec<_>, _>>());
+        fs::remove_file(&path).unwrap();
+
+        chunks
+    }
+
+    fn documents(docs: Vec<Value>, positions: Vec<usize>) -> Chunk {
+        Chunk {
+            body: ChunkBody::Documents(docs),
+            positions,
+        }
+    }
+
+    fn operations(ops: &[u8], positions: Vec<usize>) -> Chunk {
+        Chunk {
+            body: ChunkBody::Operations(ops.to_vec()),
+            positions,
+        }
+    }
+
+    fn error_message(e: Error) -> String {
+        match e {
+            Error::Client(ref e) => format!("{:?}", e),
+            _ => panic!("expected a client error"),
+        }
+    }
+
+    #[test]
+    fn chunk_ndjson_documents() {
+        let chunks = chunks("{\"a\":1}\n\n{\"a\":2}\n{\"a\":3}\n", false, 42).unwrap();
+
+        assert_eq!(
+            vec![
+                documents(vec![json!({ "a": 1 }), json!({ "a": 2 })], vec![1, 3]),
+                documents(vec![json!({ "a": 3 })], vec![4]),
+            ],
+            chunks
+        );
+    }
+
+    #[test]
+    fn chunk_array_documents() {
+        let chunks = chunks("  \n[{\"a\":1}, {\"a\":2}]", false, 1).unwrap();
+
+        assert_eq!(
+            vec![
+                documents(vec![json!({ "a": 1 })], vec![0]),
+                documents(vec![json!({ "a": 2 })], vec![1]),
+            ],
+            chunks
+        );
+    }
+
+    #[test]
+    fn chunk_large_array_documents() {
+        let docs: Vec<_> = (0..ARRAY_READ_AHEAD * 3)
+            .map(|i| json!({ "a": i }))
+            .collect();
+
+        let chunks = chunks(&serde_json::to_string(&docs).unwrap(), false, 1).unwrap();
+
+        assert_eq!(docs.len(), chunks.len());
+        assert_eq!(
+            documents(vec![json!({ "a": docs.len() - 1 })], vec![docs.len() - 1]),
+            chunks[docs.len() - 1]
+        );
+    }
+
+    #[test]
+    fn chunk_invalid_array() {
+        let e = chunks("[{\"a\":1}, {\"a\":]", false, 1).unwrap_err();
+
+        assert!(error_message(e).contains("the JSON array in the bulk file isn't valid"));
+    }
+
+    #[test]
+    fn chunk_size_includes_action() {
+        let options = BulkFileOptions {
+            id_field: Some("id".to_owned()),
+            ..options(false, 74)
+        };
+
+        let file = "{\"id\":\"abc\"}\n{\"id\":\"def\"}\n";
+
+        assert_eq!(1, chunks_with(file, &options).unwrap().len());
+        assert_eq!(
+            2,
+            chunks_with(
+                file,
+                &BulkFileOptions {
+                    body_size: 73,
+                    ..options.clone()
+                }
+            )
+            .unwrap()
+            .len()
+        );
+
+        let op = options.operation(json!({ "id": "abc" }));
+        let mut body = Vec::new();
+        op.write(&mut body).unwrap();
+
+        assert_eq!(37, body.len());
+    }
+
+    #[test]
+    fn chunk_operations() {
+        let file = "{\"index\":{\"_id\":\"1\"}}\n{\"a\":1}\n{\"delete\":{\"_id\":\"2\"}}\n{\"create\":{}}\n{\"a\":3}\n";
+        let chunks = chunks(file, true, 60).unwrap();
+
+        assert_eq!(
+            vec![
+                operations(
+                    b"{\"index\":{\"_id\":\"1\"}}\n{\"a\":1}\n{\"delete\":{\"_id\":\"2\"}}\n",
+                    vec![1, 3]
+                ),
+                operations(b"{\"create\":{}}\n{\"a\":3}\n", vec![4]),
+            ],
+            chunks
+        );
+    }
+
+    #[test]
+    fn chunk_operation_without_source() {
+        let e = chunks("{\"index\":{}}\n", true, 50).unwrap_err();
+
+        assert!(error_message(e).contains("the bulk operation on line 1 is missing its source"));
+    }
+
+    #[test]
+    fn chunk_invalid_line() {
+        let e = chunks("{\"a\":1}\n{\"a\":\n", false, 50).unwrap_err();
+
+        assert!(error_message(e).contains("line 2 of the bulk file isn't valid"));
+    }
+
+    #[test]
+    fn chunk_operations_array() {
+        let e = chunks("[{\"index\":{}}]", true, 50).unwrap_err();
+
+        assert!(error_message(e).contains("must be newline delimited"));
+    }
+
+    #[test]
+    fn operation_id_field() {
+        let options = BulkFileOptions {
+            id_field: Some("id".to_owned()),
+            ..options(false, 50)
+        };
+
+        let mut body = Vec::new();
+        options
+            .operation(json!({ "id": 1 }))
+            .write(&mut body)
+            .unwrap();
+        options
+            .operation(json!({ "id": "a" }))
+            .write(&mut body)
+            .unwrap();
+        options
+            .operation(json!({ "id": [] }))
+            .write(&mut body)
+            .unwrap();
+
+        assert_eq!(
+            "{\"index\":{\"_id\":\"1\"}}\n{\"id\":1}\n{\"index\":{\"_id\":\"a\"}}\n{\"id\":\"a\"}\n{\"index\":{}}\n{\"id\":[]}\n",
+            String::from_utf8(body).unwrap()
+        );
+    }
+
+    const BULK_RESPONSE: &str = r#"{"took":1,"errors":true,"items":[{"index":{"_index":"myindex","_type":"_doc","_id":"1","_version":1,"result":"created","status":201}},{"index":{"_index":"myindex","_type":"_doc","_id":"2","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}}]}"#;
+
+    /** A recording of two bulk requests that each index two documents, with one failure each. */
+    fn bulk_recording() -> PathBuf {
+        let interaction = |body: &str| {
+            json!({
+                "request": { "method": "POST", "path": "/myindex/_bulk", "body": body },
+                "response": { "status": 200, "body": BULK_RESPONSE }
+            })
+        };
+
+        test_recording(vec![
+            interaction("{\"index\":{\"_id\":\"1\"}}\n{\"id\":1}\n{\"index\":{\"_id\":\"2\"}}\n{\"id\":2}\n"),
+            interaction("{\"index\":{\"_id\":\"3\"}}\n{\"id\":3}\n{\"index\":{\"_id\":\"4\"}}\n{\"id\":4}\n"),
+        ])
+    }
+
+    const DOCUMENTS: &str = "{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n{\"id\":4}\n";
+
+    fn assert_summary(summary: &BulkFileSummary) {
+        assert_eq!(2, summary.requests());
+        assert_eq!(2, summary.succeeded());
+        assert_eq!(2, summary.failed());
+        assert!(!summary.is_ok());
+
+        // The second document in each request failed, which are on lines 2 and 4 of the file
+        let mut positions: Vec<_> = summary.errors().iter().map(|e| e.position()).collect();
+        positions.sort();
+
+        assert_eq!(vec![2, 4], positions);
+    }
+
+    #[test]
+    fn bulk_file_sync() {
+        let recording = bulk_recording();
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&recording)
+            .build()
+            .unwrap();
+        fs::remove_file(&recording).unwrap();
+
+        let path = temp_file(DOCUMENTS);
+        let summary = client
+            .bulk_file(&path)
+            .index("myindex")
+            .id_field("id")
+            .body_size_bytes(64)
+            .concurrency(2)
+            .send();
+        fs::remove_file(&path).unwrap();
+
+        assert_summary(&summary.unwrap());
+    }
+
+    #[test]
+    fn bulk_file_sync_request_error() {
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .pre_send_raw(|_| Err("request failed".into()))
+            .build()
+            .unwrap();
+
+        let path = temp_file(DOCUMENTS);
+        let res = client
+            .bulk_file(&path)
+            .index("myindex")
+            .body_size_bytes(64)
+            .send();
+        fs::remove_file(&path).unwrap();
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn bulk_file_async() {
+        let recording = bulk_recording();
+        let client = AsyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&recording)
+            .build()
+            .unwrap();
+        fs::remove_file(&recording).unwrap();
+
+        let path = temp_file(DOCUMENTS);
+        let summary = client
+            .bulk_file(&path)
+            .index("myindex")
+            .id_field("id")
+            .body_size_bytes(64)
+            .send()
+            .wait();
+        fs::remove_file(&path).unwrap();
+
+        assert_summary(&summary.unwrap());
+    }
+
+    #[test]
+    fn bulk_file_missing_file() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        assert!(client.bulk_file("does-not-exist.json").send().is_err());
+    }
+
+    #[test]
+    fn bulk_file_async_missing_file() {
+        let client = AsyncClientBuilder::new().build().unwrap();
+
+        assert!(client
+            .bulk_file("does-not-exist.json")
+            .send()
+            .wait()
+            .is_err());
+    }
+
+    #[test]
+    fn read_chunks_stops_after_error() {
+        let path = temp_file("{\"id\":1}\nnot json\n{\"id\":3}\n");
+
+        let chunks: Vec<_> = read_chunks(path.clone(), options(false, 1))
+            .unwrap()
+            .then(Ok::<_, ()>)
+            .collect()
+            .wait()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let mut chunks = chunks.into_iter();
+
+        match chunks.next().unwrap() {
+            Err(e) => assert!(error_message(e).contains("line 2 of the bulk file isn't valid")),
+            Ok(_) => panic!("expected an invalid line"),
+        }
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    fn bulk_file_builder_is_send() {
+        assert_send::<BulkFileRequestBuilder<AsyncSender>>();
+        assert_send::<Pending<BulkFileSummary>>();
+    }
+}
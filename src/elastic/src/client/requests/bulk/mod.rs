@@ -20,6 +20,10 @@ use serde::{
 use crate::{
     client::{
         requests::{
+            common::{
+                time_value,
+                ActiveShards,
+            },
             raw::RawRequestInner,
             Pending as BasePending,
             RequestBuilder,
@@ -70,10 +74,12 @@ Call [`Client.bulk_stream`][Client.bulk_stream] to get a `BulkRequestBuilder` th
 pub type BulkRequestBuilder<TSender, TBody, TResponse> =
     RequestBuilder<TSender, BulkRequestInner<TBody, TResponse>>;
 
+mod file;
 mod operation;
 mod stream;
 
 pub use self::{
+    file::*,
     operation::*,
     stream::*,
 };
@@ -389,9 +395,40 @@ where
     fn push_internal<TDocument, TOperation>(&mut self, op: TOperation)
     where
         TOperation: Into<BulkOperation<TDocument>>,
-        TDocument: Serialize,
+        TDocument: Serialize + 'static,
     {
-        self.inner.body.with_inner_mut(|b| b.push(op.into()));
+        let client = &self.client;
+        let op = op
+            .into()
+            .default_id_strategy(client.id_strategy())
+            .map_document_index(|index| client.document_index(index))
+            .map_index(|index| client.scoped_index(index));
+
+        match self.client.document_serializer {
+            Some(ref serializer) => self.inner.body.with_inner_mut(|b| {
+                let op = op.serialize_with(&**serializer).map_err(error::request)?;
+                b.push(op)
+            }),
+            None => self.inner.body.with_inner_mut(|b| b.push(op)),
+        }
+    }
+
+    /**
+    Set the number of shard copies that must be active before the bulk request proceeds.
+
+    If this isn't specified then Elasticsearch waits for the primary shard only.
+    */
+    pub fn wait_for_active_shards(self, shards: impl Into<ActiveShards>) -> Self {
+        let shards = shards.into();
+
+        self.params_fluent(move |params| params.url_param("wait_for_active_shards", shards))
+    }
+
+    /** Set how long to wait for active shards and for the bulk request to complete. */
+    pub fn timeout(self, timeout: Duration) -> Self {
+        let timeout = time_value(timeout);
+
+        self.params_fluent(move |params| params.url_param("timeout", &timeout))
     }
 
     /**
@@ -404,7 +441,7 @@ where
     pub fn push<TDocument, TOperation>(mut self, op: TOperation) -> Self
     where
         TOperation: Into<BulkOperation<TDocument>>,
-        TDocument: Serialize,
+        TDocument: Serialize + 'static,
     {
         self.push_internal(op);
         self
@@ -420,7 +457,7 @@ where
     pub fn extend<TIter, TDocument>(mut self, iter: TIter) -> Self
     where
         TIter: IntoIterator<Item = BulkOperation<TDocument>>,
-        TDocument: Serialize,
+        TDocument: Serialize + 'static,
     {
         for op in iter.into_iter() {
             self.push_internal(op);
@@ -434,7 +471,7 @@ impl<TSender, TBody, TDocument, TResponse> Extend<BulkOperation<TDocument>>
 where
     TSender: Sender,
     TBody: BulkBody,
-    TDocument: Serialize,
+    TDocument: Serialize + 'static,
 {
     fn extend<T>(&mut self, iter: T)
     where
@@ -522,6 +559,14 @@ where
     fn into_request(self) -> Result<BulkRequest<'static, TBody>, Error> {
         let body = self.body.try_into_inner()?;
 
+        if let Some(ref index) = self.index {
+            index.validate().map_err(error::request)?;
+        }
+
+        if let Some(ref ty) = self.ty {
+            ty.validate().map_err(error::request)?;
+        }
+
         match (self.index, self.ty) {
             (Some(index), ty) => match ty {
                 None => Ok(BulkRequest::for_index(index, body)),
@@ -595,9 +640,13 @@ where
     pub fn send(self) -> Result<TResponse, Error> {
         let req = self.inner.into_request()?;
 
-        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
-            .send()?
-            .into_response()
+        RequestBuilder::new(
+            self.client,
+            self.params_builder,
+            RawRequestInner::new(req).scoped_body(),
+        )
+        .send()?
+        .into_response()
     }
 }
 
@@ -666,9 +715,13 @@ where
         let req_future = client.sender.maybe_async(move || inner.into_request());
 
         let res_future = req_future.and_then(move |req| {
-            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
-                .send()
-                .and_then(|res| res.into_response())
+            RequestBuilder::new(
+                client,
+                params_builder,
+                RawRequestInner::new(req).scoped_body(),
+            )
+            .send()
+            .and_then(|res| res.into_response())
         });
 
         Pending::new(res_future)
@@ -837,7 +890,20 @@ impl<TIndex, TType, TId, TNewId> ChangeId<TNewId> for BulkErrorsResponse<TIndex,
 
 #[cfg(test)]
 mod tests {
+    use serde_json::Value;
+    use std::{
+        io::{
+            self,
+            Write,
+        },
+        time::Duration,
+    };
+
     use crate::{
+        client::{
+            Document,
+            DocumentSerializer,
+        },
         prelude::*,
         tests::*,
     };
@@ -893,4 +959,176 @@ mod tests {
 
         assert!(req.is_err());
     }
+
+    #[test]
+    fn invalid_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let err = client
+            .bulk()
+            .index("My-Index")
+            .inner
+            .into_request()
+            .unwrap_err();
+
+        assert!(format!("{:?}", err).contains("isn't lowercase"));
+    }
+
+    #[test]
+    fn invalid_operation_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let err = client
+            .bulk()
+            .push(bulk_raw().delete().index("My-Index").id(1))
+            .inner
+            .into_request()
+            .unwrap_err();
+
+        assert!(format!("{:?}", err).contains("isn't lowercase"));
+    }
+
+    #[test]
+    fn document_index_name() {
+        #[derive(Serialize, ElasticType)]
+        #[elastic(crate_root = "crate::types")]
+        struct TestDoc {}
+
+        let client = SyncClientBuilder::new()
+            .document_index_name(|index| format!("staging_{}", index))
+            .build()
+            .unwrap();
+
+        let req = client
+            .bulk()
+            .push(bulk::<TestDoc>().index(TestDoc {}))
+            .push(bulk::<TestDoc>().delete(1).index("test-idx"))
+            .push(bulk_raw().delete().index("raw-idx").id(2))
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected = "{\"index\":{\"_index\":\"staging_testdoc\",\"_type\":\"_doc\"}}\n{}\n{\"delete\":{\"_index\":\"test-idx\",\"_type\":\"_doc\",\"_id\":\"1\"}}\n{\"delete\":{\"_index\":\"raw-idx\",\"_id\":\"2\"}}\n";
+
+        assert_eq!(expected, String::from_utf8(req.body).unwrap());
+    }
+
+    #[test]
+    fn document_serializer() {
+        struct Tagged;
+
+        impl DocumentSerializer for Tagged {
+            fn serialize(&self, doc: Document, writer: &mut dyn Write) -> io::Result<()> {
+                serde_json::to_writer(writer, &json!({ "tagged": doc.to_value()? }))?;
+
+                Ok(())
+            }
+        }
+
+        let client = SyncClientBuilder::new()
+            .document_serializer(Tagged)
+            .build()
+            .unwrap();
+
+        let req = client
+            .bulk()
+            .push(bulk_raw().index(json!({ "key": "a" })).index("test-idx"))
+            .push(
+                bulk_raw()
+                    .update(json!({ "key": "b" }))
+                    .doc_as_upsert()
+                    .index("test-idx")
+                    .id(1),
+            )
+            .push(
+                bulk_raw()
+                    .update_script("ctx._source.n += 1")
+                    .index("test-idx")
+                    .id(2),
+            )
+            .push(bulk_raw().delete().index("test-idx").id(3))
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected = "{\"index\":{\"_index\":\"test-idx\"}}\n{\"tagged\":{\"key\":\"a\"}}\n{\"update\":{\"_index\":\"test-idx\",\"_id\":\"1\"}}\n{\"doc\":{\"tagged\":{\"key\":\"b\"}},\"doc_as_upsert\":true}\n{\"update\":{\"_index\":\"test-idx\",\"_id\":\"2\"}}\n{\"script\":{\"inline\":\"ctx._source.n += 1\"}}\n{\"delete\":{\"_index\":\"test-idx\",\"_id\":\"3\"}}\n";
+
+        assert_eq!(expected, String::from_utf8(req.body).unwrap());
+    }
+
+    #[test]
+    fn specify_id_strategy() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let ids = IdStrategy::hash(|doc: &Value| doc["key"].as_str().unwrap().to_owned());
+
+        let req = client
+            .bulk()
+            .push(
+                bulk_raw()
+                    .index(json!({ "key": "a" }))
+                    .index("test-idx")
+                    .id_strategy(&ids),
+            )
+            .push(
+                bulk_raw()
+                    .index(json!({ "key": "b" }))
+                    .index("test-idx")
+                    .id(1)
+                    .id_strategy(&ids),
+            )
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected = "{\"index\":{\"_index\":\"test-idx\",\"_id\":\"a\"}}\n{\"key\":\"a\"}\n{\"index\":{\"_index\":\"test-idx\",\"_id\":\"1\"}}\n{\"key\":\"b\"}\n";
+
+        assert_eq!(expected, String::from_utf8(req.body).unwrap());
+    }
+
+    #[test]
+    fn client_id_strategy() {
+        let client = SyncClientBuilder::new()
+            .document_id_strategy(IdStrategy::hash(|doc: &Value| {
+                doc["key"].as_str().unwrap().to_owned()
+            }))
+            .build()
+            .unwrap();
+
+        let req = client
+            .bulk()
+            .push(bulk_raw().index(json!({ "key": "a" })).index("test-idx"))
+            .push(
+                bulk_raw()
+                    .index(json!({ "key": "b" }))
+                    .index("test-idx")
+                    .id(1),
+            )
+            .push(
+                bulk_raw()
+                    .index(json!({ "key": "c" }))
+                    .index("test-idx")
+                    .id_strategy(&IdStrategy::none()),
+            )
+            .inner
+            .into_request()
+            .unwrap();
+
+        let expected = "{\"index\":{\"_index\":\"test-idx\",\"_id\":\"a\"}}\n{\"key\":\"a\"}\n{\"index\":{\"_index\":\"test-idx\",\"_id\":\"1\"}}\n{\"key\":\"b\"}\n{\"index\":{\"_index\":\"test-idx\"}}\n{\"key\":\"c\"}\n";
+
+        assert_eq!(expected, String::from_utf8(req.body).unwrap());
+    }
+
+    #[test]
+    fn specify_wait_for_active_shards_timeout() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .bulk()
+            .wait_for_active_shards(ActiveShards::All)
+            .timeout(Duration::from_secs(90))
+            .url_params();
+
+        assert_eq!(vec!["timeout=90s", "wait_for_active_shards=all"], params);
+    }
 }
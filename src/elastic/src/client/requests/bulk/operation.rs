@@ -12,18 +12,28 @@ use serde::ser::{
     SerializeMap,
     Serializer,
 };
-use serde_json::Value;
+use serde_json::{
+    value::RawValue,
+    Value,
+};
 
 use crate::{
-    client::requests::common::{
-        DefaultParams,
-        Doc,
-        Script,
-        ScriptBuilder,
+    client::{
+        document_serializer,
+        requests::common::{
+            DefaultParams,
+            Doc,
+            IdStrategy,
+            Script,
+            ScriptBuilder,
+        },
+        Document,
+        DocumentSerializer,
     },
     params::{
         Id,
         Index,
+        InvalidParamError,
         Type,
     },
     types::document::DocumentType,
@@ -60,6 +70,28 @@ struct BulkHeader {
         skip_serializing_if = "Option::is_none"
     )]
     id: Option<Id<'static>>,
+    #[serde(skip)]
+    document_index: bool,
+    #[serde(skip)]
+    id_strategy: bool,
+}
+
+impl BulkHeader {
+    fn validate(&self) -> Result<(), InvalidParamError> {
+        if let Some(ref index) = self.index {
+            index.validate()?;
+        }
+
+        if let Some(ref ty) = self.ty {
+            ty.validate()?;
+        }
+
+        if let Some(ref id) = self.id {
+            id.validate()?;
+        }
+
+        Ok(())
+    }
 }
 
 fn serialize_param<S, T>(field: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
@@ -157,6 +189,31 @@ impl<TValue> BulkOperation<TValue> {
     */
     pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
         self.header.index = Some(index.into());
+        self.header.document_index = false;
+        self
+    }
+
+    /**
+    Transform the index of the operation if it was taken from a document type.
+
+    An index that was set with [`index`](#method.index) is left unchanged.
+    */
+    pub(crate) fn map_document_index(
+        mut self,
+        f: impl FnOnce(Index<'static>) -> Index<'static>,
+    ) -> Self {
+        if self.header.document_index {
+            self.header.index = self.header.index.take().map(f);
+            self.header.document_index = false;
+        }
+
+        self
+    }
+
+    /** Map the index of the operation, if it has one. */
+    pub(crate) fn map_index(mut self, f: impl FnOnce(Index<'static>) -> Index<'static>) -> Self {
+        self.header.index = self.header.index.take().map(f);
+
         self
     }
 
@@ -228,16 +285,84 @@ impl<TDocument> BulkOperation<TDocument>
 where
     TDocument: Serialize,
 {
+    /**
+    Generate an id for an index or create operation using the given [`IdStrategy`][IdStrategy].
+
+    If the operation already has an id then it's left unchanged.
+    This strategy is used instead of one set with `document_id_strategy` on the client builder.
+
+    # Examples
+
+    Bulk index documents with ids derived from their contents, so retrying the request won't create duplicates:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    # fn get_docs() -> Vec<MyType> { vec![] }
+    #[derive(Serialize, Deserialize, ElasticType)]
+    struct MyType {
+        account: String,
+        title: String,
+    }
+
+    let ids = IdStrategy::hash(|doc: &MyType| doc.account.clone());
+
+    let ops = get_docs()
+        .into_iter()
+        .map(|doc| bulk::<MyType>().index(doc).id_strategy(&ids));
+
+    let response = client.bulk().extend(ops).send()?;
+    # Ok(())
+    # }
+    ```
+
+    [IdStrategy]: ../common/struct.IdStrategy.html
+    */
+    pub fn id_strategy(mut self, strategy: &IdStrategy<TDocument>) -> Self {
+        match self.action {
+            Action::Index | Action::Create if self.header.id.is_none() => {
+                if let Some(ref doc) = self.inner {
+                    self.header.id = strategy.id(doc);
+                }
+            }
+            _ => (),
+        }
+
+        self.header.id_strategy = true;
+        self
+    }
+
+    /**
+    Generate an id for the operation using a strategy from the client.
+
+    An operation that was given its own strategy with [`id_strategy`](#method.id_strategy) is left unchanged.
+    */
+    pub(crate) fn default_id_strategy(self, strategy: Option<&IdStrategy<TDocument>>) -> Self {
+        match strategy {
+            Some(strategy) if !self.header.id_strategy => self.id_strategy(strategy),
+            _ => self,
+        }
+    }
+
     /**
     Write the operation to the given writer.
 
     Bulk operations have a particular line-delimited format.
     This method will write a json header, then a newline, then the document body.
+
+    Returns an error without writing anything if the index, type or id of the operation isn't valid.
     */
     pub fn write<W>(&self, mut writer: W) -> io::Result<()>
     where
         W: Write,
     {
+        self.header
+            .validate()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
         struct Header<'a> {
             action: Action,
             inner: &'a BulkHeader,
@@ -279,6 +404,58 @@ where
 
         Ok(())
     }
+
+    /**
+    Serialise the document in the operation using the given serialiser.
+
+    The header of the operation is left unchanged.
+    The body of an update operation is `{"doc": ...}` or `{"script": ...}` around the document,
+    so only its partial document is given to the serialiser and the rest of the body is written with `serde_json`.
+    */
+    pub(crate) fn serialize_with(
+        self,
+        serializer: &dyn DocumentSerializer,
+    ) -> io::Result<BulkOperation<Box<RawValue>>> {
+        let inner = match (self.action, self.inner) {
+            (Action::Update, Some(ref inner)) => {
+                let mut update: UpdateBody = serde_json::from_str(&serde_json::to_string(inner)?)?;
+
+                if let Some(doc) = update.doc {
+                    update.doc = Some(document_serializer::to_raw_value(
+                        Document::raw(&doc),
+                        serializer,
+                    )?);
+                }
+
+                Some(serde_json::value::to_raw_value(&update)?)
+            }
+            (_, Some(ref inner)) => Some(document_serializer::to_raw_value(
+                Document::new(inner),
+                serializer,
+            )?),
+            (_, None) => None,
+        };
+
+        Ok(BulkOperation {
+            action: self.action,
+            header: self.header,
+            inner,
+        })
+    }
+}
+
+/** The body of an update operation, with its fields kept as raw JSON. */
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UpdateBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    doc: Option<Box<RawValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    doc_as_upsert: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    script: Option<Box<RawValue>>,
+    #[serde(rename = "_source", skip_serializing_if = "Option::is_none")]
+    source: Option<Box<RawValue>>,
 }
 
 /**
@@ -320,6 +497,8 @@ where
                 index: Some(doc.index().to_owned()),
                 ty: Some(doc.ty().to_owned()),
                 id: doc.partial_id().map(|id| id.to_owned()),
+                document_index: true,
+                id_strategy: false,
             },
             inner: Some(doc),
         }
@@ -335,6 +514,8 @@ where
                 index: Some(doc.index().to_owned()),
                 ty: Some(doc.ty().to_owned()),
                 id: doc.partial_id().map(|id| id.to_owned()),
+                document_index: true,
+                id_strategy: false,
             },
             inner: Some(Doc::value(doc)),
         }
@@ -358,6 +539,8 @@ where
                 index: TDocument::partial_static_index(),
                 ty: TDocument::partial_static_ty(),
                 id: Some(id.into()),
+                document_index: true,
+                id_strategy: false,
             },
             inner: Some(Script::new(script)),
         }
@@ -383,6 +566,8 @@ where
                 index: TDocument::partial_static_index().map(Into::into),
                 ty: TDocument::partial_static_ty().map(Into::into),
                 id: Some(id.into()),
+                document_index: true,
+                id_strategy: false,
             },
             inner: Some(Script::new(script)),
         }
@@ -399,6 +584,8 @@ where
                 index: Some(doc.index().to_owned()),
                 ty: Some(doc.ty().to_owned()),
                 id: doc.partial_id().map(|id| id.to_owned()),
+                document_index: true,
+                id_strategy: false,
             },
             inner: Some(doc),
         }
@@ -417,6 +604,8 @@ where
                 index: TDocument::partial_static_index(),
                 ty: TDocument::partial_static_ty(),
                 id: Some(id.into()),
+                document_index: true,
+                id_strategy: false,
             },
             inner: None,
         }
@@ -459,6 +648,8 @@ impl BulkRawOperation {
                 index: None,
                 ty: None,
                 id: None,
+                document_index: false,
+                id_strategy: false,
             },
             inner: Some(doc),
         }
@@ -474,6 +665,8 @@ impl BulkRawOperation {
                 index: None,
                 ty: None,
                 id: None,
+                document_index: false,
+                id_strategy: false,
             },
             inner: Some(Doc::value(doc)),
         }
@@ -492,6 +685,8 @@ impl BulkRawOperation {
                 index: None,
                 ty: None,
                 id: None,
+                document_index: false,
+                id_strategy: false,
             },
             inner: Some(Script::new(script)),
         }
@@ -515,6 +710,8 @@ impl BulkRawOperation {
                 index: None,
                 ty: None,
                 id: None,
+                document_index: false,
+                id_strategy: false,
             },
             inner: Some(Script::new(script)),
         }
@@ -531,6 +728,8 @@ impl BulkRawOperation {
                 index: None,
                 ty: None,
                 id: None,
+                document_index: false,
+                id_strategy: false,
             },
             inner: Some(doc),
         }
@@ -546,6 +745,8 @@ impl BulkRawOperation {
                 index: None,
                 ty: None,
                 id: None,
+                document_index: false,
+                id_strategy: false,
             },
             inner: None,
         }
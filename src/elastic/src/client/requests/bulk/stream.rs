@@ -284,7 +284,19 @@ where
         }
 
         if self.body.has_capacity() {
-            self.body.push(item).map_err(error::request)?;
+            let client = &self.req_template.client;
+            let item = item
+                .default_id_strategy(client.id_strategy())
+                .map_document_index(|index| client.document_index(index))
+                .map_index(|index| client.scoped_index(index));
+
+            match client.document_serializer {
+                Some(ref serializer) => item
+                    .serialize_with(&**serializer)
+                    .and_then(|item| self.body.push(item)),
+                None => self.body.push(item),
+            }
+            .map_err(error::request)?;
             Ok(AsyncSink::Ready)
         } else {
             match self.poll_complete() {
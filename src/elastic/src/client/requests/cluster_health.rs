@@ -0,0 +1,466 @@
+/*!
+Builders for [cluster health requests][docs-cluster-health].
+
+[docs-cluster-health]: https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-health.html
+*/
+
+use futures::{
+    future::{
+        loop_fn,
+        Either,
+        Loop,
+    },
+    Future,
+    IntoFuture,
+};
+use std::{
+    cmp,
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use tokio::timer::Delay;
+
+use crate::{
+    client::{
+        requests::{
+            common::time_value,
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::{
+            ClusterHealthResponse,
+            ClusterHealthStatus,
+        },
+        Client,
+    },
+    endpoints::ClusterHealthRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Index,
+};
+
+/** The longest time a single health request waits for the cluster before it's retried. */
+const READY_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/** How long to wait before retrying a health request that failed, like when the node isn't up yet. */
+const READY_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/**
+A [cluster health request][docs-cluster-health] builder that can be configured before sending.
+
+Call [`Client.cluster_health`][Client.cluster_health] to get a `ClusterHealthRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-cluster-health]: https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-health.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.cluster_health]: ../../struct.Client.html#cluster-health-request
+*/
+pub type ClusterHealthRequestBuilder<TSender> = RequestBuilder<TSender, ClusterHealthRequestInner>;
+
+#[doc(hidden)]
+pub struct ClusterHealthRequestInner {
+    index: Option<Index<'static>>,
+}
+
+/**
+# Cluster health request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`ClusterHealthRequestBuilder`][ClusterHealthRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Get the health of the cluster:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.cluster_health().send()?;
+
+    println!("status: {:?}", response.status());
+    # Ok(())
+    # }
+    ```
+
+    [ClusterHealthRequestBuilder]: requests/cluster_health/type.ClusterHealthRequestBuilder.html
+    [builder-methods]: requests/cluster_health/type.ClusterHealthRequestBuilder.html#builder-methods
+    [send-sync]: requests/cluster_health/type.ClusterHealthRequestBuilder.html#send-synchronously
+    [send-async]: requests/cluster_health/type.ClusterHealthRequestBuilder.html#send-asynchronously
+    */
+    pub fn cluster_health(&self) -> ClusterHealthRequestBuilder<TSender> {
+        RequestBuilder::initial(self.clone(), ClusterHealthRequestInner { index: None })
+    }
+}
+
+impl ClusterHealthRequestInner {
+    fn into_request(self) -> ClusterHealthRequest<'static> {
+        match self.index {
+            Some(index) => ClusterHealthRequest::for_index(index),
+            None => ClusterHealthRequest::new(),
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `ClusterHealthRequestBuilder` before sending it.
+*/
+impl<TSender> ClusterHealthRequestBuilder<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Set the indices to get the health of.
+
+    If no index is specified then the health of the whole cluster is returned.
+    */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = Some(index.into());
+        self
+    }
+
+    /**
+    Wait until the cluster reaches at least the given status before responding.
+
+    If the status isn't reached before the [`timeout`](#method.timeout) then the response is [timed out][timed_out].
+
+    [timed_out]: ../../responses/struct.ClusterHealthResponse.html#method.timed_out
+    */
+    pub fn wait_for_status(self, status: ClusterHealthStatus) -> Self {
+        self.params_fluent(move |params| params.url_param("wait_for_status", status.as_str()))
+    }
+
+    /** Set how long to wait for the cluster to reach the status waited for. */
+    pub fn timeout(self, timeout: Duration) -> Self {
+        let timeout = time_value(timeout);
+
+        self.params_fluent(move |params| params.url_param("timeout", &timeout))
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl ClusterHealthRequestBuilder<SyncSender> {
+    /**
+    Send a `ClusterHealthRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Wait up to 30 seconds for an index to be fully allocated:
+
+    ```no_run
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.cluster_health()
+                         .index("myindex")
+                         .wait_for_status(ClusterHealthStatus::Green)
+                         .timeout(Duration::from_secs(30))
+                         .send()?;
+
+    if response.timed_out() {
+        println!("the index is still {:?}", response.status());
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<ClusterHealthResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl ClusterHealthRequestBuilder<AsyncSender> {
+    /**
+    Send a `ClusterHealthRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised cluster health response.
+
+    # Examples
+
+    Get the health of the cluster:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.cluster_health().send();
+
+    future.and_then(|response| {
+        println!("status: {:?}", response.status());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/**
+# Wait until ready
+
+Block until the cluster can serve requests.
+*/
+impl Client<SyncSender> {
+    /**
+    Wait until the cluster health is at least yellow, or the `timeout` elapses.
+
+    The cluster health is polled until all primary shards are allocated.
+    Requests that fail, like when the node hasn't started yet, are retried until the `timeout` elapses.
+    This is useful in integration tests and when starting up alongside a cluster.
+
+    # Examples
+
+    Wait up to a minute for the cluster before indexing documents:
+
+    ```no_run
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let client = SyncClientBuilder::new().build()?;
+
+    client.wait_until_ready(Duration::from_secs(60))?;
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn wait_until_ready(&self, timeout: Duration) -> Result<ClusterHealthResponse, Error> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let attempt_timeout = ready_attempt_timeout(deadline, timeout)?;
+
+            let res = self
+                .cluster_health()
+                .wait_for_status(ClusterHealthStatus::Yellow)
+                .timeout(attempt_timeout)
+                .send();
+
+            match res {
+                Ok(health) => {
+                    if is_ready(&health) {
+                        return Ok(health);
+                    }
+                }
+                Err(e) => {
+                    debug!("Elasticsearch Wait Until Ready: error: '{:?}'", e);
+
+                    thread::sleep(cmp::min(READY_RETRY_DELAY, attempt_timeout));
+                }
+            }
+        }
+    }
+}
+
+impl Client<AsyncSender> {
+    /**
+    Wait until the cluster health is at least yellow, or the `timeout` elapses.
+
+    The cluster health is polled until all primary shards are allocated.
+    Requests that fail, like when the node hasn't started yet, are retried until the `timeout` elapses.
+    This is useful in integration tests and when starting up alongside a cluster.
+
+    The returned future must be run on a `tokio` runtime.
+
+    # Examples
+
+    Wait up to a minute for the cluster before indexing documents:
+
+    ```no_run
+    # use std::time::Duration;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let client = AsyncClientBuilder::new().build()?;
+
+    let future = client.wait_until_ready(Duration::from_secs(60));
+
+    future.and_then(|health| {
+        println!("status: {:?}", health.status());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn wait_until_ready(&self, timeout: Duration) -> Pending {
+        let deadline = Instant::now() + timeout;
+
+        let res_future = loop_fn(self.clone(), move |client| {
+            let attempt_timeout = match ready_attempt_timeout(deadline, timeout) {
+                Ok(attempt_timeout) => attempt_timeout,
+                Err(e) => return Either::A(Err(e).into_future()),
+            };
+
+            let res_future = client
+                .cluster_health()
+                .wait_for_status(ClusterHealthStatus::Yellow)
+                .timeout(attempt_timeout)
+                .send()
+                .then(move |res| match res {
+                    Ok(health) => {
+                        let next = if is_ready(&health) {
+                            Loop::Break(health)
+                        } else {
+                            Loop::Continue(client)
+                        };
+
+                        Either::A(Ok(next).into_future())
+                    }
+                    Err(e) => {
+                        debug!("Elasticsearch Wait Until Ready: error: '{:?}'", e);
+
+                        let retry = Instant::now() + cmp::min(READY_RETRY_DELAY, attempt_timeout);
+
+                        Either::B(
+                            Delay::new(retry)
+                                .map_err(error::request)
+                                .map(move |_| Loop::Continue(client)),
+                        )
+                    }
+                });
+
+            Either::B(res_future)
+        });
+
+        Pending::new(res_future)
+    }
+}
+
+fn is_ready(health: &ClusterHealthResponse) -> bool {
+    health.status() >= ClusterHealthStatus::Yellow
+}
+
+/** Get the timeout for the next health request, or an error if the deadline has passed. */
+fn ready_attempt_timeout(deadline: Instant, timeout: Duration) -> Result<Duration, Error> {
+    let now = Instant::now();
+
+    if now >= deadline {
+        return Err(error::request(error::message(format!(
+            "the cluster wasn't ready after {:?}",
+            timeout
+        ))));
+    }
+
+    Ok(cmp::min(deadline - now, READY_ATTEMPT_TIMEOUT))
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<ClusterHealthResponse>;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.cluster_health().inner.into_request();
+
+        assert_eq!("/_cluster/health", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .cluster_health()
+            .index("new-idx")
+            .inner
+            .into_request();
+
+        assert_eq!("/_cluster/health/new-idx", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_wait_for_status() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .cluster_health()
+            .wait_for_status(ClusterHealthStatus::Green)
+            .timeout(Duration::from_secs(30))
+            .url_params();
+
+        assert_eq!(vec!["timeout=30s", "wait_for_status=green"], params);
+    }
+
+    #[test]
+    fn wait_until_ready_times_out() {
+        // Nothing is listening on this port, so every health request fails
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .build()
+            .unwrap();
+
+        let err = client
+            .wait_until_ready(Duration::from_millis(50))
+            .unwrap_err();
+
+        assert!(format!("{:?}", err).contains("the cluster wasn't ready after 50ms"));
+    }
+}
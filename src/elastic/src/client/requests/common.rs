@@ -2,7 +2,17 @@
 Types that are common between requests.
 */
 
-use std::ops::Not;
+use std::{
+    any::{
+        Any,
+        TypeId,
+    },
+    collections::HashMap,
+    fmt,
+    ops::Not,
+    sync::Arc,
+    time::Duration,
+};
 
 use serde::ser::{
     Serialize,
@@ -12,6 +22,9 @@ use serde_json::{
     Map,
     Value,
 };
+use uuid::Uuid;
+
+use crate::params::Id;
 
 /** Update an indexed document using a new document. */
 #[derive(Serialize)]
@@ -199,3 +212,287 @@ impl<'a> From<&'a str> for ScriptBuilder<DefaultParams> {
         ScriptBuilder::new(source)
     }
 }
+
+/**
+A strategy for generating ids for documents that are indexed without one.
+
+Index requests and bulk index or create operations use an `IdStrategy` to fill in a missing id.
+A strategy can be set for a single request or operation, or for all documents of a type with `document_id_strategy` on the client builder.
+Documents that already have an id keep it.
+
+# Examples
+
+Let Elasticsearch assign ids:
+
+```
+# use elastic::prelude::*;
+# use serde_json::Value;
+let ids = IdStrategy::<Value>::none();
+```
+
+Generate a random UUIDv4 for each document:
+
+```
+# use elastic::prelude::*;
+# use serde_json::Value;
+let ids = IdStrategy::<Value>::uuid();
+```
+
+Derive the id from the document itself, so indexing the same document twice overwrites it instead of creating a duplicate:
+
+```
+# #[macro_use] extern crate serde_derive;
+# #[macro_use] extern crate elastic_derive;
+# use elastic::prelude::*;
+# fn main() {
+#[derive(Serialize, Deserialize, ElasticType)]
+struct MyType {
+    account: String,
+    timestamp: i64,
+}
+
+let ids = IdStrategy::hash(|doc: &MyType| format!("{}-{}", doc.account, doc.timestamp));
+# }
+```
+*/
+pub struct IdStrategy<TDocument> {
+    inner: IdStrategyInner<TDocument>,
+}
+
+enum IdStrategyInner<TDocument> {
+    None,
+    Uuid,
+    Hash(Arc<dyn Fn(&TDocument) -> Id<'static> + Send + Sync>),
+}
+
+impl<TDocument> IdStrategy<TDocument> {
+    /** Don't generate an id and let Elasticsearch assign one. */
+    pub fn none() -> Self {
+        IdStrategy {
+            inner: IdStrategyInner::None,
+        }
+    }
+
+    /** Generate a random UUIDv4 for each document. */
+    pub fn uuid() -> Self {
+        IdStrategy {
+            inner: IdStrategyInner::Uuid,
+        }
+    }
+
+    /**
+    Generate an id by hashing the document with the given function.
+
+    The function should return the same id for equivalent documents so ingestion can be retried without creating duplicates.
+    */
+    pub fn hash<TId>(hash: impl Fn(&TDocument) -> TId + Send + Sync + 'static) -> Self
+    where
+        TId: Into<Id<'static>>,
+    {
+        IdStrategy {
+            inner: IdStrategyInner::Hash(Arc::new(move |doc| hash(doc).into())),
+        }
+    }
+
+    /** Get an id for the given document, or `None` if Elasticsearch should assign one. */
+    pub fn id(&self, doc: &TDocument) -> Option<Id<'static>> {
+        match self.inner {
+            IdStrategyInner::None => None,
+            IdStrategyInner::Uuid => Some(Uuid::new_v4().to_string().into()),
+            IdStrategyInner::Hash(ref hash) => Some(hash(doc)),
+        }
+    }
+}
+
+impl<TDocument> Default for IdStrategy<TDocument> {
+    fn default() -> Self {
+        IdStrategy::none()
+    }
+}
+
+impl<TDocument> Clone for IdStrategy<TDocument> {
+    fn clone(&self) -> Self {
+        let inner = match self.inner {
+            IdStrategyInner::None => IdStrategyInner::None,
+            IdStrategyInner::Uuid => IdStrategyInner::Uuid,
+            IdStrategyInner::Hash(ref hash) => IdStrategyInner::Hash(hash.clone()),
+        };
+
+        IdStrategy { inner }
+    }
+}
+
+impl<TDocument> fmt::Debug for IdStrategy<TDocument> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let strategy = match self.inner {
+            IdStrategyInner::None => "None",
+            IdStrategyInner::Uuid => "Uuid",
+            IdStrategyInner::Hash(_) => "Hash",
+        };
+
+        f.debug_tuple("IdStrategy").field(&strategy).finish()
+    }
+}
+
+/**
+The default id strategies for document types, set with `document_id_strategy` on the client builder.
+*/
+#[derive(Default)]
+pub(crate) struct IdStrategies {
+    strategies: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl IdStrategies {
+    pub(crate) fn insert<TDocument>(&mut self, strategy: IdStrategy<TDocument>)
+    where
+        TDocument: 'static,
+    {
+        self.strategies
+            .insert(TypeId::of::<TDocument>(), Arc::new(strategy));
+    }
+
+    pub(crate) fn get<TDocument>(&self) -> Option<&IdStrategy<TDocument>>
+    where
+        TDocument: 'static,
+    {
+        self.strategies
+            .get(&TypeId::of::<TDocument>())
+            .and_then(|strategy| strategy.downcast_ref())
+    }
+}
+
+/**
+The number of active shard copies required before a write proceeds.
+
+# Examples
+
+Wait for all shard copies to be active:
+
+```
+# use elastic::prelude::*;
+let shards = ActiveShards::All;
+```
+
+Wait for the primary and one replica to be active:
+
+```
+# use elastic::prelude::*;
+let shards = ActiveShards::from(2);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActiveShards {
+    /** Wait for all shard copies. */
+    All,
+    /** Wait for a specific number of shard copies, including the primary. */
+    Count(u32),
+}
+
+impl From<u32> for ActiveShards {
+    fn from(count: u32) -> Self {
+        ActiveShards::Count(count)
+    }
+}
+
+impl fmt::Display for ActiveShards {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ActiveShards::All => f.write_str("all"),
+            ActiveShards::Count(count) => count.fmt(f),
+        }
+    }
+}
+
+/**
+Format a duration as an Elasticsearch time value.
+
+The largest unit that represents the duration exactly is used, so `Duration::from_secs(60)` becomes `1m` and `Duration::from_millis(1500)` becomes `1500ms`.
+*/
+pub(crate) fn time_value(duration: Duration) -> String {
+    if duration.as_nanos() == 0 {
+        return "0s".to_owned();
+    }
+
+    let (value, unit) = time_units(duration);
+
+    format!("{}{}", value, unit)
+}
+
+/**
+Split a duration into the largest unit of time that represents it exactly.
+
+Returns the number of those units and the unit's suffix, so `Duration::from_secs(5_400)` becomes `(90, "m")`.
+*/
+pub(crate) fn time_units(duration: Duration) -> (u128, &'static str) {
+    const UNITS: &[(u128, &str)] = &[
+        (86_400_000_000_000, "d"),
+        (3_600_000_000_000, "h"),
+        (60_000_000_000, "m"),
+        (1_000_000_000, "s"),
+        (1_000_000, "ms"),
+        (1_000, "micros"),
+    ];
+
+    let nanos = duration.as_nanos();
+
+    UNITS
+        .iter()
+        .find(|&&(unit, _)| nanos % unit == 0)
+        .map(|&(unit, suffix)| (nanos / unit, suffix))
+        .unwrap_or((nanos, "nanos"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        time_value,
+        ActiveShards,
+        IdStrategy,
+    };
+
+    #[test]
+    fn none_has_no_id() {
+        let ids = IdStrategy::<i32>::none();
+
+        assert!(ids.id(&1).is_none());
+    }
+
+    #[test]
+    fn uuid_is_unique() {
+        let ids = IdStrategy::<i32>::uuid();
+
+        let first = ids.id(&1).unwrap();
+        let second = ids.id(&1).unwrap();
+
+        assert_eq!(36, first.len());
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        let ids = IdStrategy::hash(|doc: &i32| format!("doc-{}", doc));
+
+        assert_eq!("doc-1", &*ids.id(&1).unwrap());
+        assert_eq!(ids.id(&1), ids.clone().id(&1));
+    }
+
+    #[test]
+    fn active_shards_display() {
+        assert_eq!("all", ActiveShards::All.to_string());
+        assert_eq!("2", ActiveShards::from(2).to_string());
+    }
+
+    #[test]
+    fn time_value_uses_largest_exact_unit() {
+        assert_eq!("0s", time_value(Duration::from_secs(0)));
+        assert_eq!("2d", time_value(Duration::from_secs(2 * 86_400)));
+        assert_eq!("1h", time_value(Duration::from_secs(3_600)));
+        assert_eq!("90m", time_value(Duration::from_secs(5_400)));
+        assert_eq!("30s", time_value(Duration::from_secs(30)));
+        assert_eq!("1500ms", time_value(Duration::from_millis(1_500)));
+        assert_eq!("5micros", time_value(Duration::from_micros(5)));
+        assert_eq!("7nanos", time_value(Duration::from_nanos(7)));
+    }
+}
@@ -4,12 +4,22 @@ Builders for [delete document requests][docs-delete].
 [docs-delete]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-delete.html
 */
 
-use futures::Future;
-use std::marker::PhantomData;
+use futures::{
+    Future,
+    IntoFuture,
+};
+use std::{
+    marker::PhantomData,
+    time::Duration,
+};
 
 use crate::{
     client::{
         requests::{
+            common::{
+                time_value,
+                ActiveShards,
+            },
             raw::RawRequestInner,
             Pending as BasePending,
             RequestBuilder,
@@ -18,7 +28,10 @@ use crate::{
         DocumentClient,
     },
     endpoints::DeleteRequest,
-    error::Error,
+    error::{
+        self,
+        Error,
+    },
     http::sender::{
         AsyncSender,
         Sender,
@@ -110,7 +123,7 @@ where
     where
         TDocument: DocumentType + StaticIndex + StaticType,
     {
-        let index = TDocument::static_index();
+        let index = self.inner.document_index(TDocument::static_index());
         let ty = TDocument::static_ty();
 
         RequestBuilder::initial(
@@ -181,8 +194,12 @@ where
 }
 
 impl<TDocument> DeleteRequestInner<TDocument> {
-    fn into_request(self) -> DeleteRequest<'static> {
-        DeleteRequest::for_index_ty_id(self.index, self.ty, self.id)
+    fn into_request(self) -> Result<DeleteRequest<'static>, Error> {
+        self.index.validate().map_err(error::request)?;
+        self.ty.validate().map_err(error::request)?;
+        self.id.validate().map_err(error::request)?;
+
+        Ok(DeleteRequest::for_index_ty_id(self.index, self.ty, self.id))
     }
 }
 
@@ -206,6 +223,24 @@ where
         self.inner.ty = ty.into();
         self
     }
+
+    /**
+    Set the number of shard copies that must be active before the delete operation proceeds.
+
+    If this isn't specified then Elasticsearch waits for the primary shard only.
+    */
+    pub fn wait_for_active_shards(self, shards: impl Into<ActiveShards>) -> Self {
+        let shards = shards.into();
+
+        self.params_fluent(move |params| params.url_param("wait_for_active_shards", shards))
+    }
+
+    /** Set how long to wait for active shards and for the delete operation to complete. */
+    pub fn timeout(self, timeout: Duration) -> Self {
+        let timeout = time_value(timeout);
+
+        self.params_fluent(move |params| params.url_param("timeout", &timeout))
+    }
 }
 
 /**
@@ -246,7 +281,7 @@ impl<TDocument> DeleteRequestBuilder<SyncSender, TDocument> {
     [documents-mod]: ../types/document/index.html
     */
     pub fn send(self) -> Result<DeleteResponse, Error> {
-        let req = self.inner.into_request();
+        let req = self.inner.into_request()?;
 
         RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
             .send()?
@@ -296,12 +331,13 @@ impl<TDocument> DeleteRequestBuilder<AsyncSender, TDocument> {
     [documents-mod]: ../types/document/index.html
     */
     pub fn send(self) -> Pending {
-        let req = self.inner.into_request();
+        let (client, params_builder) = (self.client, self.params_builder);
 
-        let res_future =
-            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+        let res_future = self.inner.into_request().into_future().and_then(|req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
                 .send()
-                .and_then(|res| res.into_response());
+                .and_then(|res| res.into_response())
+        });
 
         Pending::new(res_future)
     }
@@ -312,6 +348,8 @@ pub type Pending = BasePending<DeleteResponse>;
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use crate::{
         prelude::*,
         tests::*,
@@ -334,7 +372,8 @@ mod tests {
             .document::<TestDoc>()
             .delete("1")
             .inner
-            .into_request();
+            .into_request()
+            .unwrap();
 
         assert_eq!("/testdoc/_doc/1", req.url.as_ref());
     }
@@ -348,7 +387,8 @@ mod tests {
             .delete("1")
             .index("new-idx")
             .inner
-            .into_request();
+            .into_request()
+            .unwrap();
 
         assert_eq!("/new-idx/_doc/1", req.url.as_ref());
     }
@@ -362,8 +402,23 @@ mod tests {
             .delete("1")
             .ty("new-ty")
             .inner
-            .into_request();
+            .into_request()
+            .unwrap();
 
         assert_eq!("/testdoc/new-ty/1", req.url.as_ref());
     }
+
+    #[test]
+    fn specify_wait_for_active_shards_timeout() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .document::<TestDoc>()
+            .delete("1")
+            .wait_for_active_shards(ActiveShards::All)
+            .timeout(Duration::from_secs(90))
+            .url_params();
+
+        assert_eq!(vec!["timeout=90s", "wait_for_active_shards=all"], params);
+    }
 }
@@ -4,7 +4,10 @@ Builders for [get document requests][docs-get].
 [docs-get]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-get.html
 */
 
-use futures::Future;
+use futures::{
+    Future,
+    IntoFuture,
+};
 use serde::de::DeserializeOwned;
 use std::marker::PhantomData;
 
@@ -19,7 +22,10 @@ use crate::{
         DocumentClient,
     },
     endpoints::GetRequest,
-    error::Error,
+    error::{
+        self,
+        Error,
+    },
     http::sender::{
         AsyncSender,
         Sender,
@@ -110,7 +116,7 @@ where
     where
         TDocument: DeserializeOwned + DocumentType + StaticIndex + StaticType,
     {
-        let index = TDocument::static_index();
+        let index = self.inner.document_index(TDocument::static_index());
         let ty = TDocument::static_ty();
 
         RequestBuilder::initial(
@@ -180,8 +186,12 @@ where
 }
 
 impl<TDocument> GetRequestInner<TDocument> {
-    fn into_request(self) -> GetRequest<'static> {
-        GetRequest::for_index_ty_id(self.index, self.ty, self.id)
+    fn into_request(self) -> Result<GetRequest<'static>, Error> {
+        self.index.validate().map_err(error::request)?;
+        self.ty.validate().map_err(error::request)?;
+        self.id.validate().map_err(error::request)?;
+
+        Ok(GetRequest::for_index_ty_id(self.index, self.ty, self.id))
     }
 }
 
@@ -205,6 +215,85 @@ where
         self.inner.ty = ty.into();
         self
     }
+
+    /**
+    Set whether the get request is real-time.
+
+    Real-time gets return the latest version of a document even if the index hasn't been refreshed since it was changed.
+    If this isn't specified then Elasticsearch performs real-time gets.
+    */
+    pub fn realtime(self, realtime: bool) -> Self {
+        self.params_fluent(move |params| params.url_param("realtime", realtime))
+    }
+
+    /**
+    Set the shard copies to prefer when getting the document.
+
+    Using the same preference value, like a user's session id, for related requests routes them to the same shard copies.
+    */
+    pub fn preference(self, preference: impl Into<String>) -> Self {
+        let preference = preference.into();
+
+        self.params_fluent(move |params| params.url_param("preference", &preference))
+    }
+
+    /**
+    Only include the given fields in the source of the document.
+
+    Fields can contain wildcards, like `user.*`.
+    The document type needs to be able to deserialize from the filtered source.
+    */
+    pub fn source_include<TField>(self, fields: impl IntoIterator<Item = TField>) -> Self
+    where
+        TField: Into<String>,
+    {
+        let fields = join_fields(fields);
+
+        self.params_fluent(move |params| params.url_param("_source_includes", &fields))
+    }
+
+    /**
+    Exclude the given fields from the source of the document.
+
+    Fields can contain wildcards, like `user.*`.
+    The document type needs to be able to deserialize from the filtered source.
+    */
+    pub fn source_exclude<TField>(self, fields: impl IntoIterator<Item = TField>) -> Self
+    where
+        TField: Into<String>,
+    {
+        let fields = join_fields(fields);
+
+        self.params_fluent(move |params| params.url_param("_source_excludes", &fields))
+    }
+
+    /**
+    Return the given stored fields for the document.
+
+    Stored fields are returned separately from the source, and can be read with [`GetResponse.fields`][GetResponse.fields].
+    Only fields that are stored in the mapping are returned.
+
+    [GetResponse.fields]: ../../responses/struct.GetResponse.html#method.fields
+    */
+    pub fn stored_fields<TField>(self, fields: impl IntoIterator<Item = TField>) -> Self
+    where
+        TField: Into<String>,
+    {
+        let fields = join_fields(fields);
+
+        self.params_fluent(move |params| params.url_param("stored_fields", &fields))
+    }
+}
+
+pub(super) fn join_fields<TField>(fields: impl IntoIterator<Item = TField>) -> String
+where
+    TField: Into<String>,
+{
+    fields
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<_>>()
+        .join(",")
 }
 
 /**
@@ -248,7 +337,7 @@ where
     [documents-mod]: ../types/document/index.html
     */
     pub fn send(self) -> Result<GetResponse<TDocument>, Error> {
-        let req = self.inner.into_request();
+        let req = self.inner.into_request()?;
 
         RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
             .send()?
@@ -302,12 +391,13 @@ where
     [documents-mod]: ../types/document/index.html
     */
     pub fn send(self) -> Pending<TDocument> {
-        let req = self.inner.into_request();
+        let (client, params_builder) = (self.client, self.params_builder);
 
-        let res_future =
-            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+        let res_future = self.inner.into_request().into_future().and_then(|req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
                 .send()
-                .and_then(|res| res.into_response());
+                .and_then(|res| res.into_response())
+        });
 
         Pending::new(res_future)
     }
@@ -336,7 +426,12 @@ mod tests {
     fn default_request() {
         let client = SyncClientBuilder::new().build().unwrap();
 
-        let req = client.document::<TestDoc>().get("1").inner.into_request();
+        let req = client
+            .document::<TestDoc>()
+            .get("1")
+            .inner
+            .into_request()
+            .unwrap();
 
         assert_eq!("/testdoc/_doc/1", req.url.as_ref());
     }
@@ -350,11 +445,59 @@ mod tests {
             .get("1")
             .index("new-idx")
             .inner
-            .into_request();
+            .into_request()
+            .unwrap();
 
         assert_eq!("/new-idx/_doc/1", req.url.as_ref());
     }
 
+    #[test]
+    fn id_is_percent_encoded() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .get("my id/1")
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/testdoc/_doc/my%20id%2F1", req.url.as_ref());
+    }
+
+    #[test]
+    fn invalid_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let res = client
+            .document::<TestDoc>()
+            .get("1")
+            .index("New-Idx")
+            .inner
+            .into_request();
+
+        let err = crate::tests::invalid_param(res);
+        assert_eq!("index", err.param());
+        assert_eq!("isn't lowercase", err.reason());
+    }
+
+    #[test]
+    fn document_index_name() {
+        let client = SyncClientBuilder::new()
+            .document_index_name(|index| format!("staging_{}", index))
+            .build()
+            .unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .get("1")
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/staging_testdoc/_doc/1", req.url.as_ref());
+    }
+
     #[test]
     fn specify_ty() {
         let client = SyncClientBuilder::new().build().unwrap();
@@ -364,8 +507,45 @@ mod tests {
             .get("1")
             .ty("new-ty")
             .inner
-            .into_request();
+            .into_request()
+            .unwrap();
 
         assert_eq!("/testdoc/new-ty/1", req.url.as_ref());
     }
+
+    #[test]
+    fn specify_realtime_preference() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .document::<TestDoc>()
+            .get("1")
+            .realtime(false)
+            .preference("_local")
+            .url_params();
+
+        assert_eq!(vec!["preference=_local", "realtime=false"], params);
+    }
+
+    #[test]
+    fn specify_source_filtering_stored_fields() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .document::<TestDoc>()
+            .get("1")
+            .source_include(vec!["title", "user.*"])
+            .source_exclude(vec!["user.password"])
+            .stored_fields(vec!["tags"])
+            .url_params();
+
+        assert_eq!(
+            vec![
+                "_source_excludes=user.password",
+                "_source_includes=title%2Cuser.*",
+                "stored_fields=tags",
+            ],
+            params
+        );
+    }
 }
@@ -0,0 +1,476 @@
+/*!
+Builders for [get document source requests][docs-get-source].
+
+[docs-get-source]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-get.html#_source
+*/
+
+use futures::{
+    future::Either,
+    Future,
+    IntoFuture,
+};
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+use crate::{
+    client::{
+        requests::{
+            document_get::join_fields,
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::GetSourceResponse,
+        version::Version,
+        Client,
+    },
+    endpoints::GetSourceRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::{
+        Id,
+        Index,
+        Type,
+    },
+    types::document::DEFAULT_DOC_TYPE,
+};
+
+/**
+A [get document source request][docs-get-source] builder that can be configured before sending.
+
+Call [`Client.get_source`][Client.get_source] to get a `GetSourceRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-get-source]: http://www.elastic.co/guide/en/elasticsearch/reference/current/docs-get.html#_source
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.get_source]: ../../struct.Client.html#get-document-source-request
+*/
+pub type GetSourceRequestBuilder<TSender, TDocument> =
+    RequestBuilder<TSender, GetSourceRequestInner<TDocument>>;
+
+#[doc(hidden)]
+pub struct GetSourceRequestInner<TDocument> {
+    index: Index<'static>,
+    ty: Type<'static>,
+    id: Id<'static>,
+    _marker: PhantomData<TDocument>,
+}
+
+/** The first version with a typeless `_source` endpoint. */
+const TYPELESS_GET_SOURCE_VERSION: Version = Version::new(7, 0, 0);
+
+/**
+# Get document source request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`GetSourceRequestBuilder`][GetSourceRequestBuilder] with this `Client` that can be configured before sending.
+
+    A get document source request only returns the source of a document, so it's deserialised straight into `TDocument`.
+    Sending the request returns `None` if the document doesn't exist.
+    If the index doesn't exist then sending the request returns an error.
+
+    Getting a document source without a [type][ty] uses the typeless endpoint on Elasticsearch `7.0` and newer.
+    On earlier versions, the document source is fetched with the `_doc` type instead.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Get the source of a document called `MyType` with an id of `1`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Deserialize)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let doc = client.get_source::<MyType>("myindex", 1).send()?;
+
+    match doc {
+        Some(doc) => println!("{:?}", doc),
+        None => println!("the document doesn't exist"),
+    }
+    # Ok(())
+    # }
+    ```
+
+    [GetSourceRequestBuilder]: requests/document_get_source/type.GetSourceRequestBuilder.html
+    [ty]: requests/document_get_source/type.GetSourceRequestBuilder.html#method.ty
+    [builder-methods]: requests/document_get_source/type.GetSourceRequestBuilder.html#builder-methods
+    [send-sync]: requests/document_get_source/type.GetSourceRequestBuilder.html#send-synchronously
+    [send-async]: requests/document_get_source/type.GetSourceRequestBuilder.html#send-asynchronously
+    */
+    pub fn get_source<TDocument>(
+        &self,
+        index: impl Into<Index<'static>>,
+        id: impl Into<Id<'static>>,
+    ) -> GetSourceRequestBuilder<TSender, TDocument>
+    where
+        TDocument: DeserializeOwned,
+    {
+        RequestBuilder::initial(
+            self.clone(),
+            GetSourceRequestInner {
+                index: index.into(),
+                ty: DEFAULT_DOC_TYPE.into(),
+                id: id.into(),
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<TDocument> GetSourceRequestInner<TDocument> {
+    // Only requests without a type need the cluster version to pick an endpoint
+    fn is_typeless(&self) -> bool {
+        &self.ty[..] == DEFAULT_DOC_TYPE
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        self.index.validate().map_err(error::request)?;
+        self.ty.validate().map_err(error::request)?;
+        self.id.validate().map_err(error::request)?;
+
+        Ok(())
+    }
+
+    // Clusters before 7.0 don't have the typeless endpoint, but can get the source with the `_doc` type
+    fn into_request(self, version: Option<Version>) -> GetSourceRequest<'static> {
+        let typeless = match version {
+            Some(version) => version >= TYPELESS_GET_SOURCE_VERSION,
+            None => true,
+        };
+
+        if self.is_typeless() && typeless {
+            GetSourceRequest::for_index_id(self.index, self.id)
+        } else {
+            GetSourceRequest::for_index_ty_id(self.index, self.ty, self.id)
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `GetSourceRequestBuilder` before sending it.
+*/
+impl<TSender, TDocument> GetSourceRequestBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /** Set the type for the get document source request. */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.inner.ty = ty.into();
+        self
+    }
+
+    /**
+    Set whether the get document source request is real-time.
+
+    Real-time gets return the latest version of a document even if the index hasn't been refreshed since it was changed.
+    If this isn't specified then Elasticsearch performs real-time gets.
+    */
+    pub fn realtime(self, realtime: bool) -> Self {
+        self.params_fluent(move |params| params.url_param("realtime", realtime))
+    }
+
+    /** Set the routing value used to index the document. */
+    pub fn routing(self, routing: impl Into<String>) -> Self {
+        let routing = routing.into();
+
+        self.params_fluent(move |params| params.url_param("routing", &routing))
+    }
+
+    /**
+    Only include the given fields in the source of the document.
+
+    Fields can contain wildcards, like `user.*`.
+    The document type needs to be able to deserialize from the filtered source.
+    */
+    pub fn source_include<TField>(self, fields: impl IntoIterator<Item = TField>) -> Self
+    where
+        TField: Into<String>,
+    {
+        let fields = join_fields(fields);
+
+        self.params_fluent(move |params| params.url_param("_source_includes", &fields))
+    }
+
+    /**
+    Exclude the given fields from the source of the document.
+
+    Fields can contain wildcards, like `user.*`.
+    The document type needs to be able to deserialize from the filtered source.
+    */
+    pub fn source_exclude<TField>(self, fields: impl IntoIterator<Item = TField>) -> Self
+    where
+        TField: Into<String>,
+    {
+        let fields = join_fields(fields);
+
+        self.params_fluent(move |params| params.url_param("_source_excludes", &fields))
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TDocument> GetSourceRequestBuilder<SyncSender, TDocument>
+where
+    TDocument: DeserializeOwned,
+{
+    /**
+    Send a `GetSourceRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+    The result is `None` if the document doesn't exist.
+
+    # Examples
+
+    Get the source of a document as a `serde_json::Value`:
+
+    ```no_run
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let doc = client.get_source::<Value>("myindex", 1).send()?;
+
+    if let Some(doc) = doc {
+        println!("{}", doc);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<Option<TDocument>, Error> {
+        self.inner.validate()?;
+
+        let (version, params_builder) = if self.inner.is_typeless() {
+            self.client.detect_version(self.params_builder)?
+        } else {
+            (None, self.params_builder)
+        };
+
+        let req = self.inner.into_request(version);
+
+        RequestBuilder::new(self.client, params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response::<GetSourceResponse<TDocument>>()
+            .map(GetSourceResponse::into_document)
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TDocument> GetSourceRequestBuilder<AsyncSender, TDocument>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+{
+    /**
+    Send a `GetSourceRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised document, or `None` if the document doesn't exist.
+
+    # Examples
+
+    Get the source of a document as a `serde_json::Value`:
+
+    ```no_run
+    # use serde_json::Value;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.get_source::<Value>("myindex", 1).send();
+
+    future.and_then(|doc| {
+        if let Some(doc) = doc {
+            println!("{}", doc);
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending<TDocument> {
+        let (client, params_builder, inner) = (self.client, self.params_builder, self.inner);
+
+        let res_future = inner
+            .validate()
+            .into_future()
+            .and_then(move |_| {
+                let version = if inner.is_typeless() {
+                    Either::A(client.detect_version(params_builder))
+                } else {
+                    Either::B(Ok((None, params_builder)).into_future())
+                };
+
+                version.and_then(move |(version, params_builder)| {
+                    let req = inner.into_request(version);
+
+                    RequestBuilder::new(client, params_builder, RawRequestInner::new(req)).send()
+                })
+            })
+            .and_then(|res| res.into_response::<GetSourceResponse<TDocument>>())
+            .map(GetSourceResponse::into_document);
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending<TDocument> = BasePending<Option<TDocument>>;
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+    };
+
+    use futures::Future;
+    use serde_json::Value;
+
+    use crate::{
+        client::Version,
+        http::sender::recording::test_recording,
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending<Value>>();
+    }
+
+    /** A recording of a get document source request and its response. */
+    fn recording(path: &str, status: u16, body: &str) -> PathBuf {
+        test_recording(vec![json!({
+            "request": { "method": "GET", "path": path, "body": null },
+            "response": { "status": status, "body": body }
+        })])
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .get_source::<Value>("myindex", 1)
+            .inner
+            .into_request(Some(Version::new(7, 0, 0)));
+
+        assert_eq!("/myindex/_source/1", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_ty() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .get_source::<Value>("myindex", 1)
+            .ty("mytype")
+            .inner
+            .into_request(Some(Version::new(7, 0, 0)));
+
+        assert_eq!("/myindex/mytype/1/_source", req.url.as_ref());
+    }
+
+    #[test]
+    fn send_found() {
+        let path = recording("/myindex/_source/1", 200, r#"{"title":"A document"}"#);
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap();
+        client.version.set(Version::new(7, 4, 0));
+        fs::remove_file(&path).unwrap();
+
+        let doc = client.get_source::<Value>("myindex", 1).send().unwrap();
+
+        assert_eq!(Some(json!({ "title": "A document" })), doc);
+    }
+
+    #[test]
+    fn send_not_found() {
+        let path = recording("/myindex/_source/1", 404, "");
+        let client = AsyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap();
+        client.version.set(Version::new(7, 4, 0));
+        fs::remove_file(&path).unwrap();
+
+        let doc = client
+            .get_source::<Value>("myindex", 1)
+            .send()
+            .wait()
+            .unwrap();
+
+        assert_eq!(None, doc);
+    }
+
+    #[test]
+    fn typeless_request_before_7_uses_doc_type() {
+        let path = recording("/myindex/_doc/1/_source", 200, r#"{"title":"A document"}"#);
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap();
+        client.version.set(Version::new(6, 8, 0));
+        fs::remove_file(&path).unwrap();
+
+        let doc = client.get_source::<Value>("myindex", 1).send().unwrap();
+
+        assert_eq!(Some(json!({ "title": "A document" })), doc);
+    }
+
+    #[test]
+    fn typeless_request_with_unknown_version_is_typeless() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .get_source::<Value>("myindex", 1)
+            .inner
+            .into_request(None);
+
+        assert_eq!("/myindex/_source/1", req.url.as_ref());
+    }
+
+    #[test]
+    fn typed_request_doesnt_need_version() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client.get_source::<Value>("myindex", 1).ty("mytype");
+
+        assert!(!builder.inner.is_typeless());
+    }
+}
@@ -6,17 +6,27 @@ Builders for [index requests][docs-index].
 
 use futures::Future;
 use serde::Serialize;
-use serde_json;
+use std::{
+    sync::Arc,
+    time::Duration,
+};
 
 use crate::{
     client::{
+        document_serializer,
         requests::{
+            common::{
+                time_value,
+                ActiveShards,
+                IdStrategy,
+            },
             raw::RawRequestInner,
             Pending as BasePending,
             RequestBuilder,
         },
         responses::IndexResponse,
         DocumentClient,
+        DocumentSerializer,
     },
     endpoints::IndexRequest,
     error::{
@@ -58,7 +68,9 @@ pub struct IndexRequestInner<TDocument> {
     index: Index<'static>,
     ty: Type<'static>,
     id: Option<Id<'static>>,
+    id_strategy: Option<IdStrategy<TDocument>>,
     doc: TDocument,
+    serializer: Option<Arc<dyn DocumentSerializer>>,
 }
 
 /**
@@ -121,15 +133,21 @@ where
     */
     pub fn index(self, doc: TDocument) -> IndexRequestBuilder<TSender, TDocument>
     where
-        TDocument: Serialize + DocumentType,
+        TDocument: Serialize + DocumentType + 'static,
     {
+        let serializer = self.inner.document_serializer.clone();
+        let id_strategy = self.inner.id_strategy().cloned();
+        let index = self.inner.document_index(doc.index().to_owned());
+
         RequestBuilder::initial(
             self.inner,
             IndexRequestInner {
-                index: doc.index().to_owned(),
+                index,
                 ty: doc.ty().to_owned(),
                 id: doc.partial_id().map(|id| id.to_owned()),
+                id_strategy,
                 doc,
+                serializer,
             },
         )
     }
@@ -185,15 +203,20 @@ where
         doc: TDocument,
     ) -> IndexRequestBuilder<TSender, TDocument>
     where
-        TDocument: Serialize,
+        TDocument: Serialize + 'static,
     {
+        let serializer = self.inner.document_serializer.clone();
+        let id_strategy = self.inner.id_strategy().cloned();
+
         RequestBuilder::initial(
             self.inner,
             IndexRequestInner {
                 index: index.into(),
                 ty: DEFAULT_DOC_TYPE.into(),
                 id: None,
+                id_strategy,
                 doc,
+                serializer,
             },
         )
     }
@@ -204,9 +227,22 @@ where
     TDocument: Serialize,
 {
     fn into_request(self) -> Result<IndexRequest<'static, Vec<u8>>, Error> {
-        let body = serde_json::to_vec(&self.doc).map_err(error::request)?;
+        let doc = self.doc;
+        let id = match (self.id, self.id_strategy) {
+            (None, Some(strategy)) => strategy.id(&doc),
+            (id, _) => id,
+        };
+
+        self.index.validate().map_err(error::request)?;
+        self.ty.validate().map_err(error::request)?;
+        if let Some(ref id) = id {
+            id.validate().map_err(error::request)?;
+        }
+
+        let body = document_serializer::to_vec(&doc, self.serializer.as_deref())
+            .map_err(error::request)?;
 
-        let request = match self.id {
+        let request = match id {
             Some(id) => IndexRequest::for_index_ty_id(self.index, self.ty, id, body),
             None => IndexRequest::for_index_ty(self.index, self.ty, body),
         };
@@ -241,6 +277,108 @@ where
         self.inner.id = Some(id.into());
         self
     }
+
+    /**
+    Generate an id for the document using the given [`IdStrategy`][IdStrategy].
+
+    If the request already has an id then it's left unchanged.
+    This strategy is used instead of one set with `document_id_strategy` on the client builder.
+
+    # Examples
+
+    Index a document with an id derived from its contents:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    #[derive(Serialize, Deserialize, ElasticType)]
+    struct MyType {
+        account: String,
+        title: String,
+    }
+
+    let ids = IdStrategy::hash(|doc: &MyType| doc.account.clone());
+
+    let doc = MyType {
+        account: "account-1".to_owned(),
+        title: "A title".to_owned(),
+    };
+
+    let response = client.document()
+                         .index(doc)
+                         .id_strategy(&ids)
+                         .send()?;
+
+    assert!(response.created());
+    # Ok(())
+    # }
+    ```
+
+    [IdStrategy]: ../common/struct.IdStrategy.html
+    */
+    pub fn id_strategy(mut self, strategy: &IdStrategy<TDocument>) -> Self {
+        self.inner.id_strategy = Some(strategy.clone());
+        self
+    }
+
+    /**
+    Set the number of shard copies that must be active before the index operation proceeds.
+
+    If this isn't specified then Elasticsearch waits for the primary shard only.
+    */
+    pub fn wait_for_active_shards(self, shards: impl Into<ActiveShards>) -> Self {
+        let shards = shards.into();
+
+        self.params_fluent(move |params| params.url_param("wait_for_active_shards", shards))
+    }
+
+    /** Set how long to wait for active shards and for the index operation to complete. */
+    pub fn timeout(self, timeout: Duration) -> Self {
+        let timeout = time_value(timeout);
+
+        self.params_fluent(move |params| params.url_param("timeout", &timeout))
+    }
+
+    /**
+    Only index the document if a document with the same id doesn't already exist.
+
+    If the document already exists then the request fails with an [`ApiError::DocumentAlreadyExists`][DocumentAlreadyExists] error instead of replacing it.
+
+    # Examples
+
+    Insert a document, skipping it if it's already been indexed:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # use elastic::error::{ApiError, Error};
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType {
+    #     #[elastic(id)]
+    #     pub id: String,
+    #     pub title: String,
+    # }
+    # let client = SyncClientBuilder::new().build()?;
+    # let doc = MyType { id: "1".to_owned(), title: "A title".to_owned() };
+    match client.document().index(doc).create().send() {
+        Ok(_) => println!("inserted"),
+        Err(Error::Api(ApiError::DocumentAlreadyExists { id, .. })) => println!("skipped duplicate {}", id),
+        Err(e) => return Err(e.into()),
+    }
+    # Ok(())
+    # }
+    ```
+
+    [DocumentAlreadyExists]: ../../../error/enum.ApiError.html#variant.DocumentAlreadyExists
+    */
+    pub fn create(self) -> Self {
+        self.params_fluent(|params| params.url_param("op_type", "create"))
+    }
 }
 
 /**
@@ -367,7 +505,18 @@ pub type Pending = BasePending<IndexResponse>;
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use std::io::{
+        self,
+        Write,
+    };
+
     use crate::{
+        client::{
+            Document,
+            DocumentSerializer,
+        },
         prelude::*,
         tests::*,
     };
@@ -396,6 +545,68 @@ mod tests {
         assert_eq!(b"{}".to_vec(), req.body);
     }
 
+    #[test]
+    fn document_index_name() {
+        let client = SyncClientBuilder::new()
+            .document_index_name(|index| format!("staging_{}", index))
+            .build()
+            .unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/staging_testdoc/_doc", req.url.as_ref());
+    }
+
+    #[test]
+    fn document_index_name_explicit_index() {
+        let client = SyncClientBuilder::new()
+            .document_index_name(|index| format!("staging_{}", index))
+            .build()
+            .unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .index("new-idx")
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/new-idx/_doc", req.url.as_ref());
+    }
+
+    #[test]
+    fn document_serializer() {
+        struct Tagged;
+
+        impl DocumentSerializer for Tagged {
+            fn serialize(&self, doc: Document, writer: &mut dyn Write) -> io::Result<()> {
+                serde_json::to_writer(writer, &json!({ "tagged": doc.to_value()? }))?;
+
+                Ok(())
+            }
+        }
+
+        let client = SyncClientBuilder::new()
+            .document_serializer(Tagged)
+            .build()
+            .unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!(b"{\"tagged\":{}}".to_vec(), req.body);
+    }
+
     #[test]
     fn specify_index() {
         let client = SyncClientBuilder::new().build().unwrap();
@@ -440,4 +651,116 @@ mod tests {
 
         assert_eq!("/testdoc/_doc/1", req.url.as_ref());
     }
+
+    #[test]
+    fn specify_id_strategy() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .id_strategy(&IdStrategy::hash(|_: &TestDoc| "hashed"))
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/testdoc/_doc/hashed", req.url.as_ref());
+    }
+
+    #[test]
+    fn id_strategy_keeps_id() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .id(1)
+            .id_strategy(&IdStrategy::uuid())
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/testdoc/_doc/1", req.url.as_ref());
+    }
+
+    #[test]
+    fn client_id_strategy() {
+        let client = SyncClientBuilder::new()
+            .document_id_strategy(IdStrategy::hash(|_: &TestDoc| "hashed"))
+            .build()
+            .unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/testdoc/_doc/hashed", req.url.as_ref());
+    }
+
+    #[test]
+    fn client_id_strategy_keeps_id() {
+        let client = SyncClientBuilder::new()
+            .document_id_strategy(IdStrategy::hash(|_: &TestDoc| "hashed"))
+            .build()
+            .unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .id(1)
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/testdoc/_doc/1", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_id_strategy_overrides_client_id_strategy() {
+        let client = SyncClientBuilder::new()
+            .document_id_strategy(IdStrategy::hash(|_: &TestDoc| "hashed"))
+            .build()
+            .unwrap();
+
+        let req = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .id_strategy(&IdStrategy::none())
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/testdoc/_doc", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_wait_for_active_shards_timeout() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .wait_for_active_shards(ActiveShards::All)
+            .timeout(Duration::from_secs(90))
+            .url_params();
+
+        assert_eq!(vec!["timeout=90s", "wait_for_active_shards=all"], params);
+    }
+
+    #[test]
+    fn specify_create() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .document::<TestDoc>()
+            .index(TestDoc {})
+            .id(1)
+            .create()
+            .url_params();
+
+        assert_eq!(vec!["op_type=create"], params);
+    }
 }
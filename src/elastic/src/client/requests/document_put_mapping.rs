@@ -111,7 +111,7 @@ where
     where
         TDocument: DocumentType + StaticIndex + StaticType,
     {
-        let index = TDocument::static_index();
+        let index = self.inner.document_index(TDocument::static_index());
         let ty = TDocument::static_ty();
 
         RequestBuilder::initial(
@@ -130,6 +130,9 @@ where
     TDocument: DocumentType,
 {
     fn into_request(self) -> Result<IndicesPutMappingRequest<'static, Vec<u8>>, Error> {
+        self.index.validate().map_err(error::request)?;
+        self.ty.validate().map_err(error::request)?;
+
         let body = serde_json::to_vec(&TDocument::index_mapping()).map_err(error::request)?;
 
         if &self.ty[..] == DEFAULT_DOC_TYPE {
@@ -203,7 +203,7 @@ where
     where
         TDocument: DocumentType + StaticIndex + StaticType,
     {
-        let index = TDocument::static_index();
+        let index = self.inner.document_index(TDocument::static_index());
         let ty = TDocument::static_ty();
 
         RequestBuilder::initial(
@@ -278,6 +278,10 @@ where
     TBody: Serialize,
 {
     fn into_request(self) -> Result<UpdateRequest<'static, Vec<u8>>, Error> {
+        self.index.validate().map_err(error::request)?;
+        self.ty.validate().map_err(error::request)?;
+        self.id.validate().map_err(error::request)?;
+
         let body = serde_json::to_vec(&self.body).map_err(error::request)?;
 
         Ok(UpdateRequest::for_index_ty_id(
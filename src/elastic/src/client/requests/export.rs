@@ -0,0 +1,1431 @@
+/*!
+Builders for exporting every document that matches a query as [newline delimited JSON][ndjson].
+
+On Elasticsearch `7.12.0` and later, an export pages through the matching documents in a [point in time][docs-pit] with [`search_after`][docs-search-after].
+On earlier versions, and for clients with an index scope, it pages through a [scroll][docs-scroll] instead.
+Either way, the export sees a consistent snapshot of the indices as they were when the export started.
+Each document's `_source` is written to the writer as a single line of JSON.
+
+After each page is written, the export's progress reports where to resume from.
+Within a point in time, that's the sort values of the last document written, so no documents are skipped even if the export failed after a page was fetched but before it was written.
+In a scroll, that's the scroll id of the last page written, which can only be resumed until the scroll expires.
+
+[ndjson]: http://ndjson.org
+[docs-search-after]: https://www.elastic.co/guide/en/elasticsearch/reference/current/paginate-search-results.html#search-after
+[docs-pit]: https://www.elastic.co/guide/en/elasticsearch/reference/current/point-in-time-api.html
+[docs-scroll]: https://www.elastic.co/guide/en/elasticsearch/reference/current/paginate-search-results.html#scroll-search-results
+*/
+
+use fluent_builder::SharedFluentBuilder;
+use futures::{
+    future::{
+        self,
+        Either,
+        Loop,
+    },
+    Future,
+    IntoFuture,
+};
+use serde::{
+    de::DeserializeOwned,
+    ser::Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use std::{
+    io::Write,
+    marker::PhantomData,
+    time::Duration,
+};
+use tokio::io::{
+    self as async_io,
+    AsyncWrite,
+};
+
+use crate::{
+    client::{
+        requests::{
+            common::time_value,
+            search::{
+                Query,
+                QueryBuilder,
+            },
+            Pending as BasePending,
+        },
+        responses::SearchResponse,
+        Client,
+        Version,
+    },
+    endpoints::{
+        ClearScrollRequest,
+        Endpoint,
+        ScrollRequest,
+        SearchRequest,
+    },
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        receiver::IsOkOnSuccess,
+        sender::{
+            AsyncSender,
+            RequestParams,
+            Sender,
+            SyncSender,
+        },
+        DefaultBody,
+        Method,
+        UrlPath,
+    },
+    params::{
+        encode_path_segment,
+        Index,
+    },
+};
+
+/** The number of documents requested in each page by default. */
+const DEFAULT_PAGE_SIZE: u64 = 1000;
+
+/** How long the point in time or scroll is kept alive between pages by default. */
+const DEFAULT_KEEP_ALIVE: Duration = Duration::from_secs(60);
+
+/** The first version with a point in time that adds its own tiebreaker to the sort. */
+const POINT_IN_TIME_VERSION: Version = Version::new(7, 12, 0);
+
+/**
+An export builder that can be configured before sending.
+
+Call [`Client.export`][Client.export] to get an `ExportRequestBuilder`.
+The `send` method will either export the documents [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+An export sends a search request for each page of documents, so it doesn't support setting request parameters like other request builders.
+
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.export]: ../../struct.Client.html#export-request
+*/
+pub struct ExportRequestBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    client: Client<TSender>,
+    inner: ExportRequestInner,
+    _marker: PhantomData<TDocument>,
+}
+
+struct ExportRequestInner {
+    index: Index<'static>,
+    query: Option<Value>,
+    sort: Option<Value>,
+    page_size: u64,
+    keep_alive: Duration,
+    search_after: Option<Vec<Value>>,
+    pit_id: Option<String>,
+    scroll_id: Option<String>,
+    progress: Option<Box<ReportProgress>>,
+}
+
+type ReportProgress = dyn FnMut(&ExportProgress) + Send;
+
+/**
+The progress of an export.
+
+The progress is given to the callback set by [`ExportRequestBuilder.progress`][ExportRequestBuilder.progress] after each page of documents is written.
+Sending an export returns its progress when there are no more documents left to write.
+
+[ExportRequestBuilder.progress]: struct.ExportRequestBuilder.html#method.progress
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportProgress {
+    exported: u64,
+    total: u64,
+    search_after: Option<Vec<Value>>,
+    pit_id: Option<String>,
+    scroll_id: Option<String>,
+}
+
+impl ExportProgress {
+    fn new(inner: &ExportRequestInner) -> Self {
+        ExportProgress {
+            exported: 0,
+            total: 0,
+            search_after: inner.search_after.clone(),
+            pit_id: inner.pit_id.clone(),
+            scroll_id: inner.scroll_id.clone(),
+        }
+    }
+
+    /**
+    The number of documents written so far.
+
+    Documents written before an export was resumed aren't counted.
+    */
+    pub fn exported(&self) -> u64 {
+        self.exported
+    }
+
+    /**
+    The total number of documents that match the query, as reported by the first page.
+
+    Like [`SearchResponse.total`][SearchResponse.total], the total may only be a lower bound.
+
+    [SearchResponse.total]: ../../responses/struct.SearchResponse.html#method.total
+    */
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /**
+    The sort values of the last document that was written.
+
+    Pass these values to [`ExportRequestBuilder.search_after`][ExportRequestBuilder.search_after] to resume the export after that document.
+    This is `None` if no documents have been written yet, or if the export is paging through a scroll.
+
+    [ExportRequestBuilder.search_after]: struct.ExportRequestBuilder.html#method.search_after
+    */
+    pub fn search_after(&self) -> Option<&[Value]> {
+        self.search_after.as_deref()
+    }
+
+    /**
+    The id of the point in time that the export is searching.
+
+    Pass this id to [`ExportRequestBuilder.pit_id`][ExportRequestBuilder.pit_id] along with the sort values to resume the export from the same snapshot.
+    This is `None` if the export isn't searching a point in time.
+
+    [ExportRequestBuilder.pit_id]: struct.ExportRequestBuilder.html#method.pit_id
+    */
+    pub fn pit_id(&self) -> Option<&str> {
+        self.pit_id.as_deref()
+    }
+
+    /**
+    The id of the scroll to fetch the next page of documents from.
+
+    Pass this id to [`ExportRequestBuilder.scroll_id`][ExportRequestBuilder.scroll_id] to resume the export after the last page that was written.
+    This is `None` if the export is searching a point in time.
+
+    [ExportRequestBuilder.scroll_id]: struct.ExportRequestBuilder.html#method.scroll_id
+    */
+    pub fn scroll_id(&self) -> Option<&str> {
+        self.scroll_id.as_deref()
+    }
+}
+
+/**
+# Export request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`ExportRequestBuilder`][ExportRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Write every document in `myindex` to a file:
+
+    ```no_run
+    # use std::fs::File;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let file = File::create("myindex.ndjson")?;
+
+    let progress = client.export::<Value>()
+                         .index("myindex")
+                         .send(file)?;
+
+    println!("exported {} documents", progress.exported());
+    # Ok(())
+    # }
+    ```
+
+    [ExportRequestBuilder]: requests/export/struct.ExportRequestBuilder.html
+    [builder-methods]: requests/export/struct.ExportRequestBuilder.html#builder-methods
+    [send-sync]: requests/export/struct.ExportRequestBuilder.html#send-synchronously
+    [send-async]: requests/export/struct.ExportRequestBuilder.html#send-asynchronously
+    */
+    pub fn export<TDocument>(&self) -> ExportRequestBuilder<TSender, TDocument>
+    where
+        TDocument: DeserializeOwned + Serialize,
+    {
+        ExportRequestBuilder {
+            client: self.clone(),
+            inner: ExportRequestInner {
+                index: "_all".into(),
+                query: None,
+                sort: None,
+                page_size: DEFAULT_PAGE_SIZE,
+                keep_alive: DEFAULT_KEEP_ALIVE,
+                search_after: None,
+                pit_id: None,
+                scroll_id: None,
+                progress: None,
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure an `ExportRequestBuilder` before sending it.
+*/
+impl<TSender, TDocument> ExportRequestBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /**
+    Set the indices to export documents from.
+
+    If no index is specified then `_all` will be used.
+    */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = index.into();
+        self
+    }
+
+    /**
+    Only export documents that match a [query][docs-query].
+
+    The closure is given a [`QueryBuilder`][QueryBuilder], the same as for [`SearchRequestBuilder.query`][SearchRequestBuilder.query].
+    If no query is specified then every document is exported.
+
+    [docs-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl.html
+    [QueryBuilder]: ../search/struct.QueryBuilder.html
+    [SearchRequestBuilder.query]: ../search/type.SearchRequestBuilder.html#method.query
+    */
+    pub fn query<TQuery>(mut self, builder: impl FnOnce(QueryBuilder) -> TQuery) -> Self
+    where
+        TQuery: Into<Query>,
+    {
+        self.inner.query = Some(builder(QueryBuilder).into().into_value());
+        self
+    }
+
+    /**
+    Set the [sort][docs-sort] that documents are written in.
+
+    When the export searches a point in time, Elasticsearch breaks ties in the sort by the position of each document in the snapshot.
+    By default, documents are written in the cheapest order to page through, which is by `_shard_doc` within a point in time or by `_doc` in a scroll.
+
+    [docs-sort]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-body.html#request-body-search-sort
+    */
+    pub fn sort(mut self, sort: impl Into<Value>) -> Self {
+        self.inner.sort = Some(sort.into());
+        self
+    }
+
+    /**
+    Set the number of documents requested in each page.
+
+    By default, `1000` documents are requested at a time.
+    A page size of `0` is treated as `1`.
+    */
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.inner.page_size = page_size.max(1);
+        self
+    }
+
+    /**
+    Set how long the point in time or scroll is kept alive between pages.
+
+    The point in time or scroll has to be kept alive long enough to write each page.
+    By default, it's kept alive for `1m`.
+    */
+    pub fn keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.inner.keep_alive = keep_alive;
+        self
+    }
+
+    /**
+    Resume an export after the document with the given sort values.
+
+    The sort values come from [`ExportProgress.search_after`][ExportProgress.search_after].
+    The export continues with the first document after them, using the same index, query and sort as the export being resumed.
+
+    Pass the [`pit_id`](#method.pit_id) from the same progress to resume from the same snapshot.
+    If the point in time has expired, or isn't given, then the export is resumed from a new snapshot.
+    Sort values from a point in time include the position of the last document in that snapshot,
+    so resuming from a new snapshot only continues from the right document if the sort ends with a field that's unique to each document.
+
+    Sort values are only reported by exports that search a point in time.
+    Sending an export with sort values fails without sending any requests if it can't search a point in time.
+
+    # Examples
+
+    Save the progress after each page, then resume from it if the export fails part way through:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use std::fs::{self, OpenOptions};
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let file = OpenOptions::new().create(true).append(true).open("myindex.ndjson")?;
+
+    let export = client.export::<Value>()
+                       .index("myindex")
+                       .progress(|progress| {
+                           let saved = json!({
+                               "search_after": progress.search_after(),
+                               "pit_id": progress.pit_id(),
+                               "scroll_id": progress.scroll_id(),
+                           });
+
+                           fs::write("myindex.progress", saved.to_string()).expect("failed to save the progress");
+                       });
+
+    let export = match fs::read("myindex.progress") {
+        Ok(saved) => {
+            let saved: Value = serde_json::from_slice(&saved)?;
+
+            match (saved["search_after"].as_array(), saved["pit_id"].as_str(), saved["scroll_id"].as_str()) {
+                (Some(search_after), Some(pit_id), _) => export.search_after(search_after.clone()).pit_id(pit_id),
+                (Some(search_after), None, _) => export.search_after(search_after.clone()),
+                (None, _, Some(scroll_id)) => export.scroll_id(scroll_id),
+                _ => export,
+            }
+        }
+        Err(_) => export,
+    };
+
+    let progress = export.send(file)?;
+    # Ok(())
+    # }
+    ```
+
+    [ExportProgress.search_after]: struct.ExportProgress.html#method.search_after
+    */
+    pub fn search_after(mut self, sort_values: impl Into<Vec<Value>>) -> Self {
+        self.inner.search_after = Some(sort_values.into());
+        self
+    }
+
+    /**
+    Search the point in time with the given id instead of opening a new one.
+
+    The id comes from [`ExportProgress.pit_id`][ExportProgress.pit_id].
+    Resuming fails if the point in time has expired.
+
+    [ExportProgress.pit_id]: struct.ExportProgress.html#method.pit_id
+    */
+    pub fn pit_id(mut self, pit_id: impl Into<String>) -> Self {
+        self.inner.pit_id = Some(pit_id.into());
+        self
+    }
+
+    /**
+    Resume an export from the scroll id saved by an earlier one.
+
+    The scroll id comes from [`ExportProgress.scroll_id`][ExportProgress.scroll_id].
+    The export continues after the last page fetched with that scroll id, using the same snapshot, index, query and sort as the export being resumed.
+    Resuming fails if the scroll has expired.
+
+    [ExportProgress.scroll_id]: struct.ExportProgress.html#method.scroll_id
+    */
+    pub fn scroll_id(mut self, scroll_id: impl Into<String>) -> Self {
+        self.inner.scroll_id = Some(scroll_id.into());
+        self
+    }
+
+    /**
+    Call a function after each page of documents is written.
+
+    The function is given the progress of the export so far.
+    Saving the progress's sort values or scroll id makes it possible to resume an export that fails part way through.
+
+    # Examples
+
+    ```no_run
+    # use std::fs::File;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let file = File::create("myindex.ndjson")?;
+
+    client.export::<Value>()
+          .index("myindex")
+          .progress(|progress| {
+              println!("exported {} of {} documents", progress.exported(), progress.total())
+          })
+          .send(file)?;
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn progress(mut self, progress: impl FnMut(&ExportProgress) + Send + 'static) -> Self {
+        self.inner.progress = Some(Box::new(progress));
+        self
+    }
+}
+
+/** The response to opening a point in time. */
+#[derive(Deserialize)]
+struct OpenPointInTimeResponse {
+    id: String,
+}
+
+impl IsOkOnSuccess for OpenPointInTimeResponse {}
+
+impl ExportRequestInner {
+    /**
+    Whether to open a point in time for the export.
+
+    A point in time is searched without naming the indices in the path, so clients with an index scope don't use one.
+    An export that's resuming a scroll keeps using it.
+    */
+    fn opens_pit<TSender>(&self, client: &Client<TSender>, version: Option<Version>) -> bool {
+        self.pit_id.is_none()
+            && self.scroll_id.is_none()
+            && client.index_scope.is_none()
+            && matches!(version, Some(version) if version >= POINT_IN_TIME_VERSION)
+    }
+
+    /**
+    Check the export can resume from where it was asked to.
+
+    Sort values can only be resumed from within a point in time, because a scroll can't start after them.
+    */
+    fn check(&self) -> Result<(), Error> {
+        if self.pit_id.is_none() && self.search_after.is_some() {
+            return Err(error::request(error::message(
+                "resuming an export from sort values needs a point in time, which needs Elasticsearch 7.12.0 or later and a client without an index scope",
+            )));
+        }
+
+        if self.pit_id.is_some() && self.scroll_id.is_some() {
+            return Err(error::request(error::message(
+                "an export can't resume from both a point in time and a scroll",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /** The `keep_alive` url parameter that keeps a point in time alive. */
+    fn keep_alive_param(&self) -> impl Fn(RequestParams) -> RequestParams + Send + 'static {
+        let keep_alive = time_value(self.keep_alive);
+
+        move |params| params.url_param("keep_alive", keep_alive.clone())
+    }
+
+    /**
+    The url parameters for the next page of documents.
+
+    The search that starts a scroll needs the `scroll` url parameter to keep it alive.
+    */
+    fn page_params(&self) -> impl Fn(RequestParams) -> RequestParams + Send + 'static {
+        let scroll = match (&self.pit_id, &self.scroll_id) {
+            (None, None) => Some(time_value(self.keep_alive)),
+            _ => None,
+        };
+
+        move |params| match scroll {
+            Some(ref scroll) => params.url_param("scroll", scroll.clone()),
+            None => params,
+        }
+    }
+
+    /** The request that opens a point in time over the indices being exported. */
+    fn open_pit_request(&self) -> Endpoint<'static, DefaultBody> {
+        Endpoint {
+            url: UrlPath::from(format!("/{}/_pit", encode_path_segment(self.index.as_ref()))),
+            method: Method::POST,
+            body: None,
+        }
+    }
+
+    /** The sort for the export, which is the cheapest order to page through by default. */
+    fn sort(&self) -> Value {
+        match self.sort {
+            Some(ref sort) => sort.clone(),
+            None if self.pit_id.is_some() => json!(["_shard_doc"]),
+            None => json!(["_doc"]),
+        }
+    }
+
+    /** The search body for the next page of documents, or for the first page of a scroll. */
+    fn search_body(&self) -> Value {
+        let mut body = Map::new();
+
+        body.insert("size".to_owned(), self.page_size.into());
+        body.insert("sort".to_owned(), self.sort());
+
+        if let Some(ref query) = self.query {
+            body.insert("query".to_owned(), query.clone());
+        }
+
+        if let Some(ref search_after) = self.search_after {
+            body.insert("search_after".to_owned(), search_after.clone().into());
+        }
+
+        if let Some(ref pit_id) = self.pit_id {
+            body.insert(
+                "pit".to_owned(),
+                json!({
+                    "id": pit_id,
+                    "keep_alive": time_value(self.keep_alive),
+                }),
+            );
+        }
+
+        Value::Object(body)
+    }
+
+    /**
+    The request for the next page of documents.
+
+    A point in time already knows which indices it's searching, so they aren't named in the path.
+    A scroll is started with a search, and each page after that is fetched with the scroll id of the page before it.
+    */
+    fn page_request(&self) -> Endpoint<'static, String> {
+        match (&self.pit_id, &self.scroll_id) {
+            (Some(_), _) => SearchRequest::new(self.search_body().to_string()).into(),
+            (None, Some(scroll_id)) => ScrollRequest::new(
+                json!({
+                    "scroll": time_value(self.keep_alive),
+                    "scroll_id": scroll_id,
+                })
+                .to_string(),
+            )
+            .into(),
+            (None, None) => {
+                SearchRequest::for_index(self.index.clone(), self.search_body().to_string()).into()
+            }
+        }
+    }
+
+    /**
+    Serialise a page of documents as newline delimited JSON.
+
+    Returns the serialised documents and whether this was the last page.
+    Within a point in time, the sort values of the last document on the page are kept to request the next page after it,
+    and the export is finished when a page has fewer documents than the page size.
+    A scroll is finished when a page doesn't contain any documents.
+    */
+    fn write_page<TDocument>(
+        &mut self,
+        progress: &mut ExportProgress,
+        page: SearchResponse<TDocument>,
+    ) -> Result<(Vec<u8>, bool), Error>
+    where
+        TDocument: Serialize,
+    {
+        if progress.exported == 0 {
+            progress.total = page.total();
+        }
+
+        if let Some(pit_id) = page.pit_id() {
+            self.pit_id = Some(pit_id.to_owned());
+        }
+
+        if let Some(scroll_id) = page.scroll_id() {
+            self.scroll_id = Some(scroll_id.to_owned());
+        }
+
+        let pit = self.pit_id.is_some();
+
+        let mut buf = Vec::new();
+        let mut written = 0;
+
+        for hit in page.into_hits() {
+            let id = hit.id().to_string();
+
+            let search_after = match hit.sort() {
+                Some(Value::Array(sort)) => Some(sort.clone()),
+                _ if pit => {
+                    return Err(error::request(error::message(format!(
+                        "the hit '{}' doesn't have sort values",
+                        id
+                    ))))
+                }
+                _ => None,
+            };
+
+            let doc = hit.into_document().ok_or_else(|| {
+                error::request(error::message(format!(
+                    "the hit '{}' doesn't have a source",
+                    id
+                )))
+            })?;
+
+            serde_json::to_writer(&mut buf, &doc).map_err(error::request)?;
+            buf.push(b'\n');
+
+            if pit {
+                self.search_after = search_after;
+            }
+            written += 1;
+        }
+
+        progress.exported += written;
+        progress.search_after = self.search_after.clone();
+        progress.pit_id = self.pit_id.clone();
+        progress.scroll_id = self.scroll_id.clone();
+
+        let last = if pit {
+            written < self.page_size
+        } else {
+            written == 0
+        };
+
+        Ok((buf, last))
+    }
+
+    fn report(&mut self, progress: &ExportProgress) {
+        if let Some(ref mut report) = self.progress {
+            report(progress);
+        }
+    }
+}
+
+/** The request that closes the point in time or clears the scroll an export was paging through. */
+fn close_request(inner: &ExportRequestInner) -> Option<Endpoint<'static, String>> {
+    match (&inner.pit_id, &inner.scroll_id) {
+        (Some(pit_id), _) => Some(Endpoint {
+            url: UrlPath::from("/_pit"),
+            method: Method::DELETE,
+            body: Some(json!({ "id": pit_id }).to_string()),
+        }),
+        (None, Some(scroll_id)) => {
+            Some(ClearScrollRequest::new(json!({ "scroll_id": [scroll_id] }).to_string()).into())
+        }
+        (None, None) => None,
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TDocument> ExportRequestBuilder<SyncSender, TDocument>
+where
+    TDocument: DeserializeOwned + Serialize,
+{
+    /**
+    Export documents synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until every document has been written to the writer.
+    The point in time or scroll is closed once the export is finished.
+    If a page fails, the documents from earlier pages will already have been written and the point in time or scroll is left to expire so the export can be resumed.
+
+    # Examples
+
+    Write every matching document to standard output:
+
+    ```no_run
+    # use std::io;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let progress = client.export::<Value>()
+                         .index("myindex")
+                         .query(|q| q.match_query("title", "rust"))
+                         .send(io::stdout())?;
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self, mut writer: impl Write) -> Result<ExportProgress, Error> {
+        let ExportRequestBuilder {
+            client, mut inner, ..
+        } = self;
+
+        let (version, _) = client.detect_version(SharedFluentBuilder::new())?;
+
+        if inner.opens_pit(&client, version) {
+            let pit = client
+                .request(inner.open_pit_request())
+                .params_fluent(inner.keep_alive_param())
+                .send()?
+                .into_response::<OpenPointInTimeResponse>()?;
+
+            inner.pit_id = Some(pit.id);
+        }
+
+        inner.check()?;
+
+        let mut progress = ExportProgress::new(&inner);
+
+        loop {
+            let page = client
+                .request(inner.page_request())
+                .params_fluent(inner.page_params())
+                .send()?
+                .into_response::<SearchResponse<TDocument>>()?
+                .into_complete()?;
+
+            let (buf, last) = inner.write_page(&mut progress, page)?;
+            writer.write_all(&buf).map_err(error::request)?;
+
+            inner.report(&progress);
+
+            if last {
+                break;
+            }
+        }
+
+        writer.flush().map_err(error::request)?;
+
+        if let Some(close) = close_request(&inner) {
+            let closed = client.request(close).send();
+
+            if let Err(e) = closed {
+                warn!(
+                    "Elasticsearch Export: failed to close the point in time or scroll: '{:?}'",
+                    e
+                );
+            }
+        }
+
+        Ok(progress)
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TDocument> ExportRequestBuilder<AsyncSender, TDocument>
+where
+    TDocument: DeserializeOwned + Serialize + Send + 'static,
+{
+    /**
+    Export documents asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the progress of the export once every document has been written to the writer.
+    The point in time or scroll is closed once the export is finished.
+    If a page fails, the documents from earlier pages will already have been written and the point in time or scroll is left to expire so the export can be resumed.
+
+    # Examples
+
+    Write every document in `myindex` to standard output:
+
+    ```no_run
+    # use futures::Future;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.export::<Value>()
+                       .index("myindex")
+                       .send(tokio::io::stdout());
+
+    future.and_then(|progress| {
+        println!("exported {} documents", progress.exported());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send<TWriter>(self, writer: TWriter) -> Pending
+    where
+        TWriter: AsyncWrite + Send + 'static,
+    {
+        let ExportRequestBuilder { client, inner, .. } = self;
+
+        let opened = client
+            .detect_version(SharedFluentBuilder::new())
+            .and_then(move |(version, _)| {
+                if !inner.opens_pit(&client, version) {
+                    return Either::A(Ok((client, inner)).into_future());
+                }
+
+                let pit = client
+                    .request(inner.open_pit_request())
+                    .params_fluent(inner.keep_alive_param())
+                    .send()
+                    .and_then(|res| res.into_response::<OpenPointInTimeResponse>());
+
+                Either::B(pit.map(move |pit| {
+                    let mut inner = inner;
+                    inner.pit_id = Some(pit.id);
+
+                    (client, inner)
+                }))
+            });
+
+        let pages = opened.and_then(|(client, inner)| {
+            inner.check()?;
+
+            Ok((client, inner))
+        });
+
+        let pages = pages.and_then(|(client, inner)| {
+            let progress = ExportProgress::new(&inner);
+
+            future::loop_fn(
+                (client, inner, progress, writer),
+                |(client, mut inner, mut progress, writer)| {
+                    client
+                        .request(inner.page_request())
+                        .params_fluent(inner.page_params())
+                        .send()
+                        .and_then(|res| res.into_response::<SearchResponse<TDocument>>())
+                        .and_then(|page| page.into_complete())
+                        .and_then(move |page| {
+                            let (buf, last) = inner.write_page(&mut progress, page)?;
+
+                            Ok((client, inner, progress, writer, buf, last))
+                        })
+                        .and_then(|(client, inner, progress, writer, buf, last)| {
+                            async_io::write_all(writer, buf)
+                                .map_err(error::request)
+                                .map(move |(writer, _)| (client, inner, progress, writer, last))
+                        })
+                        .map(|(client, mut inner, progress, writer, last)| {
+                            inner.report(&progress);
+
+                            if last {
+                                Loop::Break((client, inner, progress, writer))
+                            } else {
+                                Loop::Continue((client, inner, progress, writer))
+                            }
+                        })
+                },
+            )
+        });
+
+        let export = pages
+            .and_then(|(client, inner, progress, writer)| {
+                async_io::flush(writer)
+                    .map_err(error::request)
+                    .map(move |_| (client, inner, progress))
+            })
+            .and_then(|(client, inner, progress)| {
+                let closed = match close_request(&inner) {
+                    Some(close) => Either::A(client.request(close).send().map(|_| ())),
+                    None => Either::B(Ok(()).into_future()),
+                };
+
+                closed.then(move |res| {
+                    if let Err(e) = res {
+                        warn!(
+                            "Elasticsearch Export: failed to close the point in time or scroll: '{:?}'",
+                            e
+                        );
+                    }
+
+                    Ok(progress)
+                })
+            });
+
+        Pending::new(export)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<ExportProgress>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::sender::recording::test_recording,
+        prelude::*,
+        tests::*,
+    };
+    use std::{
+        fs,
+        io,
+        path::PathBuf,
+        sync::{
+            Arc,
+            Mutex,
+        },
+    };
+    use tokio::io::AsyncWrite;
+
+    fn hit(id: &str) -> Value {
+        json!({
+            "_index": "myindex",
+            "_type": "_doc",
+            "_id": id,
+            "_score": null,
+            "_source": { "id": id },
+            "sort": [id]
+        })
+    }
+
+    fn page(hits: Vec<Value>) -> String {
+        json!({
+            "took": 1,
+            "timed_out": false,
+            "_shards": { "total": 1, "successful": 1, "skipped": 0, "failed": 0 },
+            "hits": {
+                "total": { "value": 3, "relation": "eq" },
+                "max_score": null,
+                "hits": hits
+            }
+        })
+        .to_string()
+    }
+
+    fn pit_page(pit_id: &str, hits: Vec<Value>) -> String {
+        let mut page: Value = serde_json::from_str(&page(hits)).unwrap();
+        page["pit_id"] = json!(pit_id);
+
+        page.to_string()
+    }
+
+    fn scroll_page(scroll_id: &str, hits: Vec<Value>) -> String {
+        let mut page: Value = serde_json::from_str(&page(hits)).unwrap();
+        page["_scroll_id"] = json!(scroll_id);
+
+        page.to_string()
+    }
+
+    fn interaction(
+        method: &str,
+        path: &str,
+        query: Option<&str>,
+        body: Option<Value>,
+        response: String,
+    ) -> Value {
+        json!({
+            "request": {
+                "method": method,
+                "path": path,
+                "query": query,
+                "body": body.map(|body| body.to_string())
+            },
+            "response": { "status": 200, "body": response }
+        })
+    }
+
+    fn start_scroll(page: String) -> Value {
+        interaction(
+            "POST",
+            "/myindex/_search",
+            Some("scroll=1m"),
+            Some(json!({ "size": 2, "sort": ["_doc"] })),
+            page,
+        )
+    }
+
+    fn scroll(scroll_id: &str, page: String) -> Value {
+        interaction(
+            "POST",
+            "/_search/scroll",
+            None,
+            Some(json!({ "scroll": "1m", "scroll_id": scroll_id })),
+            page,
+        )
+    }
+
+    fn clear_scroll(scroll_id: &str) -> Value {
+        interaction(
+            "DELETE",
+            "/_search/scroll",
+            None,
+            Some(json!({ "scroll_id": [scroll_id] })),
+            json!({ "succeeded": true, "num_freed": 1 }).to_string(),
+        )
+    }
+
+    fn open_pit() -> Value {
+        interaction(
+            "POST",
+            "/myindex/_pit",
+            Some("keep_alive=1m"),
+            None,
+            json!({ "id": "p1" }).to_string(),
+        )
+    }
+
+    fn pit_search(pit_id: &str, search_after: Option<&str>, page: String) -> Value {
+        let mut body = json!({ "size": 2, "sort": ["_shard_doc"] });
+
+        // The export adds `search_after` before `pit`, which matters with `preserve_order`
+        if let Some(search_after) = search_after {
+            body["search_after"] = json!([search_after]);
+        }
+
+        body["pit"] = json!({ "id": pit_id, "keep_alive": "1m" });
+
+        interaction("POST", "/_search", None, Some(body), page)
+    }
+
+    fn close_pit(pit_id: &str) -> Value {
+        interaction(
+            "DELETE",
+            "/_pit",
+            None,
+            Some(json!({ "id": pit_id })),
+            json!({ "succeeded": true, "num_freed": 1 }).to_string(),
+        )
+    }
+
+    /** A recording of an export of three documents in pages of two, through a scroll. */
+    fn export_recording() -> PathBuf {
+        test_recording(vec![
+            start_scroll(scroll_page("s1", vec![hit("a"), hit("b")])),
+            scroll("s1", scroll_page("s2", vec![hit("c")])),
+            scroll("s2", scroll_page("s2", vec![])),
+            clear_scroll("s2"),
+        ])
+    }
+
+    fn sync_client(path: &PathBuf, version: Version) -> SyncClient {
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(path)
+            .build()
+            .unwrap();
+        client.version.set(version);
+
+        fs::remove_file(path).unwrap();
+
+        client
+    }
+
+    const EXPORTED: &str = "{\"id\":\"a\"}\n{\"id\":\"b\"}\n{\"id\":\"c\"}\n";
+
+    #[test]
+    fn export_sync() {
+        let client = sync_client(&export_recording(), Version::new(7, 4, 0));
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let captured = reported.clone();
+
+        let mut buf = Vec::new();
+        let progress = client
+            .export::<Value>()
+            .index("myindex")
+            .page_size(2)
+            .progress(move |progress| {
+                captured
+                    .lock()
+                    .unwrap()
+                    .push((progress.exported(), progress.scroll_id().unwrap().to_owned()))
+            })
+            .send(&mut buf)
+            .unwrap();
+
+        assert_eq!(EXPORTED, String::from_utf8(buf).unwrap());
+        assert_eq!(
+            vec![
+                (2, "s1".to_owned()),
+                (3, "s2".to_owned()),
+                (3, "s2".to_owned())
+            ],
+            *reported.lock().unwrap()
+        );
+
+        assert_eq!(3, progress.exported());
+        assert_eq!(3, progress.total());
+        assert_eq!(None, progress.search_after());
+        assert_eq!(None, progress.pit_id());
+    }
+
+    #[test]
+    fn export_sync_resume() {
+        let client = sync_client(
+            &test_recording(vec![
+                scroll("s1", scroll_page("s1", vec![hit("c")])),
+                scroll("s1", scroll_page("s1", vec![])),
+                clear_scroll("s1"),
+            ]),
+            Version::new(7, 4, 0),
+        );
+
+        let mut buf = Vec::new();
+        let progress = client
+            .export::<Value>()
+            .index("myindex")
+            .page_size(2)
+            .scroll_id("s1")
+            .send(&mut buf)
+            .unwrap();
+
+        assert_eq!("{\"id\":\"c\"}\n", String::from_utf8(buf).unwrap());
+        assert_eq!(1, progress.exported());
+    }
+
+    #[test]
+    fn export_sync_search_after_without_pit_fails_without_sending() {
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .pre_send_raw(|_| panic!("the request shouldn't be sent"))
+            .build()
+            .unwrap();
+        client.version.set(Version::new(7, 4, 0));
+
+        let res = client
+            .export::<Value>()
+            .index("myindex")
+            .search_after(vec![json!("b")])
+            .send(io::sink());
+
+        match res {
+            Err(Error::Client(ref e)) => {
+                assert!(format!("{:?}", e).contains("needs a point in time"))
+            }
+            _ => panic!("expected a client error"),
+        }
+    }
+
+    #[test]
+    fn export_sync_failed_page_resumes_after_last_written_document() {
+        // The second page isn't recorded, so fetching it fails
+        let client = sync_client(
+            &test_recording(vec![
+                open_pit(),
+                pit_search("p1", None, pit_page("p1", vec![hit("a"), hit("b")])),
+            ]),
+            Version::new(7, 12, 0),
+        );
+
+        let reported = Arc::new(Mutex::new(None));
+        let captured = reported.clone();
+
+        let res = client
+            .export::<Value>()
+            .index("myindex")
+            .page_size(2)
+            .progress(move |progress| *captured.lock().unwrap() = Some(progress.clone()))
+            .send(io::sink());
+
+        assert!(res.is_err());
+
+        let saved = reported.lock().unwrap().clone().unwrap();
+        assert_eq!(Some(&[json!("b")][..]), saved.search_after());
+        assert_eq!(Some("p1"), saved.pit_id());
+    }
+
+    #[test]
+    fn export_sync_pit() {
+        let client = sync_client(
+            &test_recording(vec![
+                open_pit(),
+                pit_search("p1", None, pit_page("p2", vec![hit("a"), hit("b")])),
+                pit_search("p2", Some("b"), pit_page("p2", vec![hit("c")])),
+                close_pit("p2"),
+            ]),
+            Version::new(7, 12, 0),
+        );
+
+        let mut buf = Vec::new();
+        let progress = client
+            .export::<Value>()
+            .index("myindex")
+            .page_size(2)
+            .send(&mut buf)
+            .unwrap();
+
+        assert_eq!(EXPORTED, String::from_utf8(buf).unwrap());
+        assert_eq!(Some("p2"), progress.pit_id());
+        assert_eq!(Some(&[json!("c")][..]), progress.search_after());
+    }
+
+    #[test]
+    fn export_sync_pit_resume() {
+        let client = sync_client(
+            &test_recording(vec![
+                pit_search("p1", Some("b"), pit_page("p1", vec![hit("c")])),
+                close_pit("p1"),
+            ]),
+            Version::new(7, 12, 0),
+        );
+
+        let mut buf = Vec::new();
+        client
+            .export::<Value>()
+            .index("myindex")
+            .page_size(2)
+            .search_after(vec![json!("b")])
+            .pit_id("p1")
+            .send(&mut buf)
+            .unwrap();
+
+        assert_eq!("{\"id\":\"c\"}\n", String::from_utf8(buf).unwrap());
+    }
+
+    #[test]
+    fn export_sync_hit_without_source() {
+        let mut hit = hit("a");
+        hit.as_object_mut().unwrap().remove("_source");
+
+        let client = sync_client(
+            &test_recording(vec![start_scroll(scroll_page("s1", vec![hit]))]),
+            Version::new(7, 4, 0),
+        );
+
+        let res = client
+            .export::<Value>()
+            .index("myindex")
+            .page_size(2)
+            .send(io::sink());
+
+        match res {
+            Err(Error::Client(ref e)) => {
+                assert!(format!("{:?}", e).contains("the hit 'a' doesn't have a source"))
+            }
+            _ => panic!("expected a client error"),
+        }
+    }
+
+    #[test]
+    fn export_search_body() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client
+            .export::<Value>()
+            .index("myindex")
+            .query(|q| q.match_query("title", "rust"));
+
+        assert_eq!(
+            json!({
+                "size": 1000,
+                "sort": ["_doc"],
+                "query": { "match": { "title": { "query": "rust" } } }
+            }),
+            builder.inner.search_body()
+        );
+        assert_eq!("/myindex/_search", builder.inner.page_request().url.as_ref());
+    }
+
+    #[test]
+    fn export_sort_defaults_to_cheapest_order() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        assert_eq!(json!(["_doc"]), client.export::<Value>().inner.sort());
+        assert_eq!(
+            json!(["_shard_doc"]),
+            client.export::<Value>().pit_id("p1").inner.sort()
+        );
+        assert_eq!(
+            json!({ "timestamp": "desc" }),
+            client
+                .export::<Value>()
+                .sort(json!({ "timestamp": "desc" }))
+                .inner
+                .sort()
+        );
+    }
+
+    #[test]
+    fn export_pit_search_body() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client
+            .export::<Value>()
+            .sort(json!([{ "timestamp": "desc" }]))
+            .keep_alive(Duration::from_secs(300))
+            .pit_id("p1");
+
+        assert_eq!(
+            json!({
+                "size": 1000,
+                "sort": [{ "timestamp": "desc" }],
+                "pit": { "id": "p1", "keep_alive": "5m" }
+            }),
+            builder.inner.search_body()
+        );
+        assert_eq!("/_search", builder.inner.page_request().url.as_ref());
+    }
+
+    #[test]
+    fn export_opens_pit_on_supported_versions() {
+        let client = SyncClientBuilder::new().build().unwrap();
+        let inner = client.export::<Value>().inner;
+
+        assert!(inner.opens_pit(&client, Some(Version::new(7, 12, 0))));
+        assert!(!inner.opens_pit(&client, Some(Version::new(7, 11, 2))));
+        assert!(!inner.opens_pit(&client, None));
+
+        let scoped = client.scoped(|index| format!("tenant1_{}", index));
+        assert!(!inner.opens_pit(&scoped, Some(Version::new(7, 12, 0))));
+
+        let resumed = client.export::<Value>().scroll_id("s1").inner;
+        assert!(!resumed.opens_pit(&client, Some(Version::new(7, 12, 0))));
+    }
+
+    #[test]
+    fn export_page_size_is_at_least_one() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client.export::<Value>().page_size(0);
+
+        assert_eq!(json!(1), builder.inner.search_body()["size"]);
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl AsyncWrite for SharedWriter {
+        fn shutdown(&mut self) -> futures::Poll<(), io::Error> {
+            Ok(().into())
+        }
+    }
+
+    #[test]
+    fn export_async() {
+        let path = test_recording(vec![
+            open_pit(),
+            pit_search("p1", None, pit_page("p1", vec![hit("a"), hit("b")])),
+            pit_search("p1", Some("b"), pit_page("p1", vec![hit("c")])),
+            close_pit("p1"),
+        ]);
+
+        let client = AsyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap();
+        client.version.set(Version::new(7, 12, 0));
+        fs::remove_file(&path).unwrap();
+
+        let writer = SharedWriter::default();
+
+        let progress = client
+            .export::<Value>()
+            .index("myindex")
+            .page_size(2)
+            .send(writer.clone())
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            EXPORTED,
+            String::from_utf8(writer.0.lock().unwrap().clone()).unwrap()
+        );
+        assert_eq!(3, progress.exported());
+    }
+
+    #[test]
+    fn export_builder_is_send() {
+        assert_send::<ExportRequestBuilder<AsyncSender, Value>>();
+    }
+}
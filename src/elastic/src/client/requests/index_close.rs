@@ -4,7 +4,10 @@ Builders for [close index requests][docs-close-index].
 [docs-close-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-open-close.html
 */
 
-use futures::Future;
+use futures::{
+    Future,
+    IntoFuture,
+};
 
 use crate::{
     client::{
@@ -17,7 +20,10 @@ use crate::{
         IndexClient,
     },
     endpoints::IndicesCloseRequest,
-    error::Error,
+    error::{
+        self,
+        Error,
+    },
     http::{
         empty_body,
         sender::{
@@ -89,8 +95,10 @@ where
 }
 
 impl IndexCloseRequestInner {
-    fn into_request(self) -> IndicesCloseRequest<'static, DefaultBody> {
-        IndicesCloseRequest::for_index(self.index, empty_body())
+    fn into_request(self) -> Result<IndicesCloseRequest<'static, DefaultBody>, Error> {
+        self.index.validate().map_err(error::request)?;
+
+        Ok(IndicesCloseRequest::for_index(self.index, empty_body()))
     }
 }
 
@@ -121,7 +129,7 @@ impl IndexCloseRequestBuilder<SyncSender> {
     [SyncClient]: ../../type.SyncClient.html
     */
     pub fn send(self) -> Result<CommandResponse, Error> {
-        let req = self.inner.into_request();
+        let req = self.inner.into_request()?;
 
         RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
             .send()?
@@ -161,12 +169,13 @@ impl IndexCloseRequestBuilder<AsyncSender> {
     [AsyncClient]: ../../type.AsyncClient.html
     */
     pub fn send(self) -> Pending {
-        let req = self.inner.into_request();
+        let (client, params_builder) = (self.client, self.params_builder);
 
-        let res_future =
-            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+        let res_future = self.inner.into_request().into_future().and_then(|req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
                 .send()
-                .and_then(|res| res.into_response());
+                .and_then(|res| res.into_response())
+        });
 
         Pending::new(res_future)
     }
@@ -191,7 +200,12 @@ mod tests {
     fn default_request() {
         let client = SyncClientBuilder::new().build().unwrap();
 
-        let req = client.index("testindex").close().inner.into_request();
+        let req = client
+            .index("testindex")
+            .close()
+            .inner
+            .into_request()
+            .unwrap();
 
         assert_eq!("/testindex/_close", req.url.as_ref());
     }
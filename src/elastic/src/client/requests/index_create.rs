@@ -4,11 +4,19 @@ Builders for [create index requests][docs-create-index].
 [docs-create-index]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-create-index.html
 */
 
-use futures::Future;
+use futures::{
+    Future,
+    IntoFuture,
+};
+use std::time::Duration;
 
 use crate::{
     client::{
         requests::{
+            common::{
+                time_value,
+                ActiveShards,
+            },
             raw::RawRequestInner,
             Pending as BasePending,
             RequestBuilder,
@@ -17,7 +25,10 @@ use crate::{
         IndexClient,
     },
     endpoints::IndicesCreateRequest,
-    error::Error,
+    error::{
+        self,
+        Error,
+    },
     http::{
         empty_body,
         sender::{
@@ -137,8 +148,10 @@ where
 }
 
 impl<TBody> IndexCreateRequestInner<TBody> {
-    fn into_request(self) -> IndicesCreateRequest<'static, TBody> {
-        IndicesCreateRequest::for_index(self.index, self.body)
+    fn into_request(self) -> Result<IndicesCreateRequest<'static, TBody>, Error> {
+        self.index.validate().map_err(error::request)?;
+
+        Ok(IndicesCreateRequest::for_index(self.index, self.body))
     }
 }
 
@@ -170,6 +183,24 @@ where
             },
         )
     }
+
+    /**
+    Set the number of shard copies that must be active before the index creation proceeds.
+
+    If this isn't specified then Elasticsearch waits for the primary shard only.
+    */
+    pub fn wait_for_active_shards(self, shards: impl Into<ActiveShards>) -> Self {
+        let shards = shards.into();
+
+        self.params_fluent(move |params| params.url_param("wait_for_active_shards", shards))
+    }
+
+    /** Set how long to wait for active shards and for the index creation to complete. */
+    pub fn timeout(self, timeout: Duration) -> Self {
+        let timeout = time_value(timeout);
+
+        self.params_fluent(move |params| params.url_param("timeout", &timeout))
+    }
 }
 
 /**
@@ -202,7 +233,7 @@ where
     [SyncClient]: ../../type.SyncClient.html
     */
     pub fn send(self) -> Result<CommandResponse, Error> {
-        let req = self.inner.into_request();
+        let req = self.inner.into_request()?;
 
         RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
             .send()?
@@ -245,12 +276,13 @@ where
     [AsyncClient]: ../../type.AsyncClient.html
     */
     pub fn send(self) -> Pending {
-        let req = self.inner.into_request();
+        let (client, params_builder) = (self.client, self.params_builder);
 
-        let res_future =
-            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+        let res_future = self.inner.into_request().into_future().and_then(|req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
                 .send()
-                .and_then(|res| res.into_response());
+                .and_then(|res| res.into_response())
+        });
 
         Pending::new(res_future)
     }
@@ -261,6 +293,8 @@ pub type Pending = BasePending<CommandResponse>;
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use crate::{
         prelude::*,
         tests::*,
@@ -275,7 +309,12 @@ mod tests {
     fn default_request() {
         let client = SyncClientBuilder::new().build().unwrap();
 
-        let req = client.index("testindex").create().inner.into_request();
+        let req = client
+            .index("testindex")
+            .create()
+            .inner
+            .into_request()
+            .unwrap();
 
         assert_eq!("/testindex", req.url.as_ref());
     }
@@ -289,8 +328,34 @@ mod tests {
             .create()
             .body("{}")
             .inner
-            .into_request();
+            .into_request()
+            .unwrap();
 
         assert_eq!("{}", req.body);
     }
+
+    #[test]
+    fn specify_wait_for_active_shards_timeout() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let params = client
+            .index("testindex")
+            .create()
+            .wait_for_active_shards(ActiveShards::All)
+            .timeout(Duration::from_secs(90))
+            .url_params();
+
+        assert_eq!(vec!["timeout=90s", "wait_for_active_shards=all"], params);
+    }
+
+    #[test]
+    fn invalid_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let res = client.index("_myindex").create().send();
+
+        let err = crate::tests::invalid_param(res);
+        assert_eq!("index", err.param());
+        assert_eq!("starts with '_'", err.reason());
+    }
 }
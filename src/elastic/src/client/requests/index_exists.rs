@@ -4,7 +4,10 @@ Builders for [index exists requests][docs-index-exists].
 [docs-index-exists]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-exists.html
 */
 
-use futures::Future;
+use futures::{
+    Future,
+    IntoFuture,
+};
 
 use crate::{
     client::{
@@ -17,7 +20,10 @@ use crate::{
         IndexClient,
     },
     endpoints::IndicesExistsRequest,
-    error::Error,
+    error::{
+        self,
+        Error,
+    },
     http::sender::{
         AsyncSender,
         Sender,
@@ -85,8 +91,10 @@ where
 }
 
 impl IndexExistsRequestInner {
-    fn into_request(self) -> IndicesExistsRequest<'static> {
-        IndicesExistsRequest::for_index(self.index)
+    fn into_request(self) -> Result<IndicesExistsRequest<'static>, Error> {
+        self.index.validate().map_err(error::request)?;
+
+        Ok(IndicesExistsRequest::for_index(self.index))
     }
 }
 
@@ -117,7 +125,7 @@ impl IndexExistsRequestBuilder<SyncSender> {
     [SyncClient]: ../../type.SyncClient.html
     */
     pub fn send(self) -> Result<IndicesExistsResponse, Error> {
-        let req = self.inner.into_request();
+        let req = self.inner.into_request()?;
 
         RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
             .send()?
@@ -157,12 +165,13 @@ impl IndexExistsRequestBuilder<AsyncSender> {
     [AsyncClient]: ../../type.AsyncClient.html
     */
     pub fn send(self) -> Pending {
-        let req = self.inner.into_request();
+        let (client, params_builder) = (self.client, self.params_builder);
 
-        let res_future =
-            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+        let res_future = self.inner.into_request().into_future().and_then(|req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
                 .send()
-                .and_then(|res| res.into_response());
+                .and_then(|res| res.into_response())
+        });
 
         Pending::new(res_future)
     }
@@ -187,7 +196,12 @@ mod tests {
     fn default_request() {
         let client = SyncClientBuilder::new().build().unwrap();
 
-        let req = client.index("testindex").exists().inner.into_request();
+        let req = client
+            .index("testindex")
+            .exists()
+            .inner
+            .into_request()
+            .unwrap();
 
         assert_eq!("/testindex", req.url.as_ref());
     }
@@ -0,0 +1,219 @@
+/*!
+Builders for [index segments requests][docs-index-segments].
+
+[docs-index-segments]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-segments.html
+*/
+
+use futures::{
+    Future,
+    IntoFuture,
+};
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::IndicesSegmentsResponse,
+        IndexClient,
+    },
+    endpoints::IndicesSegmentsRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Index,
+};
+
+/**
+An [index segments request][docs-index-segments] builder that can be configured before sending.
+
+Call [`Client.index.segments`][Client.index.segments] to get an `IndexSegmentsRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-index-segments]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-segments.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index.segments]: ../../struct.IndexClient.html#index-segments-request
+*/
+pub type IndexSegmentsRequestBuilder<TSender> = RequestBuilder<TSender, IndexSegmentsRequestInner>;
+
+#[doc(hidden)]
+pub struct IndexSegmentsRequestInner {
+    index: Index<'static>,
+}
+
+/**
+# Index segments request
+*/
+impl<TSender> IndexClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexSegmentsRequestBuilder`][IndexSegmentsRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Check whether an index called `myindex` has enough segments to be worth force-merging:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").segments().send()?;
+
+    if let Some(index) = response.index("myindex") {
+        let most_segments = index.shards().iter().map(|shard| shard.num_search_segments()).max();
+
+        println!("most segments in a shard: {:?}", most_segments);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [IndexSegmentsRequestBuilder]: requests/index_segments/type.IndexSegmentsRequestBuilder.html
+    [send-sync]: requests/index_segments/type.IndexSegmentsRequestBuilder.html#send-synchronously
+    [send-async]: requests/index_segments/type.IndexSegmentsRequestBuilder.html#send-asynchronously
+    */
+    pub fn segments(self) -> IndexSegmentsRequestBuilder<TSender> {
+        RequestBuilder::initial(self.inner, IndexSegmentsRequestInner { index: self.index })
+    }
+}
+
+impl IndexSegmentsRequestInner {
+    fn into_request(self) -> Result<IndicesSegmentsRequest<'static>, Error> {
+        self.index.validate().map_err(error::request)?;
+
+        Ok(IndicesSegmentsRequest::for_index(self.index))
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexSegmentsRequestBuilder<SyncSender> {
+    /**
+    Send an `IndexSegmentsRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Get the segments for an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").segments().send()?;
+
+    for (index, segments) in response.indices() {
+        for shard in segments.shards() {
+            println!("{}/{}: {} bytes", index, shard.shard(), shard.size_in_bytes());
+        }
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<IndicesSegmentsResponse, Error> {
+        let req = self.inner.into_request()?;
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexSegmentsRequestBuilder<AsyncSender> {
+    /**
+    Send an `IndexSegmentsRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised index segments response.
+
+    # Examples
+
+    Get the segments for an index called `myindex`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index("myindex").segments().send();
+
+    future.and_then(|response| {
+        for (index, segments) in response.indices() {
+            for shard in segments.shards() {
+                println!("{}/{}: {} bytes", index, shard.shard(), shard.size_in_bytes());
+            }
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let (client, params_builder) = (self.client, self.params_builder);
+
+        let res_future = self.inner.into_request().into_future().and_then(|req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+        });
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<IndicesSegmentsResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index("testindex")
+            .segments()
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/testindex/_segments", req.url.as_ref());
+    }
+}
@@ -0,0 +1,209 @@
+/*!
+Builders for [index stats requests][docs-index-stats].
+
+[docs-index-stats]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-stats.html
+*/
+
+use futures::{
+    Future,
+    IntoFuture,
+};
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::IndicesStatsResponse,
+        IndexClient,
+    },
+    endpoints::IndicesStatsRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Index,
+};
+
+/**
+An [index stats request][docs-index-stats] builder that can be configured before sending.
+
+Call [`Client.index.stats`][Client.index.stats] to get an `IndexStatsRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-index-stats]: https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-stats.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.index.stats]: ../../struct.IndexClient.html#index-stats-request
+*/
+pub type IndexStatsRequestBuilder<TSender> = RequestBuilder<TSender, IndexStatsRequestInner>;
+
+#[doc(hidden)]
+pub struct IndexStatsRequestInner {
+    index: Index<'static>,
+}
+
+/**
+# Index stats request
+*/
+impl<TSender> IndexClient<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`IndexStatsRequestBuilder`][IndexStatsRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Get the number of documents and the size of an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").stats().send()?;
+
+    let primaries = response.all().primaries();
+
+    println!("docs: {:?}, bytes: {:?}", primaries.docs_count(), primaries.store_size_in_bytes());
+    # Ok(())
+    # }
+    ```
+
+    [IndexStatsRequestBuilder]: requests/index_stats/type.IndexStatsRequestBuilder.html
+    [send-sync]: requests/index_stats/type.IndexStatsRequestBuilder.html#send-synchronously
+    [send-async]: requests/index_stats/type.IndexStatsRequestBuilder.html#send-asynchronously
+    */
+    pub fn stats(self) -> IndexStatsRequestBuilder<TSender> {
+        RequestBuilder::initial(self.inner, IndexStatsRequestInner { index: self.index })
+    }
+}
+
+impl IndexStatsRequestInner {
+    fn into_request(self) -> Result<IndicesStatsRequest<'static>, Error> {
+        self.index.validate().map_err(error::request)?;
+
+        Ok(IndicesStatsRequest::for_index(self.index))
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl IndexStatsRequestBuilder<SyncSender> {
+    /**
+    Send an `IndexStatsRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Get the number of documents in an index called `myindex`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.index("myindex").stats().send()?;
+
+    println!("docs: {:?}", response.all().primaries().docs_count());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<IndicesStatsResponse, Error> {
+        let req = self.inner.into_request()?;
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl IndexStatsRequestBuilder<AsyncSender> {
+    /**
+    Send an `IndexStatsRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised index stats response.
+
+    # Examples
+
+    Get the number of documents in an index called `myindex`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.index("myindex").stats().send();
+
+    future.and_then(|response| {
+        println!("docs: {:?}", response.all().primaries().docs_count());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let (client, params_builder) = (self.client, self.params_builder);
+
+        let res_future = self.inner.into_request().into_future().and_then(|req| {
+            RequestBuilder::new(client, params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+        });
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<IndicesStatsResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .index("testindex")
+            .stats()
+            .inner
+            .into_request()
+            .unwrap();
+
+        assert_eq!("/testindex/_stats", req.url.as_ref());
+    }
+}
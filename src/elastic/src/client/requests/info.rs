@@ -0,0 +1,238 @@
+/*!
+Builders for cluster info requests.
+*/
+
+use fluent_builder::{
+    SharedFluentBuilder,
+    Stack,
+};
+use futures::Future;
+use std::sync::{
+    Arc,
+    Mutex,
+};
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::InfoResponse,
+        Client,
+    },
+    endpoints::PingRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        RequestParams,
+        Sender,
+        SyncSender,
+    },
+};
+
+/**
+A cluster info request builder that can be configured before sending.
+
+Call [`Client.info`][Client.info] to get an `InfoRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.info]: ../../struct.Client.html#info-request
+*/
+pub type InfoRequestBuilder<TSender> = RequestBuilder<TSender, InfoRequestInner>;
+
+#[doc(hidden)]
+pub struct InfoRequestInner;
+
+/**
+# Info request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an [`InfoRequestBuilder`][InfoRequestBuilder] with this `Client` that can be configured before sending.
+
+    Sending an info request captures the version of the cluster on the `Client`.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Get the version of the Elasticsearch cluster:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.info().send()?;
+
+    println!("version: {}", response.version().number());
+    # Ok(())
+    # }
+    ```
+
+    [InfoRequestBuilder]: requests/info/type.InfoRequestBuilder.html
+    [send-sync]: requests/info/type.InfoRequestBuilder.html#send-synchronously
+    [send-async]: requests/info/type.InfoRequestBuilder.html#send-asynchronously
+    */
+    pub fn info(&self) -> InfoRequestBuilder<TSender> {
+        RequestBuilder::initial(self.clone(), InfoRequestInner)
+    }
+}
+
+impl<TSender> InfoRequestBuilder<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create an info request that's sent with the parameters of another request.
+
+    The url params of the other request are dropped because they don't apply to the info endpoint,
+    but its headers, like authorization, are kept.
+    The parameters of the other request are put in `resolved` once they've been built.
+    */
+    pub(crate) fn probe(
+        client: Client<TSender>,
+        params_builder: SharedFluentBuilder<RequestParams, Stack>,
+        resolved: Arc<Mutex<Option<RequestParams>>>,
+    ) -> Self {
+        RequestBuilder::new(
+            client,
+            params_builder
+                .fluent(move |params: RequestParams| {
+                    *resolved.lock().expect("lock poisoned") = Some(params.clone());
+                    params.without_url_params()
+                })
+                .shared(),
+            InfoRequestInner,
+        )
+    }
+}
+
+impl InfoRequestInner {
+    fn into_request(self) -> PingRequest<'static> {
+        PingRequest::new()
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl InfoRequestBuilder<SyncSender> {
+    /**
+    Send an `InfoRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Get the version of the Elasticsearch cluster:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.info().send()?;
+
+    println!("version: {}", response.version().number());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<InfoResponse, Error> {
+        let req = self.inner.into_request();
+        let version = self.client.version.clone();
+
+        let res: InfoResponse =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()?
+                .into_response()?;
+
+        version.set(*res.version().number());
+
+        Ok(res)
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl InfoRequestBuilder<AsyncSender> {
+    /**
+    Send an `InfoRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised info response.
+
+    # Examples
+
+    Get the version of the Elasticsearch cluster:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.info().send();
+
+    future.and_then(|response| {
+        println!("version: {}", response.version().number());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+        let version = self.client.version.clone();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response())
+                .map(move |res: InfoResponse| {
+                    version.set(*res.version().number());
+                    res
+                });
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<InfoResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.info().inner.into_request();
+
+        assert_eq!("/", req.url.as_ref());
+    }
+}
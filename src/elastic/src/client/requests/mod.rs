@@ -7,9 +7,13 @@ This module contains implementation details that are useful if you want to custo
 use std::{
     marker::PhantomData,
     sync::Arc,
+    time::Duration,
 };
 
-use fluent_builder::SharedFluentBuilder;
+use fluent_builder::{
+    SharedFluentBuilder,
+    Stack,
+};
 use futures::{
     Future,
     Poll,
@@ -38,6 +42,31 @@ pub mod search;
 #[doc(inline)]
 pub use self::search::SearchRequestBuilder;
 
+// Search template requests
+pub mod search_template;
+pub mod search_template_delete;
+pub mod search_template_get;
+pub mod search_template_put;
+pub mod search_template_render;
+
+#[doc(inline)]
+pub use self::{
+    search_template::{
+        SearchTemplate,
+        SearchTemplateRequestBuilder,
+    },
+    search_template_delete::DeleteSearchTemplateRequestBuilder,
+    search_template_get::GetSearchTemplateRequestBuilder,
+    search_template_put::PutSearchTemplateRequestBuilder,
+    search_template_render::RenderSearchTemplateRequestBuilder,
+};
+
+// Export requests
+pub mod export;
+
+#[doc(inline)]
+pub use self::export::ExportRequestBuilder;
+
 // Sql requests
 pub mod sql;
 
@@ -47,6 +76,7 @@ pub use self::sql::SqlRequestBuilder;
 // Document requests
 pub mod document_delete;
 pub mod document_get;
+pub mod document_get_source;
 pub mod document_index;
 pub mod document_put_mapping;
 pub mod document_update;
@@ -55,6 +85,7 @@ pub mod document_update;
 pub use self::{
     document_delete::DeleteRequestBuilder,
     document_get::GetRequestBuilder,
+    document_get_source::GetSourceRequestBuilder,
     document_index::IndexRequestBuilder,
     document_put_mapping::PutMappingRequestBuilder,
     document_update::UpdateRequestBuilder,
@@ -66,6 +97,8 @@ pub mod index_create;
 pub mod index_delete;
 pub mod index_exists;
 pub mod index_open;
+pub mod index_segments;
+pub mod index_stats;
 
 #[doc(inline)]
 pub use self::{
@@ -74,15 +107,21 @@ pub use self::{
     index_delete::IndexDeleteRequestBuilder,
     index_exists::IndexExistsRequestBuilder,
     index_open::IndexOpenRequestBuilder,
+    index_segments::IndexSegmentsRequestBuilder,
+    index_stats::IndexStatsRequestBuilder,
 };
 
 // Misc requests
 pub mod bulk;
+pub mod cluster_health;
+pub mod info;
 pub mod ping;
 
 #[doc(inline)]
 pub use self::{
     bulk::BulkRequestBuilder,
+    cluster_health::ClusterHealthRequestBuilder,
+    info::InfoRequestBuilder,
     ping::PingRequestBuilder,
 };
 
@@ -104,10 +143,35 @@ where
     TSender: Sender,
 {
     client: Client<TSender>,
-    params_builder: SharedFluentBuilder<RequestParams>,
+    params_builder: SharedFluentBuilder<RequestParams, Stack>,
     inner: TRequest,
 }
 
+#[cfg(test)]
+impl<TSender, TRequest> RequestBuilder<TSender, TRequest>
+where
+    TSender: Sender,
+{
+    fn url_params(self) -> Vec<String> {
+        let (_, qry) = self
+            .params_builder
+            .into_value(RequestParams::default)
+            .get_url_qry();
+
+        let mut params: Vec<String> = qry
+            .map(|qry| {
+                qry.trim_start_matches('?')
+                    .split('&')
+                    .map(|param| param.to_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        params.sort();
+        params
+    }
+}
+
 /**
 # Methods for any request builder
 
@@ -127,7 +191,7 @@ where
 
     fn new(
         client: Client<TSender>,
-        builder: SharedFluentBuilder<RequestParams>,
+        builder: SharedFluentBuilder<RequestParams, Stack>,
         req: TRequest,
     ) -> Self {
         RequestBuilder {
@@ -207,6 +271,67 @@ where
 
         self
     }
+
+    /**
+    Set a deadline for this request.
+
+    The deadline bounds the total time spent on the request, starting when it's sent.
+    That includes choosing a node to send it to, which may involve sniffing the cluster,
+    sending it and receiving the response, and reading and parsing the response body.
+    If the request doesn't complete in time it fails with an [`Error::DeadlineExceeded`][Error.DeadlineExceeded].
+    That's different from a timeout on the underlying http client, which only applies to a single http request
+    and fails with an `Error::Client`.
+
+    The client doesn't retry requests or fail over to another node, so the deadline covers a single attempt at the request.
+
+    Asynchronous requests are cancelled as soon as the deadline passes.
+    Synchronous requests wait for the response and read its body on a bounded pool of worker threads,
+    so they also fail as soon as the deadline passes, even while a slow node is still responding.
+    A worker stays busy until the slow node responds or the http client times out,
+    and if every worker is busy then new requests with a deadline wait for one until their own deadline passes.
+    Use [`SyncClientBuilder::deadline_workers`][SyncClientBuilder.deadline_workers] to change the size of the pool.
+
+    # Examples
+
+    Fail a search that takes longer than 200ms:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use std::time::Duration;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # use elastic::Error;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client
+        .search::<Value>()
+        .index("myindex")
+        .deadline(Duration::from_millis(200))
+        .send();
+
+    match response {
+        Ok(response) => {
+            // do something with the response
+        }
+        Err(Error::DeadlineExceeded(deadline)) => {
+            // the search took longer than `deadline`
+        }
+        Err(e) => {
+            // handle any other error
+        }
+    }
+    # Ok(())
+    # }
+    ```
+
+    [Error.DeadlineExceeded]: ../../enum.Error.html#variant.DeadlineExceeded
+    [SyncClientBuilder.deadline_workers]: ../struct.SyncClientBuilder.html#method.deadline_workers
+    */
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.client.deadline = Some(deadline);
+
+        self
+    }
 }
 
 /**
@@ -295,19 +420,336 @@ pub mod prelude {
         BulkOperation,
     };
 
+    pub use super::common::{
+        ActiveShards,
+        IdStrategy,
+    };
+
     pub use super::{
+        ClusterHealthRequestBuilder,
         DeleteRequestBuilder,
+        DeleteSearchTemplateRequestBuilder,
+        ExportRequestBuilder,
         GetRequestBuilder,
+        GetSearchTemplateRequestBuilder,
+        GetSourceRequestBuilder,
         IndexCloseRequestBuilder,
         IndexCreateRequestBuilder,
         IndexDeleteRequestBuilder,
         IndexOpenRequestBuilder,
         IndexRequestBuilder,
+        InfoRequestBuilder,
         PingRequestBuilder,
         PutMappingRequestBuilder,
+        PutSearchTemplateRequestBuilder,
         RawRequestBuilder,
+        RenderSearchTemplateRequestBuilder,
         SearchRequestBuilder,
+        SearchTemplate,
+        SearchTemplateRequestBuilder,
         SqlRequestBuilder,
         UpdateRequestBuilder,
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::{
+            Read,
+            Write,
+        },
+        net::TcpListener,
+        path::PathBuf,
+        sync::{
+            Arc,
+            Mutex,
+        },
+        thread,
+        time::{
+            Duration,
+            Instant,
+        },
+    };
+
+    use futures::{
+        future,
+        Future,
+    };
+    use serde_json::Value;
+    use tokio::runtime::current_thread::block_on_all;
+
+    use crate::{
+        http::sender::recording::test_recording,
+        prelude::*,
+        Error,
+    };
+
+    /** A recording of a ping request and its response. */
+    fn recording() -> PathBuf {
+        test_recording(vec![json!({
+            "request": { "method": "GET", "path": "/", "body": null },
+            "response": { "status": 200, "body": r#"{"tagline":"You Know, for Search"}"# }
+        })])
+    }
+
+    #[test]
+    fn sync_deadline_not_exceeded() {
+        let path = recording();
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let res = client
+            .request(PingRequest::new())
+            .deadline(Duration::from_secs(60))
+            .send()
+            .and_then(|res| res.into_response::<Value>())
+            .unwrap();
+
+        assert_eq!("You Know, for Search", res["tagline"]);
+    }
+
+    #[test]
+    fn sync_deadline_exceeded() {
+        let path = recording();
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .pre_send_raw(|_| {
+                thread::sleep(Duration::from_millis(20));
+                Ok(())
+            })
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let res = client
+            .request(PingRequest::new())
+            .deadline(Duration::from_millis(10))
+            .send();
+
+        match res {
+            Err(Error::DeadlineExceeded(deadline)) => {
+                assert_eq!(Duration::from_millis(10), deadline)
+            }
+            Err(e) => panic!("unexpected error {:?}", e),
+            Ok(_) => panic!("expected the deadline to be exceeded"),
+        }
+    }
+
+    /**
+    Start a node that accepts connections and writes `response` to them, but never finishes responding.
+
+    Returns the address of the node.
+    */
+    fn stalled_node(response: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            let mut connections = Vec::new();
+
+            for mut stream in listener.incoming().filter_map(Result::ok) {
+                let _ = stream.read(&mut [0; 1024]);
+                let _ = stream.write_all(response);
+                connections.push(stream);
+            }
+        });
+
+        address
+    }
+
+    fn assert_deadline_exceeded<T>(res: Result<T, Error>, deadline: Duration) {
+        match res {
+            Err(Error::DeadlineExceeded(exceeded)) => assert_eq!(deadline, exceeded),
+            Err(e) => panic!("unexpected error {:?}", e),
+            Ok(_) => panic!("expected the deadline to be exceeded"),
+        }
+    }
+
+    #[test]
+    fn sync_deadline_exceeded_waiting_for_response() {
+        let client = SyncClientBuilder::new()
+            .static_node(stalled_node(b""))
+            .build()
+            .unwrap();
+
+        let deadline = Duration::from_millis(50);
+        let start = Instant::now();
+
+        let res = client.request(PingRequest::new()).deadline(deadline).send();
+
+        assert_deadline_exceeded(res, deadline);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn sync_deadline_exceeded_reading_body() {
+        let client = SyncClientBuilder::new()
+            .static_node(stalled_node(
+                b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 100\r\n\r\n{",
+            ))
+            .build()
+            .unwrap();
+
+        let deadline = Duration::from_millis(200);
+        let start = Instant::now();
+
+        let res = client
+            .request(PingRequest::new())
+            .deadline(deadline)
+            .send()
+            .and_then(|res| res.into_response::<Value>());
+
+        assert_deadline_exceeded(res, deadline);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn sync_deadline_workers_are_bounded() {
+        let client = SyncClientBuilder::new()
+            .static_node(stalled_node(b""))
+            .deadline_workers(1)
+            .build()
+            .unwrap();
+
+        let deadline = Duration::from_millis(50);
+
+        for _ in 0..3 {
+            let res = client.request(PingRequest::new()).deadline(deadline).send();
+
+            assert_deadline_exceeded(res, deadline);
+        }
+
+        assert_eq!(1, client.sender.blocking_workers.threads());
+    }
+
+    #[test]
+    fn sync_deadline_exceeded_recording_body() {
+        let path = std::env::temp_dir().join(format!(
+            "elastic_deadline_recording_{}.jsonl",
+            std::process::id()
+        ));
+
+        let client = SyncClientBuilder::new()
+            .static_node(stalled_node(
+                b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 100\r\n\r\n{",
+            ))
+            .record_to(&path)
+            .build()
+            .unwrap();
+
+        let deadline = Duration::from_millis(200);
+        let start = Instant::now();
+
+        let res = client.request(PingRequest::new()).deadline(deadline).send();
+
+        let _ = fs::remove_file(&path);
+
+        assert_deadline_exceeded(res, deadline);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn sync_slow_request_shares_body() {
+        static BODY: &str = r#"{"query":{"match_all":{}}}"#;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+
+        let node = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut req = Vec::new();
+            while !String::from_utf8_lossy(&req).ends_with(BODY) {
+                let mut buf = [0; 1024];
+                let read = stream.read(&mut buf).unwrap();
+                req.extend_from_slice(&buf[..read]);
+            }
+
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 2\r\n\r\n{}")
+                .unwrap();
+
+            String::from_utf8(req).unwrap()
+        });
+
+        let slow = Arc::new(Mutex::new(Vec::new()));
+        let on_slow = slow.clone();
+        let client = SyncClientBuilder::new()
+            .static_node(address)
+            .slow_request_threshold(Duration::from_millis(0))
+            .on_slow_request(move |req| {
+                on_slow
+                    .lock()
+                    .unwrap()
+                    .push(req.body().map(|body| body.to_vec()));
+            })
+            .build()
+            .unwrap();
+
+        let res = client
+            .request(SearchRequest::for_index("myindex", BODY))
+            .send()
+            .and_then(|res| res.into_response::<Value>())
+            .unwrap();
+
+        assert_eq!(json!({}), res);
+        assert!(node.join().unwrap().ends_with(BODY));
+        assert_eq!(vec![Some(BODY.as_bytes().to_vec())], *slow.lock().unwrap());
+    }
+
+    #[test]
+    fn async_deadline_not_exceeded() {
+        let path = recording();
+        let client = AsyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let res = block_on_all(
+            client
+                .request(PingRequest::new())
+                .deadline(Duration::from_secs(60))
+                .send()
+                .and_then(|res| res.into_response::<Value>()),
+        )
+        .unwrap();
+
+        assert_eq!("You Know, for Search", res["tagline"]);
+    }
+
+    #[test]
+    fn async_deadline_exceeded() {
+        let path = recording();
+        let client = AsyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .pre_send_raw(|_| Box::new(future::empty()))
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let res = block_on_all(
+            client
+                .request(PingRequest::new())
+                .deadline(Duration::from_millis(10))
+                .send(),
+        );
+
+        match res {
+            Err(Error::DeadlineExceeded(deadline)) => {
+                assert_eq!(Duration::from_millis(10), deadline)
+            }
+            Err(e) => panic!("unexpected error {:?}", e),
+            Ok(_) => panic!("expected the deadline to be exceeded"),
+        }
+    }
+}
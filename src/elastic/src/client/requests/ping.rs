@@ -12,6 +12,7 @@ use crate::{
             RequestBuilder,
         },
         responses::PingResponse,
+        version::VersionCell,
         Client,
     },
     endpoints::PingRequest,
@@ -121,10 +122,16 @@ impl PingRequestBuilder<SyncSender> {
     */
     pub fn send(self) -> Result<PingResponse, Error> {
         let req = self.inner.into_request();
+        let version = self.client.version.clone();
 
-        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
-            .send()?
-            .into_response()
+        let res: PingResponse =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()?
+                .into_response()?;
+
+        capture_version(&version, &res);
+
+        Ok(res)
     }
 }
 
@@ -165,16 +172,27 @@ impl PingRequestBuilder<AsyncSender> {
     */
     pub fn send(self) -> Pending {
         let req = self.inner.into_request();
+        let version = self.client.version.clone();
 
         let res_future =
             RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
                 .send()
-                .and_then(|res| res.into_response());
+                .and_then(|res| res.into_response())
+                .map(move |res: PingResponse| {
+                    capture_version(&version, &res);
+                    res
+                });
 
         Pending::new(res_future)
     }
 }
 
+fn capture_version(version: &VersionCell, res: &PingResponse) {
+    if let Ok(number) = res.version().number().parse() {
+        version.set(number);
+    }
+}
+
 /** A future returned by calling `send`. */
 pub type Pending = BasePending<PingResponse>;
 
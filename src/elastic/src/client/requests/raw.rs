@@ -34,6 +34,7 @@ pub type RawRequestBuilder<TSender, TEndpoint, TBody> =
 #[doc(hidden)]
 pub struct RawRequestInner<TEndpoint, TBody> {
     endpoint: TEndpoint,
+    scoped_body: bool,
     _marker: PhantomData<TBody>,
 }
 
@@ -41,9 +42,16 @@ impl<TEndpoint, TBody> RawRequestInner<TEndpoint, TBody> {
     pub(crate) fn new(endpoint: TEndpoint) -> Self {
         RawRequestInner {
             endpoint,
+            scoped_body: false,
             _marker: PhantomData,
         }
     }
+
+    /** Mark the indices in the body of the request as already within the client's index scope. */
+    pub(crate) fn scoped_body(mut self) -> Self {
+        self.scoped_body = true;
+        self
+    }
 }
 
 /**
@@ -179,7 +187,9 @@ where
             },
         };
 
-        let req = SendableRequest::new(endpoint, params);
+        let req = SendableRequest::new(endpoint, params)
+            .index_scope(client.index_scope.clone(), self.inner.scoped_body)
+            .deadline(client.deadline);
 
         client.sender.send(req)
     }
@@ -0,0 +1,879 @@
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    time::Duration,
+};
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::ser::{
+    Serialize,
+    Serializer,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+
+use crate::client::{
+    requests::common::time_units,
+    version::{
+        ApiVersions,
+        Version,
+    },
+};
+
+/** The `calendar_interval` and `fixed_interval` of a date histogram are only available in these versions. */
+const DATE_HISTOGRAM_INTERVAL_VERSIONS: ApiVersions = ApiVersions {
+    feature: "the calendar_interval and fixed_interval of a date_histogram aggregation",
+    since: Version::new(7, 2, 0),
+    removed: None,
+};
+
+/**
+A factory for aggregation builders.
+
+An `AggsBuilder` is passed to the closure given to [`SearchRequestBuilder.aggs`][SearchRequestBuilder.aggs].
+It's `Copy`, so it can be used to build sub-aggregations inside the same closure.
+
+[SearchRequestBuilder.aggs]: type.SearchRequestBuilder.html#method.aggs
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AggsBuilder;
+
+impl AggsBuilder {
+    /**
+    A [`terms`][docs-terms] bucket aggregation.
+
+    [docs-terms]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-terms-aggregation.html
+    */
+    pub fn terms(self, name: impl Into<String>, field: impl Into<String>) -> TermsAggregation {
+        TermsAggregation {
+            name: name.into(),
+            options: TermsOptions::new(field.into()),
+            aggs: Map::new(),
+        }
+    }
+
+    /**
+    A [`significant_terms`][docs-significant-terms] bucket aggregation.
+
+    [docs-significant-terms]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-significantterms-aggregation.html
+    */
+    pub fn significant_terms(
+        self,
+        name: impl Into<String>,
+        field: impl Into<String>,
+    ) -> SignificantTermsAggregation {
+        SignificantTermsAggregation {
+            name: name.into(),
+            options: TermsOptions::new(field.into()),
+            aggs: Map::new(),
+        }
+    }
+
+    /**
+    A [`date_histogram`][docs-date-histogram] bucket aggregation.
+
+    The `interval` can be a [`CalendarInterval`][CalendarInterval] or a [`FixedInterval`][FixedInterval].
+    Both kinds of interval require Elasticsearch `7.2` or newer.
+    Sending a search with a date histogram will fail without reaching the cluster if its version doesn't support them.
+
+    [docs-date-histogram]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-datehistogram-aggregation.html
+    [CalendarInterval]: enum.CalendarInterval.html
+    [FixedInterval]: struct.FixedInterval.html
+    */
+    pub fn date_histogram(
+        self,
+        name: impl Into<String>,
+        field: impl Into<String>,
+        interval: impl Into<DateHistogramInterval>,
+    ) -> DateHistogramAggregation {
+        let (calendar_interval, fixed_interval) = match interval.into() {
+            DateHistogramInterval::Calendar(interval) => (Some(interval), None),
+            DateHistogramInterval::Fixed(interval) => (None, Some(interval)),
+        };
+
+        DateHistogramAggregation {
+            name: name.into(),
+            options: DateHistogramOptions {
+                field: field.into(),
+                calendar_interval,
+                fixed_interval,
+                time_zone: None,
+                offset: None,
+                format: None,
+                min_doc_count: None,
+                extended_bounds: None,
+            },
+            aggs: Map::new(),
+        }
+    }
+
+    /**
+    An [`avg`][docs-avg] metric aggregation.
+
+    [docs-avg]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-avg-aggregation.html
+    */
+    pub fn avg(self, name: impl Into<String>, field: impl Into<String>) -> MetricAggregation {
+        MetricAggregation::new("avg", name.into(), field.into())
+    }
+
+    /**
+    A [`min`][docs-min] metric aggregation.
+
+    [docs-min]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-min-aggregation.html
+    */
+    pub fn min(self, name: impl Into<String>, field: impl Into<String>) -> MetricAggregation {
+        MetricAggregation::new("min", name.into(), field.into())
+    }
+
+    /**
+    A [`max`][docs-max] metric aggregation.
+
+    [docs-max]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-max-aggregation.html
+    */
+    pub fn max(self, name: impl Into<String>, field: impl Into<String>) -> MetricAggregation {
+        MetricAggregation::new("max", name.into(), field.into())
+    }
+
+    /**
+    A [`sum`][docs-sum] metric aggregation.
+
+    [docs-sum]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-sum-aggregation.html
+    */
+    pub fn sum(self, name: impl Into<String>, field: impl Into<String>) -> MetricAggregation {
+        MetricAggregation::new("sum", name.into(), field.into())
+    }
+
+    /**
+    A [`stats`][docs-stats] metric aggregation.
+
+    [docs-stats]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-stats-aggregation.html
+    */
+    pub fn stats(self, name: impl Into<String>, field: impl Into<String>) -> MetricAggregation {
+        MetricAggregation::new("stats", name.into(), field.into())
+    }
+
+    /**
+    A [`top_hits`][docs-top-hits] metric aggregation.
+
+    The hits can be parsed from the response using [`TopHits`][TopHits].
+
+    [docs-top-hits]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-top-hits-aggregation.html
+    [TopHits]: ../../responses/search/struct.TopHits.html
+    */
+    pub fn top_hits(self, name: impl Into<String>) -> TopHitsAggregation {
+        TopHitsAggregation {
+            name: name.into(),
+            options: TopHitsOptions {
+                size: None,
+                from: None,
+                sort: Vec::new(),
+                source: None,
+            },
+        }
+    }
+
+    /**
+    An [`extended_stats`][docs-extended-stats] metric aggregation.
+
+    [docs-extended-stats]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-extendedstats-aggregation.html
+    */
+    pub fn extended_stats(
+        self,
+        name: impl Into<String>,
+        field: impl Into<String>,
+    ) -> MetricAggregation {
+        MetricAggregation::new("extended_stats", name.into(), field.into())
+    }
+
+    /**
+    A [`cardinality`][docs-cardinality] metric aggregation.
+
+    [docs-cardinality]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-cardinality-aggregation.html
+    */
+    pub fn cardinality(
+        self,
+        name: impl Into<String>,
+        field: impl Into<String>,
+    ) -> MetricAggregation {
+        MetricAggregation::new("cardinality", name.into(), field.into())
+    }
+
+    /**
+    A [`value_count`][docs-value-count] metric aggregation.
+
+    [docs-value-count]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-valuecount-aggregation.html
+    */
+    pub fn value_count(
+        self,
+        name: impl Into<String>,
+        field: impl Into<String>,
+    ) -> MetricAggregation {
+        MetricAggregation::new("value_count", name.into(), field.into())
+    }
+}
+
+/**
+A named aggregation that can be added to a search request.
+
+Any of the aggregation builders can be converted into an `Aggregation`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregation {
+    name: String,
+    body: Value,
+}
+
+impl Aggregation {
+    /**
+    Create an aggregation from a raw JSON body.
+
+    This can be used for aggregations that don't have a builder yet:
+
+    ```
+    # #[macro_use] extern crate serde_json;
+    # use elastic::client::requests::search::Aggregation;
+    # fn main() {
+    let agg = Aggregation::raw("by_country", json!({
+        "geotile_grid": { "field": "location" }
+    }));
+    # }
+    ```
+    */
+    pub fn raw(name: impl Into<String>, body: Value) -> Self {
+        Aggregation {
+            name: name.into(),
+            body,
+        }
+    }
+
+    /** The name of the aggregation. */
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /** The JSON body of the aggregation. */
+    pub fn body(&self) -> &Value {
+        &self.body
+    }
+
+    pub(crate) fn into_parts(self) -> (String, Value) {
+        (self.name, self.body)
+    }
+
+    /** The versions the aggregation and its sub-aggregations are available in, if they're only available in some. */
+    pub(crate) fn versions(&self) -> Option<&'static ApiVersions> {
+        fn uses_interval(body: &Value) -> bool {
+            let histogram_interval = match body.get("date_histogram") {
+                Some(histogram) => {
+                    histogram.get("calendar_interval").is_some()
+                        || histogram.get("fixed_interval").is_some()
+                }
+                None => false,
+            };
+
+            let sub_interval = match body.get("aggs") {
+                Some(Value::Object(aggs)) => aggs.values().any(uses_interval),
+                _ => false,
+            };
+
+            histogram_interval || sub_interval
+        }
+
+        if uses_interval(&self.body) {
+            Some(&DATE_HISTOGRAM_INTERVAL_VERSIONS)
+        } else {
+            None
+        }
+    }
+
+    fn new<T>(name: String, kind: &str, options: &T, aggs: Map<String, Value>) -> Self
+    where
+        T: Serialize,
+    {
+        let mut body = Map::new();
+
+        body.insert(
+            kind.to_owned(),
+            serde_json::to_value(options).expect("aggregation options are always valid json"),
+        );
+
+        if !aggs.is_empty() {
+            body.insert("aggs".to_owned(), Value::Object(aggs));
+        }
+
+        Aggregation {
+            name,
+            body: Value::Object(body),
+        }
+    }
+}
+
+/** The direction to sort aggregation buckets by. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /** Sort in ascending order. */
+    Asc,
+    /** Sort in descending order. */
+    Desc,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct TermsOptions {
+    field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shard_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_doc_count: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    order: Vec<Value>,
+}
+
+impl TermsOptions {
+    fn new(field: String) -> Self {
+        TermsOptions {
+            field,
+            size: None,
+            shard_size: None,
+            min_doc_count: None,
+            order: Vec::new(),
+        }
+    }
+}
+
+fn push_agg(aggs: &mut Map<String, Value>, agg: impl Into<Aggregation>) {
+    let (name, body) = agg.into().into_parts();
+    aggs.insert(name, body);
+}
+
+/**
+A [`terms`][docs-terms] bucket aggregation.
+
+Call [`AggsBuilder.terms`][AggsBuilder.terms] to get a `TermsAggregation`.
+
+[docs-terms]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-terms-aggregation.html
+[AggsBuilder.terms]: struct.AggsBuilder.html#method.terms
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermsAggregation {
+    name: String,
+    options: TermsOptions,
+    aggs: Map<String, Value>,
+}
+
+impl TermsAggregation {
+    /** Set the number of term buckets to return. */
+    pub fn size(mut self, size: u32) -> Self {
+        self.options.size = Some(size);
+        self
+    }
+
+    /** Set the number of terms each shard returns to the coordinating node. */
+    pub fn shard_size(mut self, shard_size: u32) -> Self {
+        self.options.shard_size = Some(shard_size);
+        self
+    }
+
+    /** Only return terms that match at least `min_doc_count` documents. */
+    pub fn min_doc_count(mut self, min_doc_count: u64) -> Self {
+        self.options.min_doc_count = Some(min_doc_count);
+        self
+    }
+
+    /**
+    Sort the buckets by a key, like `_count`, `_key` or the name of a metric sub-aggregation.
+
+    Calling `order` multiple times will sort by each key in turn.
+    */
+    pub fn order(mut self, key: impl Into<String>, order: SortOrder) -> Self {
+        let mut sort = Map::new();
+        sort.insert(key.into(), json!(order));
+
+        self.options.order.push(Value::Object(sort));
+        self
+    }
+
+    /** Add a sub-aggregation that's calculated for each bucket. */
+    #[allow(clippy::should_implement_trait)]
+    pub fn sub(mut self, agg: impl Into<Aggregation>) -> Self {
+        push_agg(&mut self.aggs, agg);
+        self
+    }
+}
+
+impl From<TermsAggregation> for Aggregation {
+    fn from(agg: TermsAggregation) -> Self {
+        Aggregation::new(agg.name, "terms", &agg.options, agg.aggs)
+    }
+}
+
+/**
+A [`significant_terms`][docs-significant-terms] bucket aggregation.
+
+Call [`AggsBuilder.significant_terms`][AggsBuilder.significant_terms] to get a `SignificantTermsAggregation`.
+
+[docs-significant-terms]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-significantterms-aggregation.html
+[AggsBuilder.significant_terms]: struct.AggsBuilder.html#method.significant_terms
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignificantTermsAggregation {
+    name: String,
+    options: TermsOptions,
+    aggs: Map<String, Value>,
+}
+
+impl SignificantTermsAggregation {
+    /** Set the number of term buckets to return. */
+    pub fn size(mut self, size: u32) -> Self {
+        self.options.size = Some(size);
+        self
+    }
+
+    /** Set the number of terms each shard returns to the coordinating node. */
+    pub fn shard_size(mut self, shard_size: u32) -> Self {
+        self.options.shard_size = Some(shard_size);
+        self
+    }
+
+    /** Only return terms that match at least `min_doc_count` documents. */
+    pub fn min_doc_count(mut self, min_doc_count: u64) -> Self {
+        self.options.min_doc_count = Some(min_doc_count);
+        self
+    }
+
+    /** Add a sub-aggregation that's calculated for each bucket. */
+    #[allow(clippy::should_implement_trait)]
+    pub fn sub(mut self, agg: impl Into<Aggregation>) -> Self {
+        push_agg(&mut self.aggs, agg);
+        self
+    }
+}
+
+impl From<SignificantTermsAggregation> for Aggregation {
+    fn from(agg: SignificantTermsAggregation) -> Self {
+        Aggregation::new(agg.name, "significant_terms", &agg.options, agg.aggs)
+    }
+}
+
+/**
+A [calendar-aware interval][docs-calendar-intervals] for a `date_histogram` aggregation.
+
+Calendar intervals account for things like daylight savings and months with different numbers of days.
+
+[docs-calendar-intervals]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-datehistogram-aggregation.html#calendar_intervals
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarInterval {
+    /** A minute, starting at `00` seconds. */
+    Minute,
+    /** An hour, starting at `00` minutes and `00` seconds. */
+    Hour,
+    /** A day, starting at midnight. */
+    Day,
+    /** A week, starting at midnight on Monday. */
+    Week,
+    /** A month, starting at midnight on the first day of the month. */
+    Month,
+    /** A quarter, starting at midnight on the first day of the first month of the quarter. */
+    Quarter,
+    /** A year, starting at midnight on the first day of January. */
+    Year,
+}
+
+impl CalendarInterval {
+    fn as_str(self) -> &'static str {
+        match self {
+            CalendarInterval::Minute => "1m",
+            CalendarInterval::Hour => "1h",
+            CalendarInterval::Day => "1d",
+            CalendarInterval::Week => "1w",
+            CalendarInterval::Month => "1M",
+            CalendarInterval::Quarter => "1q",
+            CalendarInterval::Year => "1y",
+        }
+    }
+}
+
+impl fmt::Display for CalendarInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for CalendarInterval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/**
+A [fixed interval][docs-fixed-intervals] for a `date_histogram` aggregation.
+
+Fixed intervals are always a multiple of SI units and don't change with the calendar.
+A `FixedInterval` can be converted from a `std::time::Duration`, which uses the largest unit that represents the duration exactly.
+The duration must be a whole number of milliseconds, and at least one millisecond:
+
+```
+# use std::{convert::TryFrom, time::Duration};
+# use elastic::client::requests::search::FixedInterval;
+assert_eq!("90m", FixedInterval::try_from(Duration::from_secs(90 * 60)).unwrap().to_string());
+assert_eq!("1500ms", FixedInterval::try_from(Duration::from_millis(1500)).unwrap().to_string());
+
+assert!(FixedInterval::try_from(Duration::from_micros(500)).is_err());
+```
+
+[docs-fixed-intervals]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-datehistogram-aggregation.html#fixed_intervals
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedInterval {
+    value: u64,
+    unit: &'static str,
+}
+
+impl FixedInterval {
+    /** An interval of `value` milliseconds. */
+    pub fn milliseconds(value: u64) -> Self {
+        FixedInterval { value, unit: "ms" }
+    }
+
+    /** An interval of `value` seconds. */
+    pub fn seconds(value: u64) -> Self {
+        FixedInterval { value, unit: "s" }
+    }
+
+    /** An interval of `value` minutes. */
+    pub fn minutes(value: u64) -> Self {
+        FixedInterval { value, unit: "m" }
+    }
+
+    /** An interval of `value` hours. */
+    pub fn hours(value: u64) -> Self {
+        FixedInterval { value, unit: "h" }
+    }
+
+    /** An interval of `value` days, where each day is exactly 24 hours. */
+    pub fn days(value: u64) -> Self {
+        FixedInterval { value, unit: "d" }
+    }
+}
+
+impl TryFrom<Duration> for FixedInterval {
+    type Error = InvalidFixedIntervalError;
+
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        let nanos = duration.as_nanos();
+
+        if nanos == 0 || nanos % 1_000_000 != 0 {
+            return Err(InvalidFixedIntervalError(duration));
+        }
+
+        let (value, unit) = time_units(duration);
+
+        u64::try_from(value)
+            .map(|value| FixedInterval { value, unit })
+            .map_err(|_| InvalidFixedIntervalError(duration))
+    }
+}
+
+/**
+An error converting a `std::time::Duration` into a [`FixedInterval`][FixedInterval].
+
+Fixed intervals are a whole number of milliseconds, so durations shorter than a millisecond, or with a fraction of one, can't be used.
+
+[FixedInterval]: struct.FixedInterval.html
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidFixedIntervalError(Duration);
+
+impl fmt::Display for InvalidFixedIntervalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} can't be used as a fixed interval because it isn't a whole number of milliseconds",
+            self.0
+        )
+    }
+}
+
+impl Error for InvalidFixedIntervalError {}
+
+impl fmt::Display for FixedInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+impl Serialize for FixedInterval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/** The interval of a `date_histogram` aggregation, which is either a calendar or a fixed interval. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateHistogramInterval {
+    /** A calendar-aware interval. */
+    Calendar(CalendarInterval),
+    /** A fixed interval. */
+    Fixed(FixedInterval),
+}
+
+impl From<CalendarInterval> for DateHistogramInterval {
+    fn from(interval: CalendarInterval) -> Self {
+        DateHistogramInterval::Calendar(interval)
+    }
+}
+
+impl From<FixedInterval> for DateHistogramInterval {
+    fn from(interval: FixedInterval) -> Self {
+        DateHistogramInterval::Fixed(interval)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct DateHistogramOptions {
+    field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    calendar_interval: Option<CalendarInterval>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fixed_interval: Option<FixedInterval>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_zone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_doc_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extended_bounds: Option<ExtendedBounds>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ExtendedBounds {
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    min: DateTime<Utc>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    max: DateTime<Utc>,
+}
+
+/**
+A [`date_histogram`][docs-date-histogram] bucket aggregation.
+
+Call [`AggsBuilder.date_histogram`][AggsBuilder.date_histogram] to get a `DateHistogramAggregation`.
+The buckets in the response can be parsed into a [`DateHistogram`][DateHistogram].
+
+[docs-date-histogram]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-datehistogram-aggregation.html
+[AggsBuilder.date_histogram]: struct.AggsBuilder.html#method.date_histogram
+[DateHistogram]: ../../responses/search/struct.DateHistogram.html
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateHistogramAggregation {
+    name: String,
+    options: DateHistogramOptions,
+    aggs: Map<String, Value>,
+}
+
+impl DateHistogramAggregation {
+    /**
+    Use a calendar-aware interval for buckets.
+
+    This replaces any previously set fixed interval.
+    */
+    pub fn calendar_interval(mut self, interval: CalendarInterval) -> Self {
+        self.options.calendar_interval = Some(interval);
+        self.options.fixed_interval = None;
+        self
+    }
+
+    /**
+    Use a fixed interval for buckets.
+
+    This replaces any previously set calendar interval.
+    */
+    pub fn fixed_interval(mut self, interval: FixedInterval) -> Self {
+        self.options.fixed_interval = Some(interval);
+        self.options.calendar_interval = None;
+        self
+    }
+
+    /**
+    Set the time zone used for bucketing, like `Europe/Amsterdam` or `-01:00`.
+
+    Bucket keys are still returned as UTC timestamps.
+    */
+    pub fn time_zone(mut self, time_zone: impl Into<String>) -> Self {
+        self.options.time_zone = Some(time_zone.into());
+        self
+    }
+
+    /** Shift the start of each bucket by an offset, like `+6h` or `-1d`. */
+    pub fn offset(mut self, offset: impl Into<String>) -> Self {
+        self.options.offset = Some(offset.into());
+        self
+    }
+
+    /** Set the date format used for the `key_as_string` of each bucket. */
+    pub fn format(mut self, format: impl Into<String>) -> Self {
+        self.options.format = Some(format.into());
+        self
+    }
+
+    /** Only return buckets that match at least `min_doc_count` documents. */
+    pub fn min_doc_count(mut self, min_doc_count: u64) -> Self {
+        self.options.min_doc_count = Some(min_doc_count);
+        self
+    }
+
+    /**
+    Force buckets to be returned between `min` and `max`, even if they're empty.
+
+    This is usually combined with a `min_doc_count` of `0`.
+    */
+    pub fn extended_bounds(mut self, min: DateTime<Utc>, max: DateTime<Utc>) -> Self {
+        self.options.extended_bounds = Some(ExtendedBounds { min, max });
+        self
+    }
+
+    /** Add a sub-aggregation that's calculated for each bucket. */
+    #[allow(clippy::should_implement_trait)]
+    pub fn sub(mut self, agg: impl Into<Aggregation>) -> Self {
+        push_agg(&mut self.aggs, agg);
+        self
+    }
+}
+
+impl From<DateHistogramAggregation> for Aggregation {
+    fn from(agg: DateHistogramAggregation) -> Self {
+        Aggregation::new(agg.name, "date_histogram", &agg.options, agg.aggs)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct MetricOptions {
+    field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    missing: Option<Value>,
+}
+
+/**
+A single-field metric aggregation, like `avg` or `stats`.
+
+Call a method like [`AggsBuilder.avg`][AggsBuilder.avg] to get a `MetricAggregation`.
+
+[AggsBuilder.avg]: struct.AggsBuilder.html#method.avg
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricAggregation {
+    kind: &'static str,
+    name: String,
+    options: MetricOptions,
+}
+
+impl MetricAggregation {
+    fn new(kind: &'static str, name: String, field: String) -> Self {
+        MetricAggregation {
+            kind,
+            name,
+            options: MetricOptions {
+                field,
+                missing: None,
+            },
+        }
+    }
+
+    /** Use the given value for documents that don't have the field. */
+    pub fn missing(mut self, missing: impl Into<Value>) -> Self {
+        self.options.missing = Some(missing.into());
+        self
+    }
+}
+
+impl From<MetricAggregation> for Aggregation {
+    fn from(agg: MetricAggregation) -> Self {
+        Aggregation::new(agg.name, agg.kind, &agg.options, Map::new())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct TopHitsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sort: Vec<Value>,
+    #[serde(rename = "_source", skip_serializing_if = "Option::is_none")]
+    source: Option<Vec<String>>,
+}
+
+/**
+A [`top_hits`][docs-top-hits] metric aggregation.
+
+Call [`AggsBuilder.top_hits`][AggsBuilder.top_hits] to get a `TopHitsAggregation`.
+
+[docs-top-hits]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-top-hits-aggregation.html
+[AggsBuilder.top_hits]: struct.AggsBuilder.html#method.top_hits
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopHitsAggregation {
+    name: String,
+    options: TopHitsOptions,
+}
+
+impl TopHitsAggregation {
+    /** The maximum number of hits to return for each bucket. */
+    pub fn size(mut self, size: u32) -> Self {
+        self.options.size = Some(size);
+        self
+    }
+
+    /** The offset of the first hit to return. */
+    pub fn from(mut self, from: u32) -> Self {
+        self.options.from = Some(from);
+        self
+    }
+
+    /**
+    Sort hits by the value of `field`.
+
+    Calling `sort` multiple times will sort by each field in order.
+    */
+    pub fn sort(mut self, field: impl Into<String>, order: SortOrder) -> Self {
+        let mut sort = Map::new();
+        sort.insert(field.into(), json!({ "order": order }));
+
+        self.options.sort.push(Value::Object(sort));
+        self
+    }
+
+    /** Only include the given fields in the source of each hit. */
+    pub fn source<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.source = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl From<TopHitsAggregation> for Aggregation {
+    fn from(agg: TopHitsAggregation) -> Self {
+        Aggregation::new(agg.name, "top_hits", &agg.options, Map::new())
+    }
+}
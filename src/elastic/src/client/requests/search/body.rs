@@ -0,0 +1,372 @@
+use bytes::Bytes;
+use serde_json::{
+    Map,
+    Value,
+};
+use std::borrow::Cow;
+
+use super::{
+    validate,
+    Query,
+    QueryValidationError,
+};
+use crate::http::{
+    empty_body,
+    AsyncBody,
+    SyncBody,
+};
+
+/**
+A search request body that typed options can be added to.
+
+`SearchBody` wraps a JSON object.
+Options like [aggregations][aggs] are merged into that object instead of replacing it, so they can be combined with a raw `serde_json::Value` body.
+An empty `SearchBody` is sent as an empty request body.
+
+A `SearchBody` can be created from a `serde_json::Value`, a typed [`Query`][Query], or a raw JSON string or byte buffer.
+Raw bodies are sent as they are, and are only parsed when typed options are merged into them or they're validated.
+Use [`combine`](#method.combine) to merge extra raw JSON into a body that was built with typed options.
+
+If the wrapped value isn't a JSON object then typed options can't be merged into it.
+The same goes for a raw body that isn't valid JSON.
+Adding a typed option to a search request with a body like that makes `send` return an error without sending the request.
+
+[aggs]: type.SearchRequestBuilder.html#method.aggs
+[Query]: struct.Query.html
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchBody(SearchBodyInner);
+
+#[derive(Debug, Clone, PartialEq)]
+enum SearchBodyInner {
+    Value(Value),
+    Raw(Bytes),
+}
+
+impl SearchBody {
+    /** Create an empty search body. */
+    pub fn new() -> Self {
+        SearchBody(SearchBodyInner::Value(Value::Null))
+    }
+
+    /** Whether or not the body is empty. */
+    pub fn is_empty(&self) -> bool {
+        match self.0 {
+            SearchBodyInner::Value(Value::Null) => true,
+            SearchBodyInner::Value(Value::Object(ref body)) => body.is_empty(),
+            SearchBodyInner::Raw(ref body) => body.is_empty(),
+            _ => false,
+        }
+    }
+
+    /**
+    Get the JSON value of the body.
+
+    A raw body is parsed each time this is called.
+    Returns `Value::Null` if the body was created from a raw body that isn't valid JSON.
+    */
+    pub fn as_value(&self) -> Cow<'_, Value> {
+        self.parsed().unwrap_or(Cow::Owned(Value::Null))
+    }
+
+    /**
+    Convert the body into its JSON value.
+
+    Returns `Value::Null` if the body was created from a raw body that isn't valid JSON.
+    */
+    pub fn into_value(self) -> Value {
+        self.into_parsed().unwrap_or(Value::Null)
+    }
+
+    /**
+    Check the body for obviously invalid options.
+
+    This catches common mistakes that Elasticsearch would reject with an opaque `400` response, like:
+
+    - a negative `size` or `from`
+    - a top-level `filter` instead of `post_filter`
+    - a query object that contains more than one query, or none
+    - `bool` query clauses like `must` or `filter` that aren't inside a `bool` query
+    - a `bool` query without any clauses.
+
+    An empty body is valid.
+    A body that isn't a JSON object is invalid.
+
+    # Examples
+
+    ```
+    # #[macro_use] extern crate serde_json;
+    # use elastic::client::requests::search::SearchBody;
+    # fn main() {
+    let body = SearchBody::from(json!({
+        "size": -1,
+        "query": {
+            "filter": { "term": { "user": "kimchy" } }
+        }
+    }));
+
+    let err = body.validate().unwrap_err();
+
+    let paths: Vec<_> = err.problems().iter().map(|problem| problem.path()).collect();
+    assert_eq!(vec!["size", "query.filter"], paths);
+    # }
+    ```
+    */
+    pub fn validate(&self) -> Result<(), QueryValidationError> {
+        match self.parsed().as_deref() {
+            Some(Value::Null) => Ok(()),
+            Some(Value::Object(body)) => validate::validate_body(body),
+            _ => Err(validate::invalid_body()),
+        }
+    }
+
+    /**
+    Merge another body into this one.
+
+    Objects in both bodies are merged recursively.
+    Any other values in `other` replace the values in this body.
+    Returns a [`QueryValidationError`][QueryValidationError] if either body isn't a JSON object, including a raw body that isn't valid JSON.
+
+    # Examples
+
+    Add a vendor-specific option to a typed query:
+
+    ```
+    # #[macro_use] extern crate serde_json;
+    # use elastic::client::requests::search::{Query, QueryBuilder, SearchBody};
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let query = Query::from(QueryBuilder.match_query("title", "rust"));
+
+    let body = SearchBody::from(query)
+        .combine(json!({
+            "ext": {
+                "my_plugin": { "boost_recent": true }
+            }
+        }))?;
+
+    assert_eq!("rust", body.as_value()["query"]["match"]["title"]["query"]);
+    assert_eq!(true, body.as_value()["ext"]["my_plugin"]["boost_recent"]);
+    # Ok(())
+    # }
+    ```
+
+    [QueryValidationError]: struct.QueryValidationError.html
+    */
+    pub fn combine(mut self, other: impl Into<SearchBody>) -> Result<Self, QueryValidationError> {
+        self.combine_mut(other.into())?;
+
+        Ok(self)
+    }
+
+    /** Merge another body into this one, leaving this body unchanged if they can't be merged. */
+    pub(crate) fn combine_mut(&mut self, other: SearchBody) -> Result<(), QueryValidationError> {
+        let other = match other.into_parsed() {
+            Some(Value::Null) => return Ok(()),
+            Some(Value::Object(other)) => other,
+            _ => return Err(validate::invalid_body()),
+        };
+
+        let body = self.as_object_mut().ok_or_else(validate::invalid_body)?;
+        merge(body, other);
+
+        Ok(())
+    }
+
+    /**
+    Get a mutable reference to the object at `key`, creating it if it doesn't exist.
+
+    Returns an error if the body or the value at `key` isn't an object.
+    */
+    pub(crate) fn object_mut(
+        &mut self,
+        key: &str,
+    ) -> Result<&mut Map<String, Value>, QueryValidationError> {
+        self.as_object_mut()
+            .ok_or_else(validate::invalid_body)?
+            .entry(key)
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(validate::invalid_body)
+    }
+
+    /**
+    Set the value at `key`, replacing any existing value.
+
+    Returns an error if the body isn't an object.
+    */
+    pub(crate) fn insert(
+        &mut self,
+        key: &str,
+        value: impl Into<Value>,
+    ) -> Result<(), QueryValidationError> {
+        let body = self.as_object_mut().ok_or_else(validate::invalid_body)?;
+        body.insert(key.to_owned(), value.into());
+
+        Ok(())
+    }
+
+    fn as_object_mut(&mut self) -> Option<&mut Map<String, Value>> {
+        if let SearchBodyInner::Raw(ref body) = self.0 {
+            match serde_json::from_slice(body) {
+                Ok(Value::Object(body)) => self.0 = SearchBodyInner::Value(Value::Object(body)),
+                _ => return None,
+            }
+        }
+
+        match self.0 {
+            SearchBodyInner::Value(ref mut body) => {
+                if body.is_null() {
+                    *body = Value::Object(Map::new());
+                }
+
+                body.as_object_mut()
+            }
+            SearchBodyInner::Raw(_) => None,
+        }
+    }
+
+    /** Get the JSON value of the body, or `None` if it's a raw body that isn't valid JSON. */
+    fn parsed(&self) -> Option<Cow<'_, Value>> {
+        match self.0 {
+            SearchBodyInner::Value(ref body) => Some(Cow::Borrowed(body)),
+            SearchBodyInner::Raw(ref body) => serde_json::from_slice(body).ok().map(Cow::Owned),
+        }
+    }
+
+    fn into_parsed(self) -> Option<Value> {
+        match self.0 {
+            SearchBodyInner::Value(body) => Some(body),
+            SearchBodyInner::Raw(body) => serde_json::from_slice(&body).ok(),
+        }
+    }
+
+    fn raw(body: Bytes) -> Self {
+        if body.is_empty() {
+            return SearchBody::new();
+        }
+
+        SearchBody(SearchBodyInner::Raw(body))
+    }
+}
+
+fn merge(body: &mut Map<String, Value>, other: Map<String, Value>) {
+    for (key, value) in other {
+        match (body.get_mut(&key), value) {
+            (Some(Value::Object(ref mut body)), Value::Object(value)) => merge(body, value),
+            (_, value) => {
+                body.insert(key, value);
+            }
+        }
+    }
+}
+
+impl Default for SearchBody {
+    fn default() -> Self {
+        SearchBody::new()
+    }
+}
+
+impl From<Value> for SearchBody {
+    fn from(body: Value) -> Self {
+        SearchBody(SearchBodyInner::Value(body))
+    }
+}
+
+impl From<Map<String, Value>> for SearchBody {
+    fn from(body: Map<String, Value>) -> Self {
+        SearchBody(SearchBodyInner::Value(Value::Object(body)))
+    }
+}
+
+impl From<Query> for SearchBody {
+    fn from(query: Query) -> Self {
+        let mut body = Map::new();
+        body.insert("query".to_owned(), query.into_value());
+
+        SearchBody::from(body)
+    }
+}
+
+impl From<String> for SearchBody {
+    fn from(body: String) -> Self {
+        SearchBody::raw(body.into())
+    }
+}
+
+impl From<&'static str> for SearchBody {
+    fn from(body: &'static str) -> Self {
+        SearchBody::raw(Bytes::from_static(body.as_bytes()))
+    }
+}
+
+impl From<Vec<u8>> for SearchBody {
+    fn from(body: Vec<u8>) -> Self {
+        SearchBody::raw(body.into())
+    }
+}
+
+impl From<&'static [u8]> for SearchBody {
+    fn from(body: &'static [u8]) -> Self {
+        SearchBody::raw(Bytes::from_static(body))
+    }
+}
+
+impl From<Bytes> for SearchBody {
+    fn from(body: Bytes) -> Self {
+        SearchBody::raw(body)
+    }
+}
+
+/**
+Whether to count the total number of hits for a search request.
+
+A `TrackTotalHits` can be created from a `bool` to count all hits or none, or from a number to count hits accurately up to that number.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackTotalHits {
+    /** Count all hits accurately, or don't count hits at all. */
+    Enabled(bool),
+    /** Count hits accurately up to the given number. */
+    UpTo(u32),
+}
+
+impl From<bool> for TrackTotalHits {
+    fn from(track: bool) -> Self {
+        TrackTotalHits::Enabled(track)
+    }
+}
+
+impl From<u32> for TrackTotalHits {
+    fn from(count: u32) -> Self {
+        TrackTotalHits::UpTo(count)
+    }
+}
+
+impl From<TrackTotalHits> for Value {
+    fn from(track: TrackTotalHits) -> Self {
+        match track {
+            TrackTotalHits::Enabled(track) => Value::Bool(track),
+            TrackTotalHits::UpTo(count) => Value::from(count),
+        }
+    }
+}
+
+impl From<SearchBody> for SyncBody {
+    fn from(body: SearchBody) -> SyncBody {
+        match body.0 {
+            SearchBodyInner::Value(Value::Null) => empty_body().into(),
+            SearchBodyInner::Value(body) => body.into(),
+            SearchBodyInner::Raw(body) => body.into(),
+        }
+    }
+}
+
+impl From<SearchBody> for AsyncBody {
+    fn from(body: SearchBody) -> AsyncBody {
+        match body.0 {
+            SearchBodyInner::Value(Value::Null) => empty_body().into(),
+            SearchBodyInner::Value(body) => body.into(),
+            SearchBodyInner::Raw(body) => body.into(),
+        }
+    }
+}
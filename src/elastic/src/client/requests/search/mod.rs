@@ -0,0 +1,2003 @@
+/*!
+Builders for [search requests][docs-search].
+
+[docs-search]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html
+*/
+
+use futures::{
+    Future,
+    IntoFuture,
+};
+use serde::de::DeserializeOwned;
+use std::{
+    marker::PhantomData,
+    mem,
+};
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::SearchResponse,
+        version::{
+            ApiVersions,
+            Version,
+        },
+        Client,
+        DocumentClient,
+    },
+    endpoints::SearchRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        empty_body,
+        sender::{
+            AsyncSender,
+            Sender,
+            SyncSender,
+        },
+        DefaultBody,
+    },
+    params::{
+        Index,
+        Type,
+    },
+    types::document::DocumentType,
+};
+
+/**
+A [search request][docs-search] builder that can be configured before sending.
+
+Call [`Client.search`][Client.search] to get a `SearchRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-search]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.search]: ../../struct.Client.html#search-request
+*/
+pub type SearchRequestBuilder<TSender, TDocument, TBody> =
+    RequestBuilder<TSender, SearchRequestInner<TDocument, TBody>>;
+
+mod aggs;
+mod body;
+mod query;
+mod validate;
+
+pub use self::{
+    aggs::*,
+    body::*,
+    query::*,
+    validate::*,
+};
+
+#[doc(hidden)]
+pub struct SearchRequestInner<TDocument, TBody> {
+    index: Option<Index<'static>>,
+    ty: Option<Type<'static>>,
+    body: TBody,
+    strict: bool,
+    validate: Option<ValidateBody<TBody>>,
+    body_error: Option<QueryValidationError>,
+    versions: Vec<&'static ApiVersions>,
+    _marker: PhantomData<TDocument>,
+}
+
+type ValidateBody<TBody> = fn(&TBody) -> Result<(), QueryValidationError>;
+
+/** The `track_total_hits` option is only available in these versions. */
+const TRACK_TOTAL_HITS_VERSIONS: ApiVersions = ApiVersions {
+    feature: "the track_total_hits option",
+    since: Version::new(7, 0, 0),
+    removed: None,
+};
+
+/**
+# Search request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`SearchRequestBuilder`][SearchRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Run a simple [Query String][docs-querystring] query for a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+        # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let query = "a query string";
+
+    let response = client.search::<MyType>()
+                         .index("myindex")
+                         .body(json!({
+                             "query": {
+                                 "query_string": {
+                                     "query": query
+                                 }
+                             }
+                         }))
+                         .send()?;
+
+    // Iterate through the hits (of type `MyType`)
+    for hit in response.hits() {
+        println!("{:?}", hit);
+    }
+    # Ok(())
+    # }
+    ```
+
+    For more details on document types and mapping, see the [`types`][types-mod] module.
+
+    It's also possible to use `serde_json::Value`s as documents when searching:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+        # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<Value>()
+                         .index("myindex")
+                         .ty("my-type")
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    [SearchRequestBuilder]: requests/search/type.SearchRequestBuilder.html
+    [builder-methods]: requests/search/type.SearchRequestBuilder.html#builder-methods
+    [send-sync]: requests/search/type.SearchRequestBuilder.html#send-synchronously
+    [send-async]: requests/search/type.SearchRequestBuilder.html#send-asynchronously
+    [types-mod]: ../types/index.html
+    [documents-mod]: ../types/document/index.html
+    [docs-querystring]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-query-string-query.html
+    */
+    pub fn search<TDocument>(&self) -> SearchRequestBuilder<TSender, TDocument, DefaultBody>
+    where
+        TDocument: DeserializeOwned,
+    {
+        RequestBuilder::initial(self.clone(), SearchRequestInner::new(empty_body()))
+    }
+}
+
+/**
+# Search request
+*/
+impl<TSender, TDocument> DocumentClient<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`SearchRequestBuilder`][SearchRequestBuilder] with this `Client` that can be configured before sending.
+
+    The index and type parameters will be inferred from the document type.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Run a simple [Query String][docs-querystring] query for a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+        # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let query = "a query string";
+
+    let response = client.document::<MyType>()
+                         .search()
+                         .body(json!({
+                             "query": {
+                                 "query_string": {
+                                     "query": query
+                                 }
+                             }
+                         }))
+                         .send()?;
+
+    // Iterate through the hits (of type `MyType`)
+    for hit in response.hits() {
+        println!("{:?}", hit);
+    }
+    # Ok(())
+    # }
+    ```
+
+    For more details on document types and mapping, see the [`types`][types-mod] module.
+
+    [SearchRequestBuilder]: requests/search/type.SearchRequestBuilder.html
+    [builder-methods]: requests/search/type.SearchRequestBuilder.html#builder-methods
+    [send-sync]: requests/search/type.SearchRequestBuilder.html#send-synchronously
+    [send-async]: requests/search/type.SearchRequestBuilder.html#send-asynchronously
+    [types-mod]: ../../types/index.html
+    [documents-mod]: ../../types/document/index.html
+    [docs-querystring]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-query-string-query.html
+    */
+    pub fn search(self) -> SearchRequestBuilder<TSender, TDocument, DefaultBody>
+    where
+        TDocument: DeserializeOwned + DocumentType,
+    {
+        let index =
+            TDocument::partial_static_index().map(|idx| self.inner.document_index(idx.to_owned()));
+        let ty = TDocument::partial_static_ty().map(|ty| ty.to_owned());
+
+        RequestBuilder::initial(
+            self.inner,
+            SearchRequestInner {
+                index,
+                ty,
+                body: empty_body(),
+                strict: false,
+                validate: None,
+                body_error: None,
+                versions: Vec::new(),
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<TDocument, TBody> SearchRequestInner<TDocument, TBody>
+where
+    TDocument: DeserializeOwned,
+{
+    fn new(body: TBody) -> Self {
+        SearchRequestInner {
+            index: None,
+            ty: None,
+            body,
+            strict: false,
+            validate: None,
+            body_error: None,
+            versions: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn validate_params(&self) -> Result<(), Error> {
+        if let Some(ref index) = self.index {
+            index.validate().map_err(error::request)?;
+        }
+
+        if let Some(ref ty) = self.ty {
+            ty.validate().map_err(error::request)?;
+        }
+
+        Ok(())
+    }
+
+    fn into_request(self) -> SearchRequest<'static, TBody> {
+        let index = self.index.unwrap_or_else(|| "_all".into());
+
+        match self.ty {
+            Some(ty) => SearchRequest::for_index_ty(index, ty, self.body),
+            None => SearchRequest::for_index(index, self.body),
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `SearchRequestBuilder` before sending it.
+*/
+impl<TSender, TDocument, TBody> SearchRequestBuilder<TSender, TDocument, TBody>
+where
+    TSender: Sender,
+{
+    /**
+    Set the indices for the search request.
+
+    If no index is specified then `_all` will be used.
+    */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = Some(index.into());
+        self
+    }
+
+    /** Set the types for the search request. */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.inner.ty = Some(ty.into());
+        self
+    }
+
+    /**
+    Return an error instead of partial results.
+
+    By default, a search where some shards fail or the request times out still succeeds with the results that are available.
+    In strict mode, those responses are returned as an [`ApiError::PartialResults`][ApiError.PartialResults] instead.
+
+    # Examples
+
+    ```no_run
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<Value>()
+                         .index("myindex")
+                         .strict()
+                         .send()?;
+
+    assert!(!response.is_partial());
+    # Ok(())
+    # }
+    ```
+
+    [ApiError.PartialResults]: ../../../error/enum.ApiError.html#variant.PartialResults
+    */
+    pub fn strict(mut self) -> Self {
+        self.inner.strict = true;
+        self
+    }
+
+    /**
+    Set the body for the search request.
+
+    If no body is specified then an empty query will be used.
+    */
+    pub fn body<TNewBody>(
+        self,
+        body: TNewBody,
+    ) -> SearchRequestBuilder<TSender, TDocument, TNewBody>
+    where
+        TNewBody: Into<TSender::Body>,
+    {
+        RequestBuilder::new(
+            self.client,
+            self.params_builder,
+            SearchRequestInner {
+                body,
+                index: self.inner.index,
+                ty: self.inner.ty,
+                strict: self.inner.strict,
+                validate: None,
+                body_error: None,
+                versions: Vec::new(),
+                _marker: PhantomData,
+            },
+        )
+    }
+
+    /**
+    Set the [query][docs-query] for the search request.
+
+    The closure is given a [`QueryBuilder`][QueryBuilder] that can be used to build the query.
+
+    The query is merged into the request body, replacing any existing query.
+    It can be combined with a raw JSON body, but will be discarded by a later call to [`body`](#method.body).
+    If the body isn't a JSON object then `send` returns an error without sending the request.
+
+    # Examples
+
+    Find documents with a title that's similar to some text:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # use elastic::client::requests::search::Fuzziness;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<MyType>()
+                         .index("myindex")
+                         .query(|q| q
+                             .match_query("title", "quikc brwn fox")
+                             .fuzziness(Fuzziness::Auto))
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    Find documents that are similar to an already indexed document:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # use elastic::client::requests::search::LikeDocument;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<MyType>()
+                         .index("myindex")
+                         .query(|q| q
+                             .more_like_this(vec!["title", "description"])
+                             .like(LikeDocument::new("1").index("myindex"))
+                             .like("some extra text")
+                             .min_term_freq(1))
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    Find the documents with embeddings that are most similar to a vector:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<MyType>()
+                         .index("myindex")
+                         .query(|q| q
+                             .knn("embedding", vec![0.12, 0.5, 0.98])
+                             .num_candidates(100))
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    [docs-query]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl.html
+    [QueryBuilder]: struct.QueryBuilder.html
+    */
+    pub fn query<TQuery>(
+        self,
+        builder: impl FnOnce(QueryBuilder) -> TQuery,
+    ) -> SearchRequestBuilder<TSender, TDocument, SearchBody>
+    where
+        TBody: Into<SearchBody>,
+        TQuery: Into<Query>,
+    {
+        let query = builder(QueryBuilder).into();
+        let versions = query.versions();
+
+        self.search_body(versions, |body| body.insert("query", query.into_value()))
+    }
+
+    /**
+    Add an [aggregation][docs-aggs] to the search request.
+
+    The closure is given an [`AggsBuilder`][AggsBuilder] that can be used to build the aggregation and any sub-aggregations.
+    Calling `aggs` multiple times will add each aggregation to the request.
+
+    The aggregation is merged into the request body.
+    It can be combined with a raw JSON body, but will be discarded by a later call to [`body`](#method.body).
+    If the body isn't a JSON object then `send` returns an error without sending the request.
+
+    # Examples
+
+    Add a `terms` aggregation with an `avg` sub-aggregation:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<Value>()
+                         .index("myindex")
+                         .body(json!({
+                             "query": {
+                                 "match_all": {}
+                             }
+                         }))
+                         .aggs(|a| a
+                             .terms("by_user", "user.keyword")
+                             .size(50)
+                             .sub(a.avg("avg_age", "age")))
+                         .send()?;
+
+    for row in response.aggs() {
+        println!("{:?}", row);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [docs-aggs]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations.html
+    [AggsBuilder]: struct.AggsBuilder.html
+    */
+    pub fn aggs<TAgg>(
+        self,
+        builder: impl FnOnce(AggsBuilder) -> TAgg,
+    ) -> SearchRequestBuilder<TSender, TDocument, SearchBody>
+    where
+        TBody: Into<SearchBody>,
+        TAgg: Into<Aggregation>,
+    {
+        let agg = builder(AggsBuilder).into();
+        let versions = agg.versions();
+        let (name, agg) = agg.into_parts();
+
+        self.search_body(versions, |body| {
+            body.object_mut("aggs")?.insert(name, agg);
+
+            Ok(())
+        })
+    }
+
+    /**
+    Set whether to count the total number of hits.
+
+    By default Elasticsearch counts hits accurately up to `10000`.
+    Pass `true` to always count all hits, `false` to skip counting, or a number to count accurately up to that number.
+    Check [`SearchResponse.is_exact`][SearchResponse.is_exact] to see whether the total in the response is exact.
+
+    The option requires Elasticsearch `7.0` or newer.
+    Sending the request will fail without reaching the cluster if its version doesn't support the option.
+
+    The option is merged into the request body.
+    If the body isn't a JSON object then `send` returns an error without sending the request.
+
+    # Examples
+
+    Count all hits matching a query:
+
+    ```no_run
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<Value>()
+                         .index("myindex")
+                         .query(|q| q.match_query("title", "rust"))
+                         .track_total_hits(true)
+                         .send()?;
+
+    assert!(response.is_exact());
+    # Ok(())
+    # }
+    ```
+
+    [SearchResponse.is_exact]: ../../responses/struct.SearchResponse.html#method.is_exact
+    */
+    pub fn track_total_hits(
+        self,
+        track: impl Into<TrackTotalHits>,
+    ) -> SearchRequestBuilder<TSender, TDocument, SearchBody>
+    where
+        TBody: Into<SearchBody>,
+    {
+        let track = track.into();
+
+        self.search_body(Some(&TRACK_TOTAL_HITS_VERSIONS), |body| {
+            body.insert("track_total_hits", track)
+        })
+    }
+
+    /**
+    Exclude hits with a score lower than `min_score`.
+
+    The option is merged into the request body.
+    If the body isn't a JSON object then `send` returns an error without sending the request.
+    */
+    pub fn min_score(self, min_score: f32) -> SearchRequestBuilder<TSender, TDocument, SearchBody>
+    where
+        TBody: Into<SearchBody>,
+    {
+        self.search_body(None, |body| body.insert("min_score", min_score))
+    }
+
+    /**
+    Merge some raw JSON into the request body.
+
+    Objects in the raw JSON are merged recursively into the body built so far, so it can be used alongside typed options like [`query`](#method.query) and [`aggs`](#method.aggs).
+    Any other values replace the values already in the body.
+    If either body isn't a JSON object then `send` returns an error containing a [`QueryValidationError`][QueryValidationError] without sending the request.
+    See [`SearchBody.combine`][SearchBody.combine] for more details.
+
+    # Examples
+
+    Add an option that doesn't have a typed builder to a typed query:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<Value>()
+                         .index("myindex")
+                         .query(|q| q.match_query("title", "rust"))
+                         .combine_body(json!({
+                             "ext": {
+                                 "my_plugin": { "boost_recent": true }
+                             }
+                         }))
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    [QueryValidationError]: struct.QueryValidationError.html
+    [SearchBody.combine]: struct.SearchBody.html#method.combine
+    */
+    pub fn combine_body(
+        self,
+        other: impl Into<SearchBody>,
+    ) -> SearchRequestBuilder<TSender, TDocument, SearchBody>
+    where
+        TBody: Into<SearchBody>,
+    {
+        self.search_body(None, |body| body.combine_mut(other.into()))
+    }
+
+    /**
+    Update the request body with a typed option.
+
+    If the body can't be updated then the error is kept so `send` fails without sending the request.
+    If the option is only available in some versions then `send` checks the cluster version before sending the request.
+    */
+    fn search_body(
+        self,
+        versions: Option<&'static ApiVersions>,
+        f: impl FnOnce(&mut SearchBody) -> Result<(), QueryValidationError>,
+    ) -> SearchRequestBuilder<TSender, TDocument, SearchBody>
+    where
+        TBody: Into<SearchBody>,
+    {
+        let mut body = self.inner.body.into();
+        let error = f(&mut body).err();
+
+        let mut versions_used = self.inner.versions;
+        versions_used.extend(versions);
+
+        RequestBuilder::new(
+            self.client,
+            self.params_builder,
+            SearchRequestInner {
+                body,
+                index: self.inner.index,
+                ty: self.inner.ty,
+                strict: self.inner.strict,
+                validate: self
+                    .inner
+                    .validate
+                    .map(|_| validate_body::<SearchBody> as ValidateBody<SearchBody>),
+                body_error: self.inner.body_error.or(error),
+                versions: versions_used,
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+/**
+# Validation
+
+Check a search body for obviously invalid options before it's sent.
+*/
+impl<TSender, TDocument, TBody> SearchRequestBuilder<TSender, TDocument, TBody>
+where
+    TSender: Sender,
+    TBody: Clone + Into<SearchBody>,
+{
+    /**
+    Check the request body for obviously invalid options.
+
+    Returns a [`QueryValidationError`][QueryValidationError] describing every problem found in the body.
+    See [`SearchBody.validate`][SearchBody.validate] for the kinds of problems that are caught.
+
+    # Examples
+
+    ```no_run
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let builder = client.search::<Value>()
+                        .index("myindex")
+                        .query(|q| q.match_query("title", "rust"));
+
+    if let Err(e) = builder.validate() {
+        for problem in e.problems() {
+            println!("{}", problem);
+        }
+    }
+    # Ok(())
+    # }
+    ```
+
+    [QueryValidationError]: struct.QueryValidationError.html
+    [SearchBody.validate]: struct.SearchBody.html#method.validate
+    */
+    pub fn validate(&self) -> Result<(), QueryValidationError> {
+        validate_body(&self.inner.body)
+    }
+
+    /**
+    Check the request body for obviously invalid options before sending it.
+
+    If the body is invalid then `send` returns an error containing a [`QueryValidationError`][QueryValidationError] without sending the request.
+    The check applies to any typed options added after calling `validate_before_send`,
+    but is discarded by a later call to [`body`](#method.body).
+
+    # Examples
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<Value>()
+                         .index("myindex")
+                         .validate_before_send()
+                         .combine_body(json!({ "size": -1 }))
+                         .send();
+
+    assert!(response.is_err());
+    # Ok(())
+    # }
+    ```
+
+    [QueryValidationError]: struct.QueryValidationError.html
+    */
+    pub fn validate_before_send(mut self) -> Self {
+        self.inner.validate = Some(validate_body::<TBody>);
+        self
+    }
+}
+
+fn validate_body<TBody>(body: &TBody) -> Result<(), QueryValidationError>
+where
+    TBody: Clone + Into<SearchBody>,
+{
+    body.clone().into().validate()
+}
+
+/**
+# Send synchronously
+*/
+impl<TDocument, TBody> SearchRequestBuilder<SyncSender, TDocument, TBody>
+where
+    TDocument: DeserializeOwned,
+    TBody: Into<<SyncSender as Sender>::Body> + Send + 'static,
+{
+    /**
+    Send a `SearchRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Run a simple [Query String][docs-querystring] query for a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+        # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search::<MyType>()
+                         .index("myindex")
+                         .send()?;
+
+    // Iterate through the hits (of type `MyType`)
+    for hit in response.hits() {
+        println!("{:?}", hit);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    [docs-querystring]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-query-string-query.html
+    */
+    pub fn send(self) -> Result<SearchResponse<TDocument>, Error> {
+        self.inner.validate_params()?;
+
+        if let Some(e) = self.inner.body_error {
+            return Err(error::request(e));
+        }
+
+        if let Some(validate) = self.inner.validate {
+            validate(&self.inner.body).map_err(error::request)?;
+        }
+
+        let params_builder = self
+            .client
+            .check_versions(&self.inner.versions, self.params_builder)?;
+
+        let strict = self.inner.strict;
+        let req = self.inner.into_request();
+
+        let res: SearchResponse<TDocument> =
+            RequestBuilder::new(self.client, params_builder, RawRequestInner::new(req))
+                .send()?
+                .into_response()?;
+
+        if strict {
+            res.into_complete()
+        } else {
+            Ok(res)
+        }
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TDocument, TBody> SearchRequestBuilder<AsyncSender, TDocument, TBody>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+    TBody: Into<<AsyncSender as Sender>::Body> + Send + 'static,
+{
+    /**
+    Send a `SearchRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised search response.
+
+    # Examples
+
+    Run a simple [Query String][docs-querystring] query for a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+        # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.search::<MyType>()
+                       .index("myindex")
+                       .send();
+
+    future.and_then(|response| {
+        // Iterate through the hits (of type `MyType`)
+        for hit in response.hits() {
+            println!("{:?}", hit);
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    [docs-querystring]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-query-string-query.html
+    */
+    pub fn send(self) -> Pending<TDocument> {
+        if let Err(e) = self.inner.validate_params() {
+            return Pending::new(Err(e).into_future());
+        }
+
+        if let Some(e) = self.inner.body_error {
+            return Pending::new(Err(error::request(e)).into_future());
+        }
+
+        if let Some(validate) = self.inner.validate {
+            if let Err(e) = validate(&self.inner.body) {
+                return Pending::new(Err(error::request(e)).into_future());
+            }
+        }
+
+        let mut inner = self.inner;
+        let versions = mem::take(&mut inner.versions);
+
+        let strict = inner.strict;
+        let req = inner.into_request();
+        let client = self.client;
+
+        let res_future = client
+            .check_versions(versions, self.params_builder)
+            .and_then(move |params_builder| {
+                RequestBuilder::new(client, params_builder, RawRequestInner::new(req)).send()
+            })
+            .and_then(|res| res.into_response())
+            .and_then(move |res: SearchResponse<TDocument>| {
+                if strict {
+                    res.into_complete()
+                } else {
+                    Ok(res)
+                }
+            });
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending<TDocument> = BasePending<SearchResponse<TDocument>>;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use chrono::{
+        TimeZone,
+        Utc,
+    };
+    use std::{
+        convert::TryFrom,
+        error::Error as StdError,
+        time::Duration,
+    };
+
+    use super::{
+        Aggregation,
+        CalendarInterval,
+        FixedInterval,
+        Fuzziness,
+        LikeDocument,
+        Operator,
+        Query,
+        QueryValidationError,
+        SearchBody,
+        SortOrder,
+    };
+    use crate::{
+        error::Error,
+        client::Version,
+        http::{
+            empty_body,
+            SyncBody,
+        },
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending<TestDoc>>();
+    }
+
+    #[derive(Serialize, Deserialize, ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    struct TestDoc {}
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.search::<Value>().inner.into_request();
+
+        assert_eq!("/_all/_search", req.url.as_ref());
+        assert_eq!(empty_body(), req.body);
+    }
+
+    #[test]
+    fn validate_default_and_raw_bodies() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        assert!(client.search::<Value>().validate().is_ok());
+        assert!(client
+            .search::<Value>()
+            .body(r#"{ "size": -1 }"#)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn document_index_name() {
+        let client = SyncClientBuilder::new()
+            .document_index_name(|index| format!("staging_{}", index))
+            .build()
+            .unwrap();
+
+        let req = client.document::<TestDoc>().search().inner.into_request();
+
+        assert_eq!("/staging_testdoc/_doc/_search", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_index() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .index("new-idx")
+            .inner
+            .into_request();
+
+        assert_eq!("/new-idx/_search", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_ty() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.search::<Value>().ty("new-ty").inner.into_request();
+
+        assert_eq!("/_all/new-ty/_search", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_strict_survives_body() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client.search::<Value>().strict().body("{}");
+
+        assert!(builder.inner.strict);
+    }
+
+    #[test]
+    fn specify_body() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.search::<Value>().body("{}").inner.into_request();
+
+        assert_eq!("{}", req.body);
+    }
+
+    #[test]
+    fn default_body_is_empty() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client.search::<Value>().inner.into_request();
+
+        assert!(req.body.is_empty());
+    }
+
+    #[test]
+    fn specify_aggs() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .aggs(|a| {
+                a.terms("by_user", "user.keyword")
+                    .size(50)
+                    .order("avg_age", SortOrder::Desc)
+                    .sub(a.avg("avg_age", "age"))
+            })
+            .aggs(|a| a.significant_terms("tags", "tags").min_doc_count(10))
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "aggs": {
+                "by_user": {
+                    "terms": {
+                        "field": "user.keyword",
+                        "size": 50,
+                        "order": [{ "avg_age": "desc" }]
+                    },
+                    "aggs": {
+                        "avg_age": {
+                            "avg": { "field": "age" }
+                        }
+                    }
+                },
+                "tags": {
+                    "significant_terms": {
+                        "field": "tags",
+                        "min_doc_count": 10
+                    }
+                }
+            }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_aggs_merges_into_body() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .body(json!({
+                "query": { "match_all": {} }
+            }))
+            .aggs(|a| a.sum("total", "balance").missing(0))
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "query": { "match_all": {} },
+            "aggs": {
+                "total": {
+                    "sum": { "field": "balance", "missing": 0 }
+                }
+            }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_top_hits_agg() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .aggs(|a| {
+                a.terms("by_user", "user.keyword").sub(
+                    a.top_hits("latest")
+                        .size(1)
+                        .sort("timestamp", SortOrder::Desc)
+                        .source(vec!["user", "text"]),
+                )
+            })
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "aggs": {
+                "by_user": {
+                    "terms": { "field": "user.keyword" },
+                    "aggs": {
+                        "latest": {
+                            "top_hits": {
+                                "size": 1,
+                                "sort": [{ "timestamp": { "order": "desc" } }],
+                                "_source": ["user", "text"]
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_raw_agg() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .aggs(|a| {
+                a.terms("by_user", "user.keyword").sub(Aggregation::raw(
+                    "locations",
+                    json!({ "geotile_grid": { "field": "location" } }),
+                ))
+            })
+            .inner
+            .into_request();
+
+        assert_eq!(
+            &json!({ "geotile_grid": { "field": "location" } }),
+            &req.body.as_value()["aggs"]["by_user"]["aggs"]["locations"]
+        );
+    }
+
+    #[test]
+    fn specify_date_histogram_agg() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .aggs(|a| {
+                a.date_histogram("per_day", "timestamp", CalendarInterval::Day)
+                    .time_zone("Europe/Amsterdam")
+                    .offset("+6h")
+                    .min_doc_count(0)
+                    .extended_bounds(
+                        Utc.with_ymd_and_hms(2019, 10, 1, 0, 0, 0).unwrap(),
+                        Utc.with_ymd_and_hms(2019, 10, 31, 0, 0, 0).unwrap(),
+                    )
+                    .sub(a.avg("avg_age", "age"))
+            })
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "aggs": {
+                "per_day": {
+                    "date_histogram": {
+                        "field": "timestamp",
+                        "calendar_interval": "1d",
+                        "time_zone": "Europe/Amsterdam",
+                        "offset": "+6h",
+                        "min_doc_count": 0,
+                        "extended_bounds": {
+                            "min": 1569888000000i64,
+                            "max": 1572480000000i64
+                        }
+                    },
+                    "aggs": {
+                        "avg_age": {
+                            "avg": { "field": "age" }
+                        }
+                    }
+                }
+            }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_date_histogram_fixed_interval() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .aggs(|a| {
+                a.date_histogram("per_90m", "timestamp", CalendarInterval::Month)
+                    .fixed_interval(FixedInterval::minutes(90))
+            })
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "field": "timestamp",
+            "fixed_interval": "90m"
+        });
+
+        assert_eq!(
+            &expected,
+            &req.body.as_value()["aggs"]["per_90m"]["date_histogram"]
+        );
+    }
+
+    #[test]
+    fn specify_date_histogram_duration_interval() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let interval = FixedInterval::try_from(Duration::from_secs(90 * 60)).unwrap();
+
+        let req = client
+            .search::<Value>()
+            .aggs(|a| a.date_histogram("per_90m", "timestamp", interval))
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "field": "timestamp",
+            "fixed_interval": "90m"
+        });
+
+        assert_eq!(
+            &expected,
+            &req.body.as_value()["aggs"]["per_90m"]["date_histogram"]
+        );
+    }
+
+    #[test]
+    fn fixed_interval_from_duration() {
+        let fixed_interval =
+            |duration| FixedInterval::try_from(duration).map(|interval| interval.to_string());
+
+        assert_eq!(Ok("2d".to_owned()), fixed_interval(Duration::from_secs(2 * 86_400)));
+        assert_eq!(Ok("36h".to_owned()), fixed_interval(Duration::from_secs(36 * 3_600)));
+        assert_eq!(Ok("45s".to_owned()), fixed_interval(Duration::from_secs(45)));
+        assert_eq!(Ok("1ms".to_owned()), fixed_interval(Duration::from_millis(1)));
+
+        assert!(fixed_interval(Duration::from_secs(0)).is_err());
+        assert!(fixed_interval(Duration::from_micros(999)).is_err());
+        assert!(fixed_interval(Duration::from_micros(1_500)).is_err());
+    }
+
+    #[test]
+    fn specify_match_query() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .query(|q| {
+                q.match_query("title", "quikc brwn")
+                    .fuzziness(Fuzziness::AutoRange(3, 6))
+                    .operator(Operator::And)
+                    .prefix_length(1)
+            })
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "query": {
+                "match": {
+                    "title": {
+                        "query": "quikc brwn",
+                        "fuzziness": "AUTO:3,6",
+                        "operator": "and",
+                        "prefix_length": 1
+                    }
+                }
+            }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_fuzzy_query() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .query(|q| {
+                q.fuzzy("user", "kimchy")
+                    .fuzziness(Fuzziness::Edits(2))
+                    .transpositions(false)
+            })
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "query": {
+                "fuzzy": {
+                    "user": {
+                        "value": "kimchy",
+                        "fuzziness": 2,
+                        "transpositions": false
+                    }
+                }
+            }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_more_like_this_query() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .query(|q| {
+                q.more_like_this(vec!["title", "description"])
+                    .like(LikeDocument::new("1").index("imdb").ty("movies"))
+                    .like(LikeDocument::new(2))
+                    .like("once upon a time")
+                    .unlike("zombies")
+                    .min_term_freq(1)
+                    .max_query_terms(12)
+            })
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "query": {
+                "more_like_this": {
+                    "fields": ["title", "description"],
+                    "like": [
+                        { "_index": "imdb", "_type": "movies", "_id": "1" },
+                        { "_id": "2" },
+                        "once upon a time"
+                    ],
+                    "unlike": ["zombies"],
+                    "min_term_freq": 1,
+                    "max_query_terms": 12
+                }
+            }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_knn_query() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .query(|q| {
+                q.knn("embedding", vec![0.5, 1.0])
+                    .num_candidates(50)
+                    .filter(q.match_query("title", "rust"))
+                    .similarity(0.75)
+            })
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "query": {
+                "knn": {
+                    "field": "embedding",
+                    "query_vector": [0.5, 1.0],
+                    "num_candidates": 50,
+                    "filter": {
+                        "match": {
+                            "title": {
+                                "query": "rust"
+                            }
+                        }
+                    },
+                    "similarity": 0.75
+                }
+            }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_cosine_similarity_query() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .query(|q| q.cosine_similarity("embedding", vec![0.5, 1.0]))
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "query": {
+                "script_score": {
+                    "query": { "match_all": {} },
+                    "script": {
+                        "source": "cosineSimilarity(params.query_vector, 'embedding') + 1.0",
+                        "params": {
+                            "query_vector": [0.5, 1.0]
+                        }
+                    }
+                }
+            }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_cosine_similarity_query_with_filter() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .query(|q| {
+                q.cosine_similarity("embedding", vec![0.5])
+                    .filter(Query::raw(json!({ "term": { "status": "published" } })))
+            })
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "script_score": {
+                "query": { "term": { "status": "published" } },
+                "script": {
+                    "source": "cosineSimilarity(params.query_vector, 'embedding') + 1.0",
+                    "params": {
+                        "query_vector": [0.5]
+                    }
+                }
+            }
+        });
+
+        assert_eq!(Some(&expected), req.body.as_value().get("query"));
+    }
+
+    #[test]
+    fn specify_query_replaces_body_query() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .body(json!({
+                "query": { "match_all": {} },
+                "size": 5
+            }))
+            .query(|_| Query::raw(json!({ "match_none": {} })))
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "query": { "match_none": {} },
+            "size": 5
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_query_merges_into_str_body() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .body(r#"{ "size": 5 }"#)
+            .query(|_| Query::raw(json!({ "match_none": {} })))
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "query": { "match_none": {} },
+            "size": 5
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_combine_body_merges_into_body() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .query(|q| q.match_query("title", "rust"))
+            .combine_body(json!({
+                "query": { "match": { "title": { "boost": 2 } } },
+                "ext": { "my_plugin": {} }
+            }))
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "query": { "match": { "title": { "query": "rust", "boost": 2 } } },
+            "ext": { "my_plugin": {} }
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_track_total_hits_min_score() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .body(json!({ "size": 5 }))
+            .track_total_hits(true)
+            .min_score(0.5)
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "size": 5,
+            "track_total_hits": true,
+            "min_score": 0.5
+        });
+
+        assert_eq!(expected, *req.body.as_value());
+    }
+
+    #[test]
+    fn specify_track_total_hits_count() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .track_total_hits(100)
+            .inner
+            .into_request();
+
+        assert_eq!(json!({ "track_total_hits": 100 }), *req.body.as_value());
+    }
+
+    #[cfg(feature = "preserve_order")]
+    #[test]
+    fn specify_options_preserve_order() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .body(r#"{ "size": 5, "from": 10 }"#)
+            .min_score(0.5)
+            .query(|_| Query::raw(json!({ "match_all": {} })))
+            .inner
+            .into_request();
+
+        let body = req.body.as_value();
+        let keys: Vec<_> = body
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|key| key.as_str())
+            .collect();
+
+        assert_eq!(vec!["size", "from", "min_score", "query"], keys);
+    }
+
+    #[test]
+    fn query_into_search_body() {
+        let body = SearchBody::from(Query::raw(json!({ "match_all": {} })));
+
+        assert_eq!(json!({ "query": { "match_all": {} } }), *body.as_value());
+    }
+
+    #[test]
+    fn raw_body_is_sent_unchanged() {
+        let raw = r#"{ "query": { "match_all": {} } }"#;
+
+        let mut body = SyncBody::from(SearchBody::from(raw));
+
+        assert_eq!(Some(raw.as_bytes()), body.buffered_bytes().as_deref());
+        assert_eq!(
+            json!({ "query": { "match_all": {} } }),
+            *SearchBody::from(raw).as_value()
+        );
+    }
+
+    #[test]
+    fn invalid_raw_body_is_unchanged() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search::<Value>()
+            .body("not json")
+            .min_score(0.5)
+            .inner
+            .into_request();
+
+        assert!(!req.body.is_empty());
+        assert_eq!(Value::Null, *req.body.as_value());
+        assert_eq!(
+            Some(&b"not json"[..]),
+            SyncBody::from(req.body).buffered_bytes().as_deref()
+        );
+    }
+
+    #[test]
+    fn combine_invalid_raw_body_fails() {
+        let e = SearchBody::from("not json")
+            .combine(json!({ "size": 5 }))
+            .unwrap_err();
+        assert_eq!(
+            "the search request body is invalid: the body must be a JSON object",
+            e.to_string()
+        );
+
+        assert!(SearchBody::from(json!({ "size": 5 }))
+            .combine("not json")
+            .is_err());
+        assert!(SearchBody::from(json!([1, 2]))
+            .combine(json!({ "size": 5 }))
+            .is_err());
+    }
+
+    #[test]
+    fn combine_raw_bodies() {
+        let body = SearchBody::from(r#"{ "size": 5, "query": { "match_all": {} } }"#)
+            .combine(r#"{ "from": 10 }"#)
+            .unwrap();
+
+        assert_eq!(
+            json!({ "size": 5, "query": { "match_all": {} }, "from": 10 }),
+            *body.as_value()
+        );
+    }
+
+    #[test]
+    fn specify_combine_body_on_invalid_raw_body_fails_without_sending() {
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .pre_send_raw(|_| panic!("the request shouldn't be sent"))
+            .build()
+            .unwrap();
+
+        let builder = client
+            .search::<Value>()
+            .body("not json")
+            .combine_body(json!({ "size": 5 }))
+            .min_score(0.5);
+
+        assert_eq!(Value::Null, *builder.inner.body.as_value());
+        assert!(builder.send().is_err());
+    }
+
+    #[test]
+    fn specify_options_on_non_object_body_fails_without_sending() {
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .pre_send_raw(|_| panic!("the request shouldn't be sent"))
+            .build()
+            .unwrap();
+
+        assert!(client
+            .search::<Value>()
+            .body("[1, 2]")
+            .query(|q| q.match_query("title", "rust"))
+            .send()
+            .is_err());
+        assert!(client
+            .search::<Value>()
+            .body("not json")
+            .aggs(|a| a.avg("avg_age", "age"))
+            .send()
+            .is_err());
+        assert!(client
+            .search::<Value>()
+            .body(json!("not an object"))
+            .track_total_hits(true)
+            .send()
+            .is_err());
+        assert!(client
+            .search::<Value>()
+            .body(json!({ "aggs": [] }))
+            .aggs(|a| a.avg("avg_age", "age"))
+            .send()
+            .is_err());
+    }
+
+    fn version_client(version: Version) -> SyncClient {
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .pre_send_raw(|_| panic!("the request shouldn't be sent"))
+            .build()
+            .unwrap();
+        client.version.set(version);
+
+        client
+    }
+
+    fn assert_unsupported<T>(res: Result<T, Error>, expected: &str) {
+        match res {
+            Err(e) => assert!(
+                format!("{:?}", e).contains(expected),
+                "unexpected error {:?}",
+                e
+            ),
+            Ok(_) => panic!("expected the request to fail"),
+        }
+    }
+
+    #[test]
+    fn invalid_index_fails_without_sending() {
+        let client = version_client(Version::new(7, 4, 0));
+
+        assert_unsupported(
+            client.search::<Value>().index("My-Index").send(),
+            "isn't lowercase",
+        );
+    }
+
+    #[test]
+    fn unsupported_options_fail_without_sending() {
+        let client = version_client(Version::new(6, 8, 0));
+
+        assert_unsupported(
+            client.search::<Value>().track_total_hits(true).send(),
+            "the track_total_hits option requires Elasticsearch 7.0.0 or later",
+        );
+        assert_unsupported(
+            client
+                .search::<Value>()
+                .aggs(|a| {
+                    a.terms("by_user", "user.keyword").sub(a.date_histogram(
+                        "per_day",
+                        "timestamp",
+                        CalendarInterval::Day,
+                    ))
+                })
+                .send(),
+            "the calendar_interval and fixed_interval of a date_histogram aggregation requires Elasticsearch 7.2.0 or later",
+        );
+
+        let client = version_client(Version::new(8, 11, 0));
+
+        assert_unsupported(
+            client
+                .search::<Value>()
+                .query(|q| q.knn("embedding", vec![0.5, 1.0]))
+                .send(),
+            "the knn query requires Elasticsearch 8.12.0 or later",
+        );
+    }
+
+    #[test]
+    fn versions_are_only_kept_for_typed_options() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client
+            .search::<Value>()
+            .query(|q| q.match_query("title", "rust"))
+            .aggs(|a| a.terms("by_user", "user.keyword"))
+            .min_score(0.5);
+        assert!(builder.inner.versions.is_empty());
+
+        let builder = builder
+            .query(|q| q.knn("embedding", vec![0.5, 1.0]))
+            .track_total_hits(true);
+        assert_eq!(2, builder.inner.versions.len());
+
+        // Replacing the body discards the typed options
+        let builder = builder.body("{}");
+        assert!(builder.inner.versions.is_empty());
+    }
+
+    fn problems(body: Value) -> Vec<(String, String)> {
+        match SearchBody::from(body).validate() {
+            Ok(()) => vec![],
+            Err(e) => e
+                .problems()
+                .iter()
+                .map(|problem| (problem.path().to_owned(), problem.message().to_owned()))
+                .collect(),
+        }
+    }
+
+    fn problem_paths(body: Value) -> Vec<String> {
+        let mut paths: Vec<_> = problems(body).into_iter().map(|(path, _)| path).collect();
+        paths.sort();
+
+        paths
+    }
+
+    #[test]
+    fn validate_valid_body() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client
+            .search::<Value>()
+            .query(|_| {
+                Query::raw(json!({
+                    "bool": {
+                        "must": [{ "match": { "title": "rust" } }],
+                        "filter": { "term": { "user": "kimchy" } }
+                    }
+                }))
+            })
+            .combine_body(json!({ "size": 10, "from": 0 }));
+
+        assert!(builder.validate().is_ok());
+        assert!(SearchBody::new().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_size_and_from() {
+        assert_eq!(
+            vec!["from", "size"],
+            problem_paths(json!({ "size": -1, "from": "10" }))
+        );
+    }
+
+    #[test]
+    fn validate_top_level_filter() {
+        assert_eq!(
+            vec!["filter"],
+            problem_paths(json!({ "filter": { "term": { "user": "kimchy" } } }))
+        );
+    }
+
+    #[test]
+    fn validate_misnested_clauses() {
+        assert_eq!(
+            vec!["post_filter.must", "query.filter"],
+            problem_paths(json!({
+                "query": { "filter": { "term": { "user": "kimchy" } } },
+                "post_filter": { "must": [] }
+            }))
+        );
+    }
+
+    #[test]
+    fn validate_query_types() {
+        assert_eq!(
+            vec![(
+                "query".to_owned(),
+                "must contain exactly one query, but contains `match`, `term`".to_owned()
+            )],
+            problems(json!({
+                "query": {
+                    "match": { "title": "rust" },
+                    "term": { "user": "kimchy" }
+                }
+            }))
+        );
+
+        assert_eq!(vec!["query"], problem_paths(json!({ "query": {} })));
+        assert_eq!(vec!["query"], problem_paths(json!({ "query": "rust" })));
+    }
+
+    #[test]
+    fn validate_empty_bool() {
+        assert_eq!(
+            vec!["query.bool"],
+            problem_paths(json!({ "query": { "bool": {} } }))
+        );
+        assert_eq!(
+            vec!["query.bool", "query.bool.query"],
+            problem_paths(json!({ "query": { "bool": { "query": { "match_all": {} } } } }))
+        );
+        assert!(problem_paths(json!({
+            "query": { "bool": { "should": [], "minimum_should_match": 1, "must_not": { "match_all": {} } } }
+        }))
+        .is_empty());
+    }
+
+    #[test]
+    fn validate_nested_queries() {
+        assert_eq!(
+            vec![
+                "query.bool.filter.dis_max.queries[0]",
+                "query.bool.must[1].bool",
+                "query.bool.should.constant_score.filter.must",
+            ],
+            problem_paths(json!({
+                "query": {
+                    "bool": {
+                        "must": [
+                            { "match": { "title": "rust" } },
+                            { "bool": {} }
+                        ],
+                        "should": {
+                            "constant_score": { "filter": { "must": {} } }
+                        },
+                        "filter": {
+                            "dis_max": { "queries": [{}] }
+                        }
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn validate_body_that_is_not_an_object() {
+        assert_eq!(vec![""], problem_paths(json!([1, 2])));
+
+        let e = SearchBody::from("not json").validate().unwrap_err();
+        assert_eq!(
+            "the search request body is invalid: the body must be a JSON object",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn validate_error_message() {
+        let e = SearchBody::from(json!({ "size": -1, "query": { "bool": {} } }))
+            .validate()
+            .unwrap_err();
+
+        assert_eq!(
+            "the search request body is invalid: `size` must be a non-negative integer; `query.bool` must contain at least one `must`, `filter`, `should` or `must_not` clause",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn validate_before_send() {
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .pre_send_raw(|_| panic!("the request was sent"))
+            .build()
+            .unwrap();
+
+        let res = client
+            .search::<Value>()
+            .validate_before_send()
+            .query(|_| Query::raw(json!({ "bool": {} })))
+            .send();
+
+        let e = match res {
+            Err(Error::Client(e)) => e,
+            _ => panic!("expected a client error"),
+        };
+
+        let e = e
+            .source()
+            .and_then(|e| e.downcast_ref::<QueryValidationError>())
+            .expect("expected a validation error");
+
+        assert_eq!("query.bool", e.problems()[0].path());
+    }
+
+    #[test]
+    fn validate_before_send_discarded_by_body() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let builder = client
+            .search::<Value>()
+            .validate_before_send()
+            .body(json!({ "size": -1 }));
+
+        assert!(builder.inner.validate.is_none());
+    }
+}
@@ -0,0 +1,693 @@
+use std::fmt;
+
+use serde::ser::{
+    Serialize,
+    Serializer,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+
+use crate::{
+    client::version::{
+        ApiVersions,
+        Version,
+    },
+    params::{
+        Id,
+        Index,
+        Type,
+    },
+};
+
+/** The `knn` query is only available in these versions. */
+const KNN_VERSIONS: ApiVersions = ApiVersions {
+    feature: "the knn query",
+    since: Version::new(8, 12, 0),
+    removed: None,
+};
+
+/**
+A factory for query builders.
+
+A `QueryBuilder` is passed to the closure given to [`SearchRequestBuilder.query`][SearchRequestBuilder.query].
+
+[SearchRequestBuilder.query]: type.SearchRequestBuilder.html#method.query
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueryBuilder;
+
+impl QueryBuilder {
+    /**
+    A [`match`][docs-match] full text query.
+
+    [docs-match]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query.html
+    */
+    pub fn match_query(self, field: impl Into<String>, query: impl Into<Value>) -> MatchQuery {
+        MatchQuery {
+            field: field.into(),
+            options: MatchOptions {
+                query: query.into(),
+                fuzziness: None,
+                operator: None,
+                prefix_length: None,
+                max_expansions: None,
+                fuzzy_transpositions: None,
+                minimum_should_match: None,
+            },
+        }
+    }
+
+    /**
+    A [`fuzzy`][docs-fuzzy] term-level query.
+
+    [docs-fuzzy]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-fuzzy-query.html
+    */
+    pub fn fuzzy(self, field: impl Into<String>, value: impl Into<Value>) -> FuzzyQuery {
+        FuzzyQuery {
+            field: field.into(),
+            options: FuzzyOptions {
+                value: value.into(),
+                fuzziness: None,
+                prefix_length: None,
+                max_expansions: None,
+                transpositions: None,
+            },
+        }
+    }
+
+    /**
+    A [`more_like_this`][docs-mlt] query.
+
+    At least one document or piece of text to find similar documents for must be added with [`like`][like].
+
+    [docs-mlt]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-mlt-query.html
+    [like]: struct.MoreLikeThisQuery.html#method.like
+    */
+    pub fn more_like_this<TField>(
+        self,
+        fields: impl IntoIterator<Item = TField>,
+    ) -> MoreLikeThisQuery
+    where
+        TField: Into<String>,
+    {
+        MoreLikeThisQuery {
+            options: MoreLikeThisOptions {
+                fields: fields.into_iter().map(Into::into).collect(),
+                like: Vec::new(),
+                unlike: Vec::new(),
+                min_term_freq: None,
+                max_query_terms: None,
+                min_doc_freq: None,
+                minimum_should_match: None,
+            },
+        }
+    }
+
+    /**
+    A [`knn`][docs-knn] query that finds the documents with the vectors nearest to `query_vector`.
+
+    The field must be a [`dense_vector`][dense-vector-mod] that's indexed for kNN search.
+    The `knn` query requires Elasticsearch `8.12` or newer.
+    Sending a search with a `knn` query will fail without reaching the cluster if its version doesn't support it.
+
+    [docs-knn]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-knn-query.html
+    [dense-vector-mod]: ../../../types/dense_vector/index.html
+    */
+    pub fn knn(self, field: impl Into<String>, query_vector: impl Into<Vec<f32>>) -> KnnQuery {
+        KnnQuery {
+            options: KnnOptions {
+                field: field.into(),
+                query_vector: query_vector.into(),
+                num_candidates: None,
+                filter: None,
+                similarity: None,
+            },
+        }
+    }
+
+    /**
+    A [`script_score`][docs-script-score] query that scores documents by the cosine similarity of `query_vector` to their vector in `field`.
+
+    The field must be a [`dense_vector`][dense-vector-mod].
+    Documents are scored by their cosine similarity plus `1.0`, because scores can't be negative.
+    All documents are scored unless a [`filter`][filter] is given.
+
+    [docs-script-score]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-script-score-query.html
+    [dense-vector-mod]: ../../../types/dense_vector/index.html
+    [filter]: struct.CosineSimilarityQuery.html#method.filter
+    */
+    pub fn cosine_similarity(
+        self,
+        field: impl Into<String>,
+        query_vector: impl Into<Vec<f32>>,
+    ) -> CosineSimilarityQuery {
+        CosineSimilarityQuery {
+            field: field.into(),
+            query_vector: query_vector.into(),
+            filter: None,
+        }
+    }
+}
+
+/**
+A query that can be added to a search request.
+
+Any of the query builders can be converted into a `Query`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query(Value);
+
+impl Query {
+    /**
+    Create a query from a raw JSON body.
+
+    This can be used for queries that don't have a builder yet:
+
+    ```
+    # #[macro_use] extern crate serde_json;
+    # use elastic::client::requests::search::Query;
+    # fn main() {
+    let query = Query::raw(json!({
+        "query_string": { "query": "title:rust" }
+    }));
+    # }
+    ```
+    */
+    pub fn raw(query: Value) -> Self {
+        Query(query)
+    }
+
+    /** The JSON body of the query. */
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+
+    /** Convert the query into its JSON body. */
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+
+    /** The versions this kind of query is available in, if it's only available in some. */
+    pub(crate) fn versions(&self) -> Option<&'static ApiVersions> {
+        if self.0.get("knn").is_some() {
+            Some(&KNN_VERSIONS)
+        } else {
+            None
+        }
+    }
+
+    fn new<T>(kind: &str, field: Option<String>, options: &T) -> Self
+    where
+        T: Serialize,
+    {
+        let options = serde_json::to_value(options).expect("query options are always valid json");
+
+        let options = match field {
+            Some(field) => {
+                let mut query = Map::new();
+                query.insert(field, options);
+                Value::Object(query)
+            }
+            None => options,
+        };
+
+        let mut query = Map::new();
+        query.insert(kind.to_owned(), options);
+
+        Query(Value::Object(query))
+    }
+}
+
+/**
+The [fuzziness][docs-fuzziness] allowed when matching terms.
+
+[docs-fuzziness]: https://www.elastic.co/guide/en/elasticsearch/reference/current/common-options.html#fuzziness
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fuzziness {
+    /** Generate an edit distance based on the length of the term. */
+    Auto,
+    /**
+    Generate an edit distance based on the length of the term, using the given low and high distance arguments.
+
+    Terms shorter than `low` must match exactly, terms between `low` and `high` can have one edit and longer terms can have two edits.
+    */
+    AutoRange(u32, u32),
+    /** The maximum number of edits allowed. This should be `0`, `1` or `2`. */
+    Edits(u8),
+}
+
+impl Serialize for Fuzziness {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            Fuzziness::Auto => serializer.serialize_str("AUTO"),
+            Fuzziness::AutoRange(low, high) => {
+                serializer.collect_str(&format_args!("AUTO:{},{}", low, high))
+            }
+            Fuzziness::Edits(edits) => serializer.serialize_u8(edits),
+        }
+    }
+}
+
+/** The boolean logic used to combine terms in a query. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    /** All terms must match. */
+    And,
+    /** Any term can match. */
+    Or,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct MatchOptions {
+    query: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fuzziness: Option<Fuzziness>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    operator: Option<Operator>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_expansions: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fuzzy_transpositions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minimum_should_match: Option<String>,
+}
+
+/**
+A [`match`][docs-match] full text query.
+
+Call [`QueryBuilder.match_query`][QueryBuilder.match_query] to get a `MatchQuery`.
+
+[docs-match]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-match-query.html
+[QueryBuilder.match_query]: struct.QueryBuilder.html#method.match_query
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchQuery {
+    field: String,
+    options: MatchOptions,
+}
+
+impl MatchQuery {
+    /** Allow terms to match with the given fuzziness. */
+    pub fn fuzziness(mut self, fuzziness: Fuzziness) -> Self {
+        self.options.fuzziness = Some(fuzziness);
+        self
+    }
+
+    /** Set the boolean logic used to combine the terms in the query. */
+    pub fn operator(mut self, operator: Operator) -> Self {
+        self.options.operator = Some(operator);
+        self
+    }
+
+    /** Set the number of leading characters that must match exactly for fuzzy matches. */
+    pub fn prefix_length(mut self, prefix_length: u32) -> Self {
+        self.options.prefix_length = Some(prefix_length);
+        self
+    }
+
+    /** Set the maximum number of terms a fuzzy term can expand to. */
+    pub fn max_expansions(mut self, max_expansions: u32) -> Self {
+        self.options.max_expansions = Some(max_expansions);
+        self
+    }
+
+    /** Whether or not fuzzy matches can swap two adjacent characters. */
+    pub fn fuzzy_transpositions(mut self, fuzzy_transpositions: bool) -> Self {
+        self.options.fuzzy_transpositions = Some(fuzzy_transpositions);
+        self
+    }
+
+    /**
+    Set the [minimum number of terms][docs-msm] that must match, like `2` or `75%`.
+
+    [docs-msm]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-minimum-should-match.html
+    */
+    pub fn minimum_should_match(mut self, minimum_should_match: impl ToString) -> Self {
+        self.options.minimum_should_match = Some(minimum_should_match.to_string());
+        self
+    }
+}
+
+impl From<MatchQuery> for Query {
+    fn from(query: MatchQuery) -> Self {
+        Query::new("match", Some(query.field), &query.options)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct FuzzyOptions {
+    value: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fuzziness: Option<Fuzziness>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_expansions: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transpositions: Option<bool>,
+}
+
+/**
+A [`fuzzy`][docs-fuzzy] term-level query.
+
+Call [`QueryBuilder.fuzzy`][QueryBuilder.fuzzy] to get a `FuzzyQuery`.
+
+[docs-fuzzy]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-fuzzy-query.html
+[QueryBuilder.fuzzy]: struct.QueryBuilder.html#method.fuzzy
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyQuery {
+    field: String,
+    options: FuzzyOptions,
+}
+
+impl FuzzyQuery {
+    /** Set the fuzziness allowed when matching the term. */
+    pub fn fuzziness(mut self, fuzziness: Fuzziness) -> Self {
+        self.options.fuzziness = Some(fuzziness);
+        self
+    }
+
+    /** Set the number of leading characters that must match exactly. */
+    pub fn prefix_length(mut self, prefix_length: u32) -> Self {
+        self.options.prefix_length = Some(prefix_length);
+        self
+    }
+
+    /** Set the maximum number of terms the fuzzy term can expand to. */
+    pub fn max_expansions(mut self, max_expansions: u32) -> Self {
+        self.options.max_expansions = Some(max_expansions);
+        self
+    }
+
+    /** Whether or not matches can swap two adjacent characters. */
+    pub fn transpositions(mut self, transpositions: bool) -> Self {
+        self.options.transpositions = Some(transpositions);
+        self
+    }
+}
+
+impl From<FuzzyQuery> for Query {
+    fn from(query: FuzzyQuery) -> Self {
+        Query::new("fuzzy", Some(query.field), &query.options)
+    }
+}
+
+/**
+Something to find similar documents for in a `more_like_this` query.
+
+A `Like` can be converted from a string of text or a [`LikeDocument`][LikeDocument].
+
+[LikeDocument]: struct.LikeDocument.html
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Like {
+    /** Some free text. */
+    Text(String),
+    /** A document that's already indexed. */
+    Document(LikeDocument),
+}
+
+impl Serialize for Like {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            Like::Text(ref text) => serializer.serialize_str(text),
+            Like::Document(ref doc) => doc.serialize(serializer),
+        }
+    }
+}
+
+impl From<String> for Like {
+    fn from(text: String) -> Self {
+        Like::Text(text)
+    }
+}
+
+impl<'a> From<&'a str> for Like {
+    fn from(text: &'a str) -> Self {
+        Like::Text(text.to_owned())
+    }
+}
+
+impl From<LikeDocument> for Like {
+    fn from(doc: LikeDocument) -> Self {
+        Like::Document(doc)
+    }
+}
+
+/**
+A reference to an indexed document in a `more_like_this` query.
+
+# Examples
+
+```
+# use elastic::client::requests::search::LikeDocument;
+let doc = LikeDocument::new("1").index("accounts");
+```
+*/
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LikeDocument {
+    #[serde(
+        rename = "_index",
+        serialize_with = "serialize_param",
+        skip_serializing_if = "Option::is_none"
+    )]
+    index: Option<Index<'static>>,
+    #[serde(
+        rename = "_type",
+        serialize_with = "serialize_param",
+        skip_serializing_if = "Option::is_none"
+    )]
+    ty: Option<Type<'static>>,
+    #[serde(rename = "_id", serialize_with = "serialize_id")]
+    id: Id<'static>,
+}
+
+fn serialize_param<S, T>(field: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: fmt::Display,
+{
+    serializer.collect_str(field.as_ref().expect("serialize `None` value"))
+}
+
+fn serialize_id<S>(id: &Id<'static>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(id)
+}
+
+impl LikeDocument {
+    /**
+    Reference the document with the given id.
+
+    If no index is specified then the index being searched will be used.
+    */
+    pub fn new(id: impl Into<Id<'static>>) -> Self {
+        LikeDocument {
+            index: None,
+            ty: None,
+            id: id.into(),
+        }
+    }
+
+    /** Set the index of the document. */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.index = Some(index.into());
+        self
+    }
+
+    /** Set the type of the document. */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.ty = Some(ty.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct MoreLikeThisOptions {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<String>,
+    like: Vec<Like>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    unlike: Vec<Like>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_term_freq: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_query_terms: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_doc_freq: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minimum_should_match: Option<String>,
+}
+
+/**
+A [`more_like_this`][docs-mlt] query.
+
+Call [`QueryBuilder.more_like_this`][QueryBuilder.more_like_this] to get a `MoreLikeThisQuery`.
+
+[docs-mlt]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-mlt-query.html
+[QueryBuilder.more_like_this]: struct.QueryBuilder.html#method.more_like_this
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoreLikeThisQuery {
+    options: MoreLikeThisOptions,
+}
+
+impl MoreLikeThisQuery {
+    /** Find documents similar to the given text or document. */
+    pub fn like(mut self, like: impl Into<Like>) -> Self {
+        self.options.like.push(like.into());
+        self
+    }
+
+    /** Don't find documents similar to the given text or document. */
+    pub fn unlike(mut self, unlike: impl Into<Like>) -> Self {
+        self.options.unlike.push(unlike.into());
+        self
+    }
+
+    /** Ignore terms that appear fewer than `min_term_freq` times in the input. */
+    pub fn min_term_freq(mut self, min_term_freq: u32) -> Self {
+        self.options.min_term_freq = Some(min_term_freq);
+        self
+    }
+
+    /** Set the maximum number of terms that will be selected from the input. */
+    pub fn max_query_terms(mut self, max_query_terms: u32) -> Self {
+        self.options.max_query_terms = Some(max_query_terms);
+        self
+    }
+
+    /** Ignore terms that appear in fewer than `min_doc_freq` documents. */
+    pub fn min_doc_freq(mut self, min_doc_freq: u32) -> Self {
+        self.options.min_doc_freq = Some(min_doc_freq);
+        self
+    }
+
+    /**
+    Set the [minimum number of terms][docs-msm] that must match, like `2` or `75%`.
+
+    [docs-msm]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-minimum-should-match.html
+    */
+    pub fn minimum_should_match(mut self, minimum_should_match: impl ToString) -> Self {
+        self.options.minimum_should_match = Some(minimum_should_match.to_string());
+        self
+    }
+}
+
+impl From<MoreLikeThisQuery> for Query {
+    fn from(query: MoreLikeThisQuery) -> Self {
+        Query::new("more_like_this", None, &query.options)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct KnnOptions {
+    field: String,
+    query_vector: Vec<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_candidates: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    similarity: Option<f32>,
+}
+
+/**
+A [`knn`][docs-knn] vector query.
+
+Call [`QueryBuilder.knn`][QueryBuilder.knn] to get a `KnnQuery`.
+
+[docs-knn]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-knn-query.html
+[QueryBuilder.knn]: struct.QueryBuilder.html#method.knn
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnnQuery {
+    options: KnnOptions,
+}
+
+impl KnnQuery {
+    /** Set the number of nearest neighbour candidates to consider on each shard. */
+    pub fn num_candidates(mut self, num_candidates: u32) -> Self {
+        self.options.num_candidates = Some(num_candidates);
+        self
+    }
+
+    /** Only consider documents that match the given query. */
+    pub fn filter(mut self, filter: impl Into<Query>) -> Self {
+        self.options.filter = Some(filter.into().into_value());
+        self
+    }
+
+    /** Only match documents with at least the given similarity to the query vector. */
+    pub fn similarity(mut self, similarity: f32) -> Self {
+        self.options.similarity = Some(similarity);
+        self
+    }
+}
+
+impl From<KnnQuery> for Query {
+    fn from(query: KnnQuery) -> Self {
+        Query::new("knn", None, &query.options)
+    }
+}
+
+/**
+A [`script_score`][docs-script-score] query that scores documents by cosine similarity.
+
+Call [`QueryBuilder.cosine_similarity`][QueryBuilder.cosine_similarity] to get a `CosineSimilarityQuery`.
+
+[docs-script-score]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-script-score-query.html
+[QueryBuilder.cosine_similarity]: struct.QueryBuilder.html#method.cosine_similarity
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct CosineSimilarityQuery {
+    field: String,
+    query_vector: Vec<f32>,
+    filter: Option<Query>,
+}
+
+impl CosineSimilarityQuery {
+    /** Only score documents that match the given query. */
+    pub fn filter(mut self, filter: impl Into<Query>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+}
+
+impl From<CosineSimilarityQuery> for Query {
+    fn from(query: CosineSimilarityQuery) -> Self {
+        let filter = query
+            .filter
+            .map(Query::into_value)
+            .unwrap_or_else(|| json!({ "match_all": {} }));
+
+        let field = query.field.replace('\\', "\\\\").replace('\'', "\\'");
+
+        Query(json!({
+            "script_score": {
+                "query": filter,
+                "script": {
+                    "source": format!("cosineSimilarity(params.query_vector, '{}') + 1.0", field),
+                    "params": {
+                        "query_vector": query.query_vector
+                    }
+                }
+            }
+        }))
+    }
+}
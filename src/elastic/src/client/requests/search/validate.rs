@@ -0,0 +1,236 @@
+use serde_json::{
+    Map,
+    Value,
+};
+use std::{
+    error::Error as StdError,
+    fmt,
+};
+
+/** Clauses that can only appear inside a `bool` query. */
+const BOOL_CLAUSES: &[&str] = &["must", "filter", "should", "must_not"];
+
+/** Options of a `bool` query that aren't clauses. */
+const BOOL_OPTIONS: &[&str] = &[
+    "minimum_should_match",
+    "boost",
+    "_name",
+    "adjust_pure_negative",
+];
+
+/**
+An error returned when a search request body is obviously invalid.
+
+The error contains every problem found in the body.
+Validation only catches common mistakes, so a body that passes can still be rejected by Elasticsearch.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryValidationError {
+    problems: Vec<QueryProblem>,
+}
+
+impl QueryValidationError {
+    /** The problems found in the body. */
+    pub fn problems(&self) -> &[QueryProblem] {
+        &self.problems
+    }
+}
+
+impl fmt::Display for QueryValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the search request body is invalid")?;
+
+        for (i, problem) in self.problems.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { "; " };
+            write!(f, "{}{}", separator, problem)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StdError for QueryValidationError {}
+
+/** A single problem found in a search request body. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryProblem {
+    path: String,
+    message: String,
+}
+
+impl QueryProblem {
+    /**
+    The path to the invalid value in the body, like `query.bool.must[0]`.
+
+    The path is empty if the problem is with the body itself.
+    */
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /** A description of the problem. */
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for QueryProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "the body {}", self.message)
+        } else {
+            write!(f, "`{}` {}", self.path, self.message)
+        }
+    }
+}
+
+/** Check a search request body that's a JSON object. */
+pub(super) fn validate_body(body: &Map<String, Value>) -> Result<(), QueryValidationError> {
+    let mut problems = Problems::default();
+
+    for key in &["size", "from"] {
+        if let Some(value) = body.get(*key) {
+            if value.as_u64().is_none() {
+                problems.push(*key, "must be a non-negative integer");
+            }
+        }
+    }
+
+    if body.contains_key("filter") {
+        problems.push(
+            "filter",
+            "isn't a search body option; use `post_filter` or a `filter` clause in a `bool` query",
+        );
+    }
+
+    for key in &["query", "post_filter"] {
+        if let Some(query) = body.get(*key) {
+            problems.query((*key).to_owned(), query);
+        }
+    }
+
+    problems.into_result()
+}
+
+/** The error for a search request body that isn't a JSON object. */
+pub(super) fn invalid_body() -> QueryValidationError {
+    QueryValidationError {
+        problems: vec![QueryProblem {
+            path: String::new(),
+            message: "must be a JSON object".to_owned(),
+        }],
+    }
+}
+
+#[derive(Default)]
+struct Problems(Vec<QueryProblem>);
+
+impl Problems {
+    fn push(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.0.push(QueryProblem {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    fn into_result(self) -> Result<(), QueryValidationError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(QueryValidationError { problems: self.0 })
+        }
+    }
+
+    /** Check a query and any queries nested inside it. */
+    fn query(&mut self, path: String, query: &Value) {
+        let query = match query.as_object() {
+            Some(query) => query,
+            None => return self.push(path, "must be a query object"),
+        };
+
+        if query.len() != 1 {
+            let message = if query.is_empty() {
+                "must contain a query, like `match` or `bool`".to_owned()
+            } else {
+                let types: Vec<_> = query.keys().map(|ty| format!("`{}`", ty)).collect();
+                format!(
+                    "must contain exactly one query, but contains {}",
+                    types.join(", ")
+                )
+            };
+
+            return self.push(path, message);
+        }
+
+        let (ty, inner) = query.iter().next().expect("query is empty");
+        let path = format!("{}.{}", path, ty);
+
+        match ty.as_str() {
+            clause if BOOL_CLAUSES.contains(&clause) => self.push(
+                path,
+                "is a `bool` query clause, so it must be inside a `bool` query",
+            ),
+            "bool" => self.bool_query(path, inner),
+            "constant_score" => self.nested(&path, inner, "filter"),
+            "function_score" | "nested" | "has_child" | "has_parent" => {
+                self.nested(&path, inner, "query")
+            }
+            "boosting" => {
+                self.nested(&path, inner, "positive");
+                self.nested(&path, inner, "negative");
+            }
+            "dis_max" => {
+                if let Some(queries) = inner.get("queries").and_then(Value::as_array) {
+                    for (i, query) in queries.iter().enumerate() {
+                        self.query(format!("{}.queries[{}]", path, i), query);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /** Check a query nested inside another one at `key`, if there is one. */
+    fn nested(&mut self, path: &str, inner: &Value, key: &str) {
+        if let Some(query) = inner.get(key) {
+            self.query(format!("{}.{}", path, key), query);
+        }
+    }
+
+    fn bool_query(&mut self, path: String, inner: &Value) {
+        let inner = match inner.as_object() {
+            Some(inner) => inner,
+            None => return self.push(path, "must be an object"),
+        };
+
+        let mut clauses = 0;
+        for (key, value) in inner {
+            let clause_path = format!("{}.{}", path, key);
+
+            if BOOL_CLAUSES.contains(&key.as_str()) {
+                match value {
+                    Value::Array(queries) => {
+                        clauses += queries.len();
+
+                        for (i, query) in queries.iter().enumerate() {
+                            self.query(format!("{}[{}]", clause_path, i), query);
+                        }
+                    }
+                    query => {
+                        clauses += 1;
+                        self.query(clause_path, query);
+                    }
+                }
+            } else if !BOOL_OPTIONS.contains(&key.as_str()) {
+                self.push(clause_path, "isn't a `bool` query clause or option");
+            }
+        }
+
+        if clauses == 0 {
+            self.push(
+                path,
+                "must contain at least one `must`, `filter`, `should` or `must_not` clause",
+            );
+        }
+    }
+}
@@ -0,0 +1,433 @@
+/*!
+Builders for [search template requests][docs-search-template].
+
+[docs-search-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html
+*/
+
+use futures::Future;
+use serde::de::DeserializeOwned;
+use serde_json::{
+    Map,
+    Value,
+};
+use std::marker::PhantomData;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::SearchResponse,
+        Client,
+    },
+    endpoints::SearchTemplateRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::{
+        Id,
+        Index,
+        Type,
+    },
+};
+
+/**
+A [search template request][docs-search-template] builder that can be configured before sending.
+
+Call [`Client.search_template`][Client.search_template] to get a `SearchTemplateRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-search-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.search_template]: ../../struct.Client.html#search-template-request
+*/
+pub type SearchTemplateRequestBuilder<TSender, TDocument> =
+    RequestBuilder<TSender, SearchTemplateRequestInner<TDocument>>;
+
+#[doc(hidden)]
+pub struct SearchTemplateRequestInner<TDocument> {
+    index: Option<Index<'static>>,
+    ty: Option<Type<'static>>,
+    template: SearchTemplate,
+    params: Map<String, Value>,
+    _marker: PhantomData<TDocument>,
+}
+
+/**
+A search template to execute or render.
+
+A template is either stored in Elasticsearch and referenced by its id, or given inline as a [mustache][mustache] source.
+
+# Examples
+
+Reference a template stored with an id of `my-template`:
+
+```
+# use elastic::prelude::*;
+let template = SearchTemplate::stored("my-template");
+```
+
+Use an inline template:
+
+```
+# #[macro_use] extern crate serde_json;
+# use elastic::prelude::*;
+# fn main() {
+let template = SearchTemplate::inline(json!({
+    "query": {
+        "match": {
+            "title": "{{query_string}}"
+        }
+    }
+}));
+# }
+```
+
+[mustache]: https://mustache.github.io/mustache.5.html
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchTemplate {
+    /** A template stored in Elasticsearch. */
+    Stored(Id<'static>),
+    /** An inline template source. */
+    Inline(Value),
+}
+
+impl SearchTemplate {
+    /** Reference a template stored in Elasticsearch with the given id. */
+    pub fn stored(id: impl Into<Id<'static>>) -> Self {
+        SearchTemplate::Stored(id.into())
+    }
+
+    /**
+    Use an inline template source.
+
+    The source can be a JSON object or a string.
+    */
+    pub fn inline(source: impl Into<Value>) -> Self {
+        SearchTemplate::Inline(source.into())
+    }
+
+    pub(crate) fn into_body(self, params: Map<String, Value>) -> Value {
+        let mut body = Map::new();
+
+        match self {
+            SearchTemplate::Stored(id) => {
+                body.insert("id".to_owned(), Value::String(id.to_string()))
+            }
+            SearchTemplate::Inline(source) => body.insert("source".to_owned(), source),
+        };
+
+        if !params.is_empty() {
+            body.insert("params".to_owned(), Value::Object(params));
+        }
+
+        Value::Object(body)
+    }
+}
+
+/**
+# Search template request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`SearchTemplateRequestBuilder`][SearchTemplateRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Execute a stored search template for a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.search_template::<MyType>(SearchTemplate::stored("my-template"))
+                         .index("myindex")
+                         .template_param("query_string", "a query string")
+                         .send()?;
+
+    // Iterate through the hits (of type `MyType`)
+    for hit in response.hits() {
+        println!("{:?}", hit);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SearchTemplateRequestBuilder]: requests/search_template/type.SearchTemplateRequestBuilder.html
+    [builder-methods]: requests/search_template/type.SearchTemplateRequestBuilder.html#builder-methods
+    [send-sync]: requests/search_template/type.SearchTemplateRequestBuilder.html#send-synchronously
+    [send-async]: requests/search_template/type.SearchTemplateRequestBuilder.html#send-asynchronously
+    [documents-mod]: ../types/document/index.html
+    */
+    pub fn search_template<TDocument>(
+        &self,
+        template: SearchTemplate,
+    ) -> SearchTemplateRequestBuilder<TSender, TDocument>
+    where
+        TDocument: DeserializeOwned,
+    {
+        RequestBuilder::initial(
+            self.clone(),
+            SearchTemplateRequestInner {
+                index: None,
+                ty: None,
+                template,
+                params: Map::new(),
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<TDocument> SearchTemplateRequestInner<TDocument> {
+    fn into_request(self) -> SearchTemplateRequest<'static, Value> {
+        let index = self.index.unwrap_or_else(|| "_all".into());
+        let body = self.template.into_body(self.params);
+
+        match self.ty {
+            Some(ty) => SearchTemplateRequest::for_index_ty(index, ty, body),
+            None => SearchTemplateRequest::for_index(index, body),
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `SearchTemplateRequestBuilder` before sending it.
+*/
+impl<TSender, TDocument> SearchTemplateRequestBuilder<TSender, TDocument>
+where
+    TSender: Sender,
+{
+    /**
+    Set the indices for the search request.
+
+    If no index is specified then `_all` will be used.
+    */
+    pub fn index(mut self, index: impl Into<Index<'static>>) -> Self {
+        self.inner.index = Some(index.into());
+        self
+    }
+
+    /** Set the types for the search request. */
+    pub fn ty(mut self, ty: impl Into<Type<'static>>) -> Self {
+        self.inner.ty = Some(ty.into());
+        self
+    }
+
+    /** Set a parameter to fill in the template with. */
+    pub fn template_param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.inner.params.insert(key.into(), value.into());
+        self
+    }
+
+    /**
+    Set the parameters to fill in the template with.
+
+    This replaces any previously set parameters.
+    */
+    pub fn template_params(mut self, params: Map<String, Value>) -> Self {
+        self.inner.params = params;
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl<TDocument> SearchTemplateRequestBuilder<SyncSender, TDocument>
+where
+    TDocument: DeserializeOwned,
+{
+    /**
+    Send a `SearchTemplateRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Execute an inline search template for a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = SyncClientBuilder::new().build()?;
+    let template = SearchTemplate::inline(json!({
+        "query": {
+            "match": {
+                "title": "{{query_string}}"
+            }
+        }
+    }));
+
+    let response = client.search_template::<MyType>(template)
+                         .index("myindex")
+                         .template_param("query_string", "a query string")
+                         .send()?;
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    */
+    pub fn send(self) -> Result<SearchResponse<TDocument>, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl<TDocument> SearchTemplateRequestBuilder<AsyncSender, TDocument>
+where
+    TDocument: DeserializeOwned + Send + 'static,
+{
+    /**
+    Send a `SearchTemplateRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised search response.
+
+    # Examples
+
+    Execute a stored search template for a [`DocumentType`][documents-mod] called `MyType`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Debug, Serialize, Deserialize, ElasticType)]
+    # struct MyType { }
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.search_template::<MyType>(SearchTemplate::stored("my-template"))
+                       .index("myindex")
+                       .template_param("query_string", "a query string")
+                       .send();
+
+    future.and_then(|response| {
+        for hit in response.hits() {
+            println!("{:?}", hit);
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    [documents-mod]: ../../../types/document/index.html
+    */
+    pub fn send(self) -> Pending<TDocument> {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending<TDocument> = BasePending<SearchResponse<TDocument>>;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::SearchTemplate;
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending<Value>>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search_template::<Value>(SearchTemplate::stored("my-template"))
+            .inner
+            .into_request();
+
+        assert_eq!("/_all/_search/template", req.url.as_ref());
+        assert_eq!(json!({ "id": "my-template" }), req.body);
+    }
+
+    #[test]
+    fn specify_index_ty() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search_template::<Value>(SearchTemplate::stored("my-template"))
+            .index("new-idx")
+            .ty("new-ty")
+            .inner
+            .into_request();
+
+        assert_eq!("/new-idx/new-ty/_search/template", req.url.as_ref());
+    }
+
+    #[test]
+    fn specify_inline_template_params() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .search_template::<Value>(SearchTemplate::inline(
+                "{ \"query\": { \"match\": { \"title\": \"{{query_string}}\" } } }",
+            ))
+            .template_param("query_string", "rust")
+            .template_param("size", 5)
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "source": "{ \"query\": { \"match\": { \"title\": \"{{query_string}}\" } } }",
+            "params": {
+                "query_string": "rust",
+                "size": 5
+            }
+        });
+
+        assert_eq!(expected, req.body);
+    }
+}
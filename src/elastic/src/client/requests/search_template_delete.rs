@@ -0,0 +1,203 @@
+/*!
+Builders for [delete search template requests][docs-delete-template].
+
+[docs-delete-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#_deleting_templates
+*/
+
+use futures::Future;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::CommandResponse,
+        Client,
+    },
+    endpoints::DeleteScriptRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Id,
+};
+
+/**
+A [delete search template request][docs-delete-template] builder that can be configured before sending.
+
+Call [`Client.delete_search_template`][Client.delete_search_template] to get a `DeleteSearchTemplateRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-delete-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#_deleting_templates
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.delete_search_template]: ../../struct.Client.html#delete-search-template-request
+*/
+pub type DeleteSearchTemplateRequestBuilder<TSender> =
+    RequestBuilder<TSender, DeleteSearchTemplateRequestInner>;
+
+#[doc(hidden)]
+pub struct DeleteSearchTemplateRequestInner {
+    id: Id<'static>,
+}
+
+/**
+# Delete search template request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`DeleteSearchTemplateRequestBuilder`][DeleteSearchTemplateRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Delete a search template with an id of `my-template`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.delete_search_template("my-template").send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [DeleteSearchTemplateRequestBuilder]: requests/search_template_delete/type.DeleteSearchTemplateRequestBuilder.html
+    [send-sync]: requests/search_template_delete/type.DeleteSearchTemplateRequestBuilder.html#send-synchronously
+    [send-async]: requests/search_template_delete/type.DeleteSearchTemplateRequestBuilder.html#send-asynchronously
+    */
+    pub fn delete_search_template(
+        &self,
+        id: impl Into<Id<'static>>,
+    ) -> DeleteSearchTemplateRequestBuilder<TSender> {
+        RequestBuilder::initial(
+            self.clone(),
+            DeleteSearchTemplateRequestInner { id: id.into() },
+        )
+    }
+}
+
+impl DeleteSearchTemplateRequestInner {
+    fn into_request(self) -> DeleteScriptRequest<'static> {
+        DeleteScriptRequest::for_id(self.id)
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl DeleteSearchTemplateRequestBuilder<SyncSender> {
+    /**
+    Send a `DeleteSearchTemplateRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Delete a search template with an id of `my-template`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.delete_search_template("my-template").send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<CommandResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl DeleteSearchTemplateRequestBuilder<AsyncSender> {
+    /**
+    Send a `DeleteSearchTemplateRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised command response.
+
+    # Examples
+
+    Delete a search template with an id of `my-template`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.delete_search_template("my-template").send();
+
+    future.and_then(|response| {
+        assert!(response.acknowledged());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<CommandResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .delete_search_template("my-template")
+            .inner
+            .into_request();
+
+        assert_eq!("/_scripts/my-template", req.url.as_ref());
+    }
+}
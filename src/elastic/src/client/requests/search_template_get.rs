@@ -0,0 +1,209 @@
+/*!
+Builders for [get search template requests][docs-get-template].
+
+[docs-get-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#_retrieving_templates
+*/
+
+use futures::Future;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::GetSearchTemplateResponse,
+        Client,
+    },
+    endpoints::GetScriptRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Id,
+};
+
+/**
+A [get search template request][docs-get-template] builder that can be configured before sending.
+
+Call [`Client.get_search_template`][Client.get_search_template] to get a `GetSearchTemplateRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-get-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#_retrieving_templates
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.get_search_template]: ../../struct.Client.html#get-search-template-request
+*/
+pub type GetSearchTemplateRequestBuilder<TSender> =
+    RequestBuilder<TSender, GetSearchTemplateRequestInner>;
+
+#[doc(hidden)]
+pub struct GetSearchTemplateRequestInner {
+    id: Id<'static>,
+}
+
+/**
+# Get search template request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`GetSearchTemplateRequestBuilder`][GetSearchTemplateRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Get a search template with an id of `my-template`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.get_search_template("my-template").send()?;
+
+    if let Some(source) = response.source() {
+        println!("{}", source);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [GetSearchTemplateRequestBuilder]: requests/search_template_get/type.GetSearchTemplateRequestBuilder.html
+    [send-sync]: requests/search_template_get/type.GetSearchTemplateRequestBuilder.html#send-synchronously
+    [send-async]: requests/search_template_get/type.GetSearchTemplateRequestBuilder.html#send-asynchronously
+    */
+    pub fn get_search_template(
+        &self,
+        id: impl Into<Id<'static>>,
+    ) -> GetSearchTemplateRequestBuilder<TSender> {
+        RequestBuilder::initial(
+            self.clone(),
+            GetSearchTemplateRequestInner { id: id.into() },
+        )
+    }
+}
+
+impl GetSearchTemplateRequestInner {
+    fn into_request(self) -> GetScriptRequest<'static> {
+        GetScriptRequest::for_id(self.id)
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl GetSearchTemplateRequestBuilder<SyncSender> {
+    /**
+    Send a `GetSearchTemplateRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Get a search template with an id of `my-template`:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.get_search_template("my-template").send()?;
+
+    if let Some(source) = response.source() {
+        println!("{}", source);
+    }
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<GetSearchTemplateResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl GetSearchTemplateRequestBuilder<AsyncSender> {
+    /**
+    Send a `GetSearchTemplateRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised get search template response.
+
+    # Examples
+
+    Get a search template with an id of `my-template`:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.get_search_template("my-template").send();
+
+    future.and_then(|response| {
+        if let Some(source) = response.source() {
+            println!("{}", source);
+        }
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<GetSearchTemplateResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .get_search_template("my-template")
+            .inner
+            .into_request();
+
+        assert_eq!("/_scripts/my-template", req.url.as_ref());
+    }
+}
@@ -0,0 +1,252 @@
+/*!
+Builders for [put search template requests][docs-put-template].
+
+[docs-put-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#pre-registered-templates
+*/
+
+use serde_json::Value;
+
+use futures::Future;
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::CommandResponse,
+        Client,
+    },
+    endpoints::PutScriptRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+    params::Id,
+};
+
+/**
+A [put search template request][docs-put-template] builder that can be configured before sending.
+
+Call [`Client.put_search_template`][Client.put_search_template] to get a `PutSearchTemplateRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-put-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#pre-registered-templates
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.put_search_template]: ../../struct.Client.html#put-search-template-request
+*/
+pub type PutSearchTemplateRequestBuilder<TSender> =
+    RequestBuilder<TSender, PutSearchTemplateRequestInner>;
+
+#[doc(hidden)]
+pub struct PutSearchTemplateRequestInner {
+    id: Id<'static>,
+    source: Value,
+}
+
+/**
+# Put search template request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`PutSearchTemplateRequestBuilder`][PutSearchTemplateRequestBuilder] with this `Client` that can be configured before sending.
+
+    For more details, see:
+
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Store a search template with an id of `my-template`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let source = json!({
+        "query": {
+            "match": {
+                "title": "{{query_string}}"
+            }
+        }
+    });
+
+    let response = client.put_search_template("my-template", source).send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [PutSearchTemplateRequestBuilder]: requests/search_template_put/type.PutSearchTemplateRequestBuilder.html
+    [send-sync]: requests/search_template_put/type.PutSearchTemplateRequestBuilder.html#send-synchronously
+    [send-async]: requests/search_template_put/type.PutSearchTemplateRequestBuilder.html#send-asynchronously
+    */
+    pub fn put_search_template(
+        &self,
+        id: impl Into<Id<'static>>,
+        source: impl Into<Value>,
+    ) -> PutSearchTemplateRequestBuilder<TSender> {
+        RequestBuilder::initial(
+            self.clone(),
+            PutSearchTemplateRequestInner {
+                id: id.into(),
+                source: source.into(),
+            },
+        )
+    }
+}
+
+impl PutSearchTemplateRequestInner {
+    fn into_request(self) -> PutScriptRequest<'static, Value> {
+        let body = json!({
+            "script": {
+                "lang": "mustache",
+                "source": self.source
+            }
+        });
+
+        PutScriptRequest::for_id(self.id, body)
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl PutSearchTemplateRequestBuilder<SyncSender> {
+    /**
+    Send a `PutSearchTemplateRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Store a search template with an id of `my-template`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let source = json!({
+        "query": {
+            "match": {
+                "title": "{{query_string}}"
+            }
+        }
+    });
+
+    let response = client.put_search_template("my-template", source).send()?;
+
+    assert!(response.acknowledged());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<CommandResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl PutSearchTemplateRequestBuilder<AsyncSender> {
+    /**
+    Send a `PutSearchTemplateRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised command response.
+
+    # Examples
+
+    Store a search template with an id of `my-template`:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let source = json!({
+        "query": {
+            "match": {
+                "title": "{{query_string}}"
+            }
+        }
+    });
+
+    let future = client.put_search_template("my-template", source).send();
+
+    future.and_then(|response| {
+        assert!(response.acknowledged());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<CommandResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn default_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .put_search_template("my-template", "{ \"query\": { \"match_all\": {} } }")
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "script": {
+                "lang": "mustache",
+                "source": "{ \"query\": { \"match_all\": {} } }"
+            }
+        });
+
+        assert_eq!("/_scripts/my-template", req.url.as_ref());
+        assert_eq!(expected, req.body);
+    }
+}
@@ -0,0 +1,290 @@
+/*!
+Builders for [render search template requests][docs-render-template].
+
+[docs-render-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#_validating_templates
+*/
+
+use futures::Future;
+use serde_json::{
+    Map,
+    Value,
+};
+
+use crate::{
+    client::{
+        requests::{
+            raw::RawRequestInner,
+            search_template::SearchTemplate,
+            Pending as BasePending,
+            RequestBuilder,
+        },
+        responses::RenderSearchTemplateResponse,
+        Client,
+    },
+    endpoints::RenderSearchTemplateRequest,
+    error::Error,
+    http::sender::{
+        AsyncSender,
+        Sender,
+        SyncSender,
+    },
+};
+
+/**
+A [render search template request][docs-render-template] builder that can be configured before sending.
+
+Call [`Client.render_search_template`][Client.render_search_template] to get a `RenderSearchTemplateRequestBuilder`.
+The `send` method will either send the request [synchronously][send-sync] or [asynchronously][send-async], depending on the `Client` it was created from.
+
+[docs-render-template]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#_validating_templates
+[send-sync]: #send-synchronously
+[send-async]: #send-asynchronously
+[Client.render_search_template]: ../../struct.Client.html#render-search-template-request
+*/
+pub type RenderSearchTemplateRequestBuilder<TSender> =
+    RequestBuilder<TSender, RenderSearchTemplateRequestInner>;
+
+#[doc(hidden)]
+pub struct RenderSearchTemplateRequestInner {
+    template: SearchTemplate,
+    params: Map<String, Value>,
+}
+
+/**
+# Render search template request
+*/
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Create a [`RenderSearchTemplateRequestBuilder`][RenderSearchTemplateRequestBuilder] with this `Client` that can be configured before sending.
+
+    Rendering a template returns the search body it produces without executing it.
+
+    For more details, see:
+
+    - [builder methods][builder-methods]
+    - [send synchronously][send-sync]
+    - [send asynchronously][send-async]
+
+    # Examples
+
+    Preview the search body produced by a stored template:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.render_search_template(SearchTemplate::stored("my-template"))
+                         .template_param("query_string", "a query string")
+                         .send()?;
+
+    println!("{}", response.template_output());
+    # Ok(())
+    # }
+    ```
+
+    [RenderSearchTemplateRequestBuilder]: requests/search_template_render/type.RenderSearchTemplateRequestBuilder.html
+    [builder-methods]: requests/search_template_render/type.RenderSearchTemplateRequestBuilder.html#builder-methods
+    [send-sync]: requests/search_template_render/type.RenderSearchTemplateRequestBuilder.html#send-synchronously
+    [send-async]: requests/search_template_render/type.RenderSearchTemplateRequestBuilder.html#send-asynchronously
+    */
+    pub fn render_search_template(
+        &self,
+        template: SearchTemplate,
+    ) -> RenderSearchTemplateRequestBuilder<TSender> {
+        RequestBuilder::initial(
+            self.clone(),
+            RenderSearchTemplateRequestInner {
+                template,
+                params: Map::new(),
+            },
+        )
+    }
+}
+
+impl RenderSearchTemplateRequestInner {
+    fn into_request(self) -> RenderSearchTemplateRequest<'static, Value> {
+        match self.template {
+            SearchTemplate::Stored(id) => {
+                let mut body = Map::new();
+                body.insert("params".to_owned(), Value::Object(self.params));
+
+                RenderSearchTemplateRequest::for_id(id, Value::Object(body))
+            }
+            template => RenderSearchTemplateRequest::new(template.into_body(self.params)),
+        }
+    }
+}
+
+/**
+# Builder methods
+
+Configure a `RenderSearchTemplateRequestBuilder` before sending it.
+*/
+impl<TSender> RenderSearchTemplateRequestBuilder<TSender>
+where
+    TSender: Sender,
+{
+    /** Set a parameter to fill in the template with. */
+    pub fn template_param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.inner.params.insert(key.into(), value.into());
+        self
+    }
+
+    /**
+    Set the parameters to fill in the template with.
+
+    This replaces any previously set parameters.
+    */
+    pub fn template_params(mut self, params: Map<String, Value>) -> Self {
+        self.inner.params = params;
+        self
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl RenderSearchTemplateRequestBuilder<SyncSender> {
+    /**
+    Send a `RenderSearchTemplateRequestBuilder` synchronously using a [`SyncClient`][SyncClient].
+
+    This will block the current thread until a response arrives and is deserialised.
+
+    # Examples
+
+    Preview the search body produced by an inline template:
+
+    ```no_run
+    # #[macro_use] extern crate serde_json;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let template = SearchTemplate::inline(json!({
+        "query": {
+            "match": {
+                "title": "{{query_string}}"
+            }
+        }
+    }));
+
+    let response = client.render_search_template(template)
+                         .template_param("query_string", "a query string")
+                         .send()?;
+
+    println!("{}", response.template_output());
+    # Ok(())
+    # }
+    ```
+
+    [SyncClient]: ../../type.SyncClient.html
+    */
+    pub fn send(self) -> Result<RenderSearchTemplateResponse, Error> {
+        let req = self.inner.into_request();
+
+        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+            .send()?
+            .into_response()
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl RenderSearchTemplateRequestBuilder<AsyncSender> {
+    /**
+    Send a `RenderSearchTemplateRequestBuilder` asynchronously using an [`AsyncClient`][AsyncClient].
+
+    This will return a future that will resolve to the deserialised render response.
+
+    # Examples
+
+    Preview the search body produced by a stored template:
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let future = client.render_search_template(SearchTemplate::stored("my-template"))
+                       .template_param("query_string", "a query string")
+                       .send();
+
+    future.and_then(|response| {
+        println!("{}", response.template_output());
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+
+    [AsyncClient]: ../../type.AsyncClient.html
+    */
+    pub fn send(self) -> Pending {
+        let req = self.inner.into_request();
+
+        let res_future =
+            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+                .send()
+                .and_then(|res| res.into_response());
+
+        Pending::new(res_future)
+    }
+}
+
+/** A future returned by calling `send`. */
+pub type Pending = BasePending<RenderSearchTemplateResponse>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        client::requests::search_template::SearchTemplate,
+        prelude::*,
+        tests::*,
+    };
+
+    #[test]
+    fn is_send() {
+        assert_send::<super::Pending>();
+    }
+
+    #[test]
+    fn stored_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .render_search_template(SearchTemplate::stored("my-template"))
+            .template_param("query_string", "rust")
+            .inner
+            .into_request();
+
+        assert_eq!("/_render/template/my-template", req.url.as_ref());
+        assert_eq!(json!({ "params": { "query_string": "rust" } }), req.body);
+    }
+
+    #[test]
+    fn inline_request() {
+        let client = SyncClientBuilder::new().build().unwrap();
+
+        let req = client
+            .render_search_template(SearchTemplate::inline(json!({
+                "query": { "match": { "title": "{{query_string}}" } }
+            })))
+            .template_param("query_string", "rust")
+            .inner
+            .into_request();
+
+        let expected = json!({
+            "source": {
+                "query": { "match": { "title": "{{query_string}}" } }
+            },
+            "params": { "query_string": "rust" }
+        });
+
+        assert_eq!("/_render/template", req.url.as_ref());
+        assert_eq!(expected, req.body);
+    }
+}
@@ -14,6 +14,10 @@ use crate::{
             RequestBuilder,
         },
         responses::SqlQueryResponse,
+        version::{
+            ApiVersions,
+            Version,
+        },
         Client,
     },
     endpoints::SqlQueryRequest,
@@ -50,6 +54,13 @@ pub struct SqlRequestInner<TBody> {
     body: TBody,
 }
 
+/** The `_xpack/sql` endpoint is only available in these versions. */
+const SQL_VERSIONS: ApiVersions = ApiVersions {
+    feature: "the sql API",
+    since: Version::new(6, 3, 0),
+    removed: Some(Version::new(8, 0, 0)),
+};
+
 /**
 # Sql request
 */
@@ -60,6 +71,9 @@ where
     /**
     Creates a [`SqlRequestBuilder`][SqlRequestBuilder] with this `Client` that can be configured before sending.
 
+    The SQL API is available from Elasticsearch `6.3.0`.
+    Sending the request will fail without reaching the cluster if its version doesn't support the SQL API.
+
     For more details, see:
 
     - [builder methods][builder-methods]
@@ -221,9 +235,12 @@ where
     [docs-querystring]: https://www.elastic.co/guide/en/elasticsearch/reference/current/sql-commands.html
      */
     pub fn send(self) -> Result<SqlQueryResponse, Error> {
+        let (version, params_builder) = self.client.detect_version(self.params_builder)?;
+        SQL_VERSIONS.check(version)?;
+
         let req = self.inner.into_request();
 
-        RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
+        RequestBuilder::new(self.client, params_builder, RawRequestInner::new(req))
             .send()?
             .into_response()
     }
@@ -273,11 +290,17 @@ where
 
     pub fn send(self) -> Pending {
         let req = self.inner.into_request();
-
-        let res_future =
-            RequestBuilder::new(self.client, self.params_builder, RawRequestInner::new(req))
-                .send()
-                .and_then(|res| res.into_response());
+        let (client, params_builder) = (self.client, self.params_builder);
+
+        let res_future = client
+            .detect_version(params_builder)
+            .and_then(|(version, params_builder)| {
+                SQL_VERSIONS.check(version).map(|_| params_builder)
+            })
+            .and_then(move |params_builder| {
+                RequestBuilder::new(client, params_builder, RawRequestInner::new(req)).send()
+            })
+            .and_then(|res| res.into_response());
 
         Pending::new(res_future)
     }
@@ -288,11 +311,98 @@ pub type Pending = BasePending<SqlQueryResponse>;
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        sync::{
+            Arc,
+            Mutex,
+        },
+    };
+
+    use futures::Future;
+    use serde_json::Value;
+
     use crate::{
+        client::Version,
+        error::Error,
+        http::{
+            header::{
+                HeaderValue,
+                AUTHORIZATION,
+            },
+            sender::recording::test_recording,
+        },
         prelude::*,
         tests::*,
     };
 
+    const INFO_RESPONSE: &str = r#"{"name":"node","cluster_name":"elasticsearch","version":{"number":"7.4.0","build_hash":"a1b2c3","build_date":"2019-09-27T08:36:48.569419Z","build_snapshot":false,"lucene_version":"8.2.0"},"tagline":"You Know, for Search"}"#;
+
+    fn interaction(
+        method: &str,
+        path: &str,
+        query: Option<&str>,
+        status: u16,
+        body: &str,
+    ) -> Value {
+        let request_body = if method == "POST" {
+            Some(json!({ "query": "SELECT 1" }).to_string())
+        } else {
+            None
+        };
+
+        json!({
+            "request": { "method": method, "path": path, "query": query, "body": request_body },
+            "response": { "status": status, "body": body }
+        })
+    }
+
+    fn sql_interaction() -> Value {
+        interaction(
+            "POST",
+            "/_xpack/sql",
+            Some("format=json"),
+            200,
+            r#"{"columns":[],"rows":[]}"#,
+        )
+    }
+
+    // Keep the path and authorization header of each request that's sent
+    type Sent = Arc<Mutex<Vec<(String, bool)>>>;
+
+    fn sync_client(path: PathBuf, sent: &Sent) -> SyncClient {
+        let sent = sent.clone();
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .pre_send_raw(move |req| {
+                sent.lock().unwrap().push((
+                    req.url
+                        .as_str()
+                        .trim_start_matches("http://localhost:1")
+                        .to_owned(),
+                    req.headers.contains_key(AUTHORIZATION),
+                ));
+                Ok(())
+            })
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        client
+    }
+
+    fn sql_query(client: &SyncClient) -> Result<SqlQueryResponse, Error> {
+        client
+            .sql_query("SELECT 1")
+            .params_fluent(|p| {
+                p.url_param("format", "json")
+                    .header(AUTHORIZATION, HeaderValue::from_static("let me in"))
+            })
+            .send()
+    }
+
     #[test]
     fn is_send() {
         assert_send::<super::Pending>();
@@ -315,4 +425,88 @@ mod tests {
 
         assert_eq!("{}", req.body);
     }
+
+    #[test]
+    fn unsupported_version_fails_without_sending() {
+        // There's no node to send to, so this only passes if the request fails fast
+        let client = SyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .build()
+            .unwrap();
+        client.version.set(Version::new(6, 2, 4));
+
+        let err = client.sql_query("SELECT 1").send().unwrap_err();
+
+        assert!(format!("{:?}", err).contains("the sql API requires Elasticsearch 6.3.0 or later"));
+    }
+
+    #[test]
+    fn version_is_detected_with_request_params() {
+        let sent = Sent::default();
+        let client = sync_client(
+            test_recording(vec![
+                interaction("GET", "/", None, 200, INFO_RESPONSE),
+                sql_interaction(),
+                sql_interaction(),
+            ]),
+            &sent,
+        );
+
+        sql_query(&client).unwrap();
+        sql_query(&client).unwrap();
+
+        assert_eq!(Some(Version::new(7, 4, 0)), client.cluster_version());
+
+        // The version is only detected once, without the url params of the sql request
+        assert_eq!(
+            vec![
+                ("/".to_owned(), true),
+                ("/_xpack/sql?format=json".to_owned(), true),
+                ("/_xpack/sql?format=json".to_owned(), true),
+            ],
+            *sent.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn failed_version_detection_is_cached() {
+        let sent = Sent::default();
+        let client = sync_client(
+            test_recording(vec![
+                interaction("GET", "/", None, 500, r#"{"error":"failed","status":500}"#),
+                sql_interaction(),
+                sql_interaction(),
+            ]),
+            &sent,
+        );
+
+        sql_query(&client).unwrap();
+        sql_query(&client).unwrap();
+
+        assert_eq!(None, client.cluster_version());
+        assert_eq!(3, sent.lock().unwrap().len());
+    }
+
+    #[test]
+    fn async_version_is_detected_with_request_params() {
+        let path = test_recording(vec![
+            interaction("GET", "/", None, 200, INFO_RESPONSE),
+            sql_interaction(),
+        ]);
+        let client = AsyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        client
+            .sql_query("SELECT 1")
+            .params_fluent(|p| p.url_param("format", "json"))
+            .send()
+            .wait()
+            .unwrap();
+
+        assert_eq!(Some(Version::new(7, 4, 0)), client.cluster_version());
+    }
 }
@@ -37,6 +37,7 @@ Response for a [bulk request](https://www.elastic.co/guide/en/elasticsearch/refe
 Individual bulk items are a `Result` of [`OkItem`](struct.OkItem.html) or [`ErrorItem`](struct.ErrorItem.html) and can be iterated over.
 Any individual bulk item may be an `Err(ErrorItem)`, so it's important to check them.
 The `is_ok` and `is_err` methods on `BulkResponse` make it easier to assert there are no errors.
+The [`reason`](struct.ErrorItem.html#method.reason) of an `ErrorItem` classifies why it failed.
 
 # Examples
 
@@ -85,6 +86,23 @@ for item in item_iter {
 }
 ```
 
+Split failed items into ones that are worth retrying and ones that will never succeed:
+
+```no_run
+# use elastic::prelude::*;
+# fn do_request() -> BulkResponse { unimplemented!() }
+let response: BulkResponse = do_request();
+
+let (retryable, permanent): (Vec<_>, Vec<_>) = response
+    .into_iter()
+    .filter_map(Result::err)
+    .partition(|item| item.is_retryable());
+
+for item in permanent {
+    println!("operation {} failed: {}", item.position(), item.reason());
+}
+```
+
 # Optimising bulk responses
 
 If you're only interested in bulk items that failed, see [`BulkErrorsResponse`](struct.BulkErrorsResponse.html).
@@ -415,9 +433,9 @@ pub struct OkItem<
     index: TIndex,
     ty: TType,
     id: TId,
-    version: Option<u32>,
-    sequence_number: Option<u32>,
-    primary_term: Option<u32>,
+    version: Option<i64>,
+    sequence_number: Option<i64>,
+    primary_term: Option<i64>,
     shards: Option<Shards>,
     result: Option<DocumentResult>,
     get: Option<Value>,
@@ -430,7 +448,7 @@ impl<TIndex, TType, TId> OkItem<TIndex, TType, TId> {
     }
 
     /** The document version after this item. */
-    pub fn version(&self) -> Option<u32> {
+    pub fn version(&self) -> Option<i64> {
         self.version
     }
 
@@ -439,7 +457,7 @@ impl<TIndex, TType, TId> OkItem<TIndex, TType, TId> {
      *
      * [sequence number]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
      */
-    pub fn sequence_number(&self) -> Option<u32> {
+    pub fn sequence_number(&self) -> Option<i64> {
         self.sequence_number
     }
 
@@ -448,7 +466,7 @@ impl<TIndex, TType, TId> OkItem<TIndex, TType, TId> {
      *
      * [primary term]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
      */
-    pub fn primary_term(&self) -> Option<u32> {
+    pub fn primary_term(&self) -> Option<i64> {
         self.primary_term
     }
 
@@ -567,6 +585,9 @@ pub struct ErrorItem<
     index: TIndex,
     ty: TType,
     id: TId,
+    status: u16,
+    position: usize,
+    reason: BulkErrorReason,
     err: BulkError,
 }
 
@@ -591,6 +612,50 @@ impl<TIndex, TType, TId> ErrorItem<TIndex, TType, TId> {
         &self.id
     }
 
+    /** The HTTP status code for this item. */
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /**
+    The position of the operation for this item in the bulk request.
+
+    Bulk items are returned in the same order as the operations that were sent, so the position can be used to find the operation that failed.
+    */
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /**
+    Get the operation for this item from the operations that were sent in the bulk request.
+
+    Elasticsearch doesn't echo the body of a failed operation back, but items are returned in the same order as the operations that were sent.
+    So the operation that failed can be found from its [`position`](#method.position), like when retrying failed operations.
+    Returns `None` if there aren't enough operations.
+    */
+    pub fn operation<'a, TOperation>(&self, sent: &'a [TOperation]) -> Option<&'a TOperation> {
+        sent.get(self.position)
+    }
+
+    pub(crate) fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /** The reason this item failed. */
+    pub fn reason(&self) -> &BulkErrorReason {
+        &self.reason
+    }
+
+    /**
+    Whether or not sending the operation for this item again could succeed.
+
+    Items that were rejected because a node was overloaded, or that failed with a server error, are retryable.
+    Other failures, like mapping errors or version conflicts, will happen again if the operation is retried.
+    */
+    pub fn is_retryable(&self) -> bool {
+        self.reason.is_retryable() || self.status == 429 || self.status >= 500
+    }
+
     /** Raw error JSON. */
     pub fn err(&self) -> &BulkError {
         &self.err
@@ -627,6 +692,100 @@ where
     }
 }
 
+/** The reason a bulk item failed. */
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkErrorReason {
+    /** The document couldn't be parsed using the mapping for the index. */
+    MapperParsing {
+        /** The reason given by Elasticsearch. */
+        reason: String,
+    },
+    /** The document version doesn't match the expected version, or the document already exists. */
+    VersionConflict {
+        /** The reason given by Elasticsearch. */
+        reason: String,
+    },
+    /** The operation was rejected because the node was too busy to handle it. */
+    Rejected {
+        /** The reason given by Elasticsearch. */
+        reason: String,
+    },
+    /** The index for the operation doesn't exist. */
+    IndexNotFound {
+        /** The name of the missing index. */
+        index: String,
+    },
+    /** Any other error. */
+    Other {
+        /** The type of error, if Elasticsearch gave one. */
+        ty: Option<String>,
+        /** The reason given by Elasticsearch, if any. */
+        reason: Option<String>,
+    },
+}
+
+impl BulkErrorReason {
+    fn from_err(err: &BulkError) -> Self {
+        let get = |key| err.get(key).and_then(Value::as_str).map(str::to_owned);
+        let reason = get("reason");
+
+        match (get("type").as_deref(), reason) {
+            (Some("mapper_parsing_exception"), Some(reason)) => {
+                BulkErrorReason::MapperParsing { reason }
+            }
+            (Some("version_conflict_engine_exception"), Some(reason)) => {
+                BulkErrorReason::VersionConflict { reason }
+            }
+            (Some("es_rejected_execution_exception"), Some(reason)) => {
+                BulkErrorReason::Rejected { reason }
+            }
+            (Some("index_not_found_exception"), reason) => match get("index") {
+                Some(index) => BulkErrorReason::IndexNotFound { index },
+                None => BulkErrorReason::Other {
+                    ty: Some("index_not_found_exception".to_owned()),
+                    reason,
+                },
+            },
+            (ty, reason) => BulkErrorReason::Other {
+                ty: ty.map(str::to_owned),
+                reason,
+            },
+        }
+    }
+
+    /**
+    Whether or not an operation that failed for this reason could succeed if it's sent again.
+
+    Only `Rejected` errors are considered retryable.
+    */
+    pub fn is_retryable(&self) -> bool {
+        matches!(*self, BulkErrorReason::Rejected { .. })
+    }
+}
+
+impl fmt::Display for BulkErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BulkErrorReason::MapperParsing { ref reason } => {
+                write!(f, "mapper parsing failed: '{}'", reason)
+            }
+            BulkErrorReason::VersionConflict { ref reason } => {
+                write!(f, "version conflict: '{}'", reason)
+            }
+            BulkErrorReason::Rejected { ref reason } => write!(f, "rejected: '{}'", reason),
+            BulkErrorReason::IndexNotFound { ref index } => {
+                write!(f, "index not found: '{}'", index)
+            }
+            BulkErrorReason::Other { ref ty, ref reason } => write!(
+                f,
+                "{}: '{}'",
+                ty.as_ref().map(String::as_str).unwrap_or("unknown error"),
+                reason.as_ref().map(String::as_str).unwrap_or_default()
+            ),
+        }
+    }
+}
+
 /** The bulk action being performed. */
 #[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
@@ -664,11 +823,11 @@ struct ItemDeInner<TIndex, TType, TId> {
     #[serde(rename = "_id")]
     id: TId,
     #[serde(rename = "_version")]
-    version: Option<u32>,
+    version: Option<i64>,
     #[serde(rename = "_seq_no")]
-    sequence_number: Option<u32>,
+    sequence_number: Option<i64>,
     #[serde(rename = "_primary_term")]
-    primary_term: Option<u32>,
+    primary_term: Option<i64>,
     get: Option<Value>,
     #[serde(rename = "_shards")]
     shards: Option<Shards>,
@@ -683,22 +842,25 @@ where
     TType: Deserialize<'de>,
     TId: Deserialize<'de>,
 {
-    fn into_err(self) -> Option<ErrorItem<TIndex, TType, TId>> {
+    fn into_err(self, position: usize) -> Option<ErrorItem<TIndex, TType, TId>> {
         match self.inner.error {
             Some(err) => Some(ErrorItem {
                 action: self.action,
                 index: self.inner.index,
                 ty: self.inner.ty,
                 id: self.inner.id,
+                status: self.inner.status,
+                position,
+                reason: BulkErrorReason::from_err(&err),
                 err,
             }),
             None => None,
         }
     }
 
-    fn into_result(self) -> ItemResult<TIndex, TType, TId> {
+    fn into_result(self, position: usize) -> ItemResult<TIndex, TType, TId> {
         if self.inner.error.is_some() {
-            Err(self.into_err().expect("expected an error"))
+            Err(self.into_err(position).expect("expected an error"))
         } else {
             Ok(OkItem {
                 action: self.action,
@@ -805,8 +967,10 @@ where
         {
             let mut values = Vec::with_capacity(cmp::min(visitor.size_hint().unwrap_or(0), 4096));
 
+            let mut position = 0;
             while let Some(value) = visitor.next_element::<ItemDe<_, _, _>>()? {
-                values.push(value.into_result());
+                values.push(value.into_result(position));
+                position += 1;
             }
 
             Ok(values)
@@ -861,10 +1025,12 @@ where
         {
             let mut values = Vec::with_capacity(cmp::min(visitor.size_hint().unwrap_or(0), 4096));
 
+            let mut position = 0;
             while let Some(value) = visitor.next_element::<ItemDe<_, _, _>>()? {
-                if let Some(value) = value.into_err() {
+                if let Some(value) = value.into_err(position) {
                     values.push(value);
                 }
+                position += 1;
             }
 
             Ok(values)
@@ -0,0 +1,131 @@
+/*!
+Response types for a [cluster health request](https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-health.html).
+*/
+
+use crate::http::{
+    receiver::{
+        HttpResponseHead,
+        IsOk,
+        MaybeOkResponse,
+        ParseError,
+        ResponseBody,
+        Unbuffered,
+    },
+    StatusCode,
+};
+
+/** Response for a [cluster health request](https://www.elastic.co/guide/en/elasticsearch/reference/current/cluster-health.html). */
+#[derive(Deserialize, Debug)]
+pub struct ClusterHealthResponse {
+    cluster_name: String,
+    status: ClusterHealthStatus,
+    timed_out: bool,
+    number_of_nodes: u32,
+    number_of_data_nodes: u32,
+    active_primary_shards: u32,
+    active_shards: u32,
+    relocating_shards: u32,
+    initializing_shards: u32,
+    unassigned_shards: u32,
+}
+
+/**
+The health of a cluster.
+
+Statuses are ordered from least to most healthy, so `Red < Yellow < Green`.
+*/
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ClusterHealthStatus {
+    /** Some primary shards aren't allocated. */
+    Red,
+    /** All primary shards are allocated, but some replicas aren't. */
+    Yellow,
+    /** All shards are allocated. */
+    Green,
+}
+
+impl ClusterHealthStatus {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match *self {
+            ClusterHealthStatus::Red => "red",
+            ClusterHealthStatus::Yellow => "yellow",
+            ClusterHealthStatus::Green => "green",
+        }
+    }
+}
+
+impl ClusterHealthResponse {
+    /** The name of the cluster. */
+    pub fn cluster_name(&self) -> &str {
+        &self.cluster_name
+    }
+
+    /** The health of the cluster. */
+    pub fn status(&self) -> ClusterHealthStatus {
+        self.status
+    }
+
+    /** Whether or not the request timed out before the cluster reached the status waited for. */
+    pub fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /** The number of nodes in the cluster. */
+    pub fn number_of_nodes(&self) -> u32 {
+        self.number_of_nodes
+    }
+
+    /** The number of data nodes in the cluster. */
+    pub fn number_of_data_nodes(&self) -> u32 {
+        self.number_of_data_nodes
+    }
+
+    /** The number of active primary shards. */
+    pub fn active_primary_shards(&self) -> u32 {
+        self.active_primary_shards
+    }
+
+    /** The number of active primary and replica shards. */
+    pub fn active_shards(&self) -> u32 {
+        self.active_shards
+    }
+
+    /** The number of shards that are relocating. */
+    pub fn relocating_shards(&self) -> u32 {
+        self.relocating_shards
+    }
+
+    /** The number of shards that are initializing. */
+    pub fn initializing_shards(&self) -> u32 {
+        self.initializing_shards
+    }
+
+    /** The number of shards that aren't allocated. */
+    pub fn unassigned_shards(&self) -> u32 {
+        self.unassigned_shards
+    }
+}
+
+impl IsOk for ClusterHealthResponse {
+    fn is_ok<B: ResponseBody>(
+        head: HttpResponseHead,
+        body: Unbuffered<B>,
+    ) -> Result<MaybeOkResponse<B>, ParseError> {
+        match head.status() {
+            status if status.is_success() => Ok(MaybeOkResponse::ok(body)),
+            // Waiting for a status that isn't reached in time is a 408 with the current health
+            StatusCode::REQUEST_TIMEOUT => {
+                let (maybe_err, body) = body.body()?;
+
+                let is_ok = maybe_err
+                    .as_object()
+                    .and_then(|maybe_err| maybe_err.get("error"))
+                    .is_none();
+
+                Ok(MaybeOkResponse::new(is_ok, body))
+            }
+            _ => Ok(MaybeOkResponse::err(body)),
+        }
+    }
+}
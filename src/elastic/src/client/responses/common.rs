@@ -6,11 +6,15 @@ Types that are common to many responses.
 pub(crate) type DefaultAllocatedField = String;
 
 /** Returned hits metadata. */
-#[derive(Deserialize, Debug, Clone, Copy)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct Shards {
     total: u32,
     successful: u32,
+    #[serde(default)]
+    skipped: u32,
     failed: u32,
+    #[serde(default)]
+    failures: Vec<ShardFailure>,
 }
 
 impl Shards {
@@ -24,22 +28,156 @@ impl Shards {
         self.successful
     }
 
+    /**
+    The total number of shards that skipped the request.
+
+    Shards can be skipped when they can't match any documents, like when a range query falls outside the time range of an index.
+    Skipped shards are also counted as successful.
+    */
+    pub fn skipped(&self) -> u32 {
+        self.skipped
+    }
+
     /** The total number of shards that failed to process the request. */
     pub fn failed(&self) -> u32 {
         self.failed
     }
+
+    /** The reasons shards failed to process the request. */
+    pub fn failures(&self) -> &[ShardFailure] {
+        &self.failures
+    }
+}
+
+/** A shard that failed to process a request. */
+#[derive(Deserialize, Debug, Clone)]
+pub struct ShardFailure {
+    shard: Option<u32>,
+    index: Option<String>,
+    node: Option<String>,
+    reason: Option<ShardFailureReason>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ShardFailureReason {
+    #[serde(rename = "type")]
+    ty: String,
+    reason: Option<String>,
+}
+
+impl ShardFailure {
+    /** The number of the shard that failed. */
+    pub fn shard(&self) -> Option<u32> {
+        self.shard
+    }
+
+    /** The index the failed shard belongs to. */
+    pub fn index(&self) -> Option<&str> {
+        self.index.as_deref()
+    }
+
+    /** The id of the node the failed shard is allocated to. */
+    pub fn node(&self) -> Option<&str> {
+        self.node.as_deref()
+    }
+
+    /** The type of error that caused the failure, like `query_shard_exception`. */
+    pub fn reason_type(&self) -> Option<&str> {
+        self.reason.as_ref().map(|reason| &*reason.ty)
+    }
+
+    /** A description of why the shard failed. */
+    pub fn reason(&self) -> Option<&str> {
+        self.reason
+            .as_ref()
+            .and_then(|reason| reason.reason.as_deref())
+    }
 }
 
+/** The result of a write to a single document. */
 #[derive(Clone, Copy, Deserialize, Debug, PartialEq, Eq)]
-pub(crate) enum DocumentResult {
+pub enum DocumentResult {
+    /** The document was deleted. */
     #[serde(rename = "deleted")]
     Deleted,
+    /** An existing document was updated. */
     #[serde(rename = "updated")]
     Updated,
+    /** The document didn't exist. */
     #[serde(rename = "not_found")]
     NotFound,
+    /** The document was unchanged. */
     #[serde(rename = "noop")]
     NoOp,
+    /** A new document was created. */
     #[serde(rename = "created")]
     Created,
 }
+
+/**
+The metadata for a document returned by a get or write request.
+
+The sequence number and primary term can be used for [optimistic concurrency control][docs-occ],
+so a document can be read, modified and written back only if it hasn't changed in the meantime.
+
+[docs-occ]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentMetadata {
+    index: String,
+    ty: String,
+    id: String,
+    version: Option<i64>,
+    sequence_number: Option<i64>,
+    primary_term: Option<i64>,
+}
+
+impl DocumentMetadata {
+    pub(crate) fn new(
+        index: &str,
+        ty: &str,
+        id: &str,
+        version: Option<i64>,
+        sequence_number: Option<i64>,
+        primary_term: Option<i64>,
+    ) -> Self {
+        DocumentMetadata {
+            index: index.to_owned(),
+            ty: ty.to_owned(),
+            id: id.to_owned(),
+            version,
+            sequence_number,
+            primary_term,
+        }
+    }
+
+    /** The index for the document. */
+    pub fn index(&self) -> &str {
+        &self.index
+    }
+
+    /** The type of the document. */
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    /** The id of the document. */
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /** The version of the document. */
+    pub fn version(&self) -> Option<i64> {
+        self.version
+    }
+
+    /** The sequence number of the document. */
+    pub fn sequence_number(&self) -> Option<i64> {
+        self.sequence_number
+    }
+
+    /** The primary term of the document. */
+    pub fn primary_term(&self) -> Option<i64> {
+        self.primary_term
+    }
+}
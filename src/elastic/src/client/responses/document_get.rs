@@ -4,6 +4,12 @@ Response types for a [get document request](https://www.elastic.co/guide/en/elas
 
 use crate::http::StatusCode;
 use serde::de::DeserializeOwned;
+use serde_json::{
+    Map,
+    Value,
+};
+
+use super::common::DocumentMetadata;
 
 use crate::{
     http::receiver::{
@@ -31,16 +37,18 @@ pub struct GetResponse<T> {
     #[serde(rename = "_id")]
     id: String,
     #[serde(rename = "_version")]
-    version: Option<u32>,
+    version: Option<i64>,
     #[serde(rename = "_seq_no")]
-    sequence_number: Option<u32>,
+    sequence_number: Option<i64>,
     #[serde(rename = "_primary_term")]
-    primary_term: Option<u32>,
+    primary_term: Option<i64>,
     found: bool,
     #[serde(rename = "_source")]
     source: Option<T>,
     #[serde(rename = "_routing")]
     routing: Option<String>,
+    #[serde(default)]
+    fields: Map<String, Value>,
 }
 
 impl<T> GetResponse<T> {
@@ -54,6 +62,76 @@ impl<T> GetResponse<T> {
         self.source
     }
 
+    /**
+    Convert the response into the source document along with its metadata.
+
+    The metadata includes the sequence number and primary term of the document,
+    which can be used to write the document back only if it hasn't changed since it was read.
+
+    # Examples
+
+    Increment a counter on a document without losing concurrent updates:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # #[derive(Serialize, Deserialize, ElasticType)]
+    # struct MyType { id: String, likes: i64 }
+    # let client = SyncClientBuilder::new().build()?;
+    let response = client.document::<MyType>().get("1").send()?;
+
+    if let Some((mut doc, metadata)) = response.into_document_with_metadata() {
+        let seq_no = metadata.sequence_number().ok_or("the document has no sequence number")?;
+        let primary_term = metadata.primary_term().ok_or("the document has no primary term")?;
+
+        doc.likes += 1;
+
+        client.document()
+              .index(doc)
+              .params_fluent(move |p| p
+                  .url_param("if_seq_no", seq_no)
+                  .url_param("if_primary_term", primary_term))
+              .send()?;
+    }
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn into_document_with_metadata(self) -> Option<(T, DocumentMetadata)> {
+        let metadata = self.metadata();
+
+        self.source.map(|doc| (doc, metadata))
+    }
+
+    /** Get the metadata for the document. */
+    pub fn metadata(&self) -> DocumentMetadata {
+        DocumentMetadata::new(
+            &self.index,
+            &self.ty,
+            &self.id,
+            self.version,
+            self.sequence_number,
+            self.primary_term,
+        )
+    }
+
+    /**
+    Get the stored fields returned for the document.
+
+    Stored fields are only returned when they're requested with `stored_fields`.
+    Each field is an array of values.
+    */
+    pub fn fields(&self) -> &Map<String, Value> {
+        &self.fields
+    }
+
+    /** Get the values of a single stored field. */
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        self.fields.get(name)
+    }
+
     /** Whether or not a matching document was found. */
     pub fn found(&self) -> bool {
         self.found
@@ -75,7 +153,7 @@ impl<T> GetResponse<T> {
     }
 
     /** The version of the document. */
-    pub fn version(&self) -> Option<u32> {
+    pub fn version(&self) -> Option<i64> {
         self.version
     }
 
@@ -84,7 +162,7 @@ impl<T> GetResponse<T> {
      *
      * [sequence number]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
      */
-    pub fn sequence_number(&self) -> Option<u32> {
+    pub fn sequence_number(&self) -> Option<i64> {
         self.sequence_number
     }
 
@@ -93,7 +171,7 @@ impl<T> GetResponse<T> {
      *
      * [primary term]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
      */
-    pub fn primary_term(&self) -> Option<u32> {
+    pub fn primary_term(&self) -> Option<i64> {
         self.primary_term
     }
 }
@@ -0,0 +1,76 @@
+/*!
+Response types for a [get document source request](https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-get.html#_source).
+*/
+
+use serde_json::Value;
+
+use crate::http::{
+    receiver::{
+        HttpResponseHead,
+        IsOk,
+        MaybeOkResponse,
+        ParseError,
+        ResponseBody,
+        Unbuffered,
+    },
+    StatusCode,
+};
+
+/** The type of error returned when the index of a get document source request doesn't exist. */
+const INDEX_NOT_FOUND: &str = "index_not_found_exception";
+
+/**
+Response for a [get document source request](https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-get.html#_source).
+
+The body of the response is the source of the document, without any metadata.
+*/
+#[derive(Deserialize, Debug)]
+#[serde(transparent)]
+pub struct GetSourceResponse<T> {
+    source: Option<T>,
+}
+
+impl<T> GetSourceResponse<T> {
+    /** Get a reference to the source document. */
+    pub fn document(&self) -> Option<&T> {
+        self.source.as_ref()
+    }
+
+    /** Convert the response into the source document. */
+    pub fn into_document(self) -> Option<T> {
+        self.source
+    }
+
+    /** Whether or not the document was found. */
+    pub fn found(&self) -> bool {
+        self.source.is_some()
+    }
+}
+
+impl<T> IsOk for GetSourceResponse<T> {
+    fn is_ok<B: ResponseBody>(
+        head: HttpResponseHead,
+        body: Unbuffered<B>,
+    ) -> Result<MaybeOkResponse<B>, ParseError> {
+        match head.status() {
+            status if status.is_success() => Ok(MaybeOkResponse::ok(body)),
+            StatusCode::NOT_FOUND => {
+                // A missing document may have an empty body, but a missing index is an error
+                let (maybe_err, body) = match body.body() {
+                    Ok(body) => body,
+                    Err(_) => return Ok(MaybeOkResponse::ok(Value::Null)),
+                };
+
+                let index_not_found = maybe_err.pointer("/error/type").and_then(Value::as_str)
+                    == Some(INDEX_NOT_FOUND);
+
+                if index_not_found {
+                    Ok(MaybeOkResponse::err(body))
+                } else {
+                    Ok(MaybeOkResponse::ok(Value::Null))
+                }
+            }
+            _ => Ok(MaybeOkResponse::err(body)),
+        }
+    }
+}
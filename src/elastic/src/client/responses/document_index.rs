@@ -3,6 +3,7 @@ Response types for an [index document request](https://www.elastic.co/guide/en/e
 */
 
 use super::common::{
+    DocumentMetadata,
     DocumentResult,
     Shards,
 };
@@ -26,11 +27,11 @@ pub struct IndexResponse {
     #[serde(rename = "_id")]
     id: String,
     #[serde(rename = "_version")]
-    version: Option<u32>,
+    version: Option<i64>,
     #[serde(rename = "_seq_no")]
-    sequence_number: Option<u32>,
+    sequence_number: Option<i64>,
     #[serde(rename = "_primary_term")]
-    primary_term: Option<u32>,
+    primary_term: Option<i64>,
     result: DocumentResult,
     #[serde(rename = "_shards")]
     shards: Shards,
@@ -66,7 +67,7 @@ impl IndexResponse {
     }
 
     /** The version of the document. */
-    pub fn version(&self) -> Option<u32> {
+    pub fn version(&self) -> Option<i64> {
         self.version
     }
 
@@ -75,7 +76,7 @@ impl IndexResponse {
      *
      * [sequence number]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
      */
-    pub fn sequence_number(&self) -> Option<u32> {
+    pub fn sequence_number(&self) -> Option<i64> {
         self.sequence_number
     }
 
@@ -84,9 +85,26 @@ impl IndexResponse {
      *
      * [primary term]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
      */
-    pub fn primary_term(&self) -> Option<u32> {
+    pub fn primary_term(&self) -> Option<i64> {
         self.primary_term
     }
+
+    /** The result of the write to the document. */
+    pub fn result(&self) -> DocumentResult {
+        self.result
+    }
+
+    /** Get the metadata for the document. */
+    pub fn metadata(&self) -> DocumentMetadata {
+        DocumentMetadata::new(
+            &self.index,
+            &self.ty,
+            &self.id,
+            self.version,
+            self.sequence_number,
+            self.primary_term,
+        )
+    }
 }
 
 impl IsOkOnSuccess for IndexResponse {}
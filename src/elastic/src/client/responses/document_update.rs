@@ -5,7 +5,10 @@ Response types for a [update document request](https://www.elastic.co/guide/en/e
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
-use super::common::DocumentResult;
+use super::common::{
+    DocumentMetadata,
+    DocumentResult,
+};
 
 use crate::{
     http::receiver::IsOkOnSuccess,
@@ -26,11 +29,11 @@ pub struct UpdateResponse {
     #[serde(rename = "_id")]
     id: String,
     #[serde(rename = "_version")]
-    version: Option<u32>,
+    version: Option<i64>,
     #[serde(rename = "_seq_no")]
-    sequence_number: Option<u32>,
+    sequence_number: Option<i64>,
     #[serde(rename = "_primary_term")]
-    primary_term: Option<u32>,
+    primary_term: Option<i64>,
     #[serde(rename = "_routing")]
     routing: Option<String>,
     result: DocumentResult,
@@ -109,7 +112,7 @@ impl UpdateResponse {
     }
 
     /** The version of the document. */
-    pub fn version(&self) -> Option<u32> {
+    pub fn version(&self) -> Option<i64> {
         self.version
     }
 
@@ -118,7 +121,7 @@ impl UpdateResponse {
      *
      * [sequence number]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
      */
-    pub fn sequence_number(&self) -> Option<u32> {
+    pub fn sequence_number(&self) -> Option<i64> {
         self.sequence_number
     }
 
@@ -127,9 +130,26 @@ impl UpdateResponse {
      *
      * [primary term]: https://www.elastic.co/guide/en/elasticsearch/reference/current/optimistic-concurrency-control.html
      */
-    pub fn primary_term(&self) -> Option<u32> {
+    pub fn primary_term(&self) -> Option<i64> {
         self.primary_term
     }
+
+    /** The result of the write to the document. */
+    pub fn result(&self) -> DocumentResult {
+        self.result
+    }
+
+    /** Get the metadata for the document. */
+    pub fn metadata(&self) -> DocumentMetadata {
+        DocumentMetadata::new(
+            &self.index,
+            &self.ty,
+            &self.id,
+            self.version,
+            self.sequence_number,
+            self.primary_term,
+        )
+    }
 }
 
 impl IsOkOnSuccess for UpdateResponse {}
@@ -0,0 +1,273 @@
+/*!
+Response types for an [index segments request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-segments.html).
+*/
+
+use super::common::Shards;
+use crate::http::receiver::IsOkOnSuccess;
+use serde::de::{
+    Deserialize,
+    Deserializer,
+};
+
+use std::collections::{
+    btree_map,
+    BTreeMap,
+};
+
+/**
+Response for an [index segments request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-segments.html).
+
+# Examples
+
+Find shards with more than 10 searchable segments, which might be worth force-merging:
+
+```no_run
+# use elastic::prelude::*;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+# let client = SyncClientBuilder::new().build()?;
+let response = client.index("myindex").segments().send()?;
+
+for (index, segments) in response.indices() {
+    for shard in segments.shards() {
+        if shard.num_search_segments() > 10 {
+            println!("{}/{} has {} segments", index, shard.shard(), shard.num_search_segments());
+        }
+    }
+}
+# Ok(())
+# }
+```
+*/
+#[derive(Deserialize, Debug)]
+pub struct IndicesSegmentsResponse {
+    #[serde(rename = "_shards")]
+    shards: Shards,
+    #[serde(default)]
+    indices: BTreeMap<String, IndexSegments>,
+}
+
+impl IndicesSegmentsResponse {
+    /** Shards metadata for the request. */
+    pub fn shards(&self) -> &Shards {
+        &self.shards
+    }
+
+    /** Segments for a single index. */
+    pub fn index(&self, index: &str) -> Option<&IndexSegments> {
+        self.indices.get(index)
+    }
+
+    /** Iterate over the segments for each matched index. */
+    pub fn indices(&self) -> btree_map::Iter<'_, String, IndexSegments> {
+        self.indices.iter()
+    }
+}
+
+impl IsOkOnSuccess for IndicesSegmentsResponse {}
+
+/** Segments for each shard copy of an index. */
+#[derive(Deserialize, Debug)]
+pub struct IndexSegments {
+    #[serde(deserialize_with = "deserialize_shards")]
+    shards: Vec<ShardSegments>,
+}
+
+impl IndexSegments {
+    /**
+    The segments for each shard copy.
+
+    Primaries and replicas of the same shard are returned separately.
+    */
+    pub fn shards(&self) -> &[ShardSegments] {
+        &self.shards
+    }
+
+    /** The total number of searchable segments in the primary shards. */
+    pub fn num_primary_search_segments(&self) -> u64 {
+        self.shards
+            .iter()
+            .filter(|shard| shard.is_primary())
+            .map(|shard| shard.num_search_segments())
+            .sum()
+    }
+}
+
+/** The segments in a single shard copy. */
+#[derive(Deserialize, Debug)]
+pub struct ShardSegments {
+    #[serde(skip)]
+    shard: u32,
+    routing: ShardRouting,
+    num_committed_segments: u64,
+    num_search_segments: u64,
+    #[serde(deserialize_with = "deserialize_segments")]
+    segments: Vec<Segment>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ShardRouting {
+    state: String,
+    primary: bool,
+    node: Option<String>,
+}
+
+impl ShardSegments {
+    /** The number of the shard. */
+    pub fn shard(&self) -> u32 {
+        self.shard
+    }
+
+    /** Whether or not this shard copy is the primary. */
+    pub fn is_primary(&self) -> bool {
+        self.routing.primary
+    }
+
+    /** The id of the node the shard copy is allocated to. */
+    pub fn node(&self) -> Option<&str> {
+        self.routing.node.as_deref()
+    }
+
+    /** The state of the shard copy, like `STARTED`. */
+    pub fn state(&self) -> &str {
+        &self.routing.state
+    }
+
+    /** The number of segments that have been committed to disk. */
+    pub fn num_committed_segments(&self) -> u64 {
+        self.num_committed_segments
+    }
+
+    /** The number of segments that are visible to searches. */
+    pub fn num_search_segments(&self) -> u64 {
+        self.num_search_segments
+    }
+
+    /** The segments in the shard copy. */
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /** The total size of the segments on disk. */
+    pub fn size_in_bytes(&self) -> u64 {
+        self.segments
+            .iter()
+            .map(|segment| segment.size_in_bytes)
+            .sum()
+    }
+
+    /**
+    The total heap memory used by the segments.
+
+    Segments that don't report their memory usage aren't counted.
+    */
+    pub fn memory_in_bytes(&self) -> u64 {
+        self.segments
+            .iter()
+            .filter_map(|segment| segment.memory_in_bytes)
+            .sum()
+    }
+}
+
+/** A single Lucene segment. */
+#[derive(Deserialize, Debug)]
+pub struct Segment {
+    #[serde(skip)]
+    name: String,
+    generation: u64,
+    num_docs: u64,
+    deleted_docs: u64,
+    size_in_bytes: u64,
+    memory_in_bytes: Option<u64>,
+    committed: bool,
+    search: bool,
+    version: Option<String>,
+    compound: Option<bool>,
+}
+
+impl Segment {
+    /** The name of the segment, like `_0`. */
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /** The generation of the segment. */
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /** The number of documents in the segment, not including deleted documents. */
+    pub fn num_docs(&self) -> u64 {
+        self.num_docs
+    }
+
+    /** The number of deleted documents that haven't been merged away yet. */
+    pub fn deleted_docs(&self) -> u64 {
+        self.deleted_docs
+    }
+
+    /** The size of the segment on disk. */
+    pub fn size_in_bytes(&self) -> u64 {
+        self.size_in_bytes
+    }
+
+    /**
+    The heap memory used by the segment.
+
+    Newer versions of Elasticsearch don't report segment memory so this may be `None`.
+    */
+    pub fn memory_in_bytes(&self) -> Option<u64> {
+        self.memory_in_bytes
+    }
+
+    /** Whether or not the segment has been committed to disk. */
+    pub fn is_committed(&self) -> bool {
+        self.committed
+    }
+
+    /** Whether or not the segment is visible to searches. */
+    pub fn is_search(&self) -> bool {
+        self.search
+    }
+
+    /** The version of Lucene that wrote the segment. */
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /** Whether or not the segment is stored in a compound file. */
+    pub fn is_compound(&self) -> Option<bool> {
+        self.compound
+    }
+}
+
+fn deserialize_shards<'de, D>(deserializer: D) -> Result<Vec<ShardSegments>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let shards = BTreeMap::<u32, Vec<ShardSegments>>::deserialize(deserializer)?;
+
+    Ok(shards
+        .into_iter()
+        .flat_map(|(shard, copies)| {
+            copies.into_iter().map(move |mut copy| {
+                copy.shard = shard;
+                copy
+            })
+        })
+        .collect())
+}
+
+fn deserialize_segments<'de, D>(deserializer: D) -> Result<Vec<Segment>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let segments = BTreeMap::<String, Segment>::deserialize(deserializer)?;
+
+    Ok(segments
+        .into_iter()
+        .map(|(name, mut segment)| {
+            segment.name = name;
+            segment
+        })
+        .collect())
+}
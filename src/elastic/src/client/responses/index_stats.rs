@@ -0,0 +1,149 @@
+/*!
+Response types for an [index stats request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-stats.html).
+*/
+
+use super::common::Shards;
+use crate::http::receiver::IsOkOnSuccess;
+
+use std::collections::{
+    btree_map,
+    BTreeMap,
+};
+
+/**
+Response for an [index stats request](https://www.elastic.co/guide/en/elasticsearch/reference/current/indices-stats.html).
+
+Stats are reported for all matched indices combined, and for each index individually.
+Each set of stats is split into the primary shards and the total for all shards, including replicas.
+
+# Examples
+
+Check whether an index has grown beyond a given size:
+
+```no_run
+# use elastic::prelude::*;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+# let client = SyncClientBuilder::new().build()?;
+let response = client.index("myindex").stats().send()?;
+
+let size = response.all().primaries().store_size_in_bytes().unwrap_or(0);
+
+if size > 50 * 1024 * 1024 * 1024 {
+    println!("myindex is over 50GB");
+}
+# Ok(())
+# }
+```
+*/
+#[derive(Deserialize, Debug)]
+pub struct IndicesStatsResponse {
+    #[serde(rename = "_shards")]
+    shards: Shards,
+    #[serde(rename = "_all", default)]
+    all: IndexStatsGroup,
+    #[serde(default)]
+    indices: BTreeMap<String, IndexStatsGroup>,
+}
+
+impl IndicesStatsResponse {
+    /** Shards metadata for the request. */
+    pub fn shards(&self) -> &Shards {
+        &self.shards
+    }
+
+    /** Stats for all matched indices combined. */
+    pub fn all(&self) -> &IndexStatsGroup {
+        &self.all
+    }
+
+    /** Stats for a single index. */
+    pub fn index(&self, index: &str) -> Option<&IndexStatsGroup> {
+        self.indices.get(index)
+    }
+
+    /** Iterate over the stats for each matched index. */
+    pub fn indices(&self) -> btree_map::Iter<'_, String, IndexStatsGroup> {
+        self.indices.iter()
+    }
+}
+
+impl IsOkOnSuccess for IndicesStatsResponse {}
+
+/** Stats for the primary shards and all shards of one or more indices. */
+#[derive(Deserialize, Debug, Default)]
+pub struct IndexStatsGroup {
+    #[serde(default)]
+    primaries: IndexStats,
+    #[serde(default)]
+    total: IndexStats,
+}
+
+impl IndexStatsGroup {
+    /** Stats for the primary shards only. */
+    pub fn primaries(&self) -> &IndexStats {
+        &self.primaries
+    }
+
+    /** Stats for all shards, including replicas. */
+    pub fn total(&self) -> &IndexStats {
+        &self.total
+    }
+}
+
+/**
+Stats for a set of shards.
+
+Stats are `None` if their metric wasn't returned by Elasticsearch.
+*/
+#[derive(Deserialize, Debug, Default)]
+pub struct IndexStats {
+    docs: Option<DocsStats>,
+    store: Option<StoreStats>,
+    segments: Option<SegmentsStats>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DocsStats {
+    count: u64,
+    deleted: u64,
+}
+
+#[derive(Deserialize, Debug)]
+struct StoreStats {
+    size_in_bytes: u64,
+}
+
+#[derive(Deserialize, Debug)]
+struct SegmentsStats {
+    count: u64,
+    memory_in_bytes: u64,
+}
+
+impl IndexStats {
+    /** The number of documents, not including deleted documents or nested documents. */
+    pub fn docs_count(&self) -> Option<u64> {
+        self.docs.as_ref().map(|docs| docs.count)
+    }
+
+    /** The number of deleted documents that haven't been merged away yet. */
+    pub fn docs_deleted(&self) -> Option<u64> {
+        self.docs.as_ref().map(|docs| docs.deleted)
+    }
+
+    /** The size of the shards on disk. */
+    pub fn store_size_in_bytes(&self) -> Option<u64> {
+        self.store.as_ref().map(|store| store.size_in_bytes)
+    }
+
+    /** The number of segments. */
+    pub fn segments_count(&self) -> Option<u64> {
+        self.segments.as_ref().map(|segments| segments.count)
+    }
+
+    /** The heap memory used by segments. */
+    pub fn segments_memory_in_bytes(&self) -> Option<u64> {
+        self.segments
+            .as_ref()
+            .map(|segments| segments.memory_in_bytes)
+    }
+}
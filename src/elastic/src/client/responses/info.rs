@@ -0,0 +1,103 @@
+/*!
+Response types for a cluster info request.
+*/
+
+use crate::{
+    client::Version,
+    http::receiver::IsOkOnSuccess,
+};
+
+/** Response for a cluster info request. */
+#[derive(Deserialize, Debug)]
+pub struct InfoResponse {
+    name: String,
+    cluster_name: String,
+    #[serde(default)]
+    cluster_uuid: Option<String>,
+    version: InfoVersion,
+    tagline: String,
+}
+
+/** The version metadata of an Elasticsearch node. */
+#[derive(Deserialize, Debug)]
+pub struct InfoVersion {
+    number: Version,
+    #[serde(default)]
+    build_flavor: Option<String>,
+    #[serde(default)]
+    build_type: Option<String>,
+    build_hash: String,
+    build_date: String,
+    build_snapshot: bool,
+    lucene_version: String,
+}
+
+impl InfoResponse {
+    /** The name of the node that responded. */
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /** The name of the cluster the node belongs to. */
+    pub fn cluster_name(&self) -> &str {
+        &self.cluster_name
+    }
+
+    /** The unique id of the cluster the node belongs to. */
+    pub fn cluster_uuid(&self) -> Option<&str> {
+        self.cluster_uuid.as_deref()
+    }
+
+    /** The Elasticsearch version metadata. */
+    pub fn version(&self) -> &InfoVersion {
+        &self.version
+    }
+
+    /** The tagline. */
+    pub fn tagline(&self) -> &str {
+        &self.tagline
+    }
+}
+
+impl InfoVersion {
+    /** The version number. */
+    pub fn number(&self) -> &Version {
+        &self.number
+    }
+
+    /**
+    The build flavor, like `default` or `oss`.
+
+    Nodes older than `6.3.0` don't report a build flavor.
+    */
+    pub fn build_flavor(&self) -> Option<&str> {
+        self.build_flavor.as_deref()
+    }
+
+    /** The build type, like `tar` or `docker`. */
+    pub fn build_type(&self) -> Option<&str> {
+        self.build_type.as_deref()
+    }
+
+    /** The build hash. */
+    pub fn hash(&self) -> &str {
+        &self.build_hash
+    }
+
+    /** The build date. */
+    pub fn date(&self) -> &str {
+        &self.build_date
+    }
+
+    /** Whether or not the build is a snapshot. */
+    pub fn snapshot(&self) -> bool {
+        self.build_snapshot
+    }
+
+    /** The underlying Lucene version. */
+    pub fn lucene_version(&self) -> &str {
+        &self.lucene_version
+    }
+}
+
+impl IsOkOnSuccess for InfoResponse {}
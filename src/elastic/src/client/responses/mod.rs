@@ -5,18 +5,25 @@ This module contains implementation details that are useful if you want to custo
 */
 
 pub mod bulk;
+mod cluster_health;
 mod command;
 pub mod common;
 mod document_delete;
 mod document_get;
+mod document_get_source;
 mod document_index;
 mod document_update;
+mod info;
 pub mod nodes_info;
+mod partial_document;
 mod ping;
 pub mod search;
+mod search_template;
 mod sql;
 
 mod index_exists;
+pub mod index_segments;
+pub mod index_stats;
 
 #[cfg(test)]
 mod tests;
@@ -27,19 +34,34 @@ pub use self::{
         BulkErrorsResponse,
         BulkResponse,
     },
+    cluster_health::*,
     command::*,
+    common::{
+        DocumentMetadata,
+        DocumentResult,
+    },
     document_delete::*,
     document_get::*,
+    document_get_source::*,
     document_index::*,
     document_update::*,
+    info::*,
     nodes_info::NodesInfoResponse,
+    partial_document::*,
     ping::*,
     search::SearchResponse,
+    search_template::*,
     sql::*,
 };
 
 pub use self::index_exists::*;
 
+#[doc(inline)]
+pub use self::{
+    index_segments::IndicesSegmentsResponse,
+    index_stats::IndicesStatsResponse,
+};
+
 pub mod prelude {
     /*! A glob import for convenience. */
 
@@ -47,13 +69,24 @@ pub mod prelude {
         bulk::Action as BulkAction,
         BulkErrorsResponse,
         BulkResponse,
+        ClusterHealthResponse,
+        ClusterHealthStatus,
         CommandResponse,
         DeleteResponse,
+        DocumentMetadata,
+        DocumentResult,
         GetResponse,
+        GetSourceResponse,
+        GetSearchTemplateResponse,
         IndexResponse,
         IndicesExistsResponse,
+        IndicesSegmentsResponse,
+        IndicesStatsResponse,
+        InfoResponse,
         NodesInfoResponse,
+        PartialDocument,
         PingResponse,
+        RenderSearchTemplateResponse,
         SearchResponse,
         SqlQueryResponse,
         UpdateResponse,
@@ -0,0 +1,842 @@
+/*!
+A document wrapper that tolerates missing fields.
+
+The source of a document can be missing fields when a search uses [source filtering][docs-source-filtering],
+or when the user sending the request can only see some fields because of field-level security.
+A typed document that requires those fields would fail to deserialise, which fails the whole response.
+
+`PartialDocument<T>` deserialises `T` even when fields are missing:
+
+- Missing `Option` fields are `None`.
+- Missing fields with a `#[serde(default)]` attribute are given their default value.
+- Other missing fields are given an empty value, like `0`, `false`, `""` or an empty collection.
+
+Every field that was missing from the source is recorded, including fields on nested objects.
+Types that can't be given an empty value, like dates, should be wrapped in an `Option` if they might be missing.
+
+Which missing fields need an empty value is remembered for each document type,
+so documents are deserialised in a single pass once a field without a default has been seen missing.
+
+[docs-source-filtering]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-source-filtering.html
+*/
+
+use serde::de::{
+    self,
+    value::{
+        MapDeserializer,
+        SeqDeserializer,
+    },
+    Deserialize,
+    DeserializeOwned,
+    DeserializeSeed,
+    Deserializer,
+    IntoDeserializer,
+    MapAccess,
+    SeqAccess,
+    Visitor,
+};
+use serde_json::{
+    Error,
+    Map,
+    Value,
+};
+use std::{
+    any::TypeId,
+    cell::RefCell,
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    error::Error as StdError,
+    fmt,
+    iter,
+    rc::Rc,
+    vec,
+};
+
+/**
+A document that may be missing some of its fields.
+
+Use `PartialDocument<T>` in place of `T` for requests that could return documents without all of their fields,
+like searches with source filtering or searches on indices with field-level security.
+
+# Examples
+
+Search for documents that may be missing their `title` field:
+
+```no_run
+# #[macro_use] extern crate serde_derive;
+# #[macro_use] extern crate elastic_derive;
+# use elastic::prelude::*;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+# let client = SyncClientBuilder::new().build()?;
+#[derive(Debug, Serialize, Deserialize, ElasticType)]
+struct MyType {
+    id: String,
+    title: String,
+}
+
+let response = client.search::<PartialDocument<MyType>>()
+                     .index("myindex")
+                     .send()?;
+
+for doc in response.documents() {
+    if doc.is_missing("title") {
+        println!("document {} has no visible title", doc.document().id);
+    }
+}
+# Ok(())
+# }
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialDocument<T> {
+    document: T,
+    missing_fields: Vec<String>,
+}
+
+impl<T> PartialDocument<T> {
+    /** Get a reference to the document. */
+    pub fn document(&self) -> &T {
+        &self.document
+    }
+
+    /** Convert into the document. */
+    pub fn into_document(self) -> T {
+        self.document
+    }
+
+    /**
+    The fields that were missing from the source.
+
+    Fields on nested objects are separated by a `.`, like `author.name`.
+    */
+    pub fn missing_fields(&self) -> &[String] {
+        &self.missing_fields
+    }
+
+    /** Whether or not a field was missing from the source. */
+    pub fn is_missing(&self, field: &str) -> bool {
+        self.missing_fields.iter().any(|missing| missing == field)
+    }
+
+    /** Whether or not every field was present in the source. */
+    pub fn is_complete(&self) -> bool {
+        self.missing_fields.is_empty()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for PartialDocument<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+
+        from_value(value).map_err(de::Error::custom)
+    }
+}
+
+thread_local! {
+    /** The paths of missing fields that need an empty value for each document type. */
+    static EMPTY_FIELDS: RefCell<HashMap<TypeId, Rc<HashSet<String>>>> =
+        RefCell::new(HashMap::new());
+}
+
+/**
+Deserialise a partial document from a JSON value.
+
+Missing fields serde can't fill in itself are given an empty value.
+Whether serde can fill in a field is the same for every document of a type, so fields already known to need an empty value are given one up front.
+The document is only deserialised again when a missing field without a default hasn't been seen for its type before.
+*/
+fn from_value<T>(value: Value) -> Result<PartialDocument<T>, Error>
+where
+    T: DeserializeOwned + 'static,
+{
+    let ty = TypeId::of::<T>();
+    let mut empty =
+        EMPTY_FIELDS.with(|fields| fields.borrow().get(&ty).cloned().unwrap_or_default());
+
+    loop {
+        let state = State {
+            missing: RefCell::new(Vec::new()),
+            empty: empty.clone(),
+        };
+
+        let res = T::deserialize(Partial {
+            value: &value,
+            path: String::new(),
+            state: &state,
+        });
+
+        match res {
+            Ok(document) => {
+                let mut missing_fields = state.missing.into_inner();
+                let mut seen = HashSet::new();
+                missing_fields.retain(|field| seen.insert(field.clone()));
+
+                return Ok(PartialDocument {
+                    document,
+                    missing_fields,
+                });
+            }
+            Err(PartialError::Missing {
+                path: Some(path), ..
+            }) if !empty.contains(&path) => {
+                let mut learned = (*empty).clone();
+                learned.insert(path);
+
+                empty = Rc::new(learned);
+                EMPTY_FIELDS.with(|fields| fields.borrow_mut().insert(ty, empty.clone()));
+            }
+            Err(e) => return Err(e.into_json()),
+        }
+    }
+}
+
+struct State {
+    /** Every field that was missing. */
+    missing: RefCell<Vec<String>>,
+    /** The missing fields to give an empty value because they don't have a default. */
+    empty: Rc<HashSet<String>>,
+}
+
+fn join(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_owned()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+/** An error deserialising a partial document. */
+#[derive(Debug)]
+enum PartialError {
+    /**
+    A field without a default is missing from a struct.
+
+    The path to the field is added by the struct's deserialiser.
+    */
+    Missing {
+        field: &'static str,
+        path: Option<String>,
+    },
+    Json(Error),
+}
+
+impl PartialError {
+    /** Set the path of a missing field on the struct at `path`. */
+    fn at(self, path: &str) -> Self {
+        match self {
+            PartialError::Missing { field, path: None } => PartialError::Missing {
+                field,
+                path: Some(join(path, field)),
+            },
+            e => e,
+        }
+    }
+
+    fn into_json(self) -> Error {
+        match self {
+            PartialError::Missing { field, .. } => de::Error::missing_field(field),
+            PartialError::Json(e) => e,
+        }
+    }
+}
+
+impl fmt::Display for PartialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PartialError::Missing { field, .. } => write!(f, "missing field `{}`", field),
+            PartialError::Json(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl StdError for PartialError {}
+
+impl de::Error for PartialError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        PartialError::Json(de::Error::custom(msg))
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        PartialError::Missing { field, path: None }
+    }
+}
+
+/** A deserialiser for a JSON value that tracks the fields missing from structs. */
+struct Partial<'a> {
+    value: &'a Value,
+    path: String,
+    state: &'a State,
+}
+
+impl<'a> Deserializer<'a> for Partial<'a> {
+    type Error = PartialError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'a>,
+    {
+        let Partial { value, path, state } = self;
+
+        match *value {
+            Value::Object(ref map) => visitor.visit_map(Fields::present(map, &path, state)),
+            Value::Array(ref values) => {
+                let values = values
+                    .iter()
+                    .map(|value| Partial {
+                        value,
+                        path: path.clone(),
+                        state,
+                    })
+                    .collect::<Vec<_>>();
+
+                visitor.visit_seq(Elements(values.into_iter()))
+            }
+            ref value => value.deserialize_any(visitor).map_err(PartialError::Json),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'a>,
+    {
+        match *self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'a>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'a>,
+    {
+        self.value
+            .deserialize_enum(name, variants, visitor)
+            .map_err(PartialError::Json)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'a>,
+    {
+        let Partial { value, path, state } = self;
+
+        let map = match *value {
+            Value::Object(ref map) => map,
+            _ => return Partial { value, path, state }.deserialize_any(visitor),
+        };
+
+        // Missing fields are left to serde so any defaults apply,
+        // unless an earlier document of the same type found they don't have one
+        let mut empty = Vec::new();
+        for field in fields {
+            if map.contains_key(*field) {
+                continue;
+            }
+
+            let field_path = join(&path, field);
+            if state.empty.contains(&field_path) {
+                empty.push((*field, Field::Empty));
+            }
+
+            state.missing.borrow_mut().push(field_path);
+        }
+
+        let mut entries = Fields::present(map, &path, state);
+        entries.0.extend(empty);
+
+        visitor.visit_map(entries).map_err(|e| e.at(&path))
+    }
+
+    serde::forward_to_deserialize_any! {
+        <W: Visitor<'a>>
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier ignored_any
+    }
+}
+
+enum Field<'a> {
+    Present(Partial<'a>),
+    Empty,
+}
+
+struct Fields<'a>(Vec<(&'a str, Field<'a>)>, Option<Field<'a>>);
+
+impl<'a> Fields<'a> {
+    /** The fields present in an object. */
+    fn present(map: &'a Map<String, Value>, path: &str, state: &'a State) -> Self {
+        let fields = map
+            .iter()
+            .map(|(key, value)| {
+                let path = join(path, key);
+                (key.as_str(), Field::Present(Partial { value, path, state }))
+            })
+            .rev()
+            .collect();
+
+        Fields(fields, None)
+    }
+}
+
+impl<'a> MapAccess<'a> for Fields<'a> {
+    type Error = PartialError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, PartialError>
+    where
+        K: DeserializeSeed<'a>,
+    {
+        match self.0.pop() {
+            Some((key, value)) => {
+                self.1 = Some(value);
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, PartialError>
+    where
+        V: DeserializeSeed<'a>,
+    {
+        match self.1.take() {
+            Some(Field::Present(value)) => seed.deserialize(value),
+            Some(Field::Empty) => seed.deserialize(Empty),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+}
+
+struct Elements<'a>(vec::IntoIter<Partial<'a>>);
+
+impl<'a> SeqAccess<'a> for Elements<'a> {
+    type Error = PartialError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, PartialError>
+    where
+        T: DeserializeSeed<'a>,
+    {
+        self.0
+            .next()
+            .map(|value| seed.deserialize(value))
+            .transpose()
+    }
+}
+
+/** A deserialiser for the empty value of a type, used for missing fields. */
+#[derive(Clone, Copy)]
+struct Empty;
+
+impl<'de> IntoDeserializer<'de, PartialError> for Empty {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! empty_value {
+    ($($method:ident => $visit:ident($value:expr),)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, PartialError>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit($value)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Empty {
+    type Error = PartialError;
+
+    empty_value! {
+        deserialize_bool => visit_bool(false),
+        deserialize_i8 => visit_i64(0),
+        deserialize_i16 => visit_i64(0),
+        deserialize_i32 => visit_i64(0),
+        deserialize_i64 => visit_i64(0),
+        deserialize_u8 => visit_u64(0),
+        deserialize_u16 => visit_u64(0),
+        deserialize_u32 => visit_u64(0),
+        deserialize_u64 => visit_u64(0),
+        deserialize_f32 => visit_f64(0.0),
+        deserialize_f64 => visit_f64(0.0),
+        deserialize_char => visit_char('\0'),
+        deserialize_str => visit_str(""),
+        deserialize_string => visit_str(""),
+        deserialize_bytes => visit_bytes(&[]),
+        deserialize_byte_buf => visit_bytes(&[]),
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(SeqDeserializer::new(iter::empty::<Empty>()))
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(MapDeserializer::new(iter::empty::<(Empty, Empty)>()))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(MapDeserializer::new(
+            fields.iter().map(|field| (*field, Empty)),
+        ))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        _: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, PartialError>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::custom(format!(
+            "a missing `{}` can't be given an empty value",
+            name
+        )))
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 unit unit_struct tuple tuple_struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::responses::SearchResponse,
+        http::{
+            receiver::parse,
+            StatusCode,
+        },
+    };
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    };
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Author {
+        name: String,
+        email: Option<String>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Article {
+        id: String,
+        title: String,
+        likes: u32,
+        published: bool,
+        tags: Vec<String>,
+        #[serde(default = "default_rating")]
+        rating: f32,
+        summary: Option<String>,
+        author: Author,
+    }
+
+    fn default_rating() -> f32 {
+        2.5
+    }
+
+    fn complete() -> Value {
+        json!({
+            "id": "1",
+            "title": "Title",
+            "likes": 3,
+            "published": true,
+            "tags": ["rust"],
+            "rating": 4.0,
+            "summary": "Summary",
+            "author": { "name": "Author", "email": "author@example.com" }
+        })
+    }
+
+    #[test]
+    fn complete_document() {
+        let doc: PartialDocument<Article> = serde_json::from_value(complete()).unwrap();
+
+        assert!(doc.is_complete());
+        assert_eq!("Title", doc.document().title);
+    }
+
+    #[test]
+    fn missing_fields_are_empty() {
+        let doc: PartialDocument<Article> = serde_json::from_value(json!({ "id": "1" })).unwrap();
+
+        let article = Article {
+            id: "1".to_owned(),
+            title: String::new(),
+            likes: 0,
+            published: false,
+            tags: vec![],
+            rating: 2.5,
+            summary: None,
+            author: Author {
+                name: String::new(),
+                email: None,
+            },
+        };
+
+        assert_eq!(&article, doc.document());
+        assert_eq!(
+            vec![
+                "title",
+                "likes",
+                "published",
+                "tags",
+                "rating",
+                "summary",
+                "author"
+            ],
+            doc.missing_fields()
+        );
+    }
+
+    #[test]
+    fn missing_fields_with_container_default() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(default)]
+        struct Counts {
+            views: u32,
+            likes: u32,
+            title: String,
+        }
+
+        impl Default for Counts {
+            fn default() -> Self {
+                Counts {
+                    views: 1,
+                    likes: 2,
+                    title: "Untitled".to_owned(),
+                }
+            }
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct WithCounts {
+            id: String,
+            counts: Counts,
+        }
+
+        let doc: PartialDocument<WithCounts> = serde_json::from_value(json!({
+            "counts": { "views": 5 }
+        }))
+        .unwrap();
+
+        let expected = WithCounts {
+            id: String::new(),
+            counts: Counts {
+                views: 5,
+                likes: 2,
+                title: "Untitled".to_owned(),
+            },
+        };
+
+        assert_eq!(&expected, doc.document());
+        assert_eq!(
+            vec!["id", "counts.likes", "counts.title"],
+            doc.missing_fields()
+        );
+    }
+
+    #[test]
+    fn missing_nested_fields() {
+        let mut source = complete();
+        source["author"] = json!({ "email": "author@example.com" });
+        source.as_object_mut().unwrap().remove("summary");
+
+        let doc: PartialDocument<Article> = serde_json::from_value(source).unwrap();
+
+        assert_eq!(vec!["summary", "author.name"], doc.missing_fields());
+        assert!(doc.is_missing("author.name"));
+        assert!(!doc.is_missing("author.email"));
+        assert_eq!("", doc.document().author.name);
+        assert_eq!(None, doc.document().summary);
+    }
+
+    #[test]
+    fn missing_fields_in_arrays() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Thread {
+            authors: Vec<Author>,
+        }
+
+        let doc: PartialDocument<Thread> = serde_json::from_value(json!({
+            "authors": [{ "name": "a" }, { "email": "b@example.com" }]
+        }))
+        .unwrap();
+
+        assert_eq!(vec!["authors.email", "authors.name"], doc.missing_fields());
+        assert_eq!("", doc.document().authors[1].name);
+    }
+
+    #[test]
+    fn search_response_with_missing_fields() {
+        let f = br#"{
+            "took": 1,
+            "timed_out": false,
+            "_shards": { "total": 1, "successful": 1, "skipped": 0, "failed": 0 },
+            "hits": {
+                "total": { "value": 2, "relation": "eq" },
+                "max_score": 1.0,
+                "hits": [
+                    { "_index": "a", "_type": "_doc", "_id": "1", "_score": 1.0, "_source": { "name": "a", "email": "a@example.com" } },
+                    { "_index": "a", "_type": "_doc", "_id": "2", "_score": 1.0, "_source": {} }
+                ]
+            }
+        }"#;
+
+        let response = parse::<SearchResponse<PartialDocument<Author>>>()
+            .from_slice(StatusCode::OK, f as &[_])
+            .unwrap();
+
+        let docs: Vec<_> = response.documents().collect();
+
+        assert!(docs[0].is_complete());
+        assert_eq!(vec!["name", "email"], docs[1].missing_fields());
+    }
+
+    #[test]
+    fn documents_are_deserialised_once_after_missing_fields_are_seen() {
+        static PASSES: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counted;
+
+        impl<'de> Deserialize<'de> for Counted {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                PASSES.fetch_add(1, Ordering::SeqCst);
+                de::IgnoredAny::deserialize(deserializer)?;
+
+                Ok(Counted)
+            }
+        }
+
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct WithCounter {
+            counted: Counted,
+            title: String,
+            likes: u32,
+        }
+
+        let passes = || {
+            PASSES.store(0, Ordering::SeqCst);
+
+            let doc: PartialDocument<WithCounter> =
+                serde_json::from_value(json!({ "counted": null })).unwrap();
+            assert_eq!(vec!["title", "likes"], doc.missing_fields());
+
+            PASSES.load(Ordering::SeqCst)
+        };
+
+        // The first document finds which missing fields don't have a default
+        assert_eq!(3, passes());
+        assert_eq!(1, passes());
+        assert_eq!(1, passes());
+    }
+
+    #[test]
+    fn invalid_fields_still_fail() {
+        let mut source = complete();
+        source["likes"] = json!("many");
+
+        let res: Result<PartialDocument<Article>, _> = serde_json::from_value(source);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn missing_fields_without_empty_value_fail() {
+        #[derive(Deserialize, Debug)]
+        #[allow(dead_code)]
+        enum Status {
+            Draft,
+        }
+
+        #[derive(Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct WithStatus {
+            status: Status,
+        }
+
+        let res: Result<PartialDocument<WithStatus>, _> = serde_json::from_value(json!({}));
+
+        assert!(res.is_err());
+    }
+}
@@ -38,6 +38,11 @@ impl PingResponse {
     pub fn version(&self) -> &ClusterVersion {
         &self.version
     }
+
+    /** The tagline. */
+    pub fn tagline(&self) -> &str {
+        &self.tagline
+    }
 }
 
 impl ClusterVersion {
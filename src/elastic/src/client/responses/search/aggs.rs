@@ -0,0 +1,284 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{
+    Map,
+    Value,
+};
+use std::slice::Iter;
+
+use super::{
+    Documents,
+    Hits,
+    HitsWrapper,
+    IntoDocuments,
+    IntoHits,
+};
+use crate::http::receiver::ParseError;
+
+pub(super) fn parse_agg<T>(
+    aggs: Option<&Map<String, Value>>,
+    name: &str,
+) -> Option<Result<T, ParseError>>
+where
+    T: DeserializeOwned,
+{
+    aggs.and_then(|aggs| aggs.get(name))
+        .map(|agg| serde_json::from_value(agg.clone()).map_err(ParseError::from))
+}
+
+/**
+The buckets returned by a [`date_histogram`][docs-date-histogram] aggregation.
+
+Call [`SearchResponse.agg`][SearchResponse.agg] to parse a `DateHistogram` from a search response.
+
+# Examples
+
+```no_run
+# use serde_json::Value;
+# use elastic::prelude::*;
+# use elastic::client::responses::search::DateHistogram;
+# fn do_request() -> SearchResponse<Value> { unimplemented!() }
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+let response: SearchResponse<Value> = do_request();
+
+if let Some(histogram) = response.agg::<DateHistogram>("per_day") {
+    for bucket in histogram?.buckets() {
+        println!("{}: {}", bucket.key(), bucket.doc_count());
+    }
+}
+# Ok(())
+# }
+```
+
+[docs-date-histogram]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-datehistogram-aggregation.html
+[SearchResponse.agg]: struct.SearchResponse.html#method.agg
+*/
+#[derive(Deserialize, Debug, Clone)]
+pub struct DateHistogram {
+    buckets: Vec<DateHistogramBucket>,
+}
+
+impl DateHistogram {
+    /** Iterate over the buckets in the histogram. */
+    pub fn buckets(&self) -> Iter<'_, DateHistogramBucket> {
+        self.buckets.iter()
+    }
+
+    /** Convert the histogram into its buckets. */
+    pub fn into_buckets(self) -> Vec<DateHistogramBucket> {
+        self.buckets
+    }
+}
+
+/** A single bucket in a `date_histogram` aggregation. */
+#[derive(Deserialize, Debug, Clone)]
+pub struct DateHistogramBucket {
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    key: DateTime<Utc>,
+    key_as_string: Option<String>,
+    doc_count: u64,
+    #[serde(flatten)]
+    aggs: Map<String, Value>,
+}
+
+impl DateHistogramBucket {
+    /** The start of the bucket. */
+    pub fn key(&self) -> DateTime<Utc> {
+        self.key
+    }
+
+    /** The start of the bucket, formatted by Elasticsearch. */
+    pub fn key_as_string(&self) -> Option<&str> {
+        self.key_as_string.as_deref()
+    }
+
+    /** The number of documents in the bucket. */
+    pub fn doc_count(&self) -> u64 {
+        self.doc_count
+    }
+
+    /**
+    Parse the sub-aggregation called `name` into a typed result.
+
+    Returns `None` if the bucket doesn't contain a sub-aggregation with the given name.
+    */
+    pub fn agg<T>(&self, name: &str) -> Option<Result<T, ParseError>>
+    where
+        T: DeserializeOwned,
+    {
+        parse_agg(Some(&self.aggs), name)
+    }
+
+    /** Get a reference to the raw sub-aggregation values. */
+    pub fn aggs_raw(&self) -> &Map<String, Value> {
+        &self.aggs
+    }
+}
+
+/**
+The buckets returned by a [`terms`][docs-terms] aggregation.
+
+Call [`SearchResponse.agg`][SearchResponse.agg] to parse a `Terms` from a search response.
+
+# Examples
+
+Get the most recent document for each user:
+
+```no_run
+# #[macro_use] extern crate serde_derive;
+# use elastic::prelude::*;
+# use elastic::client::requests::search::SortOrder;
+# use elastic::client::responses::search::{Terms, TopHits};
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+#[derive(Debug, Deserialize)]
+struct Message {
+    user: String,
+    text: String,
+}
+
+# let client = SyncClientBuilder::new().build()?;
+let response = client.search::<Message>()
+                     .index("messages")
+                     .aggs(|a| a
+                         .terms("by_user", "user.keyword")
+                         .sub(a.top_hits("latest")
+                             .size(1)
+                             .sort("timestamp", SortOrder::Desc)))
+                     .send()?;
+
+if let Some(by_user) = response.agg::<Terms>("by_user") {
+    for bucket in by_user?.buckets() {
+        let latest = bucket.agg::<TopHits<Message>>("latest").transpose()?;
+
+        for message in latest.iter().flat_map(|latest| latest.documents()) {
+            println!("{}: {}", bucket.key(), message.text);
+        }
+    }
+}
+# Ok(())
+# }
+```
+
+[docs-terms]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-bucket-terms-aggregation.html
+[SearchResponse.agg]: struct.SearchResponse.html#method.agg
+*/
+#[derive(Deserialize, Debug, Clone)]
+pub struct Terms {
+    sum_other_doc_count: Option<u64>,
+    buckets: Vec<TermsBucket>,
+}
+
+impl Terms {
+    /** The number of documents that didn't make it into any of the returned buckets. */
+    pub fn sum_other_doc_count(&self) -> Option<u64> {
+        self.sum_other_doc_count
+    }
+
+    /** Iterate over the buckets in the aggregation. */
+    pub fn buckets(&self) -> Iter<'_, TermsBucket> {
+        self.buckets.iter()
+    }
+
+    /** Convert the aggregation into its buckets. */
+    pub fn into_buckets(self) -> Vec<TermsBucket> {
+        self.buckets
+    }
+}
+
+/** A single bucket in a `terms` aggregation. */
+#[derive(Deserialize, Debug, Clone)]
+pub struct TermsBucket {
+    key: Value,
+    key_as_string: Option<String>,
+    doc_count: u64,
+    #[serde(flatten)]
+    aggs: Map<String, Value>,
+}
+
+impl TermsBucket {
+    /** The term for the bucket. */
+    pub fn key(&self) -> &Value {
+        &self.key
+    }
+
+    /** The term for the bucket, formatted by Elasticsearch. */
+    pub fn key_as_string(&self) -> Option<&str> {
+        self.key_as_string.as_deref()
+    }
+
+    /** The number of documents in the bucket. */
+    pub fn doc_count(&self) -> u64 {
+        self.doc_count
+    }
+
+    /**
+    Parse the sub-aggregation called `name` into a typed result.
+
+    Returns `None` if the bucket doesn't contain a sub-aggregation with the given name.
+    */
+    pub fn agg<T>(&self, name: &str) -> Option<Result<T, ParseError>>
+    where
+        T: DeserializeOwned,
+    {
+        parse_agg(Some(&self.aggs), name)
+    }
+
+    /** Get a reference to the raw sub-aggregation values. */
+    pub fn aggs_raw(&self) -> &Map<String, Value> {
+        &self.aggs
+    }
+}
+
+/**
+The hits returned by a [`top_hits`][docs-top-hits] aggregation.
+
+Hits are parsed the same way as the hits in a [`SearchResponse`][SearchResponse], so the source documents are deserialised into `T`.
+A `top_hits` aggregation is usually a sub-aggregation of a bucket aggregation, like [`Terms`][Terms].
+
+[docs-top-hits]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-top-hits-aggregation.html
+[SearchResponse]: struct.SearchResponse.html
+[Terms]: struct.Terms.html
+*/
+#[derive(Deserialize, Debug)]
+pub struct TopHits<T> {
+    hits: HitsWrapper<T>,
+}
+
+impl<T> TopHits<T> {
+    /** The total number of documents in the bucket. */
+    pub fn total(&self) -> u64 {
+        self.hits.total.value
+    }
+
+    /** The max score for documents in the hits. */
+    pub fn max_score(&self) -> Option<f32> {
+        self.hits.max_score
+    }
+
+    /** Iterate over the hits. */
+    pub fn hits(&self) -> Hits<T> {
+        Hits::new(&self.hits)
+    }
+
+    /** Convert the aggregation into an iterator that consumes the hits. */
+    pub fn into_hits(self) -> IntoHits<T> {
+        IntoHits::new(self.hits)
+    }
+
+    /**
+    Iterate over the documents in the hits.
+
+    This iterator emits just the `_source` field for the hits.
+    */
+    pub fn documents(&self) -> Documents<T> {
+        Documents::new(&self.hits)
+    }
+
+    /** Convert the aggregation into an iterator that consumes the documents. */
+    pub fn into_documents(self) -> IntoDocuments<T> {
+        IntoDocuments::new(self.hits)
+    }
+}
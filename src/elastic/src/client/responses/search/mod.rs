@@ -11,7 +11,14 @@ use serde_json::{
 use super::common::Shards;
 
 use crate::{
-    http::receiver::IsOkOnSuccess,
+    error::{
+        ApiError,
+        Error,
+    },
+    http::receiver::{
+        IsOkOnSuccess,
+        ParseError,
+    },
     types::document::{
         Id,
         Index,
@@ -26,6 +33,10 @@ use std::{
     vec::IntoIter,
 };
 
+mod aggs;
+
+pub use self::aggs::*;
+
 /**
 Response for a [search request][search-req].
 
@@ -58,6 +69,23 @@ for hit in response.hits() {
 }
 ```
 
+Hits carry their metadata alongside the source document:
+
+```no_run
+# use serde_json::Value;
+# use elastic::prelude::*;
+# fn do_request() -> SearchResponse<Value> { unimplemented!() }
+let response: SearchResponse<Value> = do_request();
+
+for hit in response.hits() {
+    println!("{}/{} ({:?})", hit.index(), hit.id(), hit.routing());
+    println!("matched: {:?}", hit.matched_queries());
+}
+
+// Take ownership of just the source documents
+let docs: Vec<Value> = response.into_documents().collect();
+```
+
 [search-req]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-search.html
 [metric-aggs]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics.html
 [stats-aggs]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-aggregations-metrics-stats-aggregation.html
@@ -71,21 +99,77 @@ pub struct SearchResponse<T> {
     hits: HitsWrapper<T>,
     aggregations: Option<AggsWrapper>,
     status: Option<u16>,
+    #[serde(rename = "_scroll_id")]
+    scroll_id: Option<String>,
+    pit_id: Option<String>,
 }
 
 /** Struct to hold the search's Hits, serializable to type `T` or `serde_json::Value`. */
 #[derive(Deserialize, Debug)]
 struct HitsWrapper<T> {
+    #[serde(default)]
     total: HitsTotal,
     max_score: Option<f32>,
     #[serde(rename = "hits")]
     inner: Vec<Hit<T>>,
 }
 
+/**
+The total number of hits.
+
+Older versions of Elasticsearch return the total as a number.
+Newer versions return an object with a `value` and a `relation`, where the relation says whether the value is exact or a lower bound.
+If the total isn't returned at all, like when `track_total_hits` is `false`, then it's treated as a lower bound of `0`.
+*/
 #[derive(Deserialize, Debug)]
+#[serde(from = "HitsTotalRepr")]
 struct HitsTotal {
     value: u64,
-    relation: Option<String>,
+    relation: HitsTotalRelation,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HitsTotalRepr {
+    Count(u64),
+    Object {
+        value: u64,
+        #[serde(default)]
+        relation: HitsTotalRelation,
+    },
+}
+
+impl From<HitsTotalRepr> for HitsTotal {
+    fn from(total: HitsTotalRepr) -> Self {
+        match total {
+            HitsTotalRepr::Count(value) => HitsTotal {
+                value,
+                relation: HitsTotalRelation::Eq,
+            },
+            HitsTotalRepr::Object { value, relation } => HitsTotal { value, relation },
+        }
+    }
+}
+
+impl Default for HitsTotal {
+    fn default() -> Self {
+        HitsTotal {
+            value: 0,
+            relation: HitsTotalRelation::Gte,
+        }
+    }
+}
+
+/** How the total number of hits relates to the actual number of matching documents. */
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HitsTotalRelation {
+    /** The total is exactly the number of matching documents. */
+    #[serde(rename = "eq")]
+    #[default]
+    Eq,
+    /** The total is a lower bound on the number of matching documents. */
+    #[serde(rename = "gte")]
+    Gte,
 }
 
 impl<T> SearchResponse<T> {
@@ -109,11 +193,102 @@ impl<T> SearchResponse<T> {
         self.status
     }
 
-    /** The total number of documents that matched the search query. */
+    /**
+    The id to fetch the next page of results with, if the search was a [scroll][docs-scroll].
+
+    [docs-scroll]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-scroll.html
+    */
+    pub fn scroll_id(&self) -> Option<&str> {
+        self.scroll_id.as_deref()
+    }
+
+    /**
+    The id of the [point in time][docs-pit] to fetch the next page of results with, if the search used one.
+
+    [docs-pit]: https://www.elastic.co/guide/en/elasticsearch/reference/current/point-in-time-api.html
+    */
+    pub fn pit_id(&self) -> Option<&str> {
+        self.pit_id.as_deref()
+    }
+
+    /**
+    Whether or not the response only contains partial results.
+
+    Results are partial if the search timed out or any shards failed.
+    */
+    pub fn is_partial(&self) -> bool {
+        self.timed_out || self.shards.failed() > 0
+    }
+
+    /**
+    Convert a response with partial results into an error.
+
+    Returns an [`ApiError::PartialResults`][ApiError.PartialResults] if the search timed out or any shards failed.
+
+    # Examples
+
+    ```no_run
+    # use serde_json::Value;
+    # use elastic::prelude::*;
+    # use elastic::error::{ApiError, Error};
+    # fn do_request() -> SearchResponse<Value> { unimplemented!() }
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    match do_request().into_complete() {
+        Ok(response) => println!("{} hits", response.total()),
+        Err(Error::Api(ApiError::PartialResults { failed, .. })) => {
+            println!("{} shards failed", failed)
+        }
+        Err(e) => return Err(e.into()),
+    }
+    # Ok(())
+    # }
+    ```
+
+    [ApiError.PartialResults]: ../../../error/enum.ApiError.html#variant.PartialResults
+    */
+    pub fn into_complete(self) -> Result<Self, Error> {
+        if !self.is_partial() {
+            return Ok(self);
+        }
+
+        let reason = self
+            .shards
+            .failures()
+            .first()
+            .and_then(|failure| failure.reason())
+            .map(|reason| reason.to_owned());
+
+        Err(Error::Api(ApiError::PartialResults {
+            failed: self.shards.failed(),
+            timed_out: self.timed_out,
+            reason,
+        }))
+    }
+
+    /**
+    The total number of documents that matched the search query.
+
+    The total may only be a lower bound on the number of matching documents.
+    Use [`is_exact`](#method.is_exact) to check whether it's exact.
+    */
     pub fn total(&self) -> u64 {
         self.hits.total.value
     }
 
+    /**
+    Whether or not the total is exactly the number of matching documents.
+
+    Elasticsearch stops counting hits after a threshold unless `track_total_hits` is set to `true`.
+    */
+    pub fn is_exact(&self) -> bool {
+        self.relation() == HitsTotalRelation::Eq
+    }
+
+    /** How the total relates to the actual number of matching documents. */
+    pub fn relation(&self) -> HitsTotalRelation {
+        self.hits.total.relation
+    }
+
     /** The max score for documents that matched the search query. */
     pub fn max_score(&self) -> Option<f32> {
         self.hits.max_score
@@ -158,6 +333,27 @@ impl<T> SearchResponse<T> {
     pub fn aggs_raw(&self) -> Option<&Value> {
         self.aggregations.as_ref().map(|wrapper| &wrapper.0)
     }
+
+    /**
+    Parse the aggregation called `name` into a typed result, like a [`DateHistogram`][DateHistogram], [`Terms`][Terms] or [`TopHits`][TopHits].
+
+    Returns `None` if the response doesn't contain an aggregation with the given name.
+
+    [DateHistogram]: struct.DateHistogram.html
+    [Terms]: struct.Terms.html
+    [TopHits]: struct.TopHits.html
+    */
+    pub fn agg<TAgg>(&self, name: &str) -> Option<Result<TAgg, ParseError>>
+    where
+        TAgg: DeserializeOwned,
+    {
+        parse_agg(
+            self.aggregations
+                .as_ref()
+                .and_then(|wrapper| wrapper.0.as_object()),
+            name,
+        )
+    }
 }
 
 impl<T: DeserializeOwned> IsOkOnSuccess for SearchResponse<T> {}
@@ -256,7 +452,7 @@ pub struct Hit<T> {
     #[serde(rename = "_id")]
     id: String,
     #[serde(rename = "_version")]
-    version: Option<u32>,
+    version: Option<i64>,
     #[serde(rename = "_score")]
     score: Option<f32>,
     #[serde(rename = "_source")]
@@ -265,6 +461,8 @@ pub struct Hit<T> {
     routing: Option<String>,
     highlight: Option<Value>,
     sort: Option<Value>,
+    #[serde(default)]
+    matched_queries: Vec<String>,
 }
 
 impl<T> Hit<T> {
@@ -294,7 +492,7 @@ impl<T> Hit<T> {
     }
 
     /** The version of the hit. */
-    pub fn version(&self) -> Option<u32> {
+    pub fn version(&self) -> Option<i64> {
         self.version
     }
 
@@ -303,6 +501,11 @@ impl<T> Hit<T> {
         self.score
     }
 
+    /** The routing value used to index the hit, if one was given. */
+    pub fn routing(&self) -> Option<&str> {
+        self.routing.as_deref()
+    }
+
     /**
     A reference to the [highlighted] snippets of the part(s) of the field(s)
     matching the search query.
@@ -319,6 +522,17 @@ impl<T> Hit<T> {
     pub fn sort(&self) -> Option<&Value> {
         self.sort.as_ref()
     }
+
+    /**
+    The names of the [named queries][named-queries] that matched the hit.
+
+    This will be empty if the search query didn't contain any named queries.
+
+    [named-queries]: https://www.elastic.co/guide/en/elasticsearch/reference/current/search-request-named-queries-and-filters.html
+    */
+    pub fn matched_queries(&self) -> &[String] {
+        &self.matched_queries
+    }
 }
 
 /** Type Struct to hold a generic `serde_json::Value` tree of the aggregation results. */
@@ -0,0 +1,98 @@
+/*!
+Response types for [search template requests](https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html).
+
+Executing a search template returns a standard [`SearchResponse`](../search/struct.SearchResponse.html).
+*/
+
+use crate::http::StatusCode;
+use serde_json::Value;
+
+use crate::http::receiver::{
+    HttpResponseHead,
+    IsOk,
+    IsOkOnSuccess,
+    MaybeOkResponse,
+    ParseError,
+    ResponseBody,
+    Unbuffered,
+};
+
+/** Response for a [get search template request](https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#_retrieving_templates). */
+#[derive(Deserialize, Debug)]
+pub struct GetSearchTemplateResponse {
+    #[serde(rename = "_id")]
+    id: String,
+    found: bool,
+    script: Option<StoredTemplate>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StoredTemplate {
+    lang: String,
+    source: String,
+}
+
+impl GetSearchTemplateResponse {
+    /** The id of the template. */
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /** Whether or not a matching template was found. */
+    pub fn found(&self) -> bool {
+        self.found
+    }
+
+    /** The language of the template, usually `mustache`. */
+    pub fn lang(&self) -> Option<&str> {
+        self.script.as_ref().map(|script| &*script.lang)
+    }
+
+    /** The source of the template. */
+    pub fn source(&self) -> Option<&str> {
+        self.script.as_ref().map(|script| &*script.source)
+    }
+}
+
+impl IsOk for GetSearchTemplateResponse {
+    fn is_ok<B: ResponseBody>(
+        head: HttpResponseHead,
+        body: Unbuffered<B>,
+    ) -> Result<MaybeOkResponse<B>, ParseError> {
+        match head.status() {
+            status if status.is_success() => Ok(MaybeOkResponse::ok(body)),
+            StatusCode::NOT_FOUND => {
+                // A missing template is a 404 without a root 'error' node
+                let (maybe_err, body) = body.body()?;
+
+                let is_ok = maybe_err
+                    .as_object()
+                    .and_then(|maybe_err| maybe_err.get("error"))
+                    .is_none();
+
+                Ok(MaybeOkResponse::new(is_ok, body))
+            }
+            _ => Ok(MaybeOkResponse::err(body)),
+        }
+    }
+}
+
+/** Response for a [render search template request](https://www.elastic.co/guide/en/elasticsearch/reference/current/search-template.html#_validating_templates). */
+#[derive(Deserialize, Debug)]
+pub struct RenderSearchTemplateResponse {
+    template_output: Value,
+}
+
+impl RenderSearchTemplateResponse {
+    /** The search body produced by the template. */
+    pub fn template_output(&self) -> &Value {
+        &self.template_output
+    }
+
+    /** Convert the response into the search body produced by the template. */
+    pub fn into_template_output(self) -> Value {
+        self.template_output
+    }
+}
+
+impl IsOkOnSuccess for RenderSearchTemplateResponse {}
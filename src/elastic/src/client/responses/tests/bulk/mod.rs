@@ -99,6 +99,119 @@ fn success_parse_with_errors_errors_only() {
     assert_eq!(1, deserialized.iter().count());
 }
 
+#[test]
+fn success_parse_item_error_reasons() {
+    let f = include_bytes!("bulk_item_errors.json");
+    let deserialized = parse::<BulkResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let errors: Vec<_> = deserialized.into_iter().filter_map(Result::err).collect();
+
+    let reasons: Vec<_> = errors
+        .iter()
+        .map(|item| (item.position(), item.status(), item.reason().clone()))
+        .collect();
+
+    assert_eq!(
+        vec![
+            (
+                0,
+                400,
+                bulk::BulkErrorReason::MapperParsing {
+                    reason: "failed to parse field [timestamp] of type [date]".to_owned()
+                }
+            ),
+            (
+                1,
+                409,
+                bulk::BulkErrorReason::VersionConflict {
+                    reason: "[bulk-ty][2]: version conflict, document already exists (current version [1])".to_owned()
+                }
+            ),
+            (
+                3,
+                429,
+                bulk::BulkErrorReason::Rejected {
+                    reason: errors[2].err()["reason"].as_str().unwrap().to_owned()
+                }
+            ),
+            (
+                4,
+                404,
+                bulk::BulkErrorReason::IndexNotFound {
+                    index: "missing-index".to_owned()
+                }
+            ),
+            (
+                5,
+                503,
+                bulk::BulkErrorReason::Other {
+                    ty: Some("unavailable_shards_exception".to_owned()),
+                    reason: Some("[bulk-test][0] primary shard is not active Timeout: [1m]".to_owned())
+                }
+            ),
+        ],
+        reasons
+    );
+}
+
+#[test]
+fn success_parse_item_errors_retryable_errors_only() {
+    let f = include_bytes!("bulk_item_errors.json");
+    let deserialized = parse::<BulkErrorsResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let (retryable, permanent): (Vec<_>, Vec<_>) = deserialized
+        .into_iter()
+        .partition(|item| item.is_retryable());
+
+    assert_eq!(
+        vec!["4", "6"],
+        retryable
+            .iter()
+            .map(|item| item.id().as_str())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["1", "2", "5"],
+        permanent
+            .iter()
+            .map(|item| item.id().as_str())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![0, 1, 4],
+        permanent
+            .iter()
+            .map(|item| item.position())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn success_parse_item_errors_operation() {
+    let f = include_bytes!("bulk_item_errors.json");
+    let deserialized = parse::<BulkErrorsResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let sent = vec!["op 1", "op 2", "op 3", "op 4", "op 5", "op 6"];
+
+    let retry: Vec<_> = deserialized
+        .iter()
+        .filter(|item| item.is_retryable())
+        .filter_map(|item| item.operation(&sent))
+        .collect();
+
+    assert_eq!(vec![&"op 4", &"op 6"], retry);
+    assert_eq!(
+        None,
+        deserialized.iter().last().unwrap().operation(&sent[..2])
+    );
+}
+
 #[test]
 fn error_parse_action_request_validation() {
     let f = include_bytes!("../error/error_action_request_validation.json");
@@ -0,0 +1,38 @@
+use crate::{
+    client::responses::*,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+
+#[test]
+fn success_parse_green() {
+    let f = include_bytes!("cluster_health_green.json");
+    let deserialized = parse::<ClusterHealthResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!("testcluster", deserialized.cluster_name());
+    assert_eq!(ClusterHealthStatus::Green, deserialized.status());
+    assert!(!deserialized.timed_out());
+    assert_eq!(1, deserialized.number_of_nodes());
+}
+
+#[test]
+fn success_parse_timed_out() {
+    let f = include_bytes!("cluster_health_timed_out.json");
+    let deserialized = parse::<ClusterHealthResponse>()
+        .from_slice(StatusCode::REQUEST_TIMEOUT, f as &[_])
+        .unwrap();
+
+    assert_eq!(ClusterHealthStatus::Red, deserialized.status());
+    assert!(deserialized.timed_out());
+    assert_eq!(1, deserialized.unassigned_shards());
+}
+
+#[test]
+fn health_status_ordering() {
+    assert!(ClusterHealthStatus::Red < ClusterHealthStatus::Yellow);
+    assert!(ClusterHealthStatus::Yellow < ClusterHealthStatus::Green);
+}
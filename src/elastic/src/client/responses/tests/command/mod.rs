@@ -1,7 +1,11 @@
 use crate::{
     client::responses::*,
+    error::*,
     http::{
-        receiver::parse,
+        receiver::{
+            parse,
+            ResponseError,
+        },
         StatusCode,
     },
 };
@@ -15,3 +19,22 @@ fn success_parse_command_response() {
 
     assert!(deserialized.acknowledged());
 }
+
+#[test]
+fn error_parse_resource_already_exists() {
+    let f = include_bytes!("../error/error_resource_already_exists.json");
+    let deserialized = parse::<CommandResponse>()
+        .from_slice(StatusCode::BAD_REQUEST, f as &[_])
+        .unwrap_err();
+
+    let valid = match deserialized {
+        ResponseError::Api(ApiError::IndexAlreadyExists { ref index })
+            if index == "logs-2019.06.01" =>
+        {
+            true
+        }
+        _ => false,
+    };
+
+    assert!(valid);
+}
@@ -47,6 +47,47 @@ fn success_into_document() {
     }
 }
 
+#[test]
+fn success_parse_concurrency_metadata() {
+    let f = include_bytes!("get_found_seq_no.json");
+    let deserialized = parse::<GetResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(Some(3), deserialized.version());
+    assert_eq!(Some(10), deserialized.sequence_number());
+    assert_eq!(Some(2), deserialized.primary_term());
+}
+
+#[test]
+fn success_parse_external_version() {
+    let f = include_bytes!("get_found_external_version.json");
+    let deserialized = parse::<GetResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    // External versions like epoch millis don't fit in a `u32`
+    assert_eq!(Some(1_561_632_000_000), deserialized.version());
+}
+
+#[test]
+fn success_into_document_with_metadata() {
+    let f = include_bytes!("get_found_seq_no.json");
+    let deserialized = parse::<GetResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let (doc, metadata) = deserialized.into_document_with_metadata().unwrap();
+
+    assert_eq!(json!({ "id": 1 }), doc);
+    assert_eq!("testindex", metadata.index());
+    assert_eq!("_doc", metadata.ty());
+    assert_eq!("1", metadata.id());
+    assert_eq!(Some(3), metadata.version());
+    assert_eq!(Some(10), metadata.sequence_number());
+    assert_eq!(Some(2), metadata.primary_term());
+}
+
 #[test]
 fn success_parse_not_found_doc_response() {
     let f = include_bytes!("get_not_found.json");
@@ -72,3 +113,23 @@ fn error_parse_index_not_found() {
 
     assert!(valid);
 }
+
+#[test]
+fn success_parse_stored_fields() {
+    let f = include_bytes!("get_found_stored_fields.json");
+    let deserialized = parse::<GetResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(1, deserialized.fields().len());
+    assert_eq!(
+        Some(&json!(["rust", "elasticsearch"])),
+        deserialized.field("tags")
+    );
+    assert_eq!(
+        Some("Stored fields"),
+        deserialized
+            .document()
+            .and_then(|doc| doc["title"].as_str())
+    );
+}
@@ -0,0 +1,61 @@
+use crate::{
+    client::responses::*,
+    error::*,
+    http::{
+        receiver::{
+            parse,
+            ResponseError,
+        },
+        StatusCode,
+    },
+};
+use serde_json::Value;
+
+#[test]
+fn success_parse_found_source_response() {
+    let deserialized = parse::<GetSourceResponse<Value>>()
+        .from_slice(
+            StatusCode::OK,
+            br#"{ "id": 1, "title": "A document" }"# as &[_],
+        )
+        .unwrap();
+
+    assert!(deserialized.found());
+    assert_eq!(
+        Some(json!({ "id": 1, "title": "A document" })),
+        deserialized.into_document()
+    );
+}
+
+#[test]
+fn success_parse_not_found_source_response() {
+    let f = include_bytes!("source_not_found.json");
+    let deserialized = parse::<GetSourceResponse<Value>>()
+        .from_slice(StatusCode::NOT_FOUND, f as &[_])
+        .unwrap();
+
+    assert!(!deserialized.found());
+    assert!(deserialized.into_document().is_none());
+}
+
+#[test]
+fn success_parse_not_found_empty_source_response() {
+    let deserialized = parse::<GetSourceResponse<Value>>()
+        .from_slice(StatusCode::NOT_FOUND, b"" as &[_])
+        .unwrap();
+
+    assert!(deserialized.document().is_none());
+}
+
+#[test]
+fn error_parse_index_not_found() {
+    let f = include_bytes!("../error/error_index_not_found.json");
+    let deserialized = parse::<GetSourceResponse<Value>>()
+        .from_slice(StatusCode::NOT_FOUND, f as &[_])
+        .unwrap_err();
+
+    match deserialized {
+        ResponseError::Api(ApiError::IndexNotFound { ref index }) => assert_eq!("carrots", index),
+        _ => panic!("expected an index not found error"),
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    client::responses::*,
+    client::responses::{
+        common::DocumentResult,
+        *,
+    },
     error::*,
     http::{
         receiver::{
@@ -18,12 +21,30 @@ fn success_parse_response() {
         .unwrap();
 
     assert!(deserialized.created());
+    assert_eq!(DocumentResult::Created, deserialized.result());
     assert_eq!("testindex", deserialized.index());
     assert_eq!("testtype", deserialized.ty());
     assert_eq!("1", deserialized.id());
     assert_eq!(Some(1), deserialized.version());
 }
 
+#[test]
+fn success_parse_updated_response() {
+    let f = include_bytes!("index_updated.json");
+    let deserialized = parse::<IndexResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert!(!deserialized.created());
+    assert_eq!(DocumentResult::Updated, deserialized.result());
+
+    let metadata = deserialized.metadata();
+    assert_eq!("testindex", metadata.index());
+    assert_eq!(Some(4), metadata.version());
+    assert_eq!(Some(11), metadata.sequence_number());
+    assert_eq!(Some(2), metadata.primary_term());
+}
+
 #[test]
 fn error_parse_index_already_exists() {
     let f = include_bytes!("../error/error_index_already_exists.json");
@@ -40,3 +61,44 @@ fn error_parse_index_already_exists() {
 
     assert!(valid);
 }
+
+#[test]
+fn error_parse_document_already_exists() {
+    let f = include_bytes!("../error/error_document_already_exists.json");
+    let deserialized = parse::<IndexResponse>()
+        .from_slice(StatusCode::CONFLICT, f as &[_])
+        .unwrap_err();
+
+    let valid = match deserialized {
+        ResponseError::Api(ApiError::DocumentAlreadyExists { ref index, ref id })
+            if index == "carrots" && id == "1" =>
+        {
+            true
+        }
+        _ => false,
+    };
+
+    assert!(valid);
+}
+
+#[test]
+fn error_parse_other_version_conflict() {
+    let f = br#"{
+        "error": {
+            "type": "version_conflict_engine_exception",
+            "reason": "[1]: version conflict, required seqNo [4], primary term [1]. current document has seqNo [5] and primary term [1]",
+            "index": "carrots"
+        },
+        "status": 409
+    }"#;
+    let deserialized = parse::<IndexResponse>()
+        .from_slice(StatusCode::CONFLICT, f as &[_])
+        .unwrap_err();
+
+    let valid = match deserialized {
+        ResponseError::Parse(_) => true,
+        _ => false,
+    };
+
+    assert!(valid);
+}
@@ -0,0 +1,40 @@
+use crate::{
+    client::responses::*,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+
+#[test]
+fn deserialise_index_segments() {
+    let f = include_bytes!("index_segments.json");
+    let deserialized = parse::<IndicesSegmentsResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let index = deserialized.index("testindex").unwrap();
+
+    assert_eq!(3, index.num_primary_search_segments());
+
+    let shards = index.shards();
+    assert_eq!(2, shards.len());
+
+    let shard = &shards[0];
+    assert_eq!(0, shard.shard());
+    assert!(shard.is_primary());
+    assert_eq!("STARTED", shard.state());
+    assert_eq!(Some("zDC_RorJQCao9xf9pg3Fvw"), shard.node());
+    assert_eq!(1, shard.num_committed_segments());
+    assert_eq!(2, shard.num_search_segments());
+    assert_eq!(6000, shard.size_in_bytes());
+    assert_eq!(1410, shard.memory_in_bytes());
+
+    let segment = &shard.segments()[1];
+    assert_eq!("_1", segment.name());
+    assert_eq!(5, segment.num_docs());
+    assert_eq!(None, segment.memory_in_bytes());
+    assert!(!segment.is_committed());
+
+    assert_eq!(1, shards[1].shard());
+}
@@ -0,0 +1,39 @@
+use crate::{
+    client::responses::*,
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+
+#[test]
+fn deserialise_index_stats() {
+    let f = include_bytes!("index_stats.json");
+    let deserialized = parse::<IndicesStatsResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let primaries = deserialized.all().primaries();
+
+    assert_eq!(Some(1200), primaries.docs_count());
+    assert_eq!(Some(30), primaries.docs_deleted());
+    assert_eq!(Some(524288), primaries.store_size_in_bytes());
+    assert_eq!(Some(12), primaries.segments_count());
+    assert_eq!(Some(40960), primaries.segments_memory_in_bytes());
+
+    let index = deserialized.index("testindex").unwrap();
+
+    assert_eq!(Some(1200), index.total().docs_count());
+    assert_eq!(1, deserialized.indices().count());
+}
+
+#[test]
+fn deserialise_index_stats_missing_metrics() {
+    let f = br#"{ "_shards": { "total": 1, "successful": 1, "failed": 0 }, "_all": { "primaries": {}, "total": {} }, "indices": {} }"#;
+    let deserialized = parse::<IndicesStatsResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(None, deserialized.all().primaries().docs_count());
+    assert_eq!(None, deserialized.all().total().segments_count());
+}
@@ -0,0 +1,35 @@
+use crate::{
+    client::{
+        responses::*,
+        Version,
+    },
+    http::{
+        receiver::parse,
+        StatusCode,
+    },
+};
+
+#[test]
+fn success_parse_info_response() {
+    let f = include_bytes!("info_success.json");
+    let deserialized = parse::<InfoResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!("es01", deserialized.name());
+    assert_eq!("docker-cluster", deserialized.cluster_name());
+    assert_eq!(Some("HvrDhuxoTmSsA1rwZ5DwHA"), deserialized.cluster_uuid());
+    assert_eq!(&Version::new(7, 3, 2), deserialized.version().number());
+    assert_eq!(Some("default"), deserialized.version().build_flavor());
+}
+
+#[test]
+fn success_parse_info_response_without_flavor() {
+    let f = include_bytes!("../ping/ping_success.json");
+    let deserialized = parse::<InfoResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(&Version::new(5, 0, 0), deserialized.version().number());
+    assert_eq!(None, deserialized.version().build_flavor());
+}
@@ -1,10 +1,16 @@
 mod bulk;
+mod cluster_health;
 mod command;
 mod document_delete;
 mod document_get;
+mod document_get_source;
 mod document_index;
 mod document_update;
 mod index_exists;
+mod index_segments;
+mod index_stats;
+mod info;
 mod nodes_info;
 mod ping;
 mod search;
+mod search_template;
@@ -21,6 +21,89 @@ fn success_parse_empty() {
     assert_eq!(deserialized.hits().count(), 0);
 }
 
+#[test]
+fn success_parse_complete() {
+    let f = include_bytes!("search_empty.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(0, deserialized.shards().skipped());
+    assert!(deserialized.shards().failures().is_empty());
+    assert!(!deserialized.is_partial());
+    assert!(deserialized.into_complete().is_ok());
+}
+
+#[test]
+fn success_parse_shard_failures() {
+    let f = include_bytes!("search_shard_failures.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let shards = deserialized.shards();
+    assert_eq!(3, shards.total());
+    assert_eq!(2, shards.successful());
+    assert_eq!(1, shards.skipped());
+    assert_eq!(1, shards.failed());
+
+    let failure = &shards.failures()[0];
+    assert_eq!(Some(0), failure.shard());
+    assert_eq!(Some("logs-2019"), failure.index());
+    assert_eq!(Some("Ht8GYKffTn2Y1Vpo5jYQ7A"), failure.node());
+    assert_eq!(Some("query_shard_exception"), failure.reason_type());
+    assert_eq!(
+        Some("failed to create query: field [count] is not a number"),
+        failure.reason()
+    );
+
+    assert!(deserialized.is_partial());
+    assert_eq!(1, deserialized.hits().count());
+}
+
+#[test]
+fn error_into_complete_shard_failures() {
+    let f = include_bytes!("search_shard_failures.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    match deserialized.into_complete() {
+        Err(Error::Api(ApiError::PartialResults {
+            failed,
+            timed_out,
+            reason,
+        })) => {
+            assert_eq!(1, failed);
+            assert!(!timed_out);
+            assert_eq!(
+                Some("failed to create query: field [count] is not a number".to_owned()),
+                reason
+            );
+        }
+        res => panic!("expected partial results error, got {:?}", res),
+    }
+}
+
+#[test]
+fn error_into_complete_timed_out() {
+    let f = include_bytes!("search_timed_out.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert!(deserialized.timed_out());
+
+    match deserialized.into_complete() {
+        Err(Error::Api(ApiError::PartialResults {
+            failed: 0,
+            timed_out: true,
+            reason: None,
+        })) => (),
+        res => panic!("expected partial results error, got {:?}", res),
+    }
+}
+
 #[test]
 fn success_parse_hits_simple() {
     let f = include_bytes!("search_hits_only.json");
@@ -204,6 +287,119 @@ fn success_parse_simple_aggs_no_empty_first_record() {
     }
 }
 
+#[test]
+fn success_parse_date_histogram_aggs() {
+    use crate::client::responses::search::DateHistogram;
+    use chrono::{
+        TimeZone,
+        Utc,
+    };
+
+    #[derive(Deserialize)]
+    struct Avg {
+        value: Option<f64>,
+    }
+
+    let f = include_bytes!("search_aggregation_date_histogram.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let histogram = deserialized
+        .agg::<DateHistogram>("per_day")
+        .unwrap()
+        .unwrap();
+
+    let buckets: Vec<_> = histogram.buckets().collect();
+
+    assert_eq!(3, buckets.len());
+    assert_eq!(
+        Utc.with_ymd_and_hms(2019, 10, 1, 0, 0, 0).unwrap(),
+        buckets[0].key()
+    );
+    assert_eq!(Some("2019-10-01"), buckets[0].key_as_string());
+    assert_eq!(3, buckets[0].doc_count());
+
+    let avg = buckets[0].agg::<Avg>("avg_age").unwrap().unwrap();
+    assert_eq!(Some(31.5), avg.value);
+
+    let avg = buckets[1].agg::<Avg>("avg_age").unwrap().unwrap();
+    assert_eq!(None, avg.value);
+}
+
+#[test]
+fn success_parse_top_hits_aggs() {
+    use crate::client::responses::search::{
+        Terms,
+        TopHits,
+    };
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Message {
+        user: String,
+        text: String,
+    }
+
+    let f = include_bytes!("search_aggregation_top_hits.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let by_user = deserialized.agg::<Terms>("by_user").unwrap().unwrap();
+
+    assert_eq!(Some(3), by_user.sum_other_doc_count());
+
+    let buckets: Vec<_> = by_user.buckets().collect();
+
+    assert_eq!(2, buckets.len());
+    assert_eq!("kimchy", buckets[0].key());
+    assert_eq!(5, buckets[0].doc_count());
+
+    let latest = buckets[0]
+        .agg::<TopHits<Message>>("latest")
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(5, latest.total());
+
+    let hit = latest.hits().next().unwrap();
+
+    assert_eq!("messages", hit.index());
+    assert_eq!("4", hit.id());
+    assert_eq!(
+        Some(&Message {
+            user: "kimchy".to_owned(),
+            text: "the latest message".to_owned(),
+        }),
+        hit.document()
+    );
+
+    let latest = buckets[1]
+        .agg::<TopHits<Message>>("latest")
+        .unwrap()
+        .unwrap();
+
+    let docs: Vec<_> = latest.into_documents().collect();
+
+    assert_eq!(
+        vec![Message {
+            user: "elastic".to_owned(),
+            text: "another message".to_owned(),
+        }],
+        docs
+    );
+}
+
+#[test]
+fn success_parse_missing_agg() {
+    let f = include_bytes!("search_aggregation_date_histogram.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert!(deserialized.agg::<Value>("missing").is_none());
+}
+
 #[test]
 fn success_parse_hits_simple_as_value() {
     let f = include_bytes!("search_hits_only.json");
@@ -228,3 +424,108 @@ fn error_parse_index_not_found() {
 
     assert!(valid);
 }
+
+#[test]
+fn success_parse_hits_metadata() {
+    let f = include_bytes!("search_hits_metadata.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let hits: Vec<_> = deserialized.hits().collect();
+
+    assert_eq!("accounts", &*hits[0].index());
+    assert_eq!("1", &*hits[0].id());
+    assert_eq!(Some(1.3862944), hits[0].score());
+    assert_eq!(Some("user1"), hits[0].routing());
+    assert_eq!(Some(&json!([1492767886439u64])), hits[0].sort());
+    assert_eq!(
+        &["by_user".to_owned(), "recent".to_owned()],
+        hits[0].matched_queries()
+    );
+
+    assert_eq!(None, hits[1].routing());
+    assert_eq!(None, hits[1].sort());
+    assert!(hits[1].matched_queries().is_empty());
+}
+
+#[test]
+fn success_parse_hits_documents() {
+    let f = include_bytes!("search_hits_metadata.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let users: Vec<_> = deserialized
+        .documents()
+        .map(|doc| doc["user"].as_str().unwrap())
+        .collect();
+    assert_eq!(vec!["kimchy", "elastic"], users);
+
+    let docs: Vec<Value> = deserialized.into_documents().collect();
+    assert_eq!(
+        vec![json!({ "user": "kimchy" }), json!({ "user": "elastic" })],
+        docs
+    );
+}
+
+#[test]
+fn success_parse_total_object() {
+    let f = include_bytes!("search_empty.json");
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(2008, deserialized.total());
+    assert!(deserialized.is_exact());
+    assert_eq!(search::HitsTotalRelation::Eq, deserialized.relation());
+}
+
+#[test]
+fn success_parse_total_lower_bound() {
+    let f = br#"{
+        "took": 1,
+        "timed_out": false,
+        "_shards": { "total": 1, "successful": 1, "failed": 0 },
+        "hits": { "total": { "value": 10000, "relation": "gte" }, "max_score": null, "hits": [] }
+    }"#;
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(10000, deserialized.total());
+    assert!(!deserialized.is_exact());
+    assert_eq!(search::HitsTotalRelation::Gte, deserialized.relation());
+}
+
+#[test]
+fn success_parse_total_number() {
+    let f = br#"{
+        "took": 1,
+        "timed_out": false,
+        "_shards": { "total": 1, "successful": 1, "failed": 0 },
+        "hits": { "total": 81, "max_score": null, "hits": [] }
+    }"#;
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(81, deserialized.total());
+    assert!(deserialized.is_exact());
+}
+
+#[test]
+fn success_parse_total_missing() {
+    let f = br#"{
+        "took": 1,
+        "timed_out": false,
+        "_shards": { "total": 1, "successful": 1, "failed": 0 },
+        "hits": { "max_score": null, "hits": [] }
+    }"#;
+    let deserialized = parse::<SearchResponse<Value>>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!(0, deserialized.total());
+    assert!(!deserialized.is_exact());
+}
@@ -0,0 +1,65 @@
+use crate::{
+    client::responses::*,
+    http::{
+        receiver::{
+            parse,
+            ResponseError,
+        },
+        StatusCode,
+    },
+};
+
+#[test]
+fn success_parse_get_found() {
+    let f = include_bytes!("get_found.json");
+    let deserialized = parse::<GetSearchTemplateResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    assert_eq!("my-template", deserialized.id());
+    assert!(deserialized.found());
+    assert_eq!(Some("mustache"), deserialized.lang());
+    assert_eq!(
+        Some("{\"query\":{\"match\":{\"title\":\"{{query_string}}\"}}}"),
+        deserialized.source()
+    );
+}
+
+#[test]
+fn success_parse_get_not_found() {
+    let f = include_bytes!("get_not_found.json");
+    let deserialized = parse::<GetSearchTemplateResponse>()
+        .from_slice(StatusCode::NOT_FOUND, f as &[_])
+        .unwrap();
+
+    assert!(!deserialized.found());
+    assert!(deserialized.source().is_none());
+}
+
+#[test]
+fn error_parse_get_index_not_found() {
+    let f = include_bytes!("../error/error_index_not_found.json");
+    let deserialized = parse::<GetSearchTemplateResponse>()
+        .from_slice(StatusCode::NOT_FOUND, f as &[_])
+        .unwrap_err();
+
+    assert!(matches!(deserialized, ResponseError::Api(_)));
+}
+
+#[test]
+fn success_parse_render() {
+    let f = include_bytes!("render.json");
+    let deserialized = parse::<RenderSearchTemplateResponse>()
+        .from_slice(StatusCode::OK, f as &[_])
+        .unwrap();
+
+    let expected = json!({
+        "query": {
+            "match": {
+                "title": "rust"
+            }
+        }
+    });
+
+    assert_eq!(&expected, deserialized.template_output());
+}
@@ -5,17 +5,42 @@ use reqwest::{
 };
 use std::{
     error::Error as StdError,
+    path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
 use crate::{
-    client::Client,
+    client::{
+        requests::common::{
+            IdStrategies,
+            IdStrategy,
+        },
+        version::VersionCell,
+        Client,
+        DocumentIndexFn,
+        DocumentSerializer,
+    },
     error::{
         self,
         Error,
     },
     http::{
         sender::{
+            circuit_breaker::CircuitBreaker,
+            deadline::{
+                BlockingWorkers,
+                DEFAULT_BLOCKING_WORKERS,
+            },
+            recording::{
+                Recording,
+                RecordingSource,
+            },
+            slow_requests::{
+                SlowRequest,
+                SlowRequestFn,
+                SlowRequests,
+            },
             sniffed_nodes::SniffedNodesBuilder,
             NodeAddress,
             NodeAddressesBuilder,
@@ -69,6 +94,14 @@ pub struct SyncClientBuilder {
     nodes: NodeAddressesBuilder,
     params: SharedFluentBuilder<PreRequestParams>,
     pre_send: Option<Arc<SyncPreSend>>,
+    circuit_breaker: Option<CircuitBreaker>,
+    slow_request_threshold: Option<Duration>,
+    on_slow_request: Option<Arc<SlowRequestFn>>,
+    recording: Option<RecordingSource>,
+    document_serializer: Option<Arc<dyn DocumentSerializer>>,
+    document_index: Option<Arc<DocumentIndexFn>>,
+    id_strategies: IdStrategies,
+    deadline_workers: usize,
 }
 
 impl Default for SyncClientBuilder {
@@ -93,6 +126,14 @@ impl SyncClientBuilder {
             nodes: NodeAddressesBuilder::default(),
             params: SharedFluentBuilder::new(),
             pre_send: None,
+            circuit_breaker: None,
+            slow_request_threshold: None,
+            on_slow_request: None,
+            recording: None,
+            document_serializer: None,
+            document_index: None,
+            id_strategies: IdStrategies::default(),
+            deadline_workers: DEFAULT_BLOCKING_WORKERS,
         }
     }
 
@@ -105,6 +146,14 @@ impl SyncClientBuilder {
             nodes: NodeAddressesBuilder::default(),
             params: SharedFluentBuilder::new().value(params),
             pre_send: None,
+            circuit_breaker: None,
+            slow_request_threshold: None,
+            on_slow_request: None,
+            recording: None,
+            document_serializer: None,
+            document_index: None,
+            id_strategies: IdStrategies::default(),
+            deadline_workers: DEFAULT_BLOCKING_WORKERS,
         }
     }
 
@@ -242,6 +291,263 @@ impl SyncClientBuilder {
         self
     }
 
+    /**
+    Use a circuit breaker to stop sending requests to nodes that keep failing.
+
+    Nodes with an open circuit are skipped when choosing where to send a request.
+    Keep a clone of the `CircuitBreaker` to monitor the state of each node.
+
+    # Examples
+
+    ```no_run
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    # use elastic::http::sender::circuit_breaker::CircuitBreaker;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let breaker = CircuitBreaker::new(3, Duration::from_secs(10));
+
+    let builder = SyncClientBuilder::new()
+        .static_nodes(vec!["http://es1:9200", "http://es2:9200"])
+        .circuit_breaker(breaker.clone());
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+
+        self
+    }
+
+    /**
+    Report requests that take longer than the given threshold to get a response.
+
+    By default, slow requests are logged as a warning with their method, path, query string, elapsed time and the start of their body.
+    Use [`on_slow_request`](#method.on_slow_request) to handle them some other way.
+
+    # Examples
+
+    ```no_run
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let builder = SyncClientBuilder::new()
+        .slow_request_threshold(Duration::from_secs(2));
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn slow_request_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_request_threshold = Some(threshold);
+
+        self
+    }
+
+    /**
+    Call the given function instead of logging for requests slower than the [`slow_request_threshold`](#method.slow_request_threshold).
+
+    The function has no effect unless a threshold is set.
+    It's called on the thread that receives the response, so it shouldn't block.
+
+    # Examples
+
+    ```no_run
+    # use std::time::Duration;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let builder = SyncClientBuilder::new()
+        .slow_request_threshold(Duration::from_secs(2))
+        .on_slow_request(|req| {
+            println!("{} {} took {:?}", req.method(), req.url().path(), req.elapsed());
+        });
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn on_slow_request(
+        mut self,
+        on_slow_request: impl Fn(&SlowRequest) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_slow_request = Some(Arc::new(on_slow_request));
+
+        self
+    }
+
+    /**
+    Record each request and the response it gets to a file.
+
+    The file is created when the client is built, replacing any existing file.
+    Each request is written as a line of JSON with its method, path, query and body, along with the status and body of its response.
+    Request headers and node addresses aren't recorded, so the file can be shared without exposing credentials.
+    Use [`replay_from`](#method.replay_from) to build a client that serves the recorded responses.
+
+    Streamed request bodies can't be recorded.
+
+    # Examples
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let builder = SyncClientBuilder::new()
+        .record_to("interactions.jsonl");
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn record_to(mut self, path: impl Into<PathBuf>) -> Self {
+        self.recording = Some(RecordingSource::Record(path.into()));
+
+        self
+    }
+
+    /**
+    Serve responses from a file written by [`record_to`](#method.record_to) instead of sending requests.
+
+    Each request is answered by the first recorded response for a request with the same method, path, query and body.
+    A recorded response is only served once.
+    Sending a request that has no recorded response left returns an error.
+
+    # Examples
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let client = SyncClientBuilder::new()
+        .replay_from("interactions.jsonl")
+        .build()?;
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn replay_from(mut self, path: impl Into<PathBuf>) -> Self {
+        self.recording = Some(RecordingSource::Replay(path.into()));
+
+        self
+    }
+
+    /**
+    Use the given serialiser for documents in index and bulk requests.
+
+    By default, documents are serialised with `serde_json`.
+
+    # Examples
+
+    Sort the fields of documents by key so the same document always has the same body:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # use elastic::client::SortedKeysSerializer;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let builder = SyncClientBuilder::new()
+        .document_serializer(SortedKeysSerializer);
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn document_serializer(mut self, serializer: impl DocumentSerializer + 'static) -> Self {
+        self.document_serializer = Some(Arc::new(serializer));
+
+        self
+    }
+
+    /**
+    Transform the indices of document types before they're used in requests.
+
+    The function is called with the index name of a document type that implements `DocumentType`, like one that derives `ElasticType`.
+    Its result is used as the index for document requests, searches and bulk operations that take their index from the document type.
+    Indices that are set explicitly on a request or operation aren't transformed.
+
+    # Examples
+
+    Prefix the indices of all document types with the name of the environment:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let environment = "staging";
+
+    let builder = SyncClientBuilder::new()
+        .document_index_name(move |index| format!("{}_{}", environment, index));
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn document_index_name(
+        mut self,
+        document_index: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.document_index = Some(Arc::new(document_index));
+
+        self
+    }
+
+    /**
+    Generate ids for documents of a type that are indexed without one using the given [`IdStrategy`][IdStrategy].
+
+    The strategy is used by index requests and bulk index or create operations for documents of type `TDocument`.
+    Documents that already have an id keep it, and requests or operations with their own id strategy use that instead.
+
+    # Examples
+
+    Derive the ids of `MyType` documents from their contents, so retrying an ingestion won't create duplicates:
+
+    ```no_run
+    # #[macro_use] extern crate serde_derive;
+    # #[macro_use] extern crate elastic_derive;
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    #[derive(Serialize, Deserialize, ElasticType)]
+    struct MyType {
+        account: String,
+        title: String,
+    }
+
+    let builder = SyncClientBuilder::new()
+        .document_id_strategy(IdStrategy::hash(|doc: &MyType| doc.account.clone()));
+    # Ok(())
+    # }
+    ```
+
+    [IdStrategy]: requests/common/struct.IdStrategy.html
+    */
+    pub fn document_id_strategy<TDocument>(mut self, strategy: IdStrategy<TDocument>) -> Self
+    where
+        TDocument: 'static,
+    {
+        self.id_strategies.insert(strategy);
+
+        self
+    }
+
+    /**
+    Set the most threads that requests with a [`deadline`][deadline] wait for responses on.
+
+    A request that passes its deadline fails straight away, but the thread waiting for its response stays busy until the node responds or the http client times out.
+    A burst of requests to a slow node can keep every thread busy, so later requests with a deadline fail without being sent.
+    Set an http client timeout as well as a deadline to free up threads held by abandoned requests.
+
+    By default, up to 32 threads are used.
+    The pool always has at least one thread.
+
+    # Examples
+
+    ```no_run
+    # use elastic::prelude::*;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    let builder = SyncClientBuilder::new()
+        .deadline_workers(64);
+    # Ok(())
+    # }
+    ```
+
+    [deadline]: requests/struct.RequestBuilder.html#method.deadline
+    */
+    pub fn deadline_workers(mut self, max: usize) -> Self {
+        self.deadline_workers = max.max(1);
+
+        self
+    }
+
     /**
     Construct a [`SyncClient`][SyncClient] from this builder.
 
@@ -255,13 +561,39 @@ impl SyncClientBuilder {
             .map_err(error::build)?;
 
         let params = self.params.into_value(PreRequestParams::default);
+        let on_slow_request = self.on_slow_request;
+        let slow_requests = self
+            .slow_request_threshold
+            .map(|threshold| SlowRequests::new(threshold, on_slow_request));
+        let recording = self
+            .recording
+            .as_ref()
+            .map(Recording::open)
+            .transpose()
+            .map_err(error::build)?;
+
         let sender = SyncSender {
             http,
             pre_send: self.pre_send,
+            circuit_breaker: self.circuit_breaker.clone(),
+            slow_requests,
+            recording,
+            blocking_workers: BlockingWorkers::new(self.deadline_workers),
         };
 
-        let addresses = self.nodes.build(params, sender.clone());
-
-        Ok(SyncClient { sender, addresses })
+        let addresses = self
+            .nodes
+            .build(params, sender.clone(), self.circuit_breaker);
+
+        Ok(SyncClient {
+            sender,
+            addresses,
+            version: VersionCell::default(),
+            document_serializer: self.document_serializer,
+            document_index: self.document_index,
+            id_strategies: Arc::new(self.id_strategies),
+            index_scope: None,
+            deadline: None,
+        })
     }
 }
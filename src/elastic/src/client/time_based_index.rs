@@ -0,0 +1,703 @@
+/*!
+Time-based indices that are written to through an alias.
+*/
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use futures::{
+    future::{
+        Either,
+        IntoFuture,
+    },
+    Future,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc,
+        RwLock,
+    },
+};
+
+use crate::{
+    client::{
+        requests::document_index::IndexRequestBuilder,
+        responses::CommandResponse,
+        Client,
+    },
+    endpoints::{
+        IndicesGetAliasRequest,
+        IndicesUpdateAliasesRequest,
+    },
+    error::{
+        self,
+        ApiError,
+        Error,
+    },
+    http::{
+        receiver::{
+            HttpResponseHead,
+            IsOk,
+            MaybeOkResponse,
+            ParseError,
+            ResponseBody,
+            Unbuffered,
+        },
+        sender::{
+            AsyncSender,
+            Sender,
+            SyncSender,
+        },
+        StatusCode,
+    },
+    params::Index,
+    types::document::DocumentType,
+};
+
+/** The period of time covered by each index. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexPeriod {
+    /** One index per day, like `logs-2019.06.01`. */
+    Daily,
+    /** One index per month, like `logs-2019.06`. */
+    Monthly,
+}
+
+impl IndexPeriod {
+    fn format(&self) -> &'static str {
+        match *self {
+            IndexPeriod::Daily => "%Y.%m.%d",
+            IndexPeriod::Monthly => "%Y.%m",
+        }
+    }
+}
+
+/**
+A set of time-based indices that are written to through an alias.
+
+Time-based indices split an ever-growing stream of documents, like logs, into one index per period.
+Documents are indexed through a write alias that points to the index for the current period,
+and searches can use the [`index_pattern`](#method.index_pattern) to cover all periods.
+
+Call [`Client.time_based_index`][Client.time_based_index] to get a `TimeBasedIndex`.
+
+Indices are named `{base}-{period}`, where the period is formatted as `yyyy.MM.dd` for daily indices or `yyyy.MM` for monthly ones.
+The write alias is called `{base}-write` unless a different one is given.
+Call [`ensure_write_index`](#method.ensure_write_index) to create the index for the current period and move the write alias to it.
+This check happens the first time a document is indexed, so the write alias always exists.
+With [`auto_rollover`](#method.auto_rollover) it happens whenever a document is indexed, so the alias moves to a new index when the period changes.
+
+# Examples
+
+Index a document into today's index:
+
+```no_run
+# #[macro_use] extern crate serde_derive;
+# #[macro_use] extern crate elastic_derive;
+# use elastic::prelude::*;
+# use elastic::client::IndexPeriod;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+#[derive(Serialize, Deserialize, ElasticType)]
+struct LogEntry {
+    message: String,
+}
+
+let client = SyncClientBuilder::new().build()?;
+
+let logs = client.time_based_index("logs", IndexPeriod::Daily)
+                 .auto_rollover(true);
+
+let response = logs.index_document(LogEntry { message: "A log message".to_owned() })?
+                   .send()?;
+# Ok(())
+# }
+```
+
+[Client.time_based_index]: struct.Client.html#method.time_based_index
+*/
+#[derive(Clone)]
+pub struct TimeBasedIndex<TSender> {
+    client: Client<TSender>,
+    base: String,
+    period: IndexPeriod,
+    alias: String,
+    auto_rollover: bool,
+    current: Arc<RwLock<Option<String>>>,
+}
+
+impl<TSender> Client<TSender>
+where
+    TSender: Sender,
+{
+    /**
+    Get a set of time-based indices starting with `base` that are written to through an alias.
+
+    Nothing is sent to Elasticsearch until the write index is checked, either explicitly or when indexing a document.
+    See [`TimeBasedIndex`][TimeBasedIndex] for more details.
+
+    # Examples
+
+    Get monthly indices for logs and make sure the index for the current month exists:
+
+    ```no_run
+    # use elastic::prelude::*;
+    # use elastic::client::IndexPeriod;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let logs = client.time_based_index("logs", IndexPeriod::Monthly);
+
+    let index = logs.ensure_write_index()?;
+    # Ok(())
+    # }
+    ```
+
+    [TimeBasedIndex]: struct.TimeBasedIndex.html
+    */
+    pub fn time_based_index(
+        &self,
+        base: impl Into<String>,
+        period: IndexPeriod,
+    ) -> TimeBasedIndex<TSender> {
+        let base = base.into();
+
+        TimeBasedIndex {
+            client: self.clone(),
+            alias: format!("{}-write", base),
+            base,
+            period,
+            auto_rollover: false,
+            current: Arc::new(RwLock::new(None)),
+        }
+    }
+}
+
+impl<TSender> TimeBasedIndex<TSender>
+where
+    TSender: Sender,
+{
+    /** Set the name of the alias to write documents through. */
+    pub fn write_alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = alias.into();
+        self.current = Arc::new(RwLock::new(None));
+
+        self
+    }
+
+    /**
+    Check the write index is for the current period whenever a document is indexed.
+
+    The check only sends requests to Elasticsearch the first time it's made for each period.
+    Without auto rollover the write index is only checked the first time a document is indexed.
+    */
+    pub fn auto_rollover(mut self, auto_rollover: bool) -> Self {
+        self.auto_rollover = auto_rollover;
+
+        self
+    }
+
+    /** The name of the alias documents are written through. */
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /** An index pattern that matches the indices for all periods. */
+    pub fn index_pattern(&self) -> String {
+        format!("{}-*", self.base)
+    }
+
+    /** The name of the index for the period that includes `at`. */
+    pub fn index_name(&self, at: DateTime<Utc>) -> String {
+        format!("{}-{}", self.base, at.format(self.period.format()))
+    }
+
+    /** The name of the index for the current period. */
+    pub fn current_index_name(&self) -> String {
+        self.index_name(Utc::now())
+    }
+
+    fn is_current(&self, index: &str) -> bool {
+        self.current
+            .read()
+            .expect("lock poisoned")
+            .as_ref()
+            .map(|current| current.as_str())
+            == Some(index)
+    }
+
+    // Whether the write index needs to be checked before indexing a document
+    fn needs_check(&self) -> bool {
+        self.auto_rollover || self.current.read().expect("lock poisoned").is_none()
+    }
+
+    fn set_current(&self, index: String) {
+        *self.current.write().expect("lock poisoned") = Some(index);
+    }
+
+    // The alias follows the same naming rules as indices
+    fn validate_names(&self, index: &str) -> Result<(), Error> {
+        Index::from(index)
+            .validate()
+            .and_then(|_| Index::from(self.alias.as_str()).validate())
+            .map_err(error::request)
+    }
+
+    fn index_through_alias<TDocument>(
+        &self,
+        doc: TDocument,
+    ) -> IndexRequestBuilder<TSender, TDocument>
+    where
+        TDocument: Serialize + DocumentType + 'static,
+    {
+        self.client.document().index(doc).index(self.alias.clone())
+    }
+
+    fn get_alias_request(&self) -> IndicesGetAliasRequest<'static> {
+        IndicesGetAliasRequest::for_name(self.alias.clone())
+    }
+
+    // Atomically remove the alias from the indices it points to and add it to the given index
+    fn update_aliases_request(
+        &self,
+        index: &str,
+        aliased: &AliasedIndices,
+    ) -> IndicesUpdateAliasesRequest<'static, String> {
+        let mut actions: Vec<Value> = aliased
+            .0
+            .keys()
+            .map(|aliased| json!({ "remove": { "index": aliased, "alias": self.alias } }))
+            .collect();
+
+        actions.push(json!({ "add": { "index": index, "alias": self.alias } }));
+
+        IndicesUpdateAliasesRequest::new(json!({ "actions": actions }).to_string())
+    }
+}
+
+/**
+# Send synchronously
+*/
+impl TimeBasedIndex<SyncSender> {
+    /**
+    Make sure the index for the current period exists and the write alias points to it.
+
+    The index is created if it doesn't exist yet, and the write alias is moved to it from any other indices.
+    Returns the name of the current index.
+
+    # Examples
+
+    ```no_run
+    # use elastic::prelude::*;
+    # use elastic::client::IndexPeriod;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = SyncClientBuilder::new().build()?;
+    let logs = client.time_based_index("logs", IndexPeriod::Daily);
+
+    let index = logs.ensure_write_index()?;
+
+    assert_eq!(logs.current_index_name(), index);
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn ensure_write_index(&self) -> Result<String, Error> {
+        let index = self.current_index_name();
+
+        if self.is_current(&index) {
+            return Ok(index);
+        }
+
+        self.validate_names(&index)?;
+
+        let aliased = self
+            .client
+            .request(self.get_alias_request())
+            .send()?
+            .into_response::<AliasedIndices>()?;
+
+        if !aliased.is_only(&index) {
+            match self.client.index(index.clone()).create().send() {
+                Ok(_) | Err(Error::Api(ApiError::IndexAlreadyExists { .. })) => (),
+                Err(e) => return Err(e),
+            }
+
+            self.client
+                .request(self.update_aliases_request(&index, &aliased))
+                .send()?
+                .into_response::<CommandResponse>()?;
+        }
+
+        self.set_current(index.clone());
+
+        Ok(index)
+    }
+
+    /**
+    Get an [index request][IndexRequestBuilder] for a document that's sent through the write alias.
+
+    The write index is checked before the request is returned the first time this is called,
+    or every time if [`auto_rollover`](#method.auto_rollover) is set.
+    Any errors checking it are returned.
+
+    [IndexRequestBuilder]: requests/document_index/type.IndexRequestBuilder.html
+    */
+    pub fn index_document<TDocument>(
+        &self,
+        doc: TDocument,
+    ) -> Result<IndexRequestBuilder<SyncSender, TDocument>, Error>
+    where
+        TDocument: Serialize + DocumentType + 'static,
+    {
+        if self.needs_check() {
+            self.ensure_write_index()?;
+        }
+
+        Ok(self.index_through_alias(doc))
+    }
+}
+
+/**
+# Send asynchronously
+*/
+impl TimeBasedIndex<AsyncSender> {
+    /**
+    Make sure the index for the current period exists and the write alias points to it.
+
+    The index is created if it doesn't exist yet, and the write alias is moved to it from any other indices.
+    The future resolves to the name of the current index.
+
+    # Examples
+
+    ```no_run
+    # use futures::Future;
+    # use elastic::prelude::*;
+    # use elastic::client::IndexPeriod;
+    # fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+    # let client = AsyncClientBuilder::new().build()?;
+    let logs = client.time_based_index("logs", IndexPeriod::Daily);
+
+    let future = logs.ensure_write_index();
+
+    future.and_then(|index| {
+        println!("writing to {}", index);
+
+        Ok(())
+    });
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn ensure_write_index(&self) -> impl Future<Item = String, Error = Error> + Send {
+        let index = self.current_index_name();
+
+        if self.is_current(&index) {
+            return Either::A(Ok(index).into_future());
+        }
+
+        if let Err(e) = self.validate_names(&index) {
+            return Either::A(Err(e).into_future());
+        }
+
+        let this = self.clone();
+
+        let check = self
+            .client
+            .request(self.get_alias_request())
+            .send()
+            .and_then(|res| res.into_response::<AliasedIndices>())
+            .and_then(move |aliased| {
+                if aliased.is_only(&index) {
+                    this.set_current(index.clone());
+                    return Either::A(Ok(index).into_future());
+                }
+
+                let update = this.update_aliases_request(&index, &aliased);
+                let client = this.client.clone();
+
+                let create =
+                    this.client
+                        .index(index.clone())
+                        .create()
+                        .send()
+                        .then(|res| match res {
+                            Ok(_) | Err(Error::Api(ApiError::IndexAlreadyExists { .. })) => Ok(()),
+                            Err(e) => Err(e),
+                        });
+
+                Either::B(
+                    create
+                        .and_then(move |_| client.request(update).send())
+                        .and_then(|res| res.into_response::<CommandResponse>())
+                        .map(move |_| {
+                            this.set_current(index.clone());
+                            index
+                        }),
+                )
+            });
+
+        Either::B(check)
+    }
+
+    /**
+    Get an [index request][IndexRequestBuilder] for a document that's sent through the write alias.
+
+    The write index is checked before the request is returned the first time this is called,
+    or every time if [`auto_rollover`](#method.auto_rollover) is set.
+    Any errors checking it are returned.
+
+    [IndexRequestBuilder]: requests/document_index/type.IndexRequestBuilder.html
+    */
+    pub fn index_document<TDocument>(
+        &self,
+        doc: TDocument,
+    ) -> impl Future<Item = IndexRequestBuilder<AsyncSender, TDocument>, Error = Error> + Send
+    where
+        TDocument: Serialize + DocumentType + Send + 'static,
+    {
+        let req = self.index_through_alias(doc);
+
+        if self.needs_check() {
+            Either::A(self.ensure_write_index().map(move |_| req))
+        } else {
+            Either::B(Ok(req).into_future())
+        }
+    }
+}
+
+// The indices an alias points to
+#[derive(Deserialize, Debug)]
+struct AliasedIndices(BTreeMap<String, Value>);
+
+impl AliasedIndices {
+    fn is_only(&self, index: &str) -> bool {
+        self.0.len() == 1 && self.0.contains_key(index)
+    }
+}
+
+impl IsOk for AliasedIndices {
+    fn is_ok<B: ResponseBody>(
+        head: HttpResponseHead,
+        body: Unbuffered<B>,
+    ) -> Result<MaybeOkResponse<B>, ParseError> {
+        match head.status() {
+            status if status.is_success() => Ok(MaybeOkResponse::ok(body)),
+            // A missing alias doesn't point to any indices
+            StatusCode::NOT_FOUND => Ok(MaybeOkResponse::ok(json!({}))),
+            _ => Ok(MaybeOkResponse::err(body)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{
+            receiver::parse,
+            sender::recording::test_recording,
+        },
+        prelude::*,
+        tests::*,
+    };
+    use std::{
+        fs,
+        path::PathBuf,
+    };
+
+    fn at(date: &str) -> DateTime<Utc> {
+        date.parse().unwrap()
+    }
+
+    #[test]
+    fn daily_index_name() {
+        let logs = SyncClient::builder()
+            .build()
+            .unwrap()
+            .time_based_index("logs", IndexPeriod::Daily);
+
+        assert_eq!(
+            "logs-2019.06.01",
+            logs.index_name(at("2019-06-01T23:59:59Z"))
+        );
+        assert_eq!("logs-*", logs.index_pattern());
+        assert_eq!("logs-write", logs.alias());
+    }
+
+    #[test]
+    fn monthly_index_name() {
+        let logs = SyncClient::builder()
+            .build()
+            .unwrap()
+            .time_based_index("logs", IndexPeriod::Monthly)
+            .write_alias("logs");
+
+        assert_eq!("logs-2019.06", logs.index_name(at("2019-06-01T00:00:00Z")));
+        assert_eq!("logs", logs.alias());
+    }
+
+    #[test]
+    fn update_aliases_moves_alias() {
+        let logs = SyncClient::builder()
+            .build()
+            .unwrap()
+            .time_based_index("logs", IndexPeriod::Daily);
+
+        let aliased = parse::<AliasedIndices>()
+            .from_slice(
+                StatusCode::OK,
+                br#"{ "logs-2019.05.31": { "aliases": { "logs-write": {} } } }"# as &[_],
+            )
+            .unwrap();
+
+        let req = logs.update_aliases_request("logs-2019.06.01", &aliased);
+
+        let expected = json!({
+            "actions": [
+                { "remove": { "index": "logs-2019.05.31", "alias": "logs-write" } },
+                { "add": { "index": "logs-2019.06.01", "alias": "logs-write" } }
+            ]
+        });
+
+        assert_eq!("/_aliases", req.url.as_ref());
+        assert_eq!(expected, serde_json::from_str::<Value>(&req.body).unwrap());
+    }
+
+    #[test]
+    fn missing_alias_has_no_indices() {
+        let aliased = parse::<AliasedIndices>()
+            .from_slice(
+                StatusCode::NOT_FOUND,
+                br#"{ "error": "alias [logs-write] missing", "status": 404 }"# as &[_],
+            )
+            .unwrap();
+
+        assert!(aliased.0.is_empty());
+        assert!(!aliased.is_only("logs-2019.06.01"));
+    }
+
+    #[test]
+    fn ensure_write_index_is_cached() {
+        let logs = SyncClient::builder()
+            .static_node("http://localhost:1")
+            .build()
+            .unwrap()
+            .time_based_index("logs", IndexPeriod::Daily);
+
+        assert!(logs.ensure_write_index().is_err());
+
+        logs.set_current(logs.current_index_name());
+
+        assert_eq!(
+            logs.current_index_name(),
+            logs.ensure_write_index().unwrap()
+        );
+    }
+
+    #[test]
+    fn async_ensure_write_index_is_cached() {
+        let index = Utc::now().format("logs-%Y.%m.%d").to_string();
+
+        // The alias is only fetched once, so a second request for it would fail to replay
+        let path = current_alias_recording();
+
+        let logs = AsyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap()
+            .time_based_index("logs", IndexPeriod::Daily);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(index, logs.ensure_write_index().wait().unwrap());
+        assert_eq!(index, logs.ensure_write_index().wait().unwrap());
+    }
+
+    // The alias already points to the current index, and can only be fetched once
+    fn current_alias_recording() -> PathBuf {
+        let index = Utc::now().format("logs-%Y.%m.%d").to_string();
+
+        test_recording(vec![json!({
+            "request": { "method": "GET", "path": "/_alias/logs-write", "body": null },
+            "response": {
+                "status": 200,
+                "body": json!({ index: { "aliases": { "logs-write": {} } } }).to_string()
+            }
+        })])
+    }
+
+    #[derive(Serialize, ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    struct LogEntry {
+        message: String,
+    }
+
+    fn entry() -> LogEntry {
+        LogEntry {
+            message: "A log message".to_owned(),
+        }
+    }
+
+    #[test]
+    fn index_document_checks_write_index_once_without_auto_rollover() {
+        let path = current_alias_recording();
+        let logs = SyncClient::builder()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap()
+            .time_based_index("logs", IndexPeriod::Daily);
+        fs::remove_file(&path).unwrap();
+
+        assert!(logs.index_document(entry()).is_ok());
+        assert!(logs.index_document(entry()).is_ok());
+        assert!(logs.is_current(&logs.current_index_name()));
+    }
+
+    #[test]
+    fn async_index_document_checks_write_index_once_without_auto_rollover() {
+        let path = current_alias_recording();
+        let logs = AsyncClientBuilder::new()
+            .static_node("http://localhost:1")
+            .replay_from(&path)
+            .build()
+            .unwrap()
+            .time_based_index("logs", IndexPeriod::Daily);
+        fs::remove_file(&path).unwrap();
+
+        assert!(logs.index_document(entry()).wait().is_ok());
+        assert!(logs.index_document(entry()).wait().is_ok());
+        assert!(logs.is_current(&logs.current_index_name()));
+    }
+
+    #[test]
+    fn invalid_names_fail_without_sending() {
+        let client = SyncClient::builder()
+            .pre_send_raw(|_| panic!("the request shouldn't be sent"))
+            .build()
+            .unwrap();
+
+        let err = client
+            .time_based_index("Logs", IndexPeriod::Daily)
+            .ensure_write_index()
+            .unwrap_err();
+        assert!(format!("{:?}", err).contains("isn't lowercase"));
+
+        let err = client
+            .time_based_index("logs", IndexPeriod::Daily)
+            .write_alias("logs write")
+            .ensure_write_index()
+            .unwrap_err();
+        assert!(format!("{:?}", err).contains("logs write"));
+    }
+
+    #[test]
+    fn is_send_sync() {
+        assert_send::<TimeBasedIndex<SyncSender>>();
+        assert_sync::<TimeBasedIndex<SyncSender>>();
+        assert_send::<TimeBasedIndex<AsyncSender>>();
+        assert_sync::<TimeBasedIndex<AsyncSender>>();
+    }
+}
@@ -0,0 +1,433 @@
+/*!
+The version of the Elasticsearch cluster a client is connected to.
+*/
+
+use fluent_builder::{
+    SharedFluentBuilder,
+    Stack,
+};
+use futures::{
+    future::Either,
+    Future,
+    IntoFuture,
+};
+use serde::de::{
+    Deserialize,
+    Deserializer,
+    Error as DeError,
+};
+use std::{
+    error::Error as StdError,
+    fmt,
+    str::FromStr,
+    sync::{
+        Arc,
+        Mutex,
+        RwLock,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use crate::{
+    client::{
+        requests::InfoRequestBuilder,
+        responses::InfoResponse,
+        Client,
+    },
+    error::{
+        self,
+        Error,
+    },
+    http::sender::{
+        AsyncSender,
+        RequestParams,
+        SyncSender,
+    },
+};
+
+/** How long to wait after failing to detect the cluster version before trying again. */
+const DETECT_VERSION_BACKOFF: Duration = Duration::from_secs(30);
+
+type ParamsBuilder = SharedFluentBuilder<RequestParams, Stack>;
+
+/**
+An Elasticsearch version number.
+
+Any pre-release suffix like `-SNAPSHOT` or `-rc1` is ignored when parsing a version.
+
+# Examples
+
+```
+# use elastic::client::Version;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+let version: Version = "7.3.2".parse()?;
+
+assert_eq!(7, version.major());
+assert!(version >= Version::new(7, 0, 0));
+# Ok(())
+# }
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl Version {
+    /** Create a new version number. */
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Version {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /** The major version. */
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /** The minor version. */
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /** The patch version. */
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for Version {
+    type Err = ParseVersionError;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let err = || ParseVersionError {
+            version: version.to_owned(),
+        };
+
+        let number = version.split('-').next().unwrap_or(version);
+        let mut parts = number.split('.').map(|part| part.parse::<u32>());
+
+        let mut next = |required: bool| match parts.next() {
+            Some(Ok(part)) => Ok(part),
+            None if !required => Ok(0),
+            _ => Err(err()),
+        };
+
+        let parsed = Version::new(next(true)?, next(false)?, next(false)?);
+
+        if parts.next().is_some() {
+            return Err(err());
+        }
+
+        Ok(parsed)
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let version = String::deserialize(deserializer)?;
+
+        version.parse().map_err(D::Error::custom)
+    }
+}
+
+/** An error parsing a version number. */
+#[derive(Debug)]
+pub struct ParseVersionError {
+    version: String,
+}
+
+impl fmt::Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "'{}' is not a valid version number", self.version)
+    }
+}
+
+impl StdError for ParseVersionError {}
+
+/** The cluster version captured by a client and shared between its clones. */
+#[derive(Clone, Default)]
+pub(crate) struct VersionCell(Arc<RwLock<VersionState>>);
+
+#[derive(Clone, Copy, Default)]
+enum VersionState {
+    #[default]
+    Unknown,
+    Known(Version),
+    // Detecting the version failed at this time
+    Failed(Instant),
+}
+
+impl VersionCell {
+    pub(crate) fn get(&self) -> Option<Version> {
+        match *self.0.read().expect("lock poisoned") {
+            VersionState::Known(version) => Some(version),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn set(&self, version: Version) {
+        *self.0.write().expect("lock poisoned") = VersionState::Known(version);
+    }
+
+    fn set_failed(&self) {
+        let mut state = self.0.write().expect("lock poisoned");
+
+        if let VersionState::Known(_) = *state {
+            return;
+        }
+
+        *state = VersionState::Failed(Instant::now());
+    }
+
+    // Whether the version should be detected, which is delayed for a while after a failure
+    fn should_detect(&self) -> bool {
+        match *self.0.read().expect("lock poisoned") {
+            VersionState::Unknown => true,
+            VersionState::Known(_) => false,
+            VersionState::Failed(at) => at.elapsed() >= DETECT_VERSION_BACKOFF,
+        }
+    }
+}
+
+/**
+The range of versions that an API or request option is available in.
+
+The `feature` describes the API or option in error messages, like `the sql API`.
+*/
+pub(crate) struct ApiVersions {
+    pub(crate) feature: &'static str,
+    pub(crate) since: Version,
+    pub(crate) removed: Option<Version>,
+}
+
+impl ApiVersions {
+    /**
+    Check whether the API or option is available in the given cluster version.
+
+    If the version isn't known then the API is assumed to be available.
+    */
+    pub(crate) fn check(&self, version: Option<Version>) -> Result<(), Error> {
+        let version = match version {
+            Some(version) => version,
+            None => return Ok(()),
+        };
+
+        if version < self.since {
+            return Err(error::request(error::message(format!(
+                "{} requires Elasticsearch {} or later, but the cluster is running {}",
+                self.feature, self.since, version
+            ))));
+        }
+
+        match self.removed {
+            Some(removed) if version >= removed => Err(error::request(error::message(format!(
+                "{} was removed in Elasticsearch {}, but the cluster is running {}",
+                self.feature, removed, version
+            )))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Client<SyncSender> {
+    /**
+    Get the cluster version, sending an info request if it hasn't been captured yet.
+
+    The info request is sent with the parameters of the request that needs the version,
+    and the returned builder sends that request with the same parameters.
+    If the info request fails then the version is treated as unknown, and it isn't detected again for a while.
+    */
+    pub(crate) fn detect_version(
+        &self,
+        params_builder: ParamsBuilder,
+    ) -> Result<(Option<Version>, ParamsBuilder), Error> {
+        if let Some(version) = self.version.get() {
+            return Ok((Some(version), params_builder));
+        }
+
+        if !self.version.should_detect() {
+            return Ok((None, params_builder));
+        }
+
+        let resolved = Arc::new(Mutex::new(None));
+        let res = InfoRequestBuilder::probe(self.clone(), params_builder, resolved.clone()).send();
+
+        probed(&self.version, res, &resolved)
+    }
+
+    /**
+    Check that the APIs or options a request uses are available in the cluster version.
+
+    The version is only detected if there's something to check.
+    */
+    pub(crate) fn check_versions(
+        &self,
+        versions: &[&'static ApiVersions],
+        params_builder: ParamsBuilder,
+    ) -> Result<ParamsBuilder, Error> {
+        if versions.is_empty() {
+            return Ok(params_builder);
+        }
+
+        let (version, params_builder) = self.detect_version(params_builder)?;
+
+        for versions in versions {
+            versions.check(version)?;
+        }
+
+        Ok(params_builder)
+    }
+}
+
+impl Client<AsyncSender> {
+    /**
+    Get the cluster version, sending an info request if it hasn't been captured yet.
+
+    The info request is sent with the parameters of the request that needs the version,
+    and the returned builder sends that request with the same parameters.
+    If the info request fails then the version is treated as unknown, and it isn't detected again for a while.
+    */
+    pub(crate) fn detect_version(
+        &self,
+        params_builder: ParamsBuilder,
+    ) -> impl Future<Item = (Option<Version>, ParamsBuilder), Error = Error> + Send {
+        if let Some(version) = self.version.get() {
+            return Either::A(Ok((Some(version), params_builder)).into_future());
+        }
+
+        if !self.version.should_detect() {
+            return Either::A(Ok((None, params_builder)).into_future());
+        }
+
+        let version = self.version.clone();
+        let resolved = Arc::new(Mutex::new(None));
+
+        Either::B(
+            InfoRequestBuilder::probe(self.clone(), params_builder, resolved.clone())
+                .send()
+                .then(move |res| probed(&version, res, &resolved)),
+        )
+    }
+
+    /**
+    Check that the APIs or options a request uses are available in the cluster version.
+
+    The version is only detected if there's something to check.
+    */
+    pub(crate) fn check_versions(
+        &self,
+        versions: Vec<&'static ApiVersions>,
+        params_builder: ParamsBuilder,
+    ) -> impl Future<Item = ParamsBuilder, Error = Error> + Send {
+        if versions.is_empty() {
+            return Either::A(Ok(params_builder).into_future());
+        }
+
+        Either::B(
+            self.detect_version(params_builder)
+                .and_then(move |(version, params_builder)| {
+                    for versions in &versions {
+                        versions.check(version)?;
+                    }
+
+                    Ok(params_builder)
+                }),
+        )
+    }
+}
+
+// Capture the version from an info request sent by `detect_version`
+fn probed(
+    version: &VersionCell,
+    res: Result<InfoResponse, Error>,
+    resolved: &Mutex<Option<RequestParams>>,
+) -> Result<(Option<Version>, ParamsBuilder), Error> {
+    let params = resolved.lock().expect("lock poisoned").take();
+
+    let detected = match res {
+        Ok(info) => Some(*info.version().number()),
+        Err(e) => {
+            warn!("Elasticsearch Version Detection: error: '{:?}'", e);
+            version.set_failed();
+
+            // The info request failed before the parameters were built, so there aren't any to send the request with
+            if params.is_none() {
+                return Err(e);
+            }
+
+            None
+        }
+    };
+
+    let params = params.ok_or_else(|| {
+        error::request(error::message(
+            "the parameters for the request weren't built",
+        ))
+    })?;
+
+    Ok((detected, SharedFluentBuilder::new().value(params)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQL: ApiVersions = ApiVersions {
+        feature: "the sql API",
+        since: Version::new(6, 3, 0),
+        removed: Some(Version::new(8, 0, 0)),
+    };
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(Version::new(7, 3, 2), "7.3.2".parse::<Version>().unwrap());
+        assert_eq!(
+            Version::new(8, 0, 0),
+            "8.0.0-SNAPSHOT".parse::<Version>().unwrap()
+        );
+        assert_eq!(Version::new(6, 8, 0), "6.8".parse::<Version>().unwrap());
+    }
+
+    #[test]
+    fn parse_invalid_version() {
+        assert!("".parse::<Version>().is_err());
+        assert!("seven".parse::<Version>().is_err());
+        assert!("7.3.2.1".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn version_ordering() {
+        assert!(Version::new(6, 10, 0) > Version::new(6, 9, 5));
+        assert!(Version::new(7, 0, 0) > Version::new(6, 10, 0));
+    }
+
+    #[test]
+    fn check_supported_version() {
+        assert!(SQL.check(Some(Version::new(7, 4, 0))).is_ok());
+        assert!(SQL.check(None).is_ok());
+    }
+
+    #[test]
+    fn check_unsupported_version() {
+        assert!(SQL.check(Some(Version::new(6, 2, 4))).is_err());
+        assert!(SQL.check(Some(Version::new(8, 0, 0))).is_err());
+    }
+}
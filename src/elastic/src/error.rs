@@ -39,6 +39,7 @@ use std::{
     error::Error as StdError,
     fmt,
     io,
+    time::Duration,
 };
 
 use crate::http::{
@@ -72,6 +73,15 @@ quick_error! {
             cause(err)
             display("error sending a request or receiving a response. Caused by: {}", err)
         }
+        /**
+        A request didn't complete within the deadline set on its builder.
+
+        The deadline covers the whole request, including node selection and parsing the response,
+        so this is different from a `Client` error caused by an http timeout.
+        */
+        DeadlineExceeded(deadline: Duration) {
+            display("the request didn't complete within its deadline of {:?}", deadline)
+        }
     }
 }
 
@@ -158,6 +168,10 @@ where
     }
 }
 
+pub(crate) fn deadline_exceeded(deadline: Duration) -> Error {
+    Error::DeadlineExceeded(deadline)
+}
+
 pub(crate) fn message<E>(err: E) -> string_error::Error
 where
     E: Into<String>,
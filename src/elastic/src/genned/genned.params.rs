@@ -2,8 +2,32 @@
 This file is automatically included into the generated `genned::params` module
 */
 
+use std::borrow::Cow;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
 use crate::types::string::{Keyword, keyword::mapping::KeywordMapping};
 
+/**
+Characters that are percent-encoded in a url path param.
+
+Everything except unreserved characters is encoded, so a param can't change the structure of the path.
+Commas, wildcards and colons are left alone because they're used in index and type expressions like `logs-*,cluster:other`.
+*/
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b',')
+    .remove(b'*')
+    .remove(b':');
+
+/** Percent-encode the value of a param so it can be used as a segment in a url path. */
+pub(crate) fn encode_path_segment(value: &str) -> Cow<'_, str> {
+    utf8_percent_encode(value, PATH_SEGMENT).into()
+}
+
 impl<'a, M> From<Keyword<M>> for Id<'a>
 where
     M: KeywordMapping,
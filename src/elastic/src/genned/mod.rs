@@ -23,16 +23,16 @@ pub mod endpoints {
                 BulkUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(7usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_bulk");
                     UrlPath::from(url)
                 }
                 BulkUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(8usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_bulk");
                     UrlPath::from(url)
                 }
@@ -96,7 +96,7 @@ pub mod endpoints {
                 CatAliasesUrlParams::Name(ref name) => {
                     let mut url = String::with_capacity(14usize + name.len());
                     url.push_str("/_cat/aliases/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -145,7 +145,7 @@ pub mod endpoints {
                 CatAllocationUrlParams::NodeId(ref node_id) => {
                     let mut url = String::with_capacity(17usize + node_id.len());
                     url.push_str("/_cat/allocation/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -194,7 +194,7 @@ pub mod endpoints {
                 CatCountUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(12usize + index.len());
                     url.push_str("/_cat/count/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -243,7 +243,7 @@ pub mod endpoints {
                 CatFielddataUrlParams::Fields(ref fields) => {
                     let mut url = String::with_capacity(16usize + fields.len());
                     url.push_str("/_cat/fielddata/");
-                    url.push_str(fields.as_ref());
+                    url.push_str(&encode_path_segment(fields.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -358,7 +358,7 @@ pub mod endpoints {
                 CatIndicesUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(14usize + index.len());
                     url.push_str("/_cat/indices/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -572,7 +572,7 @@ pub mod endpoints {
                 CatRecoveryUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(15usize + index.len());
                     url.push_str("/_cat/recovery/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -654,7 +654,7 @@ pub mod endpoints {
                 CatSegmentsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(15usize + index.len());
                     url.push_str("/_cat/segments/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -703,7 +703,7 @@ pub mod endpoints {
                 CatShardsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(13usize + index.len());
                     url.push_str("/_cat/shards/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -752,7 +752,7 @@ pub mod endpoints {
                 CatSnapshotsUrlParams::Repository(ref repository) => {
                     let mut url = String::with_capacity(16usize + repository.len());
                     url.push_str("/_cat/snapshots/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -834,7 +834,7 @@ pub mod endpoints {
                 CatTemplatesUrlParams::Name(ref name) => {
                     let mut url = String::with_capacity(16usize + name.len());
                     url.push_str("/_cat/templates/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -883,7 +883,7 @@ pub mod endpoints {
                 CatThreadPoolUrlParams::ThreadPoolPatterns(ref thread_pool_patterns) => {
                     let mut url = String::with_capacity(18usize + thread_pool_patterns.len());
                     url.push_str("/_cat/thread_pool/");
-                    url.push_str(thread_pool_patterns.as_ref());
+                    url.push_str(&encode_path_segment(thread_pool_patterns.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -934,7 +934,7 @@ pub mod endpoints {
                 ClearScrollUrlParams::ScrollId(ref scroll_id) => {
                     let mut url = String::with_capacity(16usize + scroll_id.len());
                     url.push_str("/_search/scroll/");
-                    url.push_str(scroll_id.as_ref());
+                    url.push_str(&encode_path_segment(scroll_id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -1056,7 +1056,7 @@ pub mod endpoints {
                 ClusterHealthUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(17usize + index.len());
                     url.push_str("/_cluster/health/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -1242,15 +1242,15 @@ pub mod endpoints {
                 ClusterStateUrlParams::Metric(ref metric) => {
                     let mut url = String::with_capacity(16usize + metric.len());
                     url.push_str("/_cluster/state/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     UrlPath::from(url)
                 }
                 ClusterStateUrlParams::MetricIndex(ref metric, ref index) => {
                     let mut url = String::with_capacity(17usize + metric.len() + index.len());
                     url.push_str("/_cluster/state/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -1309,7 +1309,7 @@ pub mod endpoints {
                 ClusterStatsUrlParams::NodeId(ref node_id) => {
                     let mut url = String::with_capacity(22usize + node_id.len());
                     url.push_str("/_cluster/stats/nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -1359,16 +1359,16 @@ pub mod endpoints {
                 CountUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(8usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_count");
                     UrlPath::from(url)
                 }
                 CountUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(9usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_count");
                     UrlPath::from(url)
                 }
@@ -1431,20 +1431,20 @@ pub mod endpoints {
                 CreateUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(10usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_create/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 CreateUrlParams::IndexTypeId(ref index, ref ty, ref id) => {
                     let mut url =
                         String::with_capacity(11usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     url.push_str("/_create");
                     UrlPath::from(url)
                 }
@@ -1507,19 +1507,19 @@ pub mod endpoints {
                 DeleteUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(7usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_doc/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 DeleteUrlParams::IndexTypeId(ref index, ref ty, ref id) => {
                     let mut url = String::with_capacity(3usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -1573,16 +1573,16 @@ pub mod endpoints {
                 DeleteByQueryUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(18usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_delete_by_query");
                     UrlPath::from(url)
                 }
                 DeleteByQueryUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(19usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_delete_by_query");
                     UrlPath::from(url)
                 }
@@ -1637,7 +1637,7 @@ pub mod endpoints {
                 DeleteByQueryRethrottleUrlParams::TaskId(ref task_id) => {
                     let mut url = String::with_capacity(30usize + task_id.len());
                     url.push_str("/_delete_by_query/");
-                    url.push_str(task_id.as_ref());
+                    url.push_str(&encode_path_segment(task_id.as_ref()));
                     url.push_str("/_rethrottle");
                     UrlPath::from(url)
                 }
@@ -1681,7 +1681,7 @@ pub mod endpoints {
                 DeleteScriptUrlParams::Id(ref id) => {
                     let mut url = String::with_capacity(10usize + id.len());
                     url.push_str("/_scripts/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -1723,19 +1723,19 @@ pub mod endpoints {
                 ExistsUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(7usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_doc/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 ExistsUrlParams::IndexTypeId(ref index, ref ty, ref id) => {
                     let mut url = String::with_capacity(3usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -1789,20 +1789,20 @@ pub mod endpoints {
                 ExistsSourceUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(10usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_source/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 ExistsSourceUrlParams::IndexTypeId(ref index, ref ty, ref id) => {
                     let mut url =
                         String::with_capacity(11usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     url.push_str("/_source");
                     UrlPath::from(url)
                 }
@@ -1857,20 +1857,20 @@ pub mod endpoints {
                 ExplainUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(11usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_explain/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 ExplainUrlParams::IndexTypeId(ref index, ref ty, ref id) => {
                     let mut url =
                         String::with_capacity(12usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     url.push_str("/_explain");
                     UrlPath::from(url)
                 }
@@ -1934,7 +1934,7 @@ pub mod endpoints {
                 FieldCapsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(13usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_field_caps");
                     UrlPath::from(url)
                 }
@@ -1986,19 +1986,19 @@ pub mod endpoints {
                 GetUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(7usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_doc/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 GetUrlParams::IndexTypeId(ref index, ref ty, ref id) => {
                     let mut url = String::with_capacity(3usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2051,7 +2051,7 @@ pub mod endpoints {
                 GetScriptUrlParams::Id(ref id) => {
                     let mut url = String::with_capacity(10usize + id.len());
                     url.push_str("/_scripts/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2093,20 +2093,20 @@ pub mod endpoints {
                 GetSourceUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(10usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_source/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 GetSourceUrlParams::IndexTypeId(ref index, ref ty, ref id) => {
                     let mut url =
                         String::with_capacity(11usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     url.push_str("/_source");
                     UrlPath::from(url)
                 }
@@ -2163,34 +2163,34 @@ pub mod endpoints {
                 IndexUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(6usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_doc");
                     UrlPath::from(url)
                 }
                 IndexUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(7usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_doc/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 IndexUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(2usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     UrlPath::from(url)
                 }
                 IndexUrlParams::IndexTypeId(ref index, ref ty, ref id) => {
                     let mut url = String::with_capacity(3usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2274,7 +2274,7 @@ pub mod endpoints {
                 IndicesAnalyzeUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(10usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_analyze");
                     UrlPath::from(url)
                 }
@@ -2327,7 +2327,7 @@ pub mod endpoints {
                 IndicesClearCacheUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(14usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_cache/clear");
                     UrlPath::from(url)
                 }
@@ -2378,7 +2378,7 @@ pub mod endpoints {
                 IndicesCloseUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(8usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_close");
                     UrlPath::from(url)
                 }
@@ -2422,7 +2422,7 @@ pub mod endpoints {
                 IndicesCreateUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(1usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2465,7 +2465,7 @@ pub mod endpoints {
                 IndicesDeleteUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(1usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2506,9 +2506,9 @@ pub mod endpoints {
                 IndicesDeleteAliasUrlParams::IndexName(ref index, ref name) => {
                     let mut url = String::with_capacity(11usize + index.len() + name.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_aliases/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2550,7 +2550,7 @@ pub mod endpoints {
                 IndicesDeleteTemplateUrlParams::Name(ref name) => {
                     let mut url = String::with_capacity(11usize + name.len());
                     url.push_str("/_template/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2591,7 +2591,7 @@ pub mod endpoints {
                 IndicesExistsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(1usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2633,15 +2633,15 @@ pub mod endpoints {
                 IndicesExistsAliasUrlParams::IndexName(ref index, ref name) => {
                     let mut url = String::with_capacity(9usize + index.len() + name.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_alias/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
                 IndicesExistsAliasUrlParams::Name(ref name) => {
                     let mut url = String::with_capacity(8usize + name.len());
                     url.push_str("/_alias/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2692,7 +2692,7 @@ pub mod endpoints {
                 IndicesExistsTemplateUrlParams::Name(ref name) => {
                     let mut url = String::with_capacity(11usize + name.len());
                     url.push_str("/_template/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2733,9 +2733,9 @@ pub mod endpoints {
                 IndicesExistsTypeUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(11usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_mapping/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2779,7 +2779,7 @@ pub mod endpoints {
                 IndicesFlushUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(8usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_flush");
                     UrlPath::from(url)
                 }
@@ -2832,7 +2832,7 @@ pub mod endpoints {
                 IndicesFlushSyncedUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(15usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_flush/synced");
                     UrlPath::from(url)
                 }
@@ -2885,7 +2885,7 @@ pub mod endpoints {
                 IndicesForcemergeUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(13usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_forcemerge");
                     UrlPath::from(url)
                 }
@@ -2936,7 +2936,7 @@ pub mod endpoints {
                 IndicesGetUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(1usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -2981,22 +2981,22 @@ pub mod endpoints {
                 IndicesGetAliasUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(8usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_alias");
                     UrlPath::from(url)
                 }
                 IndicesGetAliasUrlParams::IndexName(ref index, ref name) => {
                     let mut url = String::with_capacity(9usize + index.len() + name.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_alias/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
                 IndicesGetAliasUrlParams::Name(ref name) => {
                     let mut url = String::with_capacity(8usize + name.len());
                     url.push_str("/_alias/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -3065,34 +3065,34 @@ pub mod endpoints {
                 IndicesGetFieldMappingUrlParams::Fields(ref fields) => {
                     let mut url = String::with_capacity(16usize + fields.len());
                     url.push_str("/_mapping/field/");
-                    url.push_str(fields.as_ref());
+                    url.push_str(&encode_path_segment(fields.as_ref()));
                     UrlPath::from(url)
                 }
                 IndicesGetFieldMappingUrlParams::IndexFields(ref index, ref fields) => {
                     let mut url = String::with_capacity(17usize + index.len() + fields.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_mapping/field/");
-                    url.push_str(fields.as_ref());
+                    url.push_str(&encode_path_segment(fields.as_ref()));
                     UrlPath::from(url)
                 }
                 IndicesGetFieldMappingUrlParams::IndexTypeFields(ref index, ref ty, ref fields) => {
                     let mut url =
                         String::with_capacity(18usize + index.len() + ty.len() + fields.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_mapping/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/field/");
-                    url.push_str(fields.as_ref());
+                    url.push_str(&encode_path_segment(fields.as_ref()));
                     UrlPath::from(url)
                 }
                 IndicesGetFieldMappingUrlParams::TypeFields(ref ty, ref fields) => {
                     let mut url = String::with_capacity(17usize + ty.len() + fields.len());
                     url.push_str("/_mapping/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/field/");
-                    url.push_str(fields.as_ref());
+                    url.push_str(&encode_path_segment(fields.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -3178,22 +3178,22 @@ pub mod endpoints {
                 IndicesGetMappingUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(10usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_mapping");
                     UrlPath::from(url)
                 }
                 IndicesGetMappingUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(11usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_mapping/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     UrlPath::from(url)
                 }
                 IndicesGetMappingUrlParams::Type(ref ty) => {
                     let mut url = String::with_capacity(10usize + ty.len());
                     url.push_str("/_mapping/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -3263,22 +3263,22 @@ pub mod endpoints {
                 IndicesGetSettingsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(11usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_settings");
                     UrlPath::from(url)
                 }
                 IndicesGetSettingsUrlParams::IndexName(ref index, ref name) => {
                     let mut url = String::with_capacity(12usize + index.len() + name.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_settings/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
                 IndicesGetSettingsUrlParams::Name(ref name) => {
                     let mut url = String::with_capacity(11usize + name.len());
                     url.push_str("/_settings/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -3346,7 +3346,7 @@ pub mod endpoints {
                 IndicesGetTemplateUrlParams::Name(ref name) => {
                     let mut url = String::with_capacity(11usize + name.len());
                     url.push_str("/_template/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -3395,7 +3395,7 @@ pub mod endpoints {
                 IndicesGetUpgradeUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(10usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_upgrade");
                     UrlPath::from(url)
                 }
@@ -3443,7 +3443,7 @@ pub mod endpoints {
                 IndicesOpenUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(7usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_open");
                     UrlPath::from(url)
                 }
@@ -3487,9 +3487,9 @@ pub mod endpoints {
                 IndicesPutAliasUrlParams::IndexName(ref index, ref name) => {
                     let mut url = String::with_capacity(11usize + index.len() + name.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_aliases/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -3535,22 +3535,22 @@ pub mod endpoints {
                 IndicesPutMappingUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(10usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_mapping");
                     UrlPath::from(url)
                 }
                 IndicesPutMappingUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(12usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_mappings/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     UrlPath::from(url)
                 }
                 IndicesPutMappingUrlParams::Type(ref ty) => {
                     let mut url = String::with_capacity(11usize + ty.len());
                     url.push_str("/_mappings/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -3616,7 +3616,7 @@ pub mod endpoints {
                 IndicesPutSettingsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(11usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_settings");
                     UrlPath::from(url)
                 }
@@ -3667,7 +3667,7 @@ pub mod endpoints {
                 IndicesPutTemplateUrlParams::Name(ref name) => {
                     let mut url = String::with_capacity(11usize + name.len());
                     url.push_str("/_template/");
-                    url.push_str(name.as_ref());
+                    url.push_str(&encode_path_segment(name.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -3712,7 +3712,7 @@ pub mod endpoints {
                 IndicesRecoveryUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(11usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_recovery");
                     UrlPath::from(url)
                 }
@@ -3762,7 +3762,7 @@ pub mod endpoints {
                 IndicesRefreshUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(10usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_refresh");
                     UrlPath::from(url)
                 }
@@ -3814,16 +3814,16 @@ pub mod endpoints {
                 IndicesRolloverUrlParams::Alias(ref alias) => {
                     let mut url = String::with_capacity(11usize + alias.len());
                     url.push_str("/");
-                    url.push_str(alias.as_ref());
+                    url.push_str(&encode_path_segment(alias.as_ref()));
                     url.push_str("/_rollover");
                     UrlPath::from(url)
                 }
                 IndicesRolloverUrlParams::AliasNewIndex(ref alias, ref new_index) => {
                     let mut url = String::with_capacity(12usize + alias.len() + new_index.len());
                     url.push_str("/");
-                    url.push_str(alias.as_ref());
+                    url.push_str(&encode_path_segment(alias.as_ref()));
                     url.push_str("/_rollover/");
-                    url.push_str(new_index.as_ref());
+                    url.push_str(&encode_path_segment(new_index.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -3883,7 +3883,7 @@ pub mod endpoints {
                 IndicesSegmentsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(11usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_segments");
                     UrlPath::from(url)
                 }
@@ -3933,7 +3933,7 @@ pub mod endpoints {
                 IndicesShardStoresUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(15usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_shard_stores");
                     UrlPath::from(url)
                 }
@@ -3981,9 +3981,9 @@ pub mod endpoints {
                 IndicesShrinkUrlParams::IndexTarget(ref index, ref target) => {
                     let mut url = String::with_capacity(10usize + index.len() + target.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_shrink/");
-                    url.push_str(target.as_ref());
+                    url.push_str(&encode_path_segment(target.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -4027,9 +4027,9 @@ pub mod endpoints {
                 IndicesSplitUrlParams::IndexTarget(ref index, ref target) => {
                     let mut url = String::with_capacity(9usize + index.len() + target.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_split/");
-                    url.push_str(target.as_ref());
+                    url.push_str(&encode_path_segment(target.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -4077,22 +4077,22 @@ pub mod endpoints {
                 IndicesStatsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(8usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_stats");
                     UrlPath::from(url)
                 }
                 IndicesStatsUrlParams::IndexMetric(ref index, ref metric) => {
                     let mut url = String::with_capacity(9usize + index.len() + metric.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_stats/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     UrlPath::from(url)
                 }
                 IndicesStatsUrlParams::Metric(ref metric) => {
                     let mut url = String::with_capacity(8usize + metric.len());
                     url.push_str("/_stats/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -4195,7 +4195,7 @@ pub mod endpoints {
                 IndicesUpgradeUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(10usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_upgrade");
                     UrlPath::from(url)
                 }
@@ -4249,16 +4249,16 @@ pub mod endpoints {
                 IndicesValidateQueryUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(17usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_validate/query");
                     UrlPath::from(url)
                 }
                 IndicesValidateQueryUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(18usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_validate/query");
                     UrlPath::from(url)
                 }
@@ -4353,7 +4353,7 @@ pub mod endpoints {
                 IngestDeletePipelineUrlParams::Id(ref id) => {
                     let mut url = String::with_capacity(18usize + id.len());
                     url.push_str("/_ingest/pipeline/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -4396,7 +4396,7 @@ pub mod endpoints {
                 IngestGetPipelineUrlParams::Id(ref id) => {
                     let mut url = String::with_capacity(18usize + id.len());
                     url.push_str("/_ingest/pipeline/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -4476,7 +4476,7 @@ pub mod endpoints {
                 IngestPutPipelineUrlParams::Id(ref id) => {
                     let mut url = String::with_capacity(18usize + id.len());
                     url.push_str("/_ingest/pipeline/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -4521,7 +4521,7 @@ pub mod endpoints {
                 IngestSimulateUrlParams::Id(ref id) => {
                     let mut url = String::with_capacity(28usize + id.len());
                     url.push_str("/_ingest/pipeline/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     url.push_str("/_simulate");
                     UrlPath::from(url)
                 }
@@ -4575,16 +4575,16 @@ pub mod endpoints {
                 MgetUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(7usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_mget");
                     UrlPath::from(url)
                 }
                 MgetUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(8usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_mget");
                     UrlPath::from(url)
                 }
@@ -4649,16 +4649,16 @@ pub mod endpoints {
                 MsearchUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(10usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_msearch");
                     UrlPath::from(url)
                 }
                 MsearchUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(11usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_msearch");
                     UrlPath::from(url)
                 }
@@ -4723,16 +4723,16 @@ pub mod endpoints {
                 MsearchTemplateUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(19usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_msearch/template");
                     UrlPath::from(url)
                 }
                 MsearchTemplateUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(20usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_msearch/template");
                     UrlPath::from(url)
                 }
@@ -4797,16 +4797,16 @@ pub mod endpoints {
                 MtermvectorsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(15usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_mtermvectors");
                     UrlPath::from(url)
                 }
                 MtermvectorsUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(16usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_mtermvectors");
                     UrlPath::from(url)
                 }
@@ -4870,7 +4870,7 @@ pub mod endpoints {
                 NodesHotThreadsUrlParams::NodeId(ref node_id) => {
                     let mut url = String::with_capacity(20usize + node_id.len());
                     url.push_str("/_nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     url.push_str("/hot_threads");
                     UrlPath::from(url)
                 }
@@ -4922,21 +4922,21 @@ pub mod endpoints {
                 NodesInfoUrlParams::Metric(ref metric) => {
                     let mut url = String::with_capacity(8usize + metric.len());
                     url.push_str("/_nodes/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     UrlPath::from(url)
                 }
                 NodesInfoUrlParams::NodeId(ref node_id) => {
                     let mut url = String::with_capacity(8usize + node_id.len());
                     url.push_str("/_nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     UrlPath::from(url)
                 }
                 NodesInfoUrlParams::NodeIdMetric(ref node_id, ref metric) => {
                     let mut url = String::with_capacity(9usize + node_id.len() + metric.len());
                     url.push_str("/_nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     url.push_str("/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -5006,7 +5006,7 @@ pub mod endpoints {
                 NodesReloadSecureSettingsUrlParams::NodeId(ref node_id) => {
                     let mut url = String::with_capacity(31usize + node_id.len());
                     url.push_str("/_nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     url.push_str("/reload_secure_settings");
                     UrlPath::from(url)
                 }
@@ -5063,31 +5063,31 @@ pub mod endpoints {
                 NodesStatsUrlParams::Metric(ref metric) => {
                     let mut url = String::with_capacity(14usize + metric.len());
                     url.push_str("/_nodes/stats/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     UrlPath::from(url)
                 }
                 NodesStatsUrlParams::MetricIndexMetric(ref metric, ref index_metric) => {
                     let mut url =
                         String::with_capacity(15usize + metric.len() + index_metric.len());
                     url.push_str("/_nodes/stats/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     url.push_str("/");
-                    url.push_str(index_metric.as_ref());
+                    url.push_str(&encode_path_segment(index_metric.as_ref()));
                     UrlPath::from(url)
                 }
                 NodesStatsUrlParams::NodeId(ref node_id) => {
                     let mut url = String::with_capacity(14usize + node_id.len());
                     url.push_str("/_nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     url.push_str("/stats");
                     UrlPath::from(url)
                 }
                 NodesStatsUrlParams::NodeIdMetric(ref node_id, ref metric) => {
                     let mut url = String::with_capacity(15usize + node_id.len() + metric.len());
                     url.push_str("/_nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     url.push_str("/stats/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     UrlPath::from(url)
                 }
                 NodesStatsUrlParams::NodeIdMetricIndexMetric(
@@ -5099,11 +5099,11 @@ pub mod endpoints {
                         16usize + node_id.len() + metric.len() + index_metric.len(),
                     );
                     url.push_str("/_nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     url.push_str("/stats/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     url.push_str("/");
-                    url.push_str(index_metric.as_ref());
+                    url.push_str(&encode_path_segment(index_metric.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -5207,22 +5207,22 @@ pub mod endpoints {
                 NodesUsageUrlParams::Metric(ref metric) => {
                     let mut url = String::with_capacity(14usize + metric.len());
                     url.push_str("/_nodes/usage/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     UrlPath::from(url)
                 }
                 NodesUsageUrlParams::NodeId(ref node_id) => {
                     let mut url = String::with_capacity(14usize + node_id.len());
                     url.push_str("/_nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     url.push_str("/usage");
                     UrlPath::from(url)
                 }
                 NodesUsageUrlParams::NodeIdMetric(ref node_id, ref metric) => {
                     let mut url = String::with_capacity(15usize + node_id.len() + metric.len());
                     url.push_str("/_nodes/");
-                    url.push_str(node_id.as_ref());
+                    url.push_str(&encode_path_segment(node_id.as_ref()));
                     url.push_str("/usage/");
-                    url.push_str(metric.as_ref());
+                    url.push_str(&encode_path_segment(metric.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -5355,15 +5355,15 @@ pub mod endpoints {
                 PutScriptUrlParams::Id(ref id) => {
                     let mut url = String::with_capacity(10usize + id.len());
                     url.push_str("/_scripts/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 PutScriptUrlParams::IdContext(ref id, ref context) => {
                     let mut url = String::with_capacity(11usize + id.len() + context.len());
                     url.push_str("/_scripts/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     url.push_str("/");
-                    url.push_str(context.as_ref());
+                    url.push_str(&encode_path_segment(context.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -5419,7 +5419,7 @@ pub mod endpoints {
                 RankEvalUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(12usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_rank_eval");
                     UrlPath::from(url)
                 }
@@ -5505,7 +5505,7 @@ pub mod endpoints {
                 ReindexRethrottleUrlParams::TaskId(ref task_id) => {
                     let mut url = String::with_capacity(22usize + task_id.len());
                     url.push_str("/_reindex/");
-                    url.push_str(task_id.as_ref());
+                    url.push_str(&encode_path_segment(task_id.as_ref()));
                     url.push_str("/_rethrottle");
                     UrlPath::from(url)
                 }
@@ -5551,7 +5551,7 @@ pub mod endpoints {
                 RenderSearchTemplateUrlParams::Id(ref id) => {
                     let mut url = String::with_capacity(18usize + id.len());
                     url.push_str("/_render/template/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -5640,7 +5640,7 @@ pub mod endpoints {
                 ScrollUrlParams::ScrollId(ref scroll_id) => {
                     let mut url = String::with_capacity(16usize + scroll_id.len());
                     url.push_str("/_search/scroll/");
-                    url.push_str(scroll_id.as_ref());
+                    url.push_str(&encode_path_segment(scroll_id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -5693,16 +5693,16 @@ pub mod endpoints {
                 SimpleSearchUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(9usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_search");
                     UrlPath::from(url)
                 }
                 SimpleSearchUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(10usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_search");
                     UrlPath::from(url)
                 }
@@ -5763,16 +5763,16 @@ pub mod endpoints {
                 SearchUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(9usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_search");
                     UrlPath::from(url)
                 }
                 SearchUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(10usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_search");
                     UrlPath::from(url)
                 }
@@ -5836,7 +5836,7 @@ pub mod endpoints {
                 SearchShardsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(16usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_search_shards");
                     UrlPath::from(url)
                 }
@@ -5890,16 +5890,16 @@ pub mod endpoints {
                 SearchTemplateUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(18usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_search/template");
                     UrlPath::from(url)
                 }
                 SearchTemplateUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(19usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_search/template");
                     UrlPath::from(url)
                 }
@@ -5962,9 +5962,9 @@ pub mod endpoints {
                     let mut url =
                         String::with_capacity(12usize + repository.len() + snapshot.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     url.push_str("/");
-                    url.push_str(snapshot.as_ref());
+                    url.push_str(&encode_path_segment(snapshot.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -6016,7 +6016,7 @@ pub mod endpoints {
                 SnapshotCreateRepositoryUrlParams::Repository(ref repository) => {
                     let mut url = String::with_capacity(11usize + repository.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -6060,9 +6060,9 @@ pub mod endpoints {
                     let mut url =
                         String::with_capacity(12usize + repository.len() + snapshot.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     url.push_str("/");
-                    url.push_str(snapshot.as_ref());
+                    url.push_str(&encode_path_segment(snapshot.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -6111,7 +6111,7 @@ pub mod endpoints {
                 SnapshotDeleteRepositoryUrlParams::Repository(ref repository) => {
                     let mut url = String::with_capacity(11usize + repository.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -6153,9 +6153,9 @@ pub mod endpoints {
                     let mut url =
                         String::with_capacity(12usize + repository.len() + snapshot.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     url.push_str("/");
-                    url.push_str(snapshot.as_ref());
+                    url.push_str(&encode_path_segment(snapshot.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -6203,7 +6203,7 @@ pub mod endpoints {
                 SnapshotGetRepositoryUrlParams::Repository(ref repository) => {
                     let mut url = String::with_capacity(11usize + repository.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -6251,9 +6251,9 @@ pub mod endpoints {
                     let mut url =
                         String::with_capacity(21usize + repository.len() + snapshot.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     url.push_str("/");
-                    url.push_str(snapshot.as_ref());
+                    url.push_str(&encode_path_segment(snapshot.as_ref()));
                     url.push_str("/_restore");
                     UrlPath::from(url)
                 }
@@ -6309,7 +6309,7 @@ pub mod endpoints {
                 SnapshotStatusUrlParams::Repository(ref repository) => {
                     let mut url = String::with_capacity(19usize + repository.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     url.push_str("/_status");
                     UrlPath::from(url)
                 }
@@ -6317,9 +6317,9 @@ pub mod endpoints {
                     let mut url =
                         String::with_capacity(20usize + repository.len() + snapshot.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     url.push_str("/");
-                    url.push_str(snapshot.as_ref());
+                    url.push_str(&encode_path_segment(snapshot.as_ref()));
                     url.push_str("/_status");
                     UrlPath::from(url)
                 }
@@ -6384,7 +6384,7 @@ pub mod endpoints {
                 SnapshotVerifyRepositoryUrlParams::Repository(ref repository) => {
                     let mut url = String::with_capacity(19usize + repository.len());
                     url.push_str("/_snapshot/");
-                    url.push_str(repository.as_ref());
+                    url.push_str(&encode_path_segment(repository.as_ref()));
                     url.push_str("/_verify");
                     UrlPath::from(url)
                 }
@@ -6465,7 +6465,7 @@ pub mod endpoints {
                 TasksCancelUrlParams::TaskId(ref task_id) => {
                     let mut url = String::with_capacity(16usize + task_id.len());
                     url.push_str("/_tasks/");
-                    url.push_str(task_id.as_ref());
+                    url.push_str(&encode_path_segment(task_id.as_ref()));
                     url.push_str("/_cancel");
                     UrlPath::from(url)
                 }
@@ -6516,7 +6516,7 @@ pub mod endpoints {
                 TasksGetUrlParams::TaskId(ref task_id) => {
                     let mut url = String::with_capacity(8usize + task_id.len());
                     url.push_str("/_tasks/");
-                    url.push_str(task_id.as_ref());
+                    url.push_str(&encode_path_segment(task_id.as_ref()));
                     UrlPath::from(url)
                 }
             }
@@ -6593,24 +6593,24 @@ pub mod endpoints {
                 TermvectorsUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(15usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_termvectors/");
                     UrlPath::from(url)
                 }
                 TermvectorsUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(15usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_termvectors/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 TermvectorsUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(15usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_termvectors");
                     UrlPath::from(url)
                 }
@@ -6618,11 +6618,11 @@ pub mod endpoints {
                     let mut url =
                         String::with_capacity(16usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     url.push_str("/_termvectors");
                     UrlPath::from(url)
                 }
@@ -6706,20 +6706,20 @@ pub mod endpoints {
                 UpdateUrlParams::IndexId(ref index, ref id) => {
                     let mut url = String::with_capacity(10usize + index.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_update/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     UrlPath::from(url)
                 }
                 UpdateUrlParams::IndexTypeId(ref index, ref ty, ref id) => {
                     let mut url =
                         String::with_capacity(11usize + index.len() + ty.len() + id.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/");
-                    url.push_str(id.as_ref());
+                    url.push_str(&encode_path_segment(id.as_ref()));
                     url.push_str("/_update");
                     UrlPath::from(url)
                 }
@@ -6782,16 +6782,16 @@ pub mod endpoints {
                 UpdateByQueryUrlParams::Index(ref index) => {
                     let mut url = String::with_capacity(18usize + index.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/_update_by_query");
                     UrlPath::from(url)
                 }
                 UpdateByQueryUrlParams::IndexType(ref index, ref ty) => {
                     let mut url = String::with_capacity(19usize + index.len() + ty.len());
                     url.push_str("/");
-                    url.push_str(index.as_ref());
+                    url.push_str(&encode_path_segment(index.as_ref()));
                     url.push_str("/");
-                    url.push_str(ty.as_ref());
+                    url.push_str(&encode_path_segment(ty.as_ref()));
                     url.push_str("/_update_by_query");
                     UrlPath::from(url)
                 }
@@ -6846,7 +6846,7 @@ pub mod endpoints {
                 UpdateByQueryRethrottleUrlParams::TaskId(ref task_id) => {
                     let mut url = String::with_capacity(30usize + task_id.len());
                     url.push_str("/_update_by_query/");
-                    url.push_str(task_id.as_ref());
+                    url.push_str(&encode_path_segment(task_id.as_ref()));
                     url.push_str("/_rethrottle");
                     UrlPath::from(url)
                 }
@@ -7,6 +7,7 @@ use std::{
         Cursor,
         Read,
     },
+    mem,
 };
 
 use futures::{
@@ -85,6 +86,27 @@ impl AsyncBody {
         }
     }
 
+    /**
+    Get a cheap copy of the body.
+
+    The body is converted into shared bytes first so the copy doesn't need to allocate.
+    */
+    pub(crate) fn shared_bytes(&mut self) -> Bytes {
+        let bytes = match self.0 {
+            AsyncBodyInner::Shared(ref bytes) => return bytes.clone(),
+            AsyncBodyInner::Bytes(Cow::Owned(ref mut bytes)) => {
+                Bytes::from(mem::take(bytes))
+            }
+            AsyncBodyInner::Str(Cow::Owned(ref mut string)) => {
+                Bytes::from(mem::take(string))
+            }
+            ref inner => Bytes::from(inner.as_ref()),
+        };
+
+        self.0 = AsyncBodyInner::Shared(bytes.clone());
+        bytes
+    }
+
     /**
     Get a reader over the asynchronous body.
     */
@@ -191,6 +213,14 @@ mod tests {
         AsyncBody::from(empty_body());
     }
 
+    #[test]
+    fn shared_body_bytes() {
+        let mut body = AsyncBody::from(String::from("abc"));
+
+        assert_eq!(Bytes::from_static(b"abc"), body.shared_bytes());
+        assert_eq!(b"abc", body.0.as_ref());
+    }
+
     #[test]
     fn json_value_into_body() {
         AsyncBody::from(json!({}));
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use futures::{
-    future::lazy,
+    future::{
+        lazy,
+        Either,
+    },
     Future,
     Poll,
     Stream,
@@ -20,6 +23,7 @@ use crate::{
             parse,
             IsOk,
         },
+        sender::deadline::Deadline,
         AsyncChunk,
         AsyncHttpResponse,
         StatusCode,
@@ -36,17 +40,20 @@ pub struct AsyncResponseBuilder {
     inner: RawResponse,
     status: StatusCode,
     de_pool: Option<Arc<ThreadPool>>,
+    deadline: Option<Deadline>,
 }
 
 pub(crate) fn async_response(
     res: RawResponse,
     de_pool: Option<Arc<ThreadPool>>,
+    deadline: Option<Deadline>,
 ) -> Result<AsyncResponseBuilder, Error> {
     let status = StatusCode::from_u16(res.status().into()).map_err(error::request)?;
     Ok(AsyncResponseBuilder {
         inner: res,
         status,
         de_pool,
+        deadline,
     })
 }
 
@@ -73,6 +80,8 @@ impl AsyncResponseBuilder {
 
     The response is parsed according to the `IsOk` implementation for `T` that will inspect the response and either return an `Ok(T)` or an `Err(ApiError)`.
 
+    If the request was sent with a `deadline` that passes before the body has been read and parsed then the future fails with an `Error::DeadlineExceeded`.
+
     # Examples
 
     Get a strongly typed `SearchResponse`:
@@ -135,12 +144,17 @@ impl AsyncResponseBuilder {
 
         let body_future = body.concat2().map_err(move |e| error::response(status, e));
 
-        if let Some(de_pool) = self.de_pool {
-            IntoResponse::new(
+        let res_future = if let Some(de_pool) = self.de_pool {
+            Either::A(
                 body_future.and_then(move |body| de_pool.spawn_handle(lazy(move || de_fn(body)))),
             )
         } else {
-            IntoResponse::new(body_future.and_then(de_fn))
+            Either::B(body_future.and_then(de_fn))
+        };
+
+        match self.deadline {
+            Some(deadline) => IntoResponse::new(deadline.bound(res_future)),
+            None => IntoResponse::new(res_future),
         }
     }
 }
@@ -143,6 +143,15 @@ quick_error! {
             display("document in index is missing: '{}'", index)
         }
         /**
+        A document already exists but was expected not to.
+
+        Indexing a document with `create` when a document with the same id is already in the index will result in a `DocumentAlreadyExists` error.
+        */
+        DocumentAlreadyExists { index: String, id: String } {
+            description("document already exists")
+            display("document already exists in index '{}': '{}'", index, id)
+        }
+        /**
         An index already exists but was expected to.
 
         Attempting to create an index with a name that's already in use will result in an `IndexAlreadyExists` error.
@@ -175,6 +184,17 @@ quick_error! {
             description("verification exception")
             display("verification error: '{}", reason)
         }
+        /**
+        A search only returned partial results.
+
+        Elasticsearch returns a successful response when some shards fail or the search times out.
+        Search requests sent in strict mode return a `PartialResults` error instead.
+        The `reason` is the reason the first failed shard gave, if any.
+        */
+        PartialResults { failed: u32, timed_out: bool, reason: Option<String> } {
+            description("partial search results")
+            display("search returned partial results: {} failed shards, timed out: {}", failed, timed_out)
+        }
         #[doc(hidden)]
         __NonExhaustive {}
     }
@@ -232,7 +252,7 @@ impl From<Map<String, Value>> for ParsedApiError {
 
                 ParsedApiError::Known(ApiError::IndexNotFound { index })
             }
-            "index_already_exists_exception" => {
+            "index_already_exists_exception" | "resource_already_exists_exception" => {
                 let index = error_key!(obj[index]: |v| v.as_str());
 
                 ParsedApiError::Known(ApiError::IndexAlreadyExists { index })
@@ -242,6 +262,19 @@ impl From<Map<String, Value>> for ParsedApiError {
 
                 ParsedApiError::Known(ApiError::DocumentMissing { index })
             }
+            "version_conflict_engine_exception" => {
+                let index = error_key!(obj[index]: |v| v.as_str());
+                let reason = error_key!(obj[reason]: |v| v.as_str());
+
+                // Conflicts on `op_type=create` look like `[1]: version conflict, document already exists (current version [1])`
+                // Other version conflicts, like a mismatched sequence number, aren't treated as a known error
+                let id = match parse_document_already_exists(&reason) {
+                    Some(id) => id.to_owned(),
+                    _ => return ParsedApiError::Unknown(obj),
+                };
+
+                ParsedApiError::Known(ApiError::DocumentAlreadyExists { index, id })
+            }
             "action_request_validation_exception" => {
                 let reason = error_key!(obj[reason]: |v| v.as_str());
 
@@ -266,3 +299,15 @@ impl From<Map<String, Value>> for ParsedApiError {
         }
     }
 }
+
+fn parse_document_already_exists(reason: &str) -> Option<&str> {
+    if !reason.contains("document already exists") {
+        return None;
+    }
+
+    // The id is the last bracketed value before the first `:`, after any type
+    let (prefix, _) = reason.split_at(reason.find("]:")?);
+    let start = prefix.rfind('[')?;
+
+    Some(&prefix[start + 1..])
+}
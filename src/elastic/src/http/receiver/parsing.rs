@@ -8,10 +8,7 @@ use serde_json::{
     Value,
 };
 use std::{
-    io::{
-        Cursor,
-        Read,
-    },
+    io::Read,
     marker::PhantomData,
 };
 
@@ -160,15 +157,24 @@ impl<B: Read> ResponseBody for ReadBody<B> {
         let mut buf = Vec::new();
         self.0.read_to_end(&mut buf)?;
 
-        let body: Value = serde_json::from_reader(Cursor::new(&buf))?;
+        let body: Value = from_buf(&buf)?;
 
         Ok((body, SliceBody(buf)))
     }
 
+    #[cfg(not(feature = "simd-json"))]
     fn parse_ok<T: DeserializeOwned>(self) -> Result<T, ParseError> {
         serde_json::from_reader(self.0).map_err(|e| e.into())
     }
 
+    #[cfg(feature = "simd-json")]
+    fn parse_ok<T: DeserializeOwned>(mut self) -> Result<T, ParseError> {
+        let mut buf = Vec::new();
+        self.0.read_to_end(&mut buf)?;
+
+        from_buf(&buf)
+    }
+
     fn parse_err(self) -> Result<ApiError, ParseError> {
         match serde_json::from_reader(self.0)? {
             ParsedApiError::Known(err) => Ok(err),
@@ -185,13 +191,13 @@ impl<B: AsRef<[u8]>> ResponseBody for SliceBody<B> {
     fn body(self) -> Result<(Value, Self::Buffered), ParseError> {
         let buf = self.0;
 
-        let body: Value = serde_json::from_slice(buf.as_ref())?;
+        let body: Value = from_buf(buf.as_ref())?;
 
         Ok((body, SliceBody(buf)))
     }
 
     fn parse_ok<T: DeserializeOwned>(self) -> Result<T, ParseError> {
-        serde_json::from_slice(self.0.as_ref()).map_err(|e| e.into())
+        from_buf(self.0.as_ref())
     }
 
     fn parse_err(self) -> Result<ApiError, ParseError> {
@@ -202,6 +208,44 @@ impl<B: AsRef<[u8]>> ResponseBody for SliceBody<B> {
     }
 }
 
+/** Parse a buffered response body. */
+#[cfg(not(feature = "simd-json"))]
+fn from_buf<T: DeserializeOwned>(buf: &[u8]) -> Result<T, ParseError> {
+    serde_json::from_slice(buf).map_err(|e| e.into())
+}
+
+/**
+Parse a buffered response body with `simd-json`.
+
+`simd-json` parses in place, so the borrowed body is copied first.
+If `simd-json` rejects the syntax of the body then the original is parsed again with `serde_json`.
+That way any body `serde_json` accepts can still be parsed, and syntax errors have `serde_json`'s messages.
+Errors deserializing `T` from a body with valid syntax are returned without parsing it again.
+*/
+#[cfg(feature = "simd-json")]
+fn from_buf<T: DeserializeOwned>(buf: &[u8]) -> Result<T, ParseError> {
+    let mut scratch = buf.to_vec();
+
+    match simd_json_from_slice(&mut scratch) {
+        Ok(parsed) => parsed.map_err(ParseError::new),
+        Err(_) => serde_json::from_slice(buf).map_err(|e| e.into()),
+    }
+}
+
+/**
+Parse a body in place with `simd-json`.
+
+The outer error is a syntax error from building the tape, and the inner one is an error deserializing `T` from it.
+*/
+#[cfg(feature = "simd-json")]
+fn simd_json_from_slice<T: DeserializeOwned>(
+    buf: &mut [u8],
+) -> Result<Result<T, simd_json::Error>, simd_json::Error> {
+    let mut de = simd_json::Deserializer::from_slice(buf)?;
+
+    Ok(serde::Deserialize::deserialize(&mut de))
+}
+
 impl ResponseBody for Value {
     type Buffered = Self;
 
@@ -429,3 +473,66 @@ where
         MaybeBufferedResponse::Value(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_buf_value() {
+        let value: Value = from_buf(br#"{"a":[1,"b",null]}"#).unwrap();
+
+        assert_eq!(json!({ "a": [1, "b", null] }), value);
+    }
+
+    #[test]
+    fn from_buf_invalid() {
+        let err = from_buf::<Value>(br#"{"a":"#).unwrap_err();
+
+        // Syntax errors always come from `serde_json`, even with `simd-json`
+        assert!(err.to_string().contains("EOF while parsing"), "{}", err);
+    }
+
+    #[test]
+    fn from_buf_typed_error() {
+        #[derive(Deserialize, Debug)]
+        struct Typed {
+            #[allow(dead_code)]
+            a: String,
+        }
+
+        let err = from_buf::<Typed>(br#"{"b":1}"#).unwrap_err();
+
+        assert!(err.to_string().contains("missing field"), "{}", err);
+    }
+
+    #[test]
+    fn from_reader_value() {
+        let value = parse::<Value>()
+            .from_reader(StatusCode::OK, &br#"{"a":[1,"b",null]}"#[..])
+            .unwrap();
+
+        assert_eq!(json!({ "a": [1, "b", null] }), value);
+    }
+
+    // `simd-json` rejects integers that don't fit in 64 bits, but `serde_json` parses them as floats
+    const TOO_BIG_FOR_SIMD_JSON: &[u8] = br#"{"a":18446744073709551616}"#;
+
+    #[test]
+    fn from_slice_falls_back_to_serde_json() {
+        let value = parse::<Value>()
+            .from_slice(StatusCode::OK, TOO_BIG_FOR_SIMD_JSON)
+            .unwrap();
+
+        assert_eq!(json!({ "a": 18446744073709551616.0 }), value);
+    }
+
+    #[test]
+    fn from_reader_falls_back_to_serde_json() {
+        let value = parse::<Value>()
+            .from_reader(StatusCode::OK, TOO_BIG_FOR_SIMD_JSON)
+            .unwrap();
+
+        assert_eq!(json!({ "a": 18446744073709551616.0 }), value);
+    }
+}
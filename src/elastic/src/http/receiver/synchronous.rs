@@ -11,6 +11,10 @@ use crate::{
             parse,
             IsOk,
         },
+        sender::deadline::{
+            BlockingWorkers,
+            Deadline,
+        },
         StatusCode,
         SyncHttpResponse,
     },
@@ -22,11 +26,14 @@ A builder for a response.
 This structure wraps the completed HTTP response but gives you options for converting it into a concrete type.
 You can also `Read` directly from the response body.
 */
-pub struct SyncResponseBuilder(StatusCode, RawResponse);
+pub struct SyncResponseBuilder(StatusCode, RawResponse, Option<(Deadline, BlockingWorkers)>);
 
-pub(crate) fn sync_response(res: RawResponse) -> Result<SyncResponseBuilder, Error> {
+pub(crate) fn sync_response(
+    res: RawResponse,
+    deadline: Option<(Deadline, BlockingWorkers)>,
+) -> Result<SyncResponseBuilder, Error> {
     let status = StatusCode::from_u16(res.status().into()).map_err(error::request)?;
-    Ok(SyncResponseBuilder(status, res))
+    Ok(SyncResponseBuilder(status, res, deadline))
 }
 
 impl SyncResponseBuilder {
@@ -51,6 +58,8 @@ impl SyncResponseBuilder {
 
     The response is parsed according to the `IsOk` implementation for `T` that will inspect the response and either return an `Ok(T)` or an `Err(ApiError)`.
 
+    If the request was sent with a `deadline` that passes before the body has been read then this returns an `Error::DeadlineExceeded`.
+
     # Examples
 
     Get a strongly typed `SearchResponse`:
@@ -97,8 +106,26 @@ impl SyncResponseBuilder {
         T: IsOk + DeserializeOwned,
     {
         let status = self.0;
-        parse()
-            .from_reader(status, self.1)
-            .map_err(|e| error::response(status, e))
+        let mut res = self.1;
+
+        match self.2 {
+            Some((deadline, workers)) => {
+                // Read the body on another thread so a slow response can't block past the deadline
+                let body = deadline.run_blocking(&workers, move || {
+                    let mut body = Vec::new();
+                    res.copy_to(&mut body)
+                        .map_err(|e| error::response(status, e))?;
+
+                    Ok(body)
+                })?;
+
+                parse()
+                    .from_slice(status, &body)
+                    .map_err(|e| error::response(status, e))
+            }
+            None => parse()
+                .from_reader(status, res)
+                .map_err(|e| error::response(status, e)),
+        }
     }
 }
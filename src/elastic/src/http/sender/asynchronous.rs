@@ -7,6 +7,7 @@ use futures::{
     Future,
     IntoFuture,
     Poll,
+    Stream,
 };
 use reqwest::r#async::{
     Client as AsyncHttpClient,
@@ -35,6 +36,17 @@ use crate::{
         sender::{
             build_reqwest_method,
             build_url,
+            circuit_breaker::{
+                self,
+                CircuitBreaker,
+            },
+            deadline::Deadline,
+            recording::{
+                self,
+                RecordedRequest,
+                Recording,
+            },
+            slow_requests::SlowRequests,
             NextParams,
             NodeAddresses,
             NodeAddressesInner,
@@ -45,6 +57,7 @@ use crate::{
         },
         AsyncBody,
         AsyncHttpRequest,
+        StatusCode,
         Url,
     },
     private,
@@ -62,6 +75,9 @@ pub struct AsyncSender {
     pub(crate) http: AsyncHttpClient,
     pub(crate) serde_pool: Option<Arc<ThreadPool>>,
     pub(crate) pre_send: Option<Arc<AsyncPreSend>>,
+    pub(crate) circuit_breaker: Option<CircuitBreaker>,
+    pub(crate) slow_requests: Option<SlowRequests>,
+    pub(crate) recording: Option<Recording>,
 }
 
 impl private::Sealed for AsyncSender {}
@@ -98,11 +114,36 @@ impl Sender for AsyncSender {
         TParams: Into<Self::Params> + Send + 'static,
     {
         let correlation_id = request.correlation_id;
+        let deadline = request.deadline.map(Deadline::start);
         let serde_pool = self.serde_pool.clone();
         let params = request.params;
+        let endpoint = request.inner.into();
+
+        // The index scope may need to check the body, so convert it first
+        let endpoint = Endpoint {
+            url: endpoint.url,
+            method: endpoint.method,
+            body: endpoint.body.map(|body| -> AsyncBody { body.into() }),
+        };
+
+        let endpoint = match request.index_scope {
+            Some(ref index_scope) => match index_scope.endpoint(endpoint, request.scoped_body) {
+                Ok(endpoint) => endpoint,
+                Err(e) => {
+                    error!(
+                        "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
+                        correlation_id, e
+                    );
+
+                    return PendingResponse::new(Err(e).into_future());
+                }
+            },
+            None => endpoint,
+        };
+
         let Endpoint {
             url, method, body, ..
-        } = request.inner.into();
+        } = endpoint;
 
         info!(
             "Elasticsearch Request: correlation_id: '{}', path: '{}'",
@@ -124,6 +165,7 @@ impl Sender for AsyncSender {
             }
         };
 
+        let circuit_breaker = self.circuit_breaker.clone();
         let build_req_future = params_future
             .and_then(move |params| {
                 Url::parse(&build_url(&url, &params))
@@ -131,34 +173,62 @@ impl Sender for AsyncSender {
                     .map(|url| (params, url))
             })
             .and_then(move |(params, url)| {
-                Ok(AsyncHttpRequest {
+                let circuit = circuit_breaker
+                    .map(|circuit_breaker| (circuit_breaker, params.get_base_url().to_owned()));
+
+                let req = AsyncHttpRequest {
                     url,
                     method,
                     headers: params.get_headers(),
-                    body: body.map(|body| body.into()),
-                })
+                    body,
+                };
+
+                Ok((circuit, req))
             });
 
         let pre_send = self.pre_send.clone();
-        let pre_send_future = build_req_future.and_then(move |mut req| {
+        let pre_send_future = build_req_future.and_then(move |(circuit, mut req)| {
             if let Some(pre_send) = pre_send {
                 Either::A(
                     pre_send(&mut req)
                         .map_err(error::wrapped)
                         .map_err(error::request)
-                        .and_then(move |_| Ok(req).into_future()),
+                        .and_then(move |_| Ok((circuit, req)).into_future()),
                 )
             } else {
-                Either::B(Ok(req).into_future())
+                Either::B(Ok((circuit, req)).into_future())
             }
         });
 
         let pre_send_http = self.http.clone();
+        let slow_requests = self.slow_requests.clone();
+        let record = self.recording.is_some();
         let pre_send_future = pre_send_future
-            .and_then(move |req| {
+            .and_then(move |(circuit, mut req)| {
+                let body = if slow_requests.is_some() || record {
+                    req.body.as_mut().map(|body| body.shared_bytes())
+                } else {
+                    None
+                };
+
+                let recorded_request = if record {
+                    Some(RecordedRequest::new(
+                        &req.method,
+                        &req.url,
+                        body.as_ref().map(|body| body.as_ref()),
+                    ))
+                } else {
+                    None
+                };
+
+                let slow_request = slow_requests.map(|slow_requests| {
+                    slow_requests.start(correlation_id, req.method.clone(), req.url.clone(), body)
+                });
+
                 build_reqwest(&pre_send_http, req)
                     .build()
                     .map_err(error::request)
+                    .map(|req| (circuit, slow_request, recorded_request, req))
             })
             .log_err(move |e| {
                 error!(
@@ -168,9 +238,57 @@ impl Sender for AsyncSender {
             });
 
         let req_http = self.http.clone();
-        let req_future = pre_send_future.and_then(move |req| {
-            req_http
+        let recording = self.recording.clone();
+        let req_future = pre_send_future.and_then(move |(circuit, slow_request, recorded, req)| {
+            if let (Some(Recording::Replay(ref replayer)), Some(ref recorded)) =
+                (&recording, &recorded)
+            {
+                let res_future = replayer
+                    .replay(recorded)
+                    .and_then(|(status, body)| {
+                        info!(
+                            "Elasticsearch Response: correlation_id: '{}', status: '{}' (replayed)",
+                            correlation_id, status
+                        );
+
+                        recording::async_raw_response(status, body)
+                    })
+                    .and_then(|res| async_response(res, serde_pool, deadline))
+                    .into_future()
+                    .log_err(move |e| {
+                        error!(
+                            "Elasticsearch Response: correlation_id: '{}', error: '{:?}'",
+                            correlation_id, e
+                        )
+                    });
+
+                return Either::A(res_future);
+            }
+
+            let res_future = req_http
                 .execute(req)
+                .then(move |res| {
+                    if let Some(slow_request) = slow_request {
+                        slow_request.finish(
+                            res.as_ref()
+                                .ok()
+                                .and_then(|res| StatusCode::from_u16(res.status().as_u16()).ok()),
+                        );
+                    }
+
+                    if let Some((circuit_breaker, address)) = circuit {
+                        match res {
+                            Ok(ref res)
+                                if !circuit_breaker::is_node_failure(res.status().as_u16()) =>
+                            {
+                                circuit_breaker.record_success(&address)
+                            }
+                            _ => circuit_breaker.record_failure(&address),
+                        }
+                    }
+
+                    res
+                })
                 .map_err(error::request)
                 .and_then(move |res| {
                     info!(
@@ -178,17 +296,45 @@ impl Sender for AsyncSender {
                         correlation_id,
                         res.status()
                     );
-                    async_response(res, serde_pool).into_future()
+
+                    match (recording, recorded) {
+                        (Some(Recording::Record(recorder)), Some(recorded)) => {
+                            let status = res.status().as_u16();
+
+                            Either::A(res.into_body().concat2().map_err(error::request).and_then(
+                                move |body| {
+                                    let body = body.to_vec();
+                                    recorder.record(recorded, status, &body);
+
+                                    recording::async_raw_response(status, body)
+                                },
+                            ))
+                        }
+                        _ => Either::B(Ok(res).into_future()),
+                    }
                 })
+                .and_then(move |res| async_response(res, serde_pool, deadline).into_future())
                 .log_err(move |e| {
                     error!(
                         "Elasticsearch Response: correlation_id: '{}', error: '{:?}'",
                         correlation_id, e
                     )
-                })
+                });
+
+            Either::B(res_future)
         });
 
-        PendingResponse::new(req_future)
+        match deadline {
+            Some(deadline) => PendingResponse::new(deadline.bound(req_future).log_err(move |e| {
+                if let Error::DeadlineExceeded(_) = *e {
+                    error!(
+                        "Elasticsearch Response: correlation_id: '{}', error: '{:?}'",
+                        correlation_id, e
+                    )
+                }
+            })),
+            None => PendingResponse::new(req_future),
+        }
     }
 }
 
@@ -0,0 +1,378 @@
+/*!
+A client-side circuit breaker for nodes that keep failing.
+
+Each node starts with a _closed_ circuit, so requests are sent to it as normal.
+After a number of consecutive failures the circuit _opens_ and node selection skips that node.
+Once a cooldown has passed the circuit becomes _half-open_ and a single probe request is sent to the node.
+If the probe succeeds the circuit closes again, otherwise it reopens for another cooldown.
+
+A request fails if it couldn't be sent or the node responded with a `502`, `503` or `504` status.
+*/
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+const DEFAULT_COOLDOWN_SECS: u64 = 30;
+
+/**
+A circuit breaker shared between the nodes of a client.
+
+The `CircuitBreaker` is cheap to clone and all clones share the same state.
+Keep a clone around to observe the state of each node through [`stats`](#method.stats).
+
+# Examples
+
+Open the circuit for a node after 3 consecutive failures and probe it again after 10 seconds:
+
+```no_run
+# use std::time::Duration;
+# use elastic::prelude::*;
+# use elastic::http::sender::circuit_breaker::CircuitBreaker;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+let breaker = CircuitBreaker::new(3, Duration::from_secs(10));
+
+let client = SyncClientBuilder::new()
+    .static_nodes(vec!["http://es1:9200", "http://es2:9200"])
+    .circuit_breaker(breaker.clone())
+    .build()?;
+
+for node in breaker.stats() {
+    println!("{}: {:?}", node.address(), node.state());
+}
+# Ok(())
+# }
+```
+*/
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    inner: Arc<CircuitBreakerInner>,
+}
+
+struct CircuitBreakerInner {
+    failure_threshold: u32,
+    cooldown: Duration,
+    nodes: Mutex<HashMap<String, NodeCircuit>>,
+}
+
+struct NodeCircuit {
+    state: CircuitState,
+    consecutive_failures: u32,
+    total_failures: u64,
+    times_opened: u64,
+    changed_at: Instant,
+}
+
+/** The state of the circuit for a single node. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /** Requests are sent to the node. */
+    Closed,
+    /** Requests aren't sent to the node until the cooldown has passed. */
+    Open,
+    /** A single probe request has been sent to the node to see whether it's recovered. */
+    HalfOpen,
+}
+
+/** A snapshot of the circuit for a single node. */
+#[derive(Debug, Clone)]
+pub struct NodeCircuitStats {
+    address: String,
+    state: CircuitState,
+    consecutive_failures: u32,
+    total_failures: u64,
+    times_opened: u64,
+}
+
+impl NodeCircuitStats {
+    /** The address of the node. */
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /** The current state of the circuit. */
+    pub fn state(&self) -> CircuitState {
+        self.state
+    }
+
+    /** The number of requests that have failed since the last successful one. */
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /** The total number of requests to the node that have failed. */
+    pub fn total_failures(&self) -> u64 {
+        self.total_failures
+    }
+
+    /** The number of times the circuit has opened. */
+    pub fn times_opened(&self) -> u64 {
+        self.times_opened
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        CircuitBreaker::new(
+            DEFAULT_FAILURE_THRESHOLD,
+            Duration::from_secs(DEFAULT_COOLDOWN_SECS),
+        )
+    }
+}
+
+impl CircuitBreaker {
+    /**
+    Create a circuit breaker that opens after `failure_threshold` consecutive failures and probes the node again after `cooldown`.
+
+    A `failure_threshold` of `0` is treated as `1`.
+    */
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        CircuitBreaker {
+            inner: Arc::new(CircuitBreakerInner {
+                failure_threshold: failure_threshold.max(1),
+                cooldown,
+                nodes: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /**
+    Get a snapshot of the circuit for each node that's been sent a request.
+
+    Nodes are ordered by address.
+    */
+    pub fn stats(&self) -> Vec<NodeCircuitStats> {
+        let nodes = self.inner.nodes.lock().expect("lock poisoned");
+
+        let mut stats: Vec<_> = nodes
+            .iter()
+            .map(|(address, node)| NodeCircuitStats {
+                address: address.clone(),
+                state: node.state,
+                consecutive_failures: node.consecutive_failures,
+                total_failures: node.total_failures,
+                times_opened: node.times_opened,
+            })
+            .collect();
+
+        stats.sort_by(|a, b| a.address.cmp(&b.address));
+        stats
+    }
+
+    /**
+    Whether or not a request can be sent to the given node.
+
+    If the cooldown for an open circuit has passed then this moves it to half-open and allows the probe request through.
+    */
+    pub(crate) fn try_acquire(&self, address: &str) -> bool {
+        self.try_acquire_at(address, Instant::now())
+    }
+
+    /** Record a request to the given node that succeeded. */
+    pub(crate) fn record_success(&self, address: &str) {
+        let mut nodes = self.inner.nodes.lock().expect("lock poisoned");
+
+        let node = node_circuit(&mut nodes, address, Instant::now());
+
+        if node.state != CircuitState::Closed {
+            info!("Elasticsearch Node Circuit Closed: address: '{}'", address);
+        }
+
+        node.state = CircuitState::Closed;
+        node.consecutive_failures = 0;
+    }
+
+    /** Record a request to the given node that failed. */
+    pub(crate) fn record_failure(&self, address: &str) {
+        self.record_failure_at(address, Instant::now())
+    }
+
+    fn try_acquire_at(&self, address: &str, now: Instant) -> bool {
+        let mut nodes = self.inner.nodes.lock().expect("lock poisoned");
+
+        let node = match nodes.get_mut(address) {
+            Some(node) => node,
+            None => return true,
+        };
+
+        match node.state {
+            CircuitState::Closed => true,
+            // A half-open probe that never finished is treated like an open circuit
+            CircuitState::Open | CircuitState::HalfOpen => {
+                if now.duration_since(node.changed_at) >= self.inner.cooldown {
+                    node.state = CircuitState::HalfOpen;
+                    node.changed_at = now;
+
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn record_failure_at(&self, address: &str, now: Instant) {
+        let mut nodes = self.inner.nodes.lock().expect("lock poisoned");
+
+        let node = node_circuit(&mut nodes, address, now);
+
+        node.consecutive_failures += 1;
+        node.total_failures += 1;
+
+        let should_open = match node.state {
+            CircuitState::Closed => node.consecutive_failures >= self.inner.failure_threshold,
+            CircuitState::HalfOpen => true,
+            CircuitState::Open => false,
+        };
+
+        if should_open {
+            warn!(
+                "Elasticsearch Node Circuit Opened: address: '{}', consecutive failures: '{}'",
+                address, node.consecutive_failures
+            );
+
+            node.state = CircuitState::Open;
+            node.times_opened += 1;
+            node.changed_at = now;
+        }
+    }
+}
+
+/** Get the circuit for a node, starting a closed one if the node hasn't been sent a request before. */
+fn node_circuit<'a>(
+    nodes: &'a mut HashMap<String, NodeCircuit>,
+    address: &str,
+    now: Instant,
+) -> &'a mut NodeCircuit {
+    nodes
+        .entry(address.to_owned())
+        .or_insert_with(|| NodeCircuit {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            total_failures: 0,
+            times_opened: 0,
+            changed_at: now,
+        })
+}
+
+/** Whether or not a response status means the node itself is unavailable. */
+pub(crate) fn is_node_failure(status: u16) -> bool {
+    (502..=504).contains(&status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NODE: &str = "http://a:9200";
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(2, Duration::from_secs(10))
+    }
+
+    fn state(breaker: &CircuitBreaker) -> CircuitState {
+        breaker.stats()[0].state()
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let breaker = breaker();
+        let now = Instant::now();
+
+        breaker.record_failure_at(NODE, now);
+        assert_eq!(CircuitState::Closed, state(&breaker));
+        assert!(breaker.try_acquire_at(NODE, now));
+
+        breaker.record_failure_at(NODE, now);
+        assert_eq!(CircuitState::Open, state(&breaker));
+        assert!(!breaker.try_acquire_at(NODE, now));
+    }
+
+    #[test]
+    fn success_resets_failures() {
+        let breaker = breaker();
+        let now = Instant::now();
+
+        breaker.record_failure_at(NODE, now);
+        breaker.record_success(NODE);
+        breaker.record_failure_at(NODE, now);
+
+        let stats = &breaker.stats()[0];
+        assert_eq!(CircuitState::Closed, stats.state());
+        assert_eq!(1, stats.consecutive_failures());
+        assert_eq!(2, stats.total_failures());
+    }
+
+    #[test]
+    fn half_open_after_cooldown() {
+        let breaker = breaker();
+        let now = Instant::now();
+
+        breaker.record_failure_at(NODE, now);
+        breaker.record_failure_at(NODE, now);
+
+        let later = now + Duration::from_secs(10);
+
+        // Only a single probe is let through
+        assert!(breaker.try_acquire_at(NODE, later));
+        assert_eq!(CircuitState::HalfOpen, state(&breaker));
+        assert!(!breaker.try_acquire_at(NODE, later));
+
+        breaker.record_success(NODE);
+        assert_eq!(CircuitState::Closed, state(&breaker));
+        assert!(breaker.try_acquire_at(NODE, later));
+    }
+
+    #[test]
+    fn failed_probe_reopens() {
+        let breaker = breaker();
+        let now = Instant::now();
+
+        breaker.record_failure_at(NODE, now);
+        breaker.record_failure_at(NODE, now);
+
+        let later = now + Duration::from_secs(10);
+        assert!(breaker.try_acquire_at(NODE, later));
+
+        breaker.record_failure_at(NODE, later);
+
+        let stats = &breaker.stats()[0];
+        assert_eq!(CircuitState::Open, stats.state());
+        assert_eq!(2, stats.times_opened());
+        assert!(!breaker.try_acquire_at(NODE, later + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn stats_include_nodes_without_failures() {
+        let breaker = breaker();
+
+        breaker.record_success("http://b:9200");
+        breaker.record_failure_at(NODE, Instant::now());
+
+        let stats = breaker.stats();
+        assert_eq!(
+            vec!["http://a:9200", "http://b:9200"],
+            stats.iter().map(|node| node.address()).collect::<Vec<_>>()
+        );
+
+        assert_eq!(CircuitState::Closed, stats[1].state());
+        assert_eq!(0, stats[1].total_failures());
+    }
+
+    #[test]
+    fn unknown_nodes_are_closed() {
+        let breaker = breaker();
+
+        assert!(breaker.try_acquire("http://b:9200"));
+        assert!(breaker.stats().is_empty());
+    }
+}
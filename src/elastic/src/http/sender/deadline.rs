@@ -0,0 +1,480 @@
+/*!
+A time budget for a single request.
+
+The deadline starts when a request is sent and covers node selection, the http round trip and parsing the response body.
+
+Synchronous requests run their blocking steps on a bounded pool of worker threads, so the caller can give up on a step when the deadline passes.
+*/
+
+use channel::{
+    self,
+    Receiver,
+    SendTimeoutError,
+    Sender,
+    TrySendError,
+};
+use futures::Future;
+use std::{
+    panic::{
+        self,
+        AssertUnwindSafe,
+    },
+    sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        mpsc::{
+            self,
+            RecvTimeoutError,
+        },
+        Arc,
+    },
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use tokio::timer::Timeout;
+
+use crate::error::{
+    self,
+    Error,
+};
+
+/** A point in time that a request must complete by. */
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Deadline {
+    budget: Duration,
+    expires_at: Instant,
+}
+
+impl Deadline {
+    /** Start a deadline that expires `budget` from now. */
+    pub(crate) fn start(budget: Duration) -> Self {
+        Deadline::start_at(Instant::now(), budget)
+    }
+
+    fn start_at(now: Instant, budget: Duration) -> Self {
+        Deadline {
+            budget,
+            expires_at: now + budget,
+        }
+    }
+
+    /** Return an `Error::DeadlineExceeded` if the deadline has passed. */
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        self.check_at(Instant::now())
+    }
+
+    fn check_at(&self, now: Instant) -> Result<(), Error> {
+        if now >= self.expires_at {
+            Err(self.exceeded())
+        } else {
+            Ok(())
+        }
+    }
+
+    /**
+    Check the deadline after a step that returned `res`.
+
+    If the step failed after the deadline passed then its error is replaced by an `Error::DeadlineExceeded`,
+    so a timeout caused by running out of time isn't reported as an ordinary request failure.
+    A step that succeeded is returned as-is.
+    */
+    pub(crate) fn check_after<T>(&self, res: Result<T, Error>) -> Result<T, Error> {
+        match res {
+            Err(_) if self.check().is_err() => Err(self.exceeded()),
+            res => res,
+        }
+    }
+
+    /**
+    Run a blocking step on one of the `workers`, giving up on it when the deadline passes.
+
+    A step that's given up on keeps its worker busy until it finishes, but its result is discarded.
+    If every worker is busy then the step waits for one to become free until the deadline passes.
+    */
+    pub(crate) fn run_blocking<T, F>(self, workers: &BlockingWorkers, step: F) -> Result<T, Error>
+    where
+        F: FnOnce() -> Result<T, Error> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        workers.run(
+            self,
+            Box::new(move || {
+                // Skip steps whose deadline passed while they were waiting for a worker
+                if self.check().is_ok() {
+                    // The receiver is gone if the deadline has already passed
+                    let _ = tx.send(step());
+                }
+            }),
+        )?;
+
+        let remaining = self.check().map(|_| self.remaining())?;
+
+        match rx.recv_timeout(remaining) {
+            Ok(res) => self.check_after(res),
+            Err(RecvTimeoutError::Timeout) => Err(self.exceeded()),
+            Err(RecvTimeoutError::Disconnected) => {
+                self.check()?;
+
+                Err(error::request(error::message(
+                    "the thread sending the request panicked",
+                )))
+            }
+        }
+    }
+
+    /** Fail a future with an `Error::DeadlineExceeded` if it doesn't complete before the deadline. */
+    pub(crate) fn bound<F>(self, fut: F) -> impl Future<Item = F::Item, Error = Error> + Send
+    where
+        F: Future<Error = Error> + Send,
+        F::Item: Send,
+    {
+        Timeout::new_at(fut, self.expires_at).map_err(move |e| {
+            if e.is_elapsed() {
+                self.exceeded()
+            } else if e.is_inner() {
+                e.into_inner().expect("inner error is missing")
+            } else {
+                error::request(e.into_timer().expect("timer error is missing"))
+            }
+        })
+    }
+
+    fn remaining(&self) -> Duration {
+        self.remaining_at(Instant::now())
+    }
+
+    fn remaining_at(&self, now: Instant) -> Duration {
+        if now < self.expires_at {
+            self.expires_at - now
+        } else {
+            Duration::from_secs(0)
+        }
+    }
+
+    fn exceeded(&self) -> Error {
+        error::deadline_exceeded(self.budget)
+    }
+}
+
+/** The most threads a synchronous client runs blocking steps with a deadline on by default. */
+pub(crate) const DEFAULT_BLOCKING_WORKERS: usize = 32;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/**
+A bounded pool of threads that blocking steps with a deadline run on.
+
+Steps are handed directly to an idle thread.
+A new thread is started when no thread is idle, up to a maximum, and threads are reused for later steps.
+Threads stop once the pool is dropped.
+*/
+#[derive(Clone)]
+pub(crate) struct BlockingWorkers {
+    jobs: Sender<Job>,
+    queue: Receiver<Job>,
+    counts: Arc<WorkerCounts>,
+}
+
+struct WorkerCounts {
+    max: usize,
+    threads: AtomicUsize,
+}
+
+impl BlockingWorkers {
+    /** Create a pool that runs up to `max` threads. */
+    pub(crate) fn new(max: usize) -> Self {
+        // Jobs are only sent to a thread that's waiting for one
+        let (jobs, queue) = channel::bounded(0);
+
+        BlockingWorkers {
+            jobs,
+            queue,
+            counts: Arc::new(WorkerCounts {
+                max,
+                threads: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    // Hand a job to a thread, waiting for one to become idle until the deadline passes
+    fn run(&self, deadline: Deadline, job: Job) -> Result<(), Error> {
+        let job = match self.jobs.try_send(job) {
+            Ok(()) => return Ok(()),
+            Err(TrySendError::Full(job)) | Err(TrySendError::Disconnected(job)) => job,
+        };
+
+        self.spawn_if_needed()?;
+
+        match self.jobs.send_timeout(job, deadline.remaining()) {
+            Ok(()) => Ok(()),
+            Err(SendTimeoutError::Timeout(_)) => Err(deadline.exceeded()),
+            Err(SendTimeoutError::Disconnected(_)) => Err(error::request(error::message(
+                "the blocking workers have stopped",
+            ))),
+        }
+    }
+
+    fn spawn_if_needed(&self) -> Result<(), Error> {
+        let counts = &self.counts;
+
+        let threads = counts.threads.load(Ordering::SeqCst);
+        if threads >= counts.max {
+            return Ok(());
+        }
+
+        // Another step has just started a thread
+        if counts
+            .threads
+            .compare_exchange(threads, threads + 1, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Ok(());
+        }
+
+        // The worker only holds the receiving end of the queue, so it stops when the pool is dropped
+        let queue = self.queue.clone();
+        let worker = Worker(self.counts.clone());
+
+        thread::Builder::new()
+            .name("elastic-deadline".to_owned())
+            .spawn(move || worker.run(queue))
+            .map(|_| ())
+            .map_err(|e| {
+                self.counts.threads.fetch_sub(1, Ordering::SeqCst);
+                error::request(e)
+            })
+    }
+
+    #[cfg(test)]
+    pub(crate) fn threads(&self) -> usize {
+        self.counts.threads.load(Ordering::SeqCst)
+    }
+}
+
+// A running worker thread, which is removed from the counts when it stops
+struct Worker(Arc<WorkerCounts>);
+
+impl Worker {
+    fn run(self, queue: Receiver<Job>) {
+        loop {
+            match queue.recv() {
+                // A step that panics is reported to its caller as an error, so the worker can keep running
+                Ok(job) => {
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                }
+                Err(_) => return,
+            }
+        }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.0.threads.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future;
+    use tokio::runtime::current_thread::block_on_all;
+
+    fn assert_exceeded<T: ::std::fmt::Debug>(res: Result<T, Error>, budget: Duration) {
+        match res {
+            Err(Error::DeadlineExceeded(exceeded)) => assert_eq!(budget, exceeded),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    // A step that blocks its worker until the returned sender is dropped
+    fn blocked_step() -> (
+        Sender<()>,
+        impl FnOnce() -> Result<(), Error> + Send + 'static,
+    ) {
+        let (release, blocked) = channel::bounded::<()>(0);
+
+        (release, move || {
+            let _ = blocked.recv();
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn check_before_deadline() {
+        let now = Instant::now();
+        let deadline = Deadline::start_at(now, Duration::from_secs(60));
+
+        assert!(deadline.check_at(now).is_ok());
+        assert!(deadline.check_at(now + Duration::from_secs(59)).is_ok());
+    }
+
+    #[test]
+    fn check_at_deadline() {
+        let now = Instant::now();
+        let deadline = Deadline::start_at(now, Duration::from_secs(60));
+
+        assert_exceeded(
+            deadline.check_at(now + Duration::from_secs(60)),
+            Duration::from_secs(60),
+        );
+        assert_exceeded(
+            deadline.check_at(now + Duration::from_secs(61)),
+            Duration::from_secs(60),
+        );
+    }
+
+    #[test]
+    fn remaining_before_and_after_deadline() {
+        let now = Instant::now();
+        let deadline = Deadline::start_at(now, Duration::from_secs(60));
+
+        assert_eq!(
+            Duration::from_secs(50),
+            deadline.remaining_at(now + Duration::from_secs(10))
+        );
+        assert_eq!(
+            Duration::from_secs(0),
+            deadline.remaining_at(now + Duration::from_secs(70))
+        );
+    }
+
+    #[test]
+    fn check_after_deadline() {
+        let deadline = Deadline::start(Duration::from_millis(0));
+
+        assert_exceeded(
+            deadline.check_after(Err::<(), _>(error::test())),
+            Duration::from_millis(0),
+        );
+    }
+
+    #[test]
+    fn check_after_deadline_keeps_success() {
+        let deadline = Deadline::start(Duration::from_millis(0));
+
+        assert_eq!(1, deadline.check_after(Ok(1)).unwrap());
+    }
+
+    #[test]
+    fn run_blocking_before_deadline() {
+        let workers = BlockingWorkers::new(1);
+        let deadline = Deadline::start(Duration::from_secs(60));
+
+        assert_eq!(1, deadline.run_blocking(&workers, || Ok(1)).unwrap());
+    }
+
+    #[test]
+    fn run_blocking_after_deadline() {
+        let workers = BlockingWorkers::new(1);
+        let deadline = Deadline::start(Duration::from_millis(10));
+
+        let (release, step) = blocked_step();
+        let res = deadline.run_blocking(&workers, step);
+        drop(release);
+
+        assert_exceeded(res, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn run_blocking_with_passed_deadline_skips_step() {
+        let workers = BlockingWorkers::new(1);
+        let budget = Duration::from_millis(0);
+        let deadline = Deadline::start(budget);
+
+        let (tx, rx) = mpsc::channel();
+        let res = deadline.run_blocking(&workers, move || {
+            tx.send(()).unwrap();
+            Ok(())
+        });
+
+        assert_exceeded(res, budget);
+
+        // The step is dropped without running once its worker picks it up
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn run_blocking_reuses_workers() {
+        let workers = BlockingWorkers::new(1);
+
+        for i in 0..10 {
+            let deadline = Deadline::start(Duration::from_secs(60));
+
+            assert_eq!(i, deadline.run_blocking(&workers, move || Ok(i)).unwrap());
+        }
+
+        assert_eq!(1, workers.threads());
+    }
+
+    #[test]
+    fn run_blocking_is_bounded() {
+        let workers = BlockingWorkers::new(1);
+        let mut released = Vec::new();
+
+        for _ in 0..3 {
+            let deadline = Deadline::start(Duration::from_millis(10));
+
+            let (release, step) = blocked_step();
+            released.push(release);
+
+            assert_exceeded(
+                deadline.run_blocking(&workers, step),
+                Duration::from_millis(10),
+            );
+        }
+
+        assert_eq!(1, workers.threads());
+    }
+
+    #[test]
+    fn run_blocking_panic_frees_worker() {
+        let workers = BlockingWorkers::new(1);
+
+        let res = Deadline::start(Duration::from_secs(60))
+            .run_blocking(&workers, || -> Result<(), Error> {
+                panic!("explicit panic")
+            });
+        assert!(res.is_err());
+
+        let deadline = Deadline::start(Duration::from_secs(60));
+        assert_eq!(1, deadline.run_blocking(&workers, || Ok(1)).unwrap());
+    }
+
+    #[test]
+    fn bound_future_before_deadline() {
+        let deadline = Deadline::start(Duration::from_secs(60));
+
+        let res = block_on_all(deadline.bound(future::ok::<_, Error>(1)));
+
+        assert_eq!(1, res.unwrap());
+    }
+
+    #[test]
+    fn bound_future_after_deadline() {
+        let budget = Duration::from_millis(0);
+        let deadline = Deadline::start(budget);
+
+        let res = block_on_all(deadline.bound(future::empty::<(), Error>()));
+
+        assert_exceeded(res, budget);
+    }
+
+    #[test]
+    fn bound_future_keeps_inner_error() {
+        let deadline = Deadline::start(Duration::from_secs(60));
+
+        let res = block_on_all(deadline.bound(future::err::<(), _>(error::test())));
+
+        match res {
+            Err(Error::Client(_)) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+}
@@ -0,0 +1,554 @@
+/*!
+Keeping the indices targeted by requests within a scope.
+
+A scope is a function that maps an index name to its name within the scope, like adding a tenant prefix.
+The index in the path of each request sent through a scoped client is rewritten by the scope.
+So is the index or alias that's created by a request, like the target of `/myindex/_shrink/target` or the alias in `/myindex/_alias/myalias`.
+Requests that could reach indices outside the scope are rejected before they're sent:
+
+- Requests that don't target an index in their path, like `/_search` or `/_cluster/health`.
+- Requests with a body that can name other indices, like `_bulk`, `_mget` and `_msearch`, unless the client has already scoped the body.
+
+The root endpoint and scroll requests are allowed because they don't target indices by name.
+Clearing every scroll with `_all`, in the path or in the body, is rejected because it would clear the scrolls of other scopes too.
+*/
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use percent_encoding::percent_decode_str;
+use serde_json::Value;
+
+use crate::{
+    endpoints::Endpoint,
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        AsyncBody,
+        Method,
+        SyncBody,
+        UrlPath,
+    },
+    params::encode_path_segment,
+};
+
+pub(crate) type IndexScopeFn = dyn Fn(&str) -> String + Send + Sync;
+
+/** The index name that matches every index. */
+const ALL_INDICES: &str = "_all";
+
+/** The scroll id that matches every scroll. */
+const ALL_SCROLLS: &str = "_all";
+
+/** The path of the scroll endpoint. */
+const SCROLL_PATH: &str = "/_search/scroll";
+
+/** Endpoints with a body that can target indices that aren't in the path. */
+const MULTI_TARGET_ENDPOINTS: &[&str] = &["_bulk", "_mget", "_msearch", "_mtermvectors"];
+
+/** Endpoints that name another index or alias in the segment after them. */
+const NAMED_TARGET_ENDPOINTS: &[&str] = &[
+    "_shrink",
+    "_split",
+    "_clone",
+    "_rollover",
+    "_alias",
+    "_aliases",
+];
+
+/**
+A request body that can be checked by an index scope.
+
+Returns `None` if the body can't be checked without consuming it.
+*/
+pub(crate) trait ScopedBody {
+    fn scoped_bytes(&mut self) -> Option<Bytes>;
+}
+
+impl ScopedBody for SyncBody {
+    fn scoped_bytes(&mut self) -> Option<Bytes> {
+        self.buffered_bytes()
+    }
+}
+
+impl ScopedBody for AsyncBody {
+    fn scoped_bytes(&mut self) -> Option<Bytes> {
+        Some(self.shared_bytes())
+    }
+}
+
+#[cfg(test)]
+impl ScopedBody for &'static [u8] {
+    fn scoped_bytes(&mut self) -> Option<Bytes> {
+        Some(Bytes::from_static(self))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct IndexScope(Arc<IndexScopeFn>);
+
+impl IndexScope {
+    pub(crate) fn new(scope: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        IndexScope(Arc::new(scope))
+    }
+
+    /** Apply this scope to an index before passing it through an outer scope. */
+    pub(crate) fn within(self, outer: Option<IndexScope>) -> Self {
+        match outer {
+            Some(outer) => IndexScope::new(move |index| (outer.0)(&(self.0)(index))),
+            None => self,
+        }
+    }
+
+    /**
+    Get the name of an index expression within the scope.
+
+    Each index in a comma-separated list is scoped separately.
+    Exclusions like `-logs` keep their prefix, and `_all` is scoped as the wildcard `*`.
+    */
+    pub(crate) fn index(&self, index: &str) -> String {
+        scope_each(index, |index| (self.0)(index))
+    }
+
+    /**
+    Get the name of an index expression from a url path within the scope.
+
+    The expression is percent-encoded, so each index is decoded before it's scoped and encoded again afterwards.
+    */
+    fn path_index(&self, index: &str) -> String {
+        scope_each(index, |index| {
+            let index = percent_decode_str(index).decode_utf8_lossy();
+
+            encode_path_segment(&(self.0)(&index)).into_owned()
+        })
+    }
+
+    /**
+    Get the rest of a url path after its index within the scope.
+
+    If the path names another index or alias, like the target of a shrink, then it's scoped too.
+    */
+    fn path_rest(&self, rest: &str) -> String {
+        let mut segments = rest.splitn(3, '/');
+        let endpoint = segments.next().unwrap_or_default();
+
+        match (segments.next(), segments.next()) {
+            (Some(target), rest) if NAMED_TARGET_ENDPOINTS.contains(&endpoint) => {
+                let target = self.path_index(target);
+
+                match rest {
+                    Some(rest) => format!("{}/{}/{}", endpoint, target, rest),
+                    None => format!("{}/{}", endpoint, target),
+                }
+            }
+            _ => rest.to_owned(),
+        }
+    }
+
+    /**
+    Rewrite the index in the path of an endpoint.
+
+    Returns an error if the endpoint could reach indices outside the scope.
+    If `scoped_body` is `true` then the indices in the body of the request have already been scoped.
+    */
+    pub(crate) fn endpoint<'a, TBody>(
+        &self,
+        mut endpoint: Endpoint<'a, TBody>,
+        scoped_body: bool,
+    ) -> Result<Endpoint<'a, TBody>, Error>
+    where
+        TBody: ScopedBody,
+    {
+        let url = {
+            let path: &str = &endpoint.url;
+
+            if path == "/" {
+                return Ok(endpoint);
+            }
+
+            if path == SCROLL_PATH || path.starts_with("/_search/scroll/") {
+                check_scroll(&mut endpoint)?;
+                return Ok(endpoint);
+            }
+
+            let mut segments = path.trim_start_matches('/').splitn(2, '/');
+            let index = segments.next().unwrap_or_default();
+            let rest = segments.next();
+
+            let targets_body = |segment: &str| MULTI_TARGET_ENDPOINTS.contains(&segment);
+
+            if scoped_body && targets_body(index) {
+                return Ok(endpoint);
+            }
+
+            if index.is_empty() || (index.starts_with('_') && index != ALL_INDICES) {
+                return Err(unscoped(path, "it doesn't target an index"));
+            }
+
+            if !scoped_body
+                && rest
+                    .iter()
+                    .flat_map(|rest| rest.split('/'))
+                    .any(targets_body)
+            {
+                return Err(unscoped(path, "its body can target other indices"));
+            }
+
+            match rest {
+                Some(rest) => format!("/{}/{}", self.path_index(index), self.path_rest(rest)),
+                None => format!("/{}", self.path_index(index)),
+            }
+        };
+
+        Ok(Endpoint {
+            url: UrlPath::from(url),
+            ..endpoint
+        })
+    }
+}
+
+fn scope_each(index: &str, scope: impl Fn(&str) -> String) -> String {
+    index
+        .split(',')
+        .map(|index| {
+            let (exclude, index) = match index.strip_prefix('-') {
+                Some(index) => ("-", index),
+                None => ("", index),
+            };
+
+            let index = if index == ALL_INDICES { "*" } else { index };
+
+            format!("{}{}", exclude, scope(index))
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/**
+Check that a scroll request doesn't clear every scroll.
+
+The scroll ids to clear can be in the path or in the body of a `DELETE` request.
+A body that can't be checked is rejected.
+*/
+fn check_scroll<TBody>(endpoint: &mut Endpoint<TBody>) -> Result<(), Error>
+where
+    TBody: ScopedBody,
+{
+    let path: &str = &endpoint.url;
+
+    let path_ids = path[SCROLL_PATH.len()..].trim_start_matches('/');
+    if path_ids
+        .split(',')
+        .any(|id| percent_decode_str(id).decode_utf8_lossy() == ALL_SCROLLS)
+    {
+        return Err(unscoped(path, "it clears every scroll"));
+    }
+
+    if endpoint.method != Method::DELETE {
+        return Ok(());
+    }
+
+    let body = match endpoint.body {
+        Some(ref mut body) => body
+            .scoped_bytes()
+            .ok_or_else(|| unscoped(path, "its body can't be checked"))?,
+        None => return Ok(()),
+    };
+
+    if body.is_empty() {
+        return Ok(());
+    }
+
+    let body: Value =
+        serde_json::from_slice(&body).map_err(|_| unscoped(path, "its body can't be checked"))?;
+
+    let clears_all = match body.get("scroll_id") {
+        Some(Value::String(id)) => id == ALL_SCROLLS,
+        Some(Value::Array(ids)) => ids.iter().any(|id| id.as_str() == Some(ALL_SCROLLS)),
+        _ => false,
+    };
+
+    if clears_all {
+        return Err(unscoped(path, "it clears every scroll"));
+    }
+
+    Ok(())
+}
+
+fn unscoped(path: &str, reason: &str) -> Error {
+    error::request(error::message(format!(
+        "the request to '{}' isn't allowed by the client's index scope because {}",
+        path, reason
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::empty_body,
+        tests::*,
+    };
+    use std::fs::{
+        self,
+        File,
+    };
+    use uuid::Uuid;
+
+    fn scope() -> IndexScope {
+        IndexScope::new(|index| format!("tenant1_{}", index))
+    }
+
+    fn endpoint(path: &'static str) -> Endpoint<'static, &'static [u8]> {
+        Endpoint {
+            url: UrlPath::from(path),
+            method: Method::GET,
+            body: Some(empty_body()),
+        }
+    }
+
+    fn delete_endpoint(
+        path: &'static str,
+        body: &'static [u8],
+    ) -> Endpoint<'static, &'static [u8]> {
+        Endpoint {
+            method: Method::DELETE,
+            body: Some(body),
+            ..endpoint(path)
+        }
+    }
+
+    fn scoped_path(path: &'static str) -> Result<String, Error> {
+        scope()
+            .endpoint(endpoint(path), false)
+            .map(|endpoint| endpoint.url.to_string())
+    }
+
+    #[test]
+    fn scope_index() {
+        assert_eq!("tenant1_myindex", scope().index("myindex"));
+    }
+
+    #[test]
+    fn scope_index_list() {
+        assert_eq!(
+            "tenant1_logs-*,-tenant1_logs-old,tenant1_*",
+            scope().index("logs-*,-logs-old,_all")
+        );
+    }
+
+    #[test]
+    fn scope_within_outer_scope() {
+        let scope = IndexScope::new(|index| format!("{}_v2", index)).within(Some(scope()));
+
+        assert_eq!("tenant1_myindex_v2", scope.index("myindex"));
+    }
+
+    #[test]
+    fn scope_endpoint_paths() {
+        assert_eq!("/tenant1_myindex", scoped_path("/myindex").unwrap());
+        assert_eq!(
+            "/tenant1_myindex/_doc/1",
+            scoped_path("/myindex/_doc/1").unwrap()
+        );
+        assert_eq!(
+            "/tenant1_a,tenant1_b/_search",
+            scoped_path("/a,b/_search").unwrap()
+        );
+        assert_eq!("/tenant1_*/_search", scoped_path("/_all/_search").unwrap());
+    }
+
+    #[test]
+    fn scope_endpoint_shrink_target() {
+        assert_eq!(
+            "/tenant1_myindex/_shrink/tenant1_small",
+            scoped_path("/myindex/_shrink/small").unwrap()
+        );
+    }
+
+    #[test]
+    fn scope_endpoint_split_target() {
+        assert_eq!(
+            "/tenant1_myindex/_split/tenant1_large",
+            scoped_path("/myindex/_split/large").unwrap()
+        );
+    }
+
+    #[test]
+    fn scope_endpoint_clone_target() {
+        assert_eq!(
+            "/tenant1_myindex/_clone/tenant1_copy",
+            scoped_path("/myindex/_clone/copy").unwrap()
+        );
+    }
+
+    #[test]
+    fn scope_endpoint_rollover_target() {
+        assert_eq!(
+            "/tenant1_logs/_rollover/tenant1_logs-2",
+            scoped_path("/logs/_rollover/logs-2").unwrap()
+        );
+        assert_eq!(
+            "/tenant1_logs/_rollover",
+            scoped_path("/logs/_rollover").unwrap()
+        );
+    }
+
+    #[test]
+    fn scope_endpoint_alias_name() {
+        assert_eq!(
+            "/tenant1_myindex/_alias/tenant1_current",
+            scoped_path("/myindex/_alias/current").unwrap()
+        );
+        assert_eq!(
+            "/tenant1_myindex/_aliases/tenant1_a,tenant1_b",
+            scoped_path("/myindex/_aliases/a,b").unwrap()
+        );
+        assert_eq!(
+            "/tenant1_myindex/_alias",
+            scoped_path("/myindex/_alias").unwrap()
+        );
+    }
+
+    #[test]
+    fn scope_endpoint_percent_encoded_paths() {
+        let scope = IndexScope::new(|index| format!("{} tenant1", index));
+
+        assert_eq!(
+            "/my%20index%20tenant1,-old%20tenant1/_doc/1",
+            &*scope
+                .endpoint(endpoint("/my%20index,-old/_doc/1"), false)
+                .unwrap()
+                .url
+        );
+    }
+
+    #[test]
+    fn scope_endpoint_unscoped_paths() {
+        assert_eq!("/", scoped_path("/").unwrap());
+        assert_eq!("/_search/scroll", scoped_path("/_search/scroll").unwrap());
+    }
+
+    #[test]
+    fn scope_endpoint_clear_scroll() {
+        let scope = scope();
+
+        assert!(scope
+            .endpoint(delete_endpoint("/_search/scroll/abc,def", b""), false)
+            .is_ok());
+        assert!(scope
+            .endpoint(
+                delete_endpoint("/_search/scroll", br#"{"scroll_id":["abc"]}"#),
+                false
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn scope_endpoint_rejects_clearing_all_scrolls_in_path() {
+        let scope = scope();
+
+        for path in &[
+            "/_search/scroll/_all",
+            "/_search/scroll/abc,_all",
+            "/_search/scroll/%5Fall",
+        ] {
+            assert!(
+                scope.endpoint(delete_endpoint(path, b""), false).is_err(),
+                "expected '{}' to be rejected",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn scope_endpoint_rejects_clearing_all_scrolls_in_body() {
+        let scope = scope();
+
+        for body in &[
+            &br#"{"scroll_id":["_all"]}"#[..],
+            &br#"{"scroll_id":["abc","_all"]}"#[..],
+            &br#"{"scroll_id":"_all"}"#[..],
+            &b"not json"[..],
+        ] {
+            assert!(
+                scope
+                    .endpoint(delete_endpoint("/_search/scroll", body), false)
+                    .is_err(),
+                "expected '{}' to be rejected",
+                String::from_utf8_lossy(body)
+            );
+        }
+    }
+
+    #[test]
+    fn scope_endpoint_rejects_unbuffered_clear_scroll_body() {
+        let path =
+            std::env::temp_dir().join(format!("elastic-clear-scroll-{}.json", Uuid::new_v4()));
+        fs::write(&path, br#"{"scroll_id":["abc"]}"#).unwrap();
+
+        let endpoint = Endpoint {
+            url: UrlPath::from("/_search/scroll"),
+            method: Method::DELETE,
+            body: Some(SyncBody::from(File::open(&path).unwrap())),
+        };
+
+        let scoped = scope().endpoint(endpoint, false);
+        fs::remove_file(&path).unwrap();
+
+        assert!(scoped.is_err());
+    }
+
+    #[test]
+    fn scope_endpoint_rejects_paths_without_index() {
+        for path in &[
+            "/_search",
+            "/_cluster/health",
+            "/_cat/indices/myindex",
+            "/_reindex",
+        ] {
+            assert!(
+                scoped_path(path).is_err(),
+                "expected '{}' to be rejected",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn scope_endpoint_rejects_multi_target_bodies() {
+        for path in &[
+            "/_bulk",
+            "/myindex/_bulk",
+            "/myindex/_doc/_bulk",
+            "/myindex/_mget",
+            "/myindex/_msearch/template",
+        ] {
+            assert!(
+                scoped_path(path).is_err(),
+                "expected '{}' to be rejected",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn scope_endpoint_scoped_body() {
+        let scope = scope();
+
+        let bulk = scope.endpoint(endpoint("/myindex/_bulk"), true).unwrap();
+        let bulk_without_index = scope.endpoint(endpoint("/_bulk"), true).unwrap();
+
+        assert_eq!("/tenant1_myindex/_bulk", &*bulk.url);
+        assert_eq!("/_bulk", &*bulk_without_index.url);
+        assert!(scope.endpoint(endpoint("/_search"), true).is_err());
+    }
+
+    #[test]
+    fn scope_is_send_sync() {
+        assert_send::<IndexScope>();
+        assert_sync::<IndexScope>();
+    }
+}
@@ -16,11 +16,18 @@ Some notable types include:
 use fluent_builder::{
     SharedFluentBuilder,
     SharedStatefulFluentBuilder,
+    Stack,
 };
 
+pub mod circuit_breaker;
+pub mod slow_requests;
 pub mod sniffed_nodes;
 pub mod static_nodes;
 
+pub(crate) mod deadline;
+pub(crate) mod index_scope;
+pub(crate) mod recording;
+
 mod asynchronous;
 mod params;
 mod synchronous;
@@ -33,10 +40,13 @@ pub use self::{
 use std::{
     marker::PhantomData,
     sync::Arc,
+    time::Duration,
 };
 use uuid::Uuid;
 
 use self::{
+    circuit_breaker::CircuitBreaker,
+    index_scope::IndexScope,
     sniffed_nodes::{
         SniffedNodes,
         SniffedNodesBuilder,
@@ -60,6 +70,9 @@ pub struct SendableRequest<TEndpoint, TParams, TBody> {
     correlation_id: Uuid,
     inner: TEndpoint,
     params: SendableRequestParams<TParams>,
+    index_scope: Option<IndexScope>,
+    scoped_body: bool,
+    deadline: Option<Duration>,
     _marker: PhantomData<TBody>,
 }
 
@@ -69,16 +82,40 @@ impl<TEndpoint, TParams, TBody> SendableRequest<TEndpoint, TParams, TBody> {
             correlation_id: Uuid::new_v4(),
             inner,
             params,
+            index_scope: None,
+            scoped_body: false,
+            deadline: None,
             _marker: PhantomData,
         }
     }
+
+    /**
+    Keep the indices targeted by the request within a scope.
+
+    If `scoped_body` is `true` then any indices in the body of the request are already within the scope.
+    */
+    pub(crate) fn index_scope(
+        mut self,
+        index_scope: Option<IndexScope>,
+        scoped_body: bool,
+    ) -> Self {
+        self.index_scope = index_scope;
+        self.scoped_body = scoped_body;
+        self
+    }
+
+    /** Fail the request if it doesn't complete within `deadline`. */
+    pub(crate) fn deadline(mut self, deadline: Option<Duration>) -> Self {
+        self.deadline = deadline;
+        self
+    }
 }
 
 pub(crate) enum SendableRequestParams<TParams> {
     Value(RequestParams),
     Builder {
         params: TParams,
-        builder: SharedFluentBuilder<RequestParams>,
+        builder: SharedFluentBuilder<RequestParams, Stack>,
     },
 }
 
@@ -227,17 +264,21 @@ impl NodeAddressesBuilder {
         self,
         params: PreRequestParams,
         sender: TSender,
+        circuit_breaker: Option<CircuitBreaker>,
     ) -> NodeAddresses<TSender> {
         match self {
             NodeAddressesBuilder::Static(nodes) => {
-                let nodes = StaticNodes::round_robin(nodes, params);
+                let nodes =
+                    StaticNodes::round_robin(nodes, params).circuit_breaker(circuit_breaker);
 
                 NodeAddresses::static_nodes(nodes)
             }
             NodeAddressesBuilder::Sniffed(builder) => {
-                let nodes = builder
-                    .into_value(SniffedNodesBuilder::new)
-                    .build(params, sender);
+                let nodes = builder.into_value(SniffedNodesBuilder::new).build(
+                    params,
+                    sender,
+                    circuit_breaker,
+                );
 
                 NodeAddresses::sniffed_nodes(nodes)
             }
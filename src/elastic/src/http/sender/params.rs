@@ -4,6 +4,11 @@ use std::{
     sync::Arc,
 };
 
+use percent_encoding::{
+    utf8_percent_encode,
+    AsciiSet,
+    CONTROLS,
+};
 use reqwest::{
     self,
     header::{
@@ -27,6 +32,25 @@ The default Elasticsearch address to connect to.
 */
 pub const DEFAULT_NODE_ADDRESS: &str = "http://localhost:9200";
 
+/**
+Characters that are percent-encoded in a path prefix.
+
+These are the characters that aren't allowed in a url path.
+A `%` isn't encoded, so a prefix can contain characters that are already percent-encoded.
+*/
+const PATH_PREFIX: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+type RewritePathFn = dyn Fn(&str) -> String + Send + Sync;
+
 /**
 An incomplete set of request parameters.
 
@@ -38,6 +62,8 @@ pub struct PreRequestParams {
     url_params: Arc<HashMap<&'static str, String>>,
     // We should be able to replace this with `Arc<HeaderMapMap>` from the `http` crate
     headers: Arc<HeaderMap>,
+    path_prefix: Option<Arc<str>>,
+    rewrite_path: Option<Arc<RewritePathFn>>,
 }
 
 /**
@@ -110,6 +136,8 @@ impl PreRequestParams {
                 headers
             }),
             url_params: Arc::new(HashMap::new()),
+            path_prefix: None,
+            rewrite_path: None,
         }
     }
 
@@ -129,6 +157,75 @@ impl PreRequestParams {
         self
     }
 
+    /**
+    Set a path prefix for request urls.
+
+    The prefix is added between the base url of a node and the path of each request, like `http://proxy:8080/elasticsearch/myindex/_search`.
+    This is useful when Elasticsearch is served behind a reverse proxy under a path.
+    Because the prefix isn't part of the base url, it's also added to requests sent to sniffed nodes.
+
+    Leading and trailing slashes are ignored, so `elasticsearch` and `/elasticsearch/` are the same prefix.
+    Characters that aren't allowed in a url path are percent-encoded.
+
+    # Examples
+
+    ```
+    # use elastic::client::PreRequestParams;
+    let params = PreRequestParams::default()
+        .path_prefix("/elasticsearch");
+    ```
+    */
+    pub fn path_prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        let prefix = prefix
+            .as_ref()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| utf8_percent_encode(segment, PATH_PREFIX).to_string())
+            .collect::<Vec<_>>();
+
+        self.path_prefix = if prefix.is_empty() {
+            None
+        } else {
+            Some(format!("/{}", prefix.join("/")).into())
+        };
+
+        self
+    }
+
+    /**
+    Rewrite the path of request urls.
+
+    The function is given the path of each request after the [path prefix](#method.path_prefix) has been added, like `/elasticsearch/myindex/_search`.
+    It returns the path the request is sent to.
+    The path doesn't include the base url of the node or the query string.
+
+    The path is given to the function as it will be sent, so any percent-encoded characters in it are still encoded.
+    The returned path is sent as-is, so the function must percent-encode any characters it adds that aren't allowed in a url path.
+
+    # Examples
+
+    Send requests for the `/_cluster` APIs to an admin route on the proxy:
+
+    ```
+    # use elastic::client::PreRequestParams;
+    let params = PreRequestParams::default()
+        .rewrite_path(|path| {
+            if path.starts_with("/_cluster/") {
+                format!("/admin{}", path)
+            } else {
+                path.to_owned()
+            }
+        });
+    ```
+    */
+    pub fn rewrite_path(
+        mut self,
+        rewrite: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.rewrite_path = Some(Arc::new(rewrite));
+        self
+    }
+
     /** Enables HTTP basic authentication. */
     pub fn basic_auth<U, P>(
         mut self,
@@ -190,17 +287,55 @@ impl RequestParams {
         self
     }
 
+    /** Remove any url params, keeping the base url, headers and path settings. */
+    pub(crate) fn without_url_params(mut self) -> Self {
+        self.inner.url_params = Arc::new(HashMap::new());
+        self
+    }
+
     /** Set a request header. */
     pub fn header(mut self, key: HeaderName, value: HeaderValue) -> Self {
         self.inner = self.inner.header(key, value);
         self
     }
 
+    /**
+    Set a path prefix for request urls.
+
+    See [`PreRequestParams.path_prefix`][PreRequestParams.path_prefix] for details.
+
+    [PreRequestParams.path_prefix]: struct.PreRequestParams.html#method.path_prefix
+    */
+    pub fn path_prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        self.inner = self.inner.path_prefix(prefix);
+        self
+    }
+
+    /**
+    Rewrite the path of request urls.
+
+    See [`PreRequestParams.rewrite_path`][PreRequestParams.rewrite_path] for details.
+
+    [PreRequestParams.rewrite_path]: struct.PreRequestParams.html#method.rewrite_path
+    */
+    pub fn rewrite_path(
+        mut self,
+        rewrite: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.inner = self.inner.rewrite_path(rewrite);
+        self
+    }
+
     /** Get the base url. */
     pub fn get_base_url(&self) -> &str {
         self.base_url.as_ref()
     }
 
+    /** Get the path prefix, if there is one. */
+    pub fn get_path_prefix(&self) -> Option<&str> {
+        self.inner.path_prefix.as_deref()
+    }
+
     pub(crate) fn get_headers(&self) -> Arc<HeaderMap> {
         self.inner.headers.clone()
     }
@@ -236,13 +371,29 @@ impl Default for RequestParams {
     }
 }
 
+/**
+Build the url for a request from its path and the request parameters.
+
+The path prefix is added to the path before it's rewritten.
+A trailing slash on the base url is ignored so the path isn't doubled up.
+*/
 pub(crate) fn build_url(req_url: &str, params: &RequestParams) -> String {
     let (qry_len, qry) = params.get_url_qry();
 
-    let mut url = String::with_capacity(params.base_url.as_ref().len() + req_url.len() + qry_len);
+    let base_url = params.base_url.as_ref().trim_end_matches('/');
+    let prefix = params.inner.path_prefix.as_deref().unwrap_or_default();
+
+    let mut url = String::with_capacity(base_url.len() + prefix.len() + req_url.len() + qry_len);
+
+    url.push_str(base_url);
 
-    url.push_str(params.base_url.as_ref());
-    url.push_str(&req_url);
+    match params.inner.rewrite_path {
+        Some(ref rewrite) => url.push_str(&rewrite(&format!("{}{}", prefix, req_url))),
+        None => {
+            url.push_str(prefix);
+            url.push_str(req_url);
+        }
+    }
 
     if let Some(qry) = qry {
         url.push_str(&qry);
@@ -279,6 +430,7 @@ mod tests {
             assert_sync,
         },
     };
+    use url::Url;
 
     #[test]
     fn assert_send_sync() {
@@ -359,4 +511,83 @@ mod tests {
 
         assert_eq!((0, None), req.get_url_qry());
     }
+
+    #[test]
+    fn build_url_without_prefix() {
+        let req = RequestParams::new("http://eshost:9200/").url_param("pretty", true);
+
+        assert_eq!(
+            "http://eshost:9200/myindex/_search?pretty=true",
+            build_url("/myindex/_search", &req)
+        );
+    }
+
+    #[test]
+    fn path_prefix_is_normalised() {
+        for prefix in &["es/proxy", "/es/proxy", "/es/proxy/", "//es//proxy"] {
+            let req = RequestParams::default().path_prefix(prefix);
+
+            assert_eq!(Some("/es/proxy"), req.get_path_prefix());
+        }
+
+        assert_eq!(
+            None,
+            RequestParams::default().path_prefix("/").get_path_prefix()
+        );
+    }
+
+    #[test]
+    fn path_prefix_is_percent_encoded() {
+        let req = RequestParams::default().path_prefix("/my proxy/a%2Fb?c");
+
+        assert_eq!(Some("/my%20proxy/a%2Fb%3Fc"), req.get_path_prefix());
+    }
+
+    #[test]
+    fn build_url_with_prefix() {
+        let req = RequestParams::new("http://proxy:8080/")
+            .path_prefix("elasticsearch")
+            .url_param("pretty", true);
+
+        assert_eq!(
+            "http://proxy:8080/elasticsearch/myindex/_search?pretty=true",
+            build_url("/myindex/_search", &req)
+        );
+        assert_eq!(
+            "http://proxy:8080/elasticsearch/?pretty=true",
+            build_url("/", &req)
+        );
+    }
+
+    #[test]
+    fn build_url_with_rewrite() {
+        let req = RequestParams::new("http://proxy:8080")
+            .path_prefix("/es")
+            .rewrite_path(|path| path.replacen("/es/_cluster", "/admin/_cluster", 1))
+            .url_param("pretty", true);
+
+        assert_eq!(
+            "http://proxy:8080/admin/_cluster/health?pretty=true",
+            build_url("/_cluster/health", &req)
+        );
+        assert_eq!(
+            "http://proxy:8080/es/myindex/_search?pretty=true",
+            build_url("/myindex/_search", &req)
+        );
+    }
+
+    #[test]
+    fn build_url_with_encoded_path() {
+        let req = RequestParams::new("http://proxy:8080")
+            .path_prefix("/es")
+            .rewrite_path(|path| {
+                assert_eq!("/es/my%2Findex/_doc/a%3Fb", path);
+                path.to_owned()
+            });
+
+        let url = Url::parse(&build_url("/my%2Findex/_doc/a%3Fb", &req)).unwrap();
+
+        assert_eq!("/es/my%2Findex/_doc/a%3Fb", url.path());
+        assert_eq!(None, url.query());
+    }
 }
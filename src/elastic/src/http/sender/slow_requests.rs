@@ -0,0 +1,227 @@
+/*!
+Reporting for requests that are slow to get a response.
+
+Set a threshold on the client builder with `slow_request_threshold` and any request that takes longer than it to get a response is logged as a warning.
+Use `on_slow_request` to handle slow requests with a callback instead.
+*/
+
+use bytes::Bytes;
+use std::{
+    fmt,
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use uuid::Uuid;
+
+use crate::http::{
+    Method,
+    StatusCode,
+    Url,
+};
+
+/** The maximum number of bytes of a request body that are logged for a slow request. */
+const MAX_LOGGED_BODY_LEN: usize = 1024;
+
+pub(crate) type SlowRequestFn = dyn Fn(&SlowRequest) + Send + Sync;
+
+/**
+A request that took longer than the slow request threshold to get a response.
+
+The elapsed time is measured from when the request is sent until the response headers are received.
+*/
+pub struct SlowRequest {
+    correlation_id: Uuid,
+    method: Method,
+    url: Url,
+    body: Option<Bytes>,
+    elapsed: Duration,
+    status: Option<StatusCode>,
+}
+
+impl SlowRequest {
+    /** The id the request is logged with. */
+    pub fn correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    /** The HTTP method of the request. */
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /** The full url the request was sent to. */
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /**
+    The body of the request.
+
+    Returns `None` if the request didn't have a body, or if the body was streamed from a reader.
+    */
+    pub fn body(&self) -> Option<&[u8]> {
+        self.body.as_ref().map(|body| body.as_ref())
+    }
+
+    /** How long the request took to get a response. */
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /**
+    The status of the response.
+
+    Returns `None` if the request failed without a response.
+    */
+    pub fn status(&self) -> Option<StatusCode> {
+        self.status
+    }
+}
+
+impl fmt::Debug for SlowRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SlowRequest")
+            .field("correlation_id", &self.correlation_id)
+            .field("method", &self.method)
+            .field("path", &self.url.path())
+            .field("elapsed", &self.elapsed)
+            .field("status", &self.status)
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct SlowRequests {
+    threshold: Duration,
+    on_slow_request: Option<Arc<SlowRequestFn>>,
+}
+
+impl SlowRequests {
+    pub(crate) fn new(threshold: Duration, on_slow_request: Option<Arc<SlowRequestFn>>) -> Self {
+        SlowRequests {
+            threshold,
+            on_slow_request,
+        }
+    }
+
+    /** Start timing a request that's about to be sent. */
+    pub(crate) fn start(
+        &self,
+        correlation_id: Uuid,
+        method: Method,
+        url: Url,
+        body: Option<Bytes>,
+    ) -> PendingSlowRequest {
+        PendingSlowRequest {
+            slow_requests: self.clone(),
+            correlation_id,
+            method,
+            url,
+            body,
+            start: Instant::now(),
+        }
+    }
+}
+
+pub(crate) struct PendingSlowRequest {
+    slow_requests: SlowRequests,
+    correlation_id: Uuid,
+    method: Method,
+    url: Url,
+    body: Option<Bytes>,
+    start: Instant,
+}
+
+impl PendingSlowRequest {
+    /** Report the request if it took longer than the threshold to get a response. */
+    pub(crate) fn finish(self, status: Option<StatusCode>) {
+        let elapsed = self.start.elapsed();
+
+        if elapsed < self.slow_requests.threshold {
+            return;
+        }
+
+        let slow_request = SlowRequest {
+            correlation_id: self.correlation_id,
+            method: self.method,
+            url: self.url,
+            body: self.body,
+            elapsed,
+            status,
+        };
+
+        match self.slow_requests.on_slow_request {
+            Some(ref on_slow_request) => on_slow_request(&slow_request),
+            None => log_slow_request(&slow_request),
+        }
+    }
+}
+
+fn log_slow_request(req: &SlowRequest) {
+    let body = req
+        .body()
+        .map(|body| String::from_utf8_lossy(&body[..body.len().min(MAX_LOGGED_BODY_LEN)]));
+
+    warn!(
+        "Elasticsearch Slow Request: correlation_id: '{}', method: '{}', path: '{}', query: '{}', elapsed: '{:?}', status: '{:?}', body: '{}'",
+        req.correlation_id,
+        req.method,
+        req.url.path(),
+        req.url.query().unwrap_or_default(),
+        req.elapsed,
+        req.status,
+        body.as_ref().map(|body| body.as_ref()).unwrap_or("<none>")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn slow_requests(threshold: Duration) -> (SlowRequests, Arc<Mutex<Vec<Duration>>>) {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+
+        let on_slow_request = {
+            let reported = reported.clone();
+            Arc::new(move |req: &SlowRequest| {
+                reported.lock().unwrap().push(req.elapsed());
+            })
+        };
+
+        (
+            SlowRequests::new(threshold, Some(on_slow_request)),
+            reported,
+        )
+    }
+
+    fn start(slow_requests: &SlowRequests) -> PendingSlowRequest {
+        slow_requests.start(
+            Uuid::new_v4(),
+            Method::GET,
+            Url::parse("http://localhost:9200/_search").unwrap(),
+            Some(Bytes::from_static(b"{}")),
+        )
+    }
+
+    #[test]
+    fn fast_request_is_not_reported() {
+        let (slow_requests, reported) = slow_requests(Duration::from_secs(60));
+
+        start(&slow_requests).finish(Some(StatusCode::OK));
+
+        assert!(reported.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn slow_request_is_reported() {
+        let (slow_requests, reported) = slow_requests(Duration::from_millis(0));
+
+        start(&slow_requests).finish(None);
+
+        assert_eq!(1, reported.lock().unwrap().len());
+    }
+}
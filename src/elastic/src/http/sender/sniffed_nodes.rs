@@ -48,6 +48,7 @@ use crate::{
     },
     http::{
         sender::{
+            circuit_breaker::CircuitBreaker,
             static_nodes::StaticNodes,
             AsyncSender,
             NextParams,
@@ -193,8 +194,10 @@ impl SniffedNodesBuilder {
         self,
         base_params: PreRequestParams,
         sender: TSender,
+        circuit_breaker: Option<CircuitBreaker>,
     ) -> SniffedNodes<TSender> {
-        let nodes = StaticNodes::round_robin(vec![self.base_url.clone()], base_params.clone());
+        let nodes = StaticNodes::round_robin(vec![self.base_url.clone()], base_params.clone())
+            .circuit_breaker(circuit_breaker);
         let wait = self.wait.unwrap_or_else(|| Duration::from_secs(90));
 
         // Specify a `filter_path` when updating node stats because deserialisation occurs on tokio thread
@@ -368,7 +371,7 @@ mod tests {
     use serde_json;
 
     fn sender() -> SniffedNodes<()> {
-        SniffedNodesBuilder::new(initial_address()).build(PreRequestParams::default(), ())
+        SniffedNodesBuilder::new(initial_address()).build(PreRequestParams::default(), (), None)
     }
 
     fn expected_nodes() -> NodesInfoResponse {
@@ -8,6 +8,7 @@ use crate::{
         Error,
     },
     http::sender::{
+        circuit_breaker::CircuitBreaker,
         NextParams,
         NodeAddress,
         PreRequestParams,
@@ -26,9 +27,10 @@ use std::sync::{
 /** Select a base address for a given request using some strategy. */
 #[derive(Clone)]
 pub struct StaticNodes<TStrategy = RoundRobin> {
-    nodes: Vec<NodeAddress>,
+    nodes: Arc<[NodeAddress]>,
     strategy: TStrategy,
     params: PreRequestParams,
+    circuit_breaker: Option<CircuitBreaker>,
 }
 
 impl<TStrategy> NextParams for StaticNodes<TStrategy>
@@ -38,10 +40,39 @@ where
     type Params = Result<RequestParams, Error>;
 
     fn next(&self) -> Self::Params {
-        self.strategy
-            .try_next(&self.nodes)
-            .map(|address| RequestParams::from_parts(address, self.params.clone()))
-            .map_err(error::request)
+        let address = match self.circuit_breaker {
+            Some(ref circuit_breaker) => self.try_next_closed(circuit_breaker)?,
+            None => self
+                .strategy
+                .try_next(&self.nodes)
+                .map_err(error::request)?,
+        };
+
+        Ok(RequestParams::from_parts(address, self.params.clone()))
+    }
+}
+
+impl<TStrategy> StaticNodes<TStrategy>
+where
+    TStrategy: Strategy,
+{
+    /** Get the next address that the circuit breaker will let a request through to. */
+    fn try_next_closed(&self, circuit_breaker: &CircuitBreaker) -> Result<NodeAddress, Error> {
+        // Ask the strategy at most once per node so an empty set of nodes still returns its error
+        for _ in 0..self.nodes.len().max(1) {
+            let address = self
+                .strategy
+                .try_next(&self.nodes)
+                .map_err(error::request)?;
+
+            if circuit_breaker.try_acquire(address.as_ref()) {
+                return Ok(address);
+            }
+        }
+
+        Err(error::request(error::message(
+            "all node addresses are unavailable because their circuits are open",
+        )))
     }
 }
 
@@ -55,11 +86,16 @@ impl<TStrategy> StaticNodes<TStrategy> {
             )));
         }
 
-        self.nodes = nodes;
+        self.nodes = nodes.into();
 
         Ok(())
     }
 
+    pub(crate) fn circuit_breaker(mut self, circuit_breaker: Option<CircuitBreaker>) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn get(&self) -> &[NodeAddress] {
         &self.nodes
@@ -78,9 +114,10 @@ impl StaticNodes<RoundRobin> {
         let strategy = RoundRobin::default();
 
         StaticNodes {
-            nodes,
+            nodes: nodes.into(),
             strategy,
             params,
+            circuit_breaker: None,
         }
     }
 }
@@ -139,6 +176,7 @@ impl Strategy for RoundRobin {
 mod tests {
     use super::*;
     use crate::http::sender::NextParams;
+    use std::time::Duration;
 
     fn round_robin(addresses: Vec<&'static str>) -> StaticNodes<RoundRobin> {
         StaticNodes::round_robin(addresses, PreRequestParams::default())
@@ -148,6 +186,16 @@ mod tests {
         vec!["http://a:9200", "http://b:9200", "http://c:9200"]
     }
 
+    #[test]
+    fn round_robin_clones_share_position() {
+        let nodes = round_robin(expected_addresses());
+        let cloned = nodes.clone();
+
+        assert_eq!("http://a:9200", nodes.next().unwrap().get_base_url());
+        assert_eq!("http://b:9200", cloned.next().unwrap().get_base_url());
+        assert_eq!("http://c:9200", nodes.next().unwrap().get_base_url());
+    }
+
     #[test]
     fn round_robin_next_multi() {
         let nodes = round_robin(expected_addresses());
@@ -179,4 +227,32 @@ mod tests {
 
         assert!(nodes.next().is_err());
     }
+
+    #[test]
+    fn round_robin_next_skips_open_circuits() {
+        let circuit_breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        circuit_breaker.record_failure("http://b:9200");
+
+        let nodes = round_robin(expected_addresses()).circuit_breaker(Some(circuit_breaker));
+
+        for _ in 0..10 {
+            for expected in &["http://a:9200", "http://c:9200"] {
+                let actual = nodes.next().unwrap();
+
+                assert_eq!(*expected, actual.get_base_url());
+            }
+        }
+    }
+
+    #[test]
+    fn round_robin_next_all_open_fails() {
+        let circuit_breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        for address in expected_addresses() {
+            circuit_breaker.record_failure(address);
+        }
+
+        let nodes = round_robin(expected_addresses()).circuit_breaker(Some(circuit_breaker));
+
+        assert!(nodes.next().is_err());
+    }
 }
@@ -21,6 +21,20 @@ use crate::{
         sender::{
             build_reqwest_method,
             build_url,
+            circuit_breaker::{
+                self,
+                CircuitBreaker,
+            },
+            deadline::{
+                BlockingWorkers,
+                Deadline,
+            },
+            recording::{
+                self,
+                RecordedRequest,
+                Recording,
+            },
+            slow_requests::SlowRequests,
             NextParams,
             NodeAddresses,
             NodeAddressesInner,
@@ -29,6 +43,7 @@ use crate::{
             SendableRequestParams,
             Sender,
         },
+        StatusCode,
         SyncBody,
         SyncHttpRequest,
         Url,
@@ -44,6 +59,10 @@ pub(crate) type SyncPreSend =
 pub struct SyncSender {
     pub(crate) http: SyncHttpClient,
     pub(crate) pre_send: Option<Arc<SyncPreSend>>,
+    pub(crate) circuit_breaker: Option<CircuitBreaker>,
+    pub(crate) slow_requests: Option<SlowRequests>,
+    pub(crate) recording: Option<Recording>,
+    pub(crate) blocking_workers: BlockingWorkers,
 }
 
 impl private::Sealed for SyncSender {}
@@ -63,9 +82,29 @@ impl Sender for SyncSender {
         TParams: Into<Self::Params> + Send + 'static,
     {
         let correlation_id = request.correlation_id;
+        let deadline = request.deadline.map(Deadline::start);
         let params = request.params;
         let endpoint = request.inner.into();
 
+        // The index scope may need to check the body, so convert it first
+        let endpoint = Endpoint {
+            url: endpoint.url,
+            method: endpoint.method,
+            body: endpoint.body.map(|body| -> SyncBody { body.into() }),
+        };
+
+        let endpoint = match request.index_scope {
+            Some(ref index_scope) => index_scope
+                .endpoint(endpoint, request.scoped_body)
+                .log_err(|e| {
+                    error!(
+                        "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
+                        correlation_id, e
+                    )
+                })?,
+            None => endpoint,
+        };
+
         info!(
             "Elasticsearch Request: correlation_id: '{}', path: '{}'",
             correlation_id,
@@ -86,6 +125,11 @@ impl Sender for SyncSender {
             }
         };
 
+        let circuit = self
+            .circuit_breaker
+            .as_ref()
+            .map(|circuit_breaker| (circuit_breaker, params.get_base_url().to_owned()));
+
         let mut req = build_req(endpoint, params).log_err(|e| {
             error!(
                 "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
@@ -105,29 +149,150 @@ impl Sender for SyncSender {
                 })?;
         }
 
+        // Node selection and pre-send hooks may block, so make sure there's still time to send the request
+        if let Some(deadline) = deadline {
+            deadline.check().log_err(|e| {
+                error!(
+                    "Elasticsearch Request: correlation_id: '{}', error: '{:?}'",
+                    correlation_id, e
+                )
+            })?;
+        }
+
+        let body = if self.recording.is_some() || self.slow_requests.is_some() {
+            req.body.as_mut().and_then(|body| body.buffered_bytes())
+        } else {
+            None
+        };
+
+        let recorded_request = self.recording.as_ref().map(|_| {
+            RecordedRequest::new(
+                &req.method,
+                &req.url,
+                body.as_ref().map(|body| body.as_ref()),
+            )
+        });
+
+        if let (Some(Recording::Replay(ref replayer)), Some(ref recorded_request)) =
+            (&self.recording, &recorded_request)
+        {
+            let (status, body) = replayer.replay(recorded_request).log_err(|e| {
+                error!(
+                    "Elasticsearch Response: correlation_id: '{}', error: '{:?}'",
+                    correlation_id, e
+                )
+            })?;
+
+            info!(
+                "Elasticsearch Response: correlation_id: '{}', status: '{}' (replayed)",
+                correlation_id, status
+            );
+
+            return sync_response(
+                recording::sync_raw_response(status, body)?,
+                self.with_workers(deadline),
+            );
+        }
+
+        let slow_request = self.slow_requests.as_ref().map(|slow_requests| {
+            slow_requests.start(correlation_id, req.method.clone(), req.url.clone(), body)
+        });
+
         let req = build_reqwest(&self.http, req)
             .build()
             .map_err(error::request)?;
 
-        let res = match self.http.execute(req).map_err(error::request) {
+        let res = match deadline {
+            Some(deadline) => {
+                let http = self.http.clone();
+                deadline.run_blocking(&self.blocking_workers, move || {
+                    http.execute(req).map_err(error::request)
+                })
+            }
+            None => self.http.execute(req).map_err(error::request),
+        };
+
+        if let Some(slow_request) = slow_request {
+            slow_request.finish(
+                res.as_ref()
+                    .ok()
+                    .and_then(|res| StatusCode::from_u16(res.status().as_u16()).ok()),
+            );
+        }
+
+        let res = match res {
             Ok(res) => {
                 info!(
                     "Elasticsearch Response: correlation_id: '{}', status: '{}'",
                     correlation_id,
                     res.status()
                 );
-                res
+
+                if let Some((circuit_breaker, address)) = circuit {
+                    if circuit_breaker::is_node_failure(res.status().as_u16()) {
+                        circuit_breaker.record_failure(&address);
+                    } else {
+                        circuit_breaker.record_success(&address);
+                    }
+                }
+
+                match (&self.recording, recorded_request) {
+                    (Some(Recording::Record(ref recorder)), Some(recorded_request)) => {
+                        let status = res.status().as_u16();
+
+                        let read_body = move || {
+                            let mut res = res;
+                            let mut body = Vec::new();
+                            res.copy_to(&mut body).map_err(error::request)?;
+
+                            Ok(body)
+                        };
+
+                        // The deadline also covers reading the body so it can be recorded
+                        let body = match deadline {
+                            Some(deadline) => {
+                                deadline.run_blocking(&self.blocking_workers, read_body)
+                            }
+                            None => read_body(),
+                        }
+                        .log_err(|e| {
+                            error!(
+                                "Elasticsearch Response: correlation_id: '{}', error: '{:?}'",
+                                correlation_id, e
+                            )
+                        })?;
+                        recorder.record(recorded_request, status, &body);
+
+                        recording::sync_raw_response(status, body)?
+                    }
+                    _ => res,
+                }
             }
             Err(e) => {
                 error!(
                     "Elasticsearch Response: correlation_id: '{}', error: '{:?}'",
                     correlation_id, e
                 );
+
+                if let Some((circuit_breaker, address)) = circuit {
+                    // Running out of time doesn't mean the node has failed
+                    if !matches!(e, Error::DeadlineExceeded(_)) {
+                        circuit_breaker.record_failure(&address);
+                    }
+                }
+
                 return Err(e);
             }
         };
 
-        sync_response(res)
+        sync_response(res, self.with_workers(deadline))
+    }
+}
+
+impl SyncSender {
+    // Reading the response body with a deadline runs on the same workers as sending the request
+    fn with_workers(&self, deadline: Option<Deadline>) -> Option<(Deadline, BlockingWorkers)> {
+        deadline.map(|deadline| (deadline, self.blocking_workers.clone()))
     }
 }
 
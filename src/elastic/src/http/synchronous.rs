@@ -8,6 +8,7 @@ use std::{
         Cursor,
         Read,
     },
+    mem,
 };
 
 use reqwest::{
@@ -69,7 +70,10 @@ impl SyncBody {
     pub(crate) fn into_inner(self) -> Body {
         match self.0 {
             SyncBodyInner::UnBuffered(reader) => Body::new(reader),
-            SyncBodyInner::Buffered(BufferedSyncBodyInner::Shared(bytes)) => bytes.to_vec().into(),
+            SyncBodyInner::Buffered(BufferedSyncBodyInner::Shared(bytes)) => {
+                let len = bytes.len() as u64;
+                Body::sized(Cursor::new(bytes), len)
+            }
             SyncBodyInner::Buffered(BufferedSyncBodyInner::Bytes(bytes)) => match bytes {
                 Cow::Owned(bytes) => bytes.into(),
                 Cow::Borrowed(bytes) => bytes.into(),
@@ -81,6 +85,31 @@ impl SyncBody {
         }
     }
 
+    /**
+    Get a cheap copy of the buffered body.
+
+    The body is converted into shared bytes first so the copy doesn't need to allocate.
+    Returns `None` if the body is unbuffered, because reading it would consume it.
+    */
+    pub(crate) fn buffered_bytes(&mut self) -> Option<Bytes> {
+        let bytes = match self.0 {
+            SyncBodyInner::UnBuffered(_) => return None,
+            SyncBodyInner::Buffered(BufferedSyncBodyInner::Shared(ref bytes)) => {
+                return Some(bytes.clone())
+            }
+            SyncBodyInner::Buffered(BufferedSyncBodyInner::Bytes(Cow::Owned(ref mut bytes))) => {
+                Bytes::from(mem::take(bytes))
+            }
+            SyncBodyInner::Buffered(BufferedSyncBodyInner::Str(Cow::Owned(ref mut string))) => {
+                Bytes::from(mem::take(string))
+            }
+            SyncBodyInner::Buffered(ref inner) => Bytes::from(AsRef::<[u8]>::as_ref(inner)),
+        };
+
+        self.0 = SyncBodyInner::Buffered(BufferedSyncBodyInner::Shared(bytes.clone()));
+        Some(bytes)
+    }
+
     /**
     Get a reader over the synchronous body.
 
@@ -247,4 +276,33 @@ mod tests {
     fn json_value_into_body() {
         SyncBody::from(json!({}));
     }
+
+    #[test]
+    fn buffered_body_bytes() {
+        let mut body = SyncBody::from("abc");
+
+        assert_eq!(Some(Bytes::from_static(b"abc")), body.buffered_bytes());
+    }
+
+    #[test]
+    fn buffered_body_bytes_are_shared() {
+        let mut body = SyncBody::from(b"abc".to_vec());
+
+        let first = body.buffered_bytes().unwrap();
+        let second = body.buffered_bytes().unwrap();
+
+        assert_eq!(first.as_ptr(), second.as_ptr());
+
+        let mut read = Vec::new();
+        body.reader().read_to_end(&mut read).unwrap();
+
+        assert_eq!(b"abc".to_vec(), read);
+    }
+
+    #[test]
+    fn unbuffered_body_bytes() {
+        let mut body = SyncBody::from(File::open("Cargo.toml").unwrap());
+
+        assert_eq!(None, body.buffered_bytes());
+    }
 }
@@ -46,6 +46,40 @@ extern crate elastic;
 extern crate elastic_derive;
 ```
 
+## Preserving field order
+
+By default, JSON objects are stored in maps that are sorted by key, so the order of fields in request bodies and parsed responses isn't kept.
+The `preserve_order` feature keeps fields in the order they were added or parsed instead.
+This is useful for diffing generated mappings and settings against the ones returned by Elasticsearch, or for snapshot tests of request bodies:
+
+```ignore
+[dependencies]
+elastic = { version = "~0.21.0-pre.5", features = ["preserve_order"] }
+```
+
+The feature is enabled for `serde_json` too, so it applies to all `serde_json::Value`s in the dependency graph.
+
+## Faster response parsing
+
+The `simd-json` feature parses response bodies with [`simd-json`](https://docs.rs/simd-json) instead of `serde_json`.
+That can reduce the time spent parsing large responses, like searches that return a lot of hits:
+
+```ignore
+[dependencies]
+elastic = { version = "~0.21.0-pre.5", features = ["simd-json"] }
+```
+
+If `simd-json` rejects the syntax of a response then it's parsed again with `serde_json`, so any response `serde_json` can parse is still accepted.
+Errors deserializing a response type are returned without parsing the response again.
+`simd-json` can't deserialize `serde_json::value::RawValue`, so response types with raw value fields can't be used with this feature.
+
+Compare the two parsers on your machine with the `parse_search` benchmark:
+
+```ignore
+cargo bench -p elastic --bench parse_search
+cargo bench -p elastic --bench parse_search --features simd-json
+```
+
 # Examples
 
 ## Creating a synchronous client
@@ -281,8 +315,16 @@ mod genned;
 /// owned string.
 /// They can all be constructed from a `String` or an `&str`, but some
 /// parameters may have other implementations in the future.
+///
+/// Values are percent-encoded when they're added to the url path of a
+/// request, so they shouldn't be encoded beforehand.
+/// The `Index`, `Type` and `Id` params can be checked against
+/// Elasticsearch's naming rules with their `validate` methods.
 pub mod params {
     pub use super::genned::params::*;
+
+    mod validate;
+    pub use self::validate::*;
 }
 
 /// REST API endpoints.
@@ -316,6 +358,9 @@ pub mod client;
 pub mod http;
 pub mod types;
 
+#[cfg(feature = "test-support")]
+pub mod test_support;
+
 pub use self::{
     client::{
         AsyncClient,
@@ -338,6 +383,25 @@ pub mod prelude {
 
 #[cfg(test)]
 mod tests {
+    use std::error::Error as StdError;
+
+    use crate::{
+        params::InvalidParamError,
+        Error,
+    };
+
     pub fn assert_send<T: Send>() {}
     pub fn assert_sync<T: Sync>() {}
+
+    /** Get the invalid param that caused a request to fail, panicking if it failed for another reason. */
+    pub fn invalid_param<T>(res: Result<T, Error>) -> InvalidParamError {
+        match res {
+            Err(Error::Client(ref e)) => e
+                .source()
+                .and_then(|e| e.downcast_ref::<InvalidParamError>())
+                .cloned()
+                .unwrap_or_else(|| panic!("expected an invalid param error, but got '{}'", e)),
+            _ => panic!("expected an invalid param error"),
+        }
+    }
 }
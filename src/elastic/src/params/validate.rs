@@ -0,0 +1,283 @@
+/*!
+Checking the names used as index, type and id params before they're sent.
+
+Param values are percent-encoded when they're added to a url path, so any value can be sent.
+Elasticsearch still rejects names that break its naming rules, so these checks catch the common mistakes on the client instead.
+*/
+
+use std::{
+    error::Error as StdError,
+    fmt,
+};
+
+use super::{
+    Id,
+    Index,
+    Type,
+};
+
+/** The index name that matches every index. */
+const ALL_INDICES: &str = "_all";
+
+/** The default document type name, which is the only type that can start with `_`. */
+const DEFAULT_DOC_TYPE: &str = "_doc";
+
+/** Characters that can't be used in an index name. */
+const INVALID_INDEX_CHARS: &[char] = &['\\', '/', '?', '"', '<', '>', '|', ' ', '#'];
+
+/** The longest index or type name in bytes. */
+const MAX_NAME_BYTES: usize = 255;
+
+/** The longest document id in bytes. */
+const MAX_ID_BYTES: usize = 512;
+
+/**
+An error returned when an index, type or id param isn't valid.
+
+Requests made with the client's builders aren't sent when one of their params is invalid.
+Raw endpoint requests sent with `Client.request` aren't checked.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidParamError {
+    param: &'static str,
+    value: String,
+    reason: String,
+}
+
+impl InvalidParamError {
+    fn new(param: &'static str, value: &str, reason: impl Into<String>) -> Self {
+        InvalidParamError {
+            param,
+            value: value.to_owned(),
+            reason: reason.into(),
+        }
+    }
+
+    /** The kind of param that's invalid, like `index`. */
+    pub fn param(&self) -> &str {
+        self.param
+    }
+
+    /**
+    The invalid value.
+
+    For a comma-separated list this is the name in the list that's invalid.
+    */
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /** A description of the problem with the value. */
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for InvalidParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the {} '{}' isn't valid because it {}",
+            self.param, self.value, self.reason
+        )
+    }
+}
+
+impl StdError for InvalidParamError {}
+
+impl<'a> Index<'a> {
+    /**
+    Check that this index expression follows Elasticsearch's naming rules.
+
+    An expression can be a comma-separated list of names, wildcards like `logs-*`, exclusions like `-logs-old`, `_all` or date math like `<logs-{now/d}>`.
+    Each name in the expression must be lowercase, can't start with `_` or `+`, can't be `.` or `..`, can't contain any of `\ / ? " < > | #` or a space, and can't be longer than 255 bytes.
+    */
+    pub fn validate(&self) -> Result<(), InvalidParamError> {
+        let invalid =
+            |name: &str, reason: String| Err(InvalidParamError::new("index", name, reason));
+
+        if self.is_empty() {
+            return invalid(self, "is empty".to_owned());
+        }
+
+        for (i, name) in self.split(',').enumerate() {
+            let name = match name.strip_prefix('-') {
+                Some(excluded) if i > 0 && !excluded.is_empty() => excluded,
+                _ => name,
+            };
+
+            if name.is_empty() {
+                return invalid(self, "contains an empty index name".to_owned());
+            }
+
+            if name == ALL_INDICES {
+                continue;
+            }
+
+            if name.len() > MAX_NAME_BYTES {
+                return invalid(name, format!("is longer than {} bytes", MAX_NAME_BYTES));
+            }
+
+            // Date math is resolved by Elasticsearch, so only its delimiters are checked
+            if name.starts_with('<') && name.ends_with('>') && name.len() > 2 {
+                continue;
+            }
+
+            if name == "." || name == ".." {
+                return invalid(name, "isn't an index name".to_owned());
+            }
+
+            if let Some(first) = name.chars().next().filter(|c| ['_', '-', '+'].contains(c)) {
+                return invalid(name, format!("starts with '{}'", first));
+            }
+
+            if let Some(c) = name.chars().find(|c| INVALID_INDEX_CHARS.contains(c)) {
+                return invalid(name, format!("contains '{}'", c));
+            }
+
+            if name.chars().any(char::is_uppercase) {
+                return invalid(name, "isn't lowercase".to_owned());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Type<'a> {
+    /**
+    Check that this type expression follows Elasticsearch's naming rules.
+
+    Each name in a comma-separated list can't be empty, can't start with `_` unless it's `_doc`, can't contain `#`, and can't be longer than 255 bytes.
+    */
+    pub fn validate(&self) -> Result<(), InvalidParamError> {
+        let invalid =
+            |name: &str, reason: String| Err(InvalidParamError::new("type", name, reason));
+
+        for name in self.split(',') {
+            if name.is_empty() {
+                return invalid(self, "contains an empty type name".to_owned());
+            }
+
+            if name.len() > MAX_NAME_BYTES {
+                return invalid(name, format!("is longer than {} bytes", MAX_NAME_BYTES));
+            }
+
+            if name.starts_with('_') && name != DEFAULT_DOC_TYPE {
+                return invalid(name, "starts with '_'".to_owned());
+            }
+
+            if name.contains('#') {
+                return invalid(name, "contains '#'".to_owned());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Id<'a> {
+    /**
+    Check that this document id is valid.
+
+    An id can contain any characters, but it can't be empty or longer than 512 bytes.
+    */
+    pub fn validate(&self) -> Result<(), InvalidParamError> {
+        if self.is_empty() {
+            return Err(InvalidParamError::new("id", self, "is empty"));
+        }
+
+        if self.len() > MAX_ID_BYTES {
+            return Err(InvalidParamError::new(
+                "id",
+                self,
+                format!("is longer than {} bytes", MAX_ID_BYTES),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_reason(index: &str) -> String {
+        Index::from(index)
+            .validate()
+            .unwrap_err()
+            .reason()
+            .to_owned()
+    }
+
+    #[test]
+    fn valid_indices() {
+        for index in &[
+            "myindex",
+            "logs-2019.01.01",
+            "logs-*,-logs-old",
+            "_all",
+            ".kibana",
+            "cluster:myindex",
+            "<logs-{now/d}>",
+            "données",
+        ] {
+            assert!(
+                Index::from(*index).validate().is_ok(),
+                "expected '{}' to be valid",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_indices() {
+        assert_eq!("is empty", index_reason(""));
+        assert_eq!("contains an empty index name", index_reason("a,,b"));
+        assert_eq!("isn't lowercase", index_reason("MyIndex"));
+        assert_eq!("contains ' '", index_reason("my index"));
+        assert_eq!("contains '/'", index_reason("my/index"));
+        assert_eq!("starts with '_'", index_reason("_myindex"));
+        assert_eq!("starts with '-'", index_reason("-myindex"));
+        assert_eq!("starts with '+'", index_reason("+myindex"));
+        assert_eq!("isn't an index name", index_reason(".."));
+        assert_eq!("is longer than 255 bytes", index_reason(&"a".repeat(256)));
+    }
+
+    #[test]
+    fn invalid_index_in_list() {
+        let err = Index::from("logs,Metrics").validate().unwrap_err();
+
+        assert_eq!("index", err.param());
+        assert_eq!("Metrics", err.value());
+        assert_eq!(
+            "the index 'Metrics' isn't valid because it isn't lowercase",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn validate_types() {
+        assert!(Type::from("_doc").validate().is_ok());
+        assert!(Type::from("mytype,other").validate().is_ok());
+
+        assert_eq!(
+            "starts with '_'",
+            Type::from("_mytype").validate().unwrap_err().reason()
+        );
+        assert_eq!(
+            "contains '#'",
+            Type::from("my#type").validate().unwrap_err().reason()
+        );
+        assert!(Type::from("").validate().is_err());
+    }
+
+    #[test]
+    fn validate_ids() {
+        assert!(Id::from("_my id/1").validate().is_ok());
+
+        assert_eq!("is empty", Id::from("").validate().unwrap_err().reason());
+        assert!(Id::from("a".repeat(513)).validate().is_err());
+    }
+}
@@ -0,0 +1,249 @@
+/*!
+Helpers for writing integration tests against a real Elasticsearch cluster.
+
+This module is only available with the `test-support` feature:
+
+```toml,ignore
+[dev-dependencies]
+elastic = { version = "*", features = ["test-support"] }
+```
+
+A [`TestIndex`][TestIndex] is a uniquely named index that's deleted when it goes out of scope,
+so tests can run in parallel against the same cluster without stepping on each other.
+
+# Examples
+
+Seed an index with some documents and search them:
+
+```no_run
+# #[macro_use] extern crate serde_derive;
+# #[macro_use] extern crate elastic_derive;
+# use std::time::Duration;
+# use serde_json::Value;
+# use elastic::prelude::*;
+# use elastic::test_support::TestIndex;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+#[derive(Serialize, Deserialize, ElasticType)]
+struct MyType {
+    title: String,
+}
+
+let client = SyncClientBuilder::new().build()?;
+client.wait_until_ready(Duration::from_secs(60))?;
+
+let index = TestIndex::create(&client, "my-test")?;
+index.put_mapping::<MyType>()?;
+index.seed(vec![
+    MyType { title: "A document".to_owned() },
+    MyType { title: "Another document".to_owned() },
+])?;
+
+let response = client.search::<Value>()
+                     .index(index.name().to_owned())
+                     .send()?;
+
+assert_eq!(2, response.hits().count());
+
+// The index is deleted here
+# Ok(())
+# }
+```
+
+[TestIndex]: struct.TestIndex.html
+*/
+
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    client::{
+        requests::bulk::bulk_raw,
+        IndexClient,
+        SyncClient,
+    },
+    endpoints::IndicesRefreshRequest,
+    error::{
+        self,
+        Error,
+    },
+    http::{
+        empty_body,
+        sender::SyncSender,
+        SyncBody,
+    },
+    types::document::{
+        DocumentType,
+        StaticIndex,
+        StaticType,
+    },
+};
+
+/**
+Generate an index name that's unique to this call.
+
+The name starts with the given `prefix` and is always lowercase, so it's a valid index name as long as the prefix is.
+*/
+pub fn unique_index_name(prefix: &str) -> String {
+    format!("{}-{}", prefix.to_lowercase(), Uuid::new_v4().to_simple())
+}
+
+/**
+A uniquely named throwaway index.
+
+The index is created by [`create`](#method.create) and deleted when the `TestIndex` is dropped.
+Errors deleting the index on drop are only logged; call [`delete`](#method.delete) to check them.
+*/
+pub struct TestIndex {
+    client: SyncClient,
+    name: String,
+    keep: bool,
+}
+
+impl TestIndex {
+    /** Create an empty index with a unique name starting with `prefix`. */
+    pub fn create(client: &SyncClient, prefix: &str) -> Result<Self, Error> {
+        let index = TestIndex::new(client, prefix);
+
+        index.client().create().send()?;
+
+        Ok(index)
+    }
+
+    /**
+    Create an index with a unique name starting with `prefix` using the given body.
+
+    The body can contain settings and mappings for the index.
+    */
+    pub fn create_with_body(
+        client: &SyncClient,
+        prefix: &str,
+        body: impl Into<SyncBody> + Send + 'static,
+    ) -> Result<Self, Error> {
+        let index = TestIndex::new(client, prefix);
+
+        index.client().create().body(body).send()?;
+
+        Ok(index)
+    }
+
+    fn new(client: &SyncClient, prefix: &str) -> Self {
+        TestIndex {
+            client: client.clone(),
+            name: unique_index_name(prefix),
+            keep: false,
+        }
+    }
+
+    /** The name of the index. */
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /** Get a client for the index. */
+    pub fn client(&self) -> IndexClient<SyncSender> {
+        self.client.index(self.name.clone())
+    }
+
+    /** Put the mapping for a document type on the index. */
+    pub fn put_mapping<TDocument>(&self) -> Result<(), Error>
+    where
+        TDocument: DocumentType + StaticIndex + StaticType,
+    {
+        self.client
+            .document::<TDocument>()
+            .put_mapping()
+            .index(self.name.clone())
+            .send()?;
+
+        Ok(())
+    }
+
+    /**
+    Index the given documents and refresh the index so they're visible to searches.
+
+    Documents are indexed in a single bulk request, so the ids are generated by Elasticsearch.
+    Returns an error if any of the documents fail to index.
+    */
+    pub fn seed<TDocument>(&self, docs: impl IntoIterator<Item = TDocument>) -> Result<(), Error>
+    where
+        TDocument: Serialize,
+    {
+        let res = self
+            .client
+            .bulk()
+            .index(self.name.clone())
+            .errors_only()
+            .extend(docs.into_iter().map(|doc| bulk_raw().index(doc)))
+            .params_fluent(|params| params.url_param("refresh", true))
+            .send()?;
+
+        if res.is_err() {
+            return Err(error::request(error::message(format!(
+                "failed to seed {} documents into index '{}'",
+                res.iter().count(),
+                self.name
+            ))));
+        }
+
+        Ok(())
+    }
+
+    /** Refresh the index so recent changes are visible to searches. */
+    pub fn refresh(&self) -> Result<(), Error> {
+        self.client
+            .request(IndicesRefreshRequest::for_index(
+                self.name.clone(),
+                empty_body(),
+            ))
+            .send()?
+            .into_response::<Value>()?;
+
+        Ok(())
+    }
+
+    /**
+    Don't delete the index when the `TestIndex` is dropped.
+
+    This can be useful for inspecting the index after a failing test.
+    */
+    pub fn keep(mut self) -> Self {
+        self.keep = true;
+        self
+    }
+
+    /** Delete the index. */
+    pub fn delete(mut self) -> Result<(), Error> {
+        self.keep = true;
+        self.client().delete().send()?;
+
+        Ok(())
+    }
+}
+
+impl Drop for TestIndex {
+    fn drop(&mut self) {
+        if !self.keep {
+            if let Err(e) = self.client().delete().send() {
+                warn!(
+                    "Elasticsearch Test Index: index: '{}', error deleting: '{:?}'",
+                    self.name, e
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_index_names() {
+        let a = unique_index_name("My-Test");
+        let b = unique_index_name("My-Test");
+
+        assert!(a.starts_with("my-test-"));
+        assert_ne!(a, b);
+    }
+}
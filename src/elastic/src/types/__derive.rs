@@ -43,6 +43,11 @@ pub use crate::types::{
         Type,
         DEFAULT_DOC_TYPE,
     },
+    string::keyword::mapping::{
+        DefaultKeywordMapping,
+        KeywordFieldType,
+        KeywordValues,
+    },
 };
 
 pub use chrono::format::{
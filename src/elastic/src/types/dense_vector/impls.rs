@@ -0,0 +1,254 @@
+use super::mapping::{
+    DenseVectorFieldType,
+    DenseVectorMapping,
+};
+use serde::{
+    de::Error as DeError,
+    ser::Error as SerError,
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+use std::{
+    marker::PhantomData,
+    ops::Deref,
+};
+
+/**
+An Elasticsearch `dense_vector` with a mapping.
+
+The number of values in the vector must match the dimensions in its mapping.
+A `DenseVector` with the wrong number of values will fail to serialise,
+so bad vectors are caught before they're sent to Elasticsearch.
+
+# Examples
+
+Defining a `dense_vector` with a mapping:
+
+```
+# use elastic::types::prelude::*;
+# #[derive(Default)]
+# struct MyVectorMapping;
+# impl DenseVectorMapping for MyVectorMapping {
+#     fn dims() -> u32 { 3 }
+# }
+let vector = DenseVector::<MyVectorMapping>::new(vec![0.5, 10.0, 6.0]);
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenseVector<TMapping>
+where
+    TMapping: DenseVectorMapping,
+{
+    value: Vec<f32>,
+    _m: PhantomData<TMapping>,
+}
+
+impl<TMapping> DenseVector<TMapping>
+where
+    TMapping: DenseVectorMapping,
+{
+    /**
+    Creates a new `DenseVector` with the given mapping.
+
+    # Examples
+
+    Create a new `DenseVector` from a `Vec<f32>`:
+
+    ```
+    # use elastic::types::prelude::*;
+    # #[derive(Default)]
+    # struct MyVectorMapping;
+    # impl DenseVectorMapping for MyVectorMapping {
+    #     fn dims() -> u32 { 3 }
+    # }
+    let vector = DenseVector::<MyVectorMapping>::new(vec![0.5, 10.0, 6.0]);
+
+    assert!(vector.has_dims());
+    ```
+    */
+    pub fn new<I>(value: I) -> DenseVector<TMapping>
+    where
+        I: Into<Vec<f32>>,
+    {
+        DenseVector {
+            value: value.into(),
+            _m: PhantomData,
+        }
+    }
+
+    /** Whether or not the number of values matches the dimensions in the mapping. */
+    pub fn has_dims(&self) -> bool {
+        self.value.len() == TMapping::dims() as usize
+    }
+
+    /** Get the values of the vector. */
+    pub fn into_inner(self) -> Vec<f32> {
+        self.value
+    }
+
+    /**
+    Change the mapping of this vector.
+
+    # Examples
+
+    Change the mapping for a given `DenseVector`:
+
+    ```
+    # use elastic::types::prelude::*;
+    # #[derive(Default)]
+    # struct MyVectorMapping;
+    # impl DenseVectorMapping for MyVectorMapping {
+    #     fn dims() -> u32 { 3 }
+    # }
+    # #[derive(Default)]
+    # struct MyIndexedVectorMapping;
+    # impl DenseVectorMapping for MyIndexedVectorMapping {
+    #     fn dims() -> u32 { 3 }
+    #     fn index() -> Option<bool> { Some(true) }
+    # }
+    let vector = DenseVector::<MyVectorMapping>::new(vec![0.5, 10.0, 6.0]);
+
+    let vector: DenseVector<MyIndexedVectorMapping> = DenseVector::remap(vector);
+    ```
+    */
+    pub fn remap<TNewMapping>(vector: DenseVector<TMapping>) -> DenseVector<TNewMapping>
+    where
+        TNewMapping: DenseVectorMapping,
+    {
+        DenseVector::new(vector.value)
+    }
+}
+
+impl<TMapping> DenseVectorFieldType<TMapping> for DenseVector<TMapping> where
+    TMapping: DenseVectorMapping
+{
+}
+
+impl<TMapping> From<Vec<f32>> for DenseVector<TMapping>
+where
+    TMapping: DenseVectorMapping,
+{
+    fn from(value: Vec<f32>) -> Self {
+        DenseVector::new(value)
+    }
+}
+
+impl<TMapping> Deref for DenseVector<TMapping>
+where
+    TMapping: DenseVectorMapping,
+{
+    type Target = [f32];
+
+    fn deref(&self) -> &[f32] {
+        &self.value
+    }
+}
+
+// Serialize elastic dense vector
+impl<TMapping> Serialize for DenseVector<TMapping>
+where
+    TMapping: DenseVectorMapping,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !self.has_dims() {
+            return Err(S::Error::custom(format_args!(
+                "expected a vector with {} dimensions, but found {}",
+                TMapping::dims(),
+                self.value.len()
+            )));
+        }
+
+        self.value.serialize(serializer)
+    }
+}
+
+// Deserialize elastic dense vector
+impl<'de, TMapping> Deserialize<'de> for DenseVector<TMapping>
+where
+    TMapping: DenseVectorMapping,
+{
+    fn deserialize<D>(deserializer: D) -> Result<DenseVector<TMapping>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let vector = DenseVector::new(Vec::<f32>::deserialize(deserializer)?);
+
+        if !vector.has_dims() {
+            return Err(D::Error::invalid_length(
+                vector.value.len(),
+                &format!("a vector with {} dimensions", TMapping::dims()).as_str(),
+            ));
+        }
+
+        Ok(vector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use crate::types::prelude::*;
+
+    #[derive(Default)]
+    struct MyVectorMapping;
+    impl DenseVectorMapping for MyVectorMapping {
+        fn dims() -> u32 {
+            3
+        }
+    }
+
+    #[derive(Default)]
+    struct MyOtherVectorMapping;
+    impl DenseVectorMapping for MyOtherVectorMapping {
+        fn dims() -> u32 {
+            3
+        }
+    }
+
+    #[test]
+    fn can_change_vector_mapping() {
+        fn takes_custom_mapping(_: DenseVector<MyOtherVectorMapping>) -> bool {
+            true
+        }
+
+        let vector: DenseVector<MyVectorMapping> = DenseVector::new(vec![1.0, 2.0, 3.0]);
+
+        assert!(takes_custom_mapping(DenseVector::remap(vector)));
+    }
+
+    #[test]
+    fn serialise_elastic_vector() {
+        let vector: DenseVector<MyVectorMapping> = DenseVector::new(vec![0.5, 10.0, 6.0]);
+
+        let ser = serde_json::to_string(&vector).unwrap();
+
+        assert_eq!("[0.5,10.0,6.0]", ser);
+    }
+
+    #[test]
+    fn serialise_elastic_vector_wrong_dims() {
+        let vector: DenseVector<MyVectorMapping> = DenseVector::new(vec![0.5, 10.0]);
+
+        assert!(serde_json::to_string(&vector).is_err());
+    }
+
+    #[test]
+    fn deserialise_elastic_vector() {
+        let vector: DenseVector<MyVectorMapping> = serde_json::from_str("[0.5,10,6]").unwrap();
+
+        assert_eq!(&[0.5, 10.0, 6.0], &*vector);
+    }
+
+    #[test]
+    fn deserialise_elastic_vector_wrong_dims() {
+        let vector = serde_json::from_str::<DenseVector<MyVectorMapping>>("[0.5,10,6,1]");
+
+        assert!(vector.is_err());
+    }
+}
@@ -0,0 +1,229 @@
+/*! Mapping for the Elasticsearch `dense_vector` type. */
+
+use serde::{
+    Serialize,
+    Serializer,
+};
+
+/** A field that will be mapped as a `dense_vector`. */
+pub trait DenseVectorFieldType<TMapping> {}
+
+/**
+The base requirements for mapping a `dense_vector` type.
+
+There's no default mapping for a `dense_vector`, because the number of dimensions must always be given.
+
+# Examples
+
+Define a custom `DenseVectorMapping`:
+
+```
+# #[macro_use] use elastic::types::prelude::*;
+#[derive(Default)]
+struct MyVectorMapping;
+impl DenseVectorMapping for MyVectorMapping {
+    fn dims() -> u32 {
+        128
+    }
+
+    fn index() -> Option<bool> {
+        Some(true)
+    }
+
+    fn similarity() -> Option<VectorSimilarity> {
+        Some(VectorSimilarity::Cosine)
+    }
+}
+```
+
+This will produce the following mapping:
+
+```
+# #[macro_use] extern crate serde_json;
+# use elastic::types::prelude::*;
+# #[derive(Default)]
+# struct MyVectorMapping;
+# impl DenseVectorMapping for MyVectorMapping {
+#     fn dims() -> u32 {
+#         128
+#     }
+#     fn index() -> Option<bool> {
+#         Some(true)
+#     }
+#     fn similarity() -> Option<VectorSimilarity> {
+#         Some(VectorSimilarity::Cosine)
+#     }
+# }
+# let json = json!(
+{
+    "type": "dense_vector",
+    "dims": 128,
+    "index": true,
+    "similarity": "cosine"
+}
+# );
+# let mapping = elastic::types::__derive::standalone_field_ser(MyVectorMapping).unwrap();
+# assert_eq!(json, mapping);
+```
+*/
+pub trait DenseVectorMapping {
+    /**
+    The number of dimensions in the vector.
+
+    Vectors with a different number of dimensions can't be serialised or deserialised.
+    */
+    fn dims() -> u32;
+
+    /** Should the field be indexed for kNN search? Requires Elasticsearch `8.0` or newer. */
+    fn index() -> Option<bool> {
+        None
+    }
+
+    /** The similarity metric used for kNN search. Only used when the field is indexed. */
+    fn similarity() -> Option<VectorSimilarity> {
+        None
+    }
+}
+
+/** The similarity metric used to compare vectors in kNN search. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorSimilarity {
+    /** The euclidean distance between vectors. */
+    L2Norm,
+    /** The dot product of vectors. All vectors must have a length of `1`. */
+    DotProduct,
+    /** The cosine of the angle between vectors. */
+    Cosine,
+}
+
+impl Serialize for VectorSimilarity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match *self {
+            VectorSimilarity::L2Norm => "l2_norm",
+            VectorSimilarity::DotProduct => "dot_product",
+            VectorSimilarity::Cosine => "cosine",
+        })
+    }
+}
+
+mod private {
+    use super::{
+        DenseVectorFieldType,
+        DenseVectorMapping,
+    };
+    use crate::types::private::field::{
+        FieldMapping,
+        FieldType,
+        SerializeFieldMapping,
+        StaticSerialize,
+    };
+    use serde::{
+        ser::SerializeStruct,
+        Serialize,
+        Serializer,
+    };
+
+    #[derive(Default)]
+    pub struct DenseVectorPivot;
+
+    impl<TField, TMapping> FieldType<TMapping, DenseVectorPivot> for TField
+    where
+        TField: DenseVectorFieldType<TMapping> + Serialize,
+        TMapping: DenseVectorMapping,
+    {
+    }
+
+    impl<TMapping> FieldMapping<DenseVectorPivot> for TMapping
+    where
+        TMapping: DenseVectorMapping,
+    {
+        type SerializeFieldMapping = SerializeFieldMapping<TMapping, DenseVectorPivot>;
+
+        fn data_type() -> &'static str {
+            "dense_vector"
+        }
+    }
+
+    impl<TMapping> StaticSerialize for SerializeFieldMapping<TMapping, DenseVectorPivot>
+    where
+        TMapping: FieldMapping<DenseVectorPivot> + DenseVectorMapping,
+    {
+        fn static_serialize<S>(serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut state = serializer.serialize_struct("mapping", 4)?;
+
+            state.serialize_field("type", TMapping::data_type())?;
+            state.serialize_field("dims", &TMapping::dims())?;
+
+            ser_field!(state, "index", TMapping::index());
+            ser_field!(state, "similarity", TMapping::similarity());
+
+            state.end()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use crate::types::{
+        prelude::*,
+        private::field,
+    };
+
+    #[derive(Default, Clone)]
+    pub struct MyVectorMapping;
+    impl DenseVectorMapping for MyVectorMapping {
+        fn dims() -> u32 {
+            3
+        }
+    }
+
+    #[derive(Default, Clone)]
+    pub struct MyIndexedVectorMapping;
+    impl DenseVectorMapping for MyIndexedVectorMapping {
+        fn dims() -> u32 {
+            384
+        }
+
+        fn index() -> Option<bool> {
+            Some(true)
+        }
+
+        fn similarity() -> Option<VectorSimilarity> {
+            Some(VectorSimilarity::DotProduct)
+        }
+    }
+
+    #[test]
+    fn serialise_mapping_dims() {
+        let ser = serde_json::to_value(&field::serialize(MyVectorMapping)).unwrap();
+
+        let expected = json!({
+            "type": "dense_vector",
+            "dims": 3
+        });
+
+        assert_eq!(expected, ser);
+    }
+
+    #[test]
+    fn serialise_mapping_custom() {
+        let ser = serde_json::to_value(&field::serialize(MyIndexedVectorMapping)).unwrap();
+
+        let expected = json!({
+            "type": "dense_vector",
+            "dims": 384,
+            "index": true,
+            "similarity": "dot_product"
+        });
+
+        assert_eq!(expected, ser);
+    }
+}
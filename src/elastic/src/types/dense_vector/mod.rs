@@ -0,0 +1,66 @@
+/*!
+Implementation of the Elasticsearch `dense_vector` type.
+
+Dense vectors store a fixed number of floating point values, like the embedding of a document.
+They can be used for [kNN][docs-knn] and [script score][docs-script-score] vector searches.
+
+# Examples
+
+Dense vectors need a mapping that gives the number of dimensions:
+
+```
+# use elastic::types::prelude::*;
+#[derive(Default)]
+struct MyVectorMapping;
+impl DenseVectorMapping for MyVectorMapping {
+    fn dims() -> u32 {
+        3
+    }
+}
+
+struct MyType {
+    pub field: DenseVector<MyVectorMapping>
+}
+```
+
+Map a custom type as a `dense_vector` field:
+
+```
+#[macro_use] extern crate serde_derive;
+# use elastic::types::prelude::*;
+# #[derive(Default)]
+# struct MyVectorMapping;
+# impl DenseVectorMapping for MyVectorMapping {
+#     fn dims() -> u32 { 3 }
+# }
+#[derive(Serialize)]
+struct MyVectorField(Vec<f32>);
+
+impl DenseVectorFieldType<MyVectorMapping> for MyVectorField {}
+```
+
+# Links
+
+- [Elasticsearch Doc](https://www.elastic.co/guide/en/elasticsearch/reference/current/dense-vector.html)
+
+[docs-knn]: https://www.elastic.co/guide/en/elasticsearch/reference/current/knn-search.html
+[docs-script-score]: https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl-script-score-query.html
+*/
+
+pub mod mapping;
+
+mod impls;
+pub use self::impls::*;
+
+pub mod prelude {
+    /*!
+    Includes all types for the `dense_vector` type.
+
+    This is a convenience module to make it easy to build mappings for multiple types without too many `use` statements.
+    */
+
+    pub use super::{
+        impls::*,
+        mapping::*,
+    };
+}
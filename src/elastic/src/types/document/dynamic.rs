@@ -0,0 +1,852 @@
+/*!
+Documents with fields that aren't known until runtime.
+
+A `DynamicDocument` can be used instead of a static document type when the fields of a document come from a user-defined schema.
+*/
+
+use serde::{
+    de::{
+        Deserialize,
+        Deserializer,
+        Error as DeError,
+        MapAccess,
+        SeqAccess,
+        Visitor,
+    },
+    ser::{
+        Serialize,
+        SerializeMap,
+        Serializer,
+    },
+};
+use serde_json::{
+    Map,
+    Number,
+    Value,
+};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    error::Error,
+    fmt,
+};
+
+use super::{
+    mapping::ObjectFieldType,
+    ValueObjectMapping,
+};
+use crate::types::date::{
+    mapping::DefaultDateMapping,
+    ChronoDateTime,
+    ChronoFormat,
+    Date,
+    DateFormat,
+};
+
+/** A date in a dynamic document, formatted like `2019-01-01T00:00:00Z`. */
+pub type DynamicDate = Date<DefaultDateMapping<ChronoFormat>>;
+
+/** A geo point in a dynamic document, formatted as a `{ "lat": lat, "lon": lon }` object. */
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DynamicGeoPoint {
+    /** The latitude of the point. */
+    pub lat: f64,
+    /** The longitude of the point. */
+    pub lon: f64,
+}
+
+impl DynamicGeoPoint {
+    /** Create a geo point from a latitude and longitude. */
+    pub fn new(lat: f64, lon: f64) -> Self {
+        DynamicGeoPoint { lat, lon }
+    }
+}
+
+/**
+A document with fields that aren't known until runtime.
+
+The fields of a `DynamicDocument` are kept in the order they were added, and each field has a [`DynamicValue`][DynamicValue].
+Deserialized documents keep their fields in the order they appear in the JSON.
+A `DynamicDocument` can be used anywhere a `serde_json::Value` can be used as a document, like in searches and the `_raw` get and index requests.
+It doesn't implement [`DocumentType`][DocumentType], because it doesn't have an index, type or id of its own, so those need to be given to the request instead.
+
+# Examples
+
+Build a document and index it:
+
+```no_run
+# use elastic::prelude::*;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+# let client = SyncClientBuilder::new().build()?;
+let mut doc = DynamicDocument::new();
+doc.insert("title", "A document");
+doc.insert("views", 3);
+doc.insert("published", DynamicDate::now());
+doc.insert("location", DynamicGeoPoint::new(41.12, -71.34));
+
+client.document().index_raw("myindex", doc).id(1).send()?;
+# Ok(())
+# }
+```
+
+Get a document and read its fields:
+
+```no_run
+# use elastic::prelude::*;
+# fn main() -> Result<(), Box<dyn ::std::error::Error>> {
+# let client = SyncClientBuilder::new().build()?;
+let response = client.document::<DynamicDocument>().get_raw("myindex", 1).send()?;
+
+if let Some(doc) = response.into_document() {
+    let title = doc.get("title").and_then(DynamicValue::as_str);
+    let published = doc.get("published").and_then(DynamicValue::as_date);
+
+    println!("{:?} was published on {:?}", title, published);
+}
+# Ok(())
+# }
+```
+
+[DynamicValue]: enum.DynamicValue.html
+[DocumentType]: trait.DocumentType.html
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DynamicDocument {
+    fields: Vec<(String, DynamicValue)>,
+    // The index of each field in `fields`, so lookups don't have to scan every field
+    positions: HashMap<String, usize>,
+}
+
+impl DynamicDocument {
+    /** Create an empty document. */
+    pub fn new() -> Self {
+        DynamicDocument::default()
+    }
+
+    /** The number of fields in the document. */
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /** Whether or not the document has any fields. */
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /** Whether or not the document has a field called `name`. */
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    /** Get the value of a field. */
+    pub fn get(&self, name: &str) -> Option<&DynamicValue> {
+        self.position(name).map(|i| &self.fields[i].1)
+    }
+
+    /** Get a mutable reference to the value of a field. */
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut DynamicValue> {
+        match self.position(name) {
+            Some(i) => Some(&mut self.fields[i].1),
+            None => None,
+        }
+    }
+
+    /**
+    Set the value of a field.
+
+    If the document already has a field called `name` then its value is replaced in place and the old value is returned.
+    Otherwise the field is added to the end of the document.
+    */
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<DynamicValue>,
+    ) -> Option<DynamicValue> {
+        let name = name.into();
+        let value = value.into();
+
+        match self.position(&name) {
+            Some(i) => Some(std::mem::replace(&mut self.fields[i].1, value)),
+            None => {
+                self.positions.insert(name.clone(), self.fields.len());
+                self.fields.push((name, value));
+                None
+            }
+        }
+    }
+
+    /**
+    Remove a field from the document, returning its value.
+
+    The fields after it keep their order.
+    */
+    pub fn remove(&mut self, name: &str) -> Option<DynamicValue> {
+        let i = self.positions.remove(name)?;
+        let (_, value) = self.fields.remove(i);
+
+        for (field, _) in &self.fields[i..] {
+            if let Some(position) = self.positions.get_mut(field) {
+                *position -= 1;
+            }
+        }
+
+        Some(value)
+    }
+
+    /** Iterate through the fields of the document in order. */
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DynamicValue)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.positions.get(name).cloned()
+    }
+}
+
+impl ObjectFieldType for DynamicDocument {
+    type Mapping = ValueObjectMapping;
+}
+
+impl From<Map<String, Value>> for DynamicDocument {
+    fn from(fields: Map<String, Value>) -> Self {
+        fields
+            .into_iter()
+            .map(|(name, value)| (name, DynamicValue::from(value)))
+            .collect()
+    }
+}
+
+impl From<DynamicDocument> for Value {
+    fn from(doc: DynamicDocument) -> Value {
+        Value::Object(
+            doc.fields
+                .into_iter()
+                .map(|(name, value)| (name, Value::from(value)))
+                .collect(),
+        )
+    }
+}
+
+impl<TName, TValue> std::iter::FromIterator<(TName, TValue)> for DynamicDocument
+where
+    TName: Into<String>,
+    TValue: Into<DynamicValue>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (TName, TValue)>,
+    {
+        let mut doc = DynamicDocument::new();
+
+        for (name, value) in iter {
+            doc.insert(name, value);
+        }
+
+        doc
+    }
+}
+
+impl Serialize for DynamicDocument {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+
+        for (name, value) in &self.fields {
+            map.serialize_entry(name, value)?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for DynamicDocument {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(DynamicDocumentVisitor)
+    }
+}
+
+// Fields are read straight into the document so they keep the order they were sent in
+struct DynamicDocumentVisitor;
+
+impl<'de> Visitor<'de> for DynamicDocumentVisitor {
+    type Value = DynamicDocument;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a document as a JSON object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<DynamicDocument, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut doc = DynamicDocument::new();
+
+        while let Some((name, value)) = map.next_entry::<String, DynamicValue>()? {
+            doc.insert(name, value);
+        }
+
+        Ok(doc)
+    }
+}
+
+/**
+The value of a field in a [`DynamicDocument`][DynamicDocument].
+
+Values converted from JSON are never `Date` or `GeoPoint`, because the same JSON could also be a plain string or array.
+Use [`as_date`][as_date] and [`as_geo_point`][as_geo_point] to read a JSON value as a date or geo point.
+
+[DynamicDocument]: struct.DynamicDocument.html
+[as_date]: #method.as_date
+[as_geo_point]: #method.as_geo_point
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynamicValue {
+    /** A `null` value. */
+    Null,
+    /** A `boolean` value. */
+    Boolean(bool),
+    /** A numeric value. */
+    Number(Number),
+    /** A string value, like a `text` or `keyword`. */
+    String(String),
+    /** A `date` value. */
+    Date(DynamicDate),
+    /** A `geo_point` value. */
+    GeoPoint(DynamicGeoPoint),
+    /** An array of values. */
+    Array(Vec<DynamicValue>),
+    /** An `object` or `nested` value. */
+    Object(DynamicDocument),
+}
+
+impl DynamicValue {
+    /** Get the value as a boolean. */
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            DynamicValue::Boolean(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /** Get the value as a signed integer. */
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            DynamicValue::Number(ref value) => value.as_i64(),
+            _ => None,
+        }
+    }
+
+    /** Get the value as an unsigned integer. */
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            DynamicValue::Number(ref value) => value.as_u64(),
+            _ => None,
+        }
+    }
+
+    /** Get the value as a floating point number. */
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            DynamicValue::Number(ref value) => value.as_f64(),
+            _ => None,
+        }
+    }
+
+    /** Get the value as a string. */
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            DynamicValue::String(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /**
+    Get the value as a date.
+
+    A string is parsed as a date formatted like `2019-01-01T00:00:00Z`.
+    */
+    pub fn as_date(&self) -> Option<DynamicDate> {
+        match *self {
+            DynamicValue::Date(ref value) => Some(value.clone()),
+            DynamicValue::String(ref value) => ChronoFormat::parse(value).ok().map(Date::from),
+            _ => None,
+        }
+    }
+
+    /**
+    Get the value as a geo point.
+
+    A JSON value is read as a geo point if it's a `[lon, lat]` array, a `{ "lat": lat, "lon": lon }` object or a `"lat,lon"` string.
+    */
+    pub fn as_geo_point(&self) -> Option<DynamicGeoPoint> {
+        match *self {
+            DynamicValue::GeoPoint(value) => Some(value),
+            DynamicValue::Array(ref values) => match values.as_slice() {
+                [lon, lat] => Some(DynamicGeoPoint::new(lat.as_f64()?, lon.as_f64()?)),
+                _ => None,
+            },
+            DynamicValue::Object(ref fields) if fields.len() == 2 => Some(DynamicGeoPoint::new(
+                fields.get("lat")?.as_f64()?,
+                fields.get("lon")?.as_f64()?,
+            )),
+            DynamicValue::String(ref value) => {
+                let mut parts = value.splitn(2, ',').map(|part| part.trim().parse::<f64>());
+
+                let lat = parts.next()?.ok()?;
+                let lon = parts.next()?.ok()?;
+
+                Some(DynamicGeoPoint::new(lat, lon))
+            }
+            _ => None,
+        }
+    }
+
+    /** Get the values of an array. */
+    pub fn as_array(&self) -> Option<&[DynamicValue]> {
+        match *self {
+            DynamicValue::Array(ref values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /** Get the value as an object. */
+    pub fn as_object(&self) -> Option<&DynamicDocument> {
+        match *self {
+            DynamicValue::Object(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /** Whether or not the value is `null`. */
+    pub fn is_null(&self) -> bool {
+        *self == DynamicValue::Null
+    }
+}
+
+impl From<Value> for DynamicValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => DynamicValue::Null,
+            Value::Bool(value) => DynamicValue::Boolean(value),
+            Value::Number(value) => DynamicValue::Number(value),
+            Value::String(value) => DynamicValue::String(value),
+            Value::Array(values) => {
+                DynamicValue::Array(values.into_iter().map(DynamicValue::from).collect())
+            }
+            Value::Object(fields) => DynamicValue::Object(DynamicDocument::from(fields)),
+        }
+    }
+}
+
+impl From<DynamicValue> for Value {
+    fn from(value: DynamicValue) -> Value {
+        match value {
+            DynamicValue::Null => Value::Null,
+            DynamicValue::Boolean(value) => Value::Bool(value),
+            DynamicValue::Number(value) => Value::Number(value),
+            DynamicValue::String(value) => Value::String(value),
+            DynamicValue::Date(value) => Value::String(value.to_string()),
+            DynamicValue::GeoPoint(value) => json!({ "lat": value.lat, "lon": value.lon }),
+            DynamicValue::Array(values) => {
+                Value::Array(values.into_iter().map(Value::from).collect())
+            }
+            DynamicValue::Object(doc) => Value::from(doc),
+        }
+    }
+}
+
+impl Serialize for DynamicValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            DynamicValue::Null => serializer.serialize_unit(),
+            DynamicValue::Boolean(value) => serializer.serialize_bool(value),
+            DynamicValue::Number(ref value) => value.serialize(serializer),
+            DynamicValue::String(ref value) => serializer.serialize_str(value),
+            DynamicValue::Date(ref value) => value.serialize(serializer),
+            DynamicValue::GeoPoint(ref value) => value.serialize(serializer),
+            DynamicValue::Array(ref values) => values.serialize(serializer),
+            DynamicValue::Object(ref doc) => doc.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DynamicValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DynamicValueVisitor)
+    }
+}
+
+struct DynamicValueVisitor;
+
+impl<'de> Visitor<'de> for DynamicValueVisitor {
+    type Value = DynamicValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<DynamicValue, E> {
+        Ok(DynamicValue::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<DynamicValue, E> {
+        Ok(DynamicValue::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<DynamicValue, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DynamicValue::deserialize(deserializer)
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<DynamicValue, E> {
+        Ok(DynamicValue::Boolean(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<DynamicValue, E> {
+        Ok(DynamicValue::from(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<DynamicValue, E> {
+        Ok(DynamicValue::from(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<DynamicValue, E>
+    where
+        E: DeError,
+    {
+        DynamicValue::try_from(value).map_err(E::custom)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<DynamicValue, E> {
+        Ok(DynamicValue::from(value))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<DynamicValue, E> {
+        Ok(DynamicValue::from(value))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<DynamicValue, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+
+        Ok(DynamicValue::Array(values))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<DynamicValue, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        DynamicDocumentVisitor.visit_map(map).map(DynamicValue::Object)
+    }
+}
+
+macro_rules! impl_from_for_dynamic_value {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl From<$ty> for DynamicValue {
+                fn from(value: $ty) -> Self {
+                    DynamicValue::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_dynamic_value!(
+    bool => Boolean,
+    i32 => Number,
+    i64 => Number,
+    u32 => Number,
+    u64 => Number,
+    String => String,
+    &str => String,
+    DynamicDate => Date,
+    DynamicGeoPoint => GeoPoint,
+    Vec<DynamicValue> => Array,
+    DynamicDocument => Object
+);
+
+impl From<ChronoDateTime> for DynamicValue {
+    fn from(value: ChronoDateTime) -> Self {
+        DynamicValue::Date(Date::new(value))
+    }
+}
+
+impl TryFrom<f64> for DynamicValue {
+    type Error = NonFiniteNumberError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Number::from_f64(value)
+            .map(DynamicValue::Number)
+            .ok_or(NonFiniteNumberError(value))
+    }
+}
+
+/**
+An error converting an infinite or `NaN` number into a [`DynamicValue`][DynamicValue].
+
+JSON can't represent infinite or `NaN` numbers.
+
+[DynamicValue]: enum.DynamicValue.html
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NonFiniteNumberError(f64);
+
+impl fmt::Display for NonFiniteNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} can't be used as a JSON number", self.0)
+    }
+}
+
+impl Error for NonFiniteNumberError {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{
+        TimeZone,
+        Utc,
+    };
+
+    use std::convert::TryFrom;
+
+    use crate::types::prelude::*;
+
+    fn doc() -> DynamicDocument {
+        let mut doc = DynamicDocument::new();
+        doc.insert("title", "A document");
+        doc.insert("views", 3);
+        doc.insert(
+            "published",
+            Utc.with_ymd_and_hms(2019, 1, 1, 13, 2, 0).unwrap(),
+        );
+        doc.insert("location", DynamicGeoPoint::new(41.12, -71.34));
+        doc.insert(
+            "author",
+            vec![("name", "Ashley")]
+                .into_iter()
+                .collect::<DynamicDocument>(),
+        );
+
+        doc
+    }
+
+    #[test]
+    fn serialize_dynamic_document() {
+        let ser = serde_json::to_string(&doc()).unwrap();
+
+        let expected = r#"{"title":"A document","views":3,"published":"2019-01-01T13:02:00Z","location":{"lat":41.12,"lon":-71.34},"author":{"name":"Ashley"}}"#;
+
+        assert_eq!(expected, ser);
+    }
+
+    #[test]
+    fn dynamic_document_into_value() {
+        let value = serde_json::Value::from(doc());
+
+        assert_eq!(value, serde_json::to_value(doc()).unwrap());
+    }
+
+    #[test]
+    fn dynamic_document_from_value() {
+        let value = json!({
+            "title": "A document",
+            "tags": ["a", "b"],
+            "deleted": null,
+            "author": { "name": "Ashley" }
+        });
+
+        let doc: DynamicDocument = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(4, doc.len());
+        assert_eq!(
+            Some("A document"),
+            doc.get("title").and_then(DynamicValue::as_str)
+        );
+        assert_eq!(
+            2,
+            doc.get("tags")
+                .and_then(DynamicValue::as_array)
+                .unwrap()
+                .len()
+        );
+        assert!(doc.get("deleted").unwrap().is_null());
+        assert_eq!(
+            Some("Ashley"),
+            doc.get("author")
+                .and_then(DynamicValue::as_object)
+                .and_then(|author| author.get("name"))
+                .and_then(DynamicValue::as_str)
+        );
+
+        assert_eq!(value, serde_json::Value::from(doc));
+    }
+
+    #[test]
+    fn deserialize_keeps_field_order() {
+        let doc: DynamicDocument = serde_json::from_str(
+            r#"{"title":"A document","author":{"name":"Ashley","age":30},"views":3}"#,
+        )
+        .unwrap();
+
+        let names: Vec<_> = doc.iter().map(|(name, _)| name).collect();
+        assert_eq!(vec!["title", "author", "views"], names);
+
+        let author: Vec<_> = doc
+            .get("author")
+            .and_then(DynamicValue::as_object)
+            .unwrap()
+            .iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(vec!["name", "age"], author);
+    }
+
+    #[test]
+    fn deserialize_replaces_repeated_fields() {
+        let doc: DynamicDocument = serde_json::from_str(r#"{"a":1,"b":2,"a":3}"#).unwrap();
+
+        let fields: Vec<_> = doc
+            .iter()
+            .map(|(name, value)| (name, value.as_u64().unwrap()))
+            .collect();
+        assert_eq!(vec![("a", 3), ("b", 2)], fields);
+    }
+
+    #[test]
+    fn remove_keeps_lookups_in_sync() {
+        let mut doc = doc();
+
+        doc.remove("views");
+        doc.insert("views", 4);
+
+        assert_eq!(
+            Some("A document"),
+            doc.get("title").and_then(DynamicValue::as_str)
+        );
+        assert_eq!(
+            Some("Ashley"),
+            doc.get("author")
+                .and_then(DynamicValue::as_object)
+                .and_then(|author| author.get("name"))
+                .and_then(DynamicValue::as_str)
+        );
+        assert_eq!(Some(4), doc.get("views").and_then(DynamicValue::as_u64));
+
+        let names: Vec<_> = doc.iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            vec!["title", "published", "location", "author", "views"],
+            names
+        );
+    }
+
+    #[test]
+    fn dynamic_document_must_be_object() {
+        assert!(serde_json::from_str::<DynamicDocument>("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn insert_keeps_field_order() {
+        let mut doc = doc();
+
+        let old = doc.insert("title", "A new title");
+        doc.insert("archived", false);
+
+        assert_eq!(
+            Some("A document"),
+            old.as_ref().and_then(DynamicValue::as_str)
+        );
+
+        let names: Vec<_> = doc.iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            vec![
+                "title",
+                "views",
+                "published",
+                "location",
+                "author",
+                "archived"
+            ],
+            names
+        );
+
+        assert_eq!(Some(3), doc.remove("views").and_then(|v| v.as_u64()));
+        assert!(!doc.contains_key("views"));
+    }
+
+    #[test]
+    fn read_json_values_as_dates() {
+        let value = DynamicValue::from("2019-01-01T13:02:00Z");
+
+        assert_eq!(
+            Utc.with_ymd_and_hms(2019, 1, 1, 13, 2, 0).unwrap(),
+            *value.as_date().unwrap()
+        );
+        assert!(DynamicValue::from("not a date").as_date().is_none());
+    }
+
+    #[test]
+    fn read_json_values_as_geo_points() {
+        let expected = DynamicGeoPoint::new(41.12, -71.34);
+
+        for value in &[
+            json!([-71.34, 41.12]),
+            json!({ "lat": 41.12, "lon": -71.34 }),
+            json!("41.12,-71.34"),
+        ] {
+            assert_eq!(
+                Some(&expected),
+                DynamicValue::from(value.clone()).as_geo_point().as_ref(),
+                "expected {} to be a geo point",
+                value
+            );
+        }
+
+        assert!(DynamicValue::from(json!([1, 2, 3]))
+            .as_geo_point()
+            .is_none());
+    }
+
+    #[test]
+    fn finite_numbers_are_numbers() {
+        assert_eq!(Some(1.5), DynamicValue::try_from(1.5).unwrap().as_f64());
+    }
+
+    #[test]
+    fn non_finite_numbers_are_err() {
+        for &value in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(DynamicValue::try_from(value).is_err());
+        }
+    }
+
+    #[test]
+    fn collect_replaces_repeated_fields() {
+        let doc: DynamicDocument = vec![("a", 1), ("b", 2), ("a", 3)].into_iter().collect();
+
+        let fields: Vec<_> = doc
+            .iter()
+            .map(|(name, value)| (name, value.as_u64().unwrap()))
+            .collect();
+        assert_eq!(vec![("a", 3), ("b", 2)], fields);
+    }
+}
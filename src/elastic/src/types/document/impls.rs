@@ -364,6 +364,41 @@ mod tests {
     #[elastic(crate_root = "crate::types")]
     pub struct NoProps {}
 
+    #[derive(Serialize, ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    #[serde(rename_all = "kebab-case")]
+    pub enum KeywordEnum {
+        OptionA,
+        #[serde(rename = "renamed")]
+        OptionB,
+        #[serde(skip_serializing)]
+        #[allow(dead_code)]
+        Ignored,
+    }
+
+    #[derive(Serialize, ElasticType)]
+    #[elastic(crate_root = "crate::types", mapping = "CustomKeywordMapping")]
+    pub enum CustomKeywordEnum {
+        OptionA,
+    }
+
+    #[derive(Default)]
+    pub struct CustomKeywordMapping;
+    impl KeywordMapping for CustomKeywordMapping {
+        fn boost() -> Option<f32> {
+            Some(1.5)
+        }
+    }
+
+    #[derive(Serialize, ElasticType)]
+    #[elastic(crate_root = "crate::types")]
+    pub struct WithEnums {
+        pub field1: KeywordEnum,
+        pub field2: Option<KeywordEnum>,
+        pub field3: Vec<KeywordEnum>,
+        pub field4: CustomKeywordEnum,
+    }
+
     #[derive(Default, Serialize)]
     pub struct Index {
         mappings: Mappings,
@@ -545,6 +580,46 @@ mod tests {
         assert_eq!(expected, ser);
     }
 
+    #[test]
+    fn serialise_mapping_with_enums() {
+        let ser = serde_json::to_value(&WithEnums::index_mapping()).unwrap();
+
+        let expected = json!({
+            "properties": {
+                "field1": {
+                    "type": "keyword"
+                },
+                "field2": {
+                    "type": "keyword"
+                },
+                "field3": {
+                    "type": "keyword"
+                },
+                "field4": {
+                    "type": "keyword",
+                    "boost": 1.5
+                }
+            }
+        });
+
+        assert_eq!(expected, ser);
+    }
+
+    #[test]
+    fn get_enum_keyword_values() {
+        assert_eq!(&["option-a", "renamed"], KeywordEnum::keyword_values());
+        assert_eq!(&["OptionA"], CustomKeywordEnum::keyword_values());
+    }
+
+    #[test]
+    fn enum_keyword_values_match_serialised_values() {
+        let ser = serde_json::to_value(&[KeywordEnum::OptionA, KeywordEnum::OptionB]).unwrap();
+        assert_eq!(json!(KeywordEnum::keyword_values()), ser);
+
+        let ser = serde_json::to_value(&[CustomKeywordEnum::OptionA]).unwrap();
+        assert_eq!(json!(CustomKeywordEnum::keyword_values()), ser);
+    }
+
     #[test]
     fn serialise_index_mapping() {
         let ser = serde_json::to_value(&Index::default()).unwrap();
@@ -295,8 +295,12 @@ So you can't share `MyTypeMapping` between `MyType` and `MyOtherType`.
 
 pub mod mapping;
 
+mod dynamic;
 mod impls;
-pub use self::impls::*;
+pub use self::{
+    dynamic::*,
+    impls::*,
+};
 
 pub mod prelude {
     /*!
@@ -306,6 +310,12 @@ pub mod prelude {
     */
 
     pub use super::{
+        dynamic::{
+            DynamicDate,
+            DynamicDocument,
+            DynamicGeoPoint,
+            DynamicValue,
+        },
         impls::{
             DocumentType,
             IndexDocumentMapping,
@@ -48,6 +48,7 @@ The following table illustrates the types provided by `elastic`:
  `text`              | `String`                    | `std`     | [`Text<M>`][string-mod]                                  | -
  `boolean`           | `bool`                      | `std`     | [`Boolean<M>`][boolean-mod]                              | -
  `ip`                | `Ipv4Addr`                  | `std`     | [`Ip<M>`][ip-mod]                                        | -
+ `dense_vector`      | -                           | -         | [`DenseVector<M>`][dense-vector-mod]                     | -
  `date`              | `DateTime<UTC>`             | `chrono`  | [`Date<M>`][date-mod]                                    | `DateFormat`
  `geo_point`[^geo]   | `Point`                     | `geo`     | [`GeoPoint<M>`][geopoint-mod]                            | `GeoPointFormat`
  `geo_shape`[^geo]   | -                           | `geojson` | [`GeoShape<M>`][geoshape-mod]                            | -
@@ -178,6 +179,22 @@ enum MyEnum {
 impl KeywordFieldType<DefaultKeywordMapping> for MyEnum {}
 ```
 
+Enums with only unit variants can also derive `ElasticType` to be mapped as a `keyword`.
+The values they're serialised as respect `serde`'s `rename` and `rename_all` attributes:
+
+```
+# #[macro_use] extern crate elastic_derive;
+# #[macro_use] extern crate serde_derive;
+# use elastic::prelude::*;
+#[derive(Serialize, Deserialize, ElasticType)]
+#[serde(rename_all = "lowercase")]
+enum MyEnum {
+    OptionA,
+    OptionB,
+    OptionC
+}
+```
+
 You can then use `MyEnum` on any document type:
 
 ```
@@ -230,6 +247,7 @@ Serialising `MyType`s mapping will produce the following json:
 [string-mod]: string/index.html
 [boolean-mod]: boolean/index.html
 [ip-mod]: ip/index.html
+[dense-vector-mod]: dense_vector/index.html
 [date-mod]: date/index.html
 [geopoint-mod]: geo/point/index.html
 [geoshape-mod]: geo/shape/index.html
@@ -240,6 +258,7 @@ mod private;
 
 pub mod boolean;
 pub mod date;
+pub mod dense_vector;
 pub mod document;
 #[cfg(features = "geo-types")]
 pub mod geo;
@@ -262,6 +281,7 @@ pub mod prelude {
     pub use super::{
         boolean::prelude::*,
         date::prelude::*,
+        dense_vector::prelude::*,
         ip::prelude::*,
         number::prelude::*,
         string::prelude::*,
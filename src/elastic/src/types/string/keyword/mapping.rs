@@ -17,6 +17,33 @@ use std::collections::BTreeMap;
 /** A field that will be mapped as a `keyword`. */
 pub trait KeywordFieldType<TMapping> {}
 
+/**
+A `keyword` field that can only take one of a fixed set of values.
+
+This trait is implemented when deriving `ElasticType` for an enum.
+The values are the names that each variant is serialised as.
+
+# Examples
+
+```
+# #[macro_use] extern crate elastic_derive;
+# #[macro_use] extern crate serde_derive;
+# use elastic::types::prelude::*;
+#[derive(Serialize, Deserialize, ElasticType)]
+#[serde(rename_all = "snake_case")]
+enum MyEnum {
+    OptionA,
+    OptionB,
+}
+
+assert_eq!(&["option_a", "option_b"], MyEnum::keyword_values());
+```
+*/
+pub trait KeywordValues {
+    /** The values this field can be serialised as. */
+    fn keyword_values() -> &'static [&'static str];
+}
+
 /**
 The base requirements for mapping a `string` type.
 
@@ -0,0 +1,100 @@
+use super::DeriveElasticTypeError;
+use crate::{
+    expect_name_value,
+    get_elastic_meta_items,
+    get_ident_from_lit,
+};
+use serde_derive_internals::{
+    self,
+    ast as serde_ast,
+    attr as serde_attr,
+};
+use syn::{
+    DeriveInput,
+    Lit,
+    LitStr,
+};
+
+/**
+Derive `KeywordFieldType` for the given enum.
+
+The input must satisfy the following rules:
+
+- It must be an enum with only unit variants.
+- It must be externally tagged, so variants are serialised as plain strings.
+- A mapping type supplied by `#[elastic(mapping="<ident>")]` must implement `KeywordMapping`.
+
+The values the enum can take are taken from the `serde` names of its variants,
+so `#[serde(rename_all)]` and `#[serde(rename)]` attributes are respected.
+*/
+pub fn expand_derive(
+    crate_root: proc_macro2::TokenStream,
+    input: &DeriveInput,
+) -> Result<Vec<proc_macro2::TokenStream>, DeriveElasticTypeError> {
+    let ctxt = serde_derive_internals::Ctxt::new();
+    let container =
+        serde_ast::Container::from_ast(&ctxt, input, serde_derive_internals::Derive::Serialize);
+
+    // If the `serde` parse fails, don't generate anything and let `serde` panic later
+    if ctxt.check().is_err() {
+        return Ok(vec![]);
+    }
+
+    let container = container.ok_or(DeriveElasticTypeError::InvalidInput)?;
+
+    let variants = match (&container.data, container.attrs.tag()) {
+        (serde_ast::Data::Enum(variants), serde_attr::TagType::External) => Ok(variants),
+        _ => Err(DeriveElasticTypeError::InvalidEnumInput),
+    }?;
+
+    if variants
+        .iter()
+        .any(|variant| !matches!(variant.style, serde_ast::Style::Unit))
+    {
+        return Err(DeriveElasticTypeError::InvalidEnumInput);
+    }
+
+    // Get the names of all variants that can be serialised
+    let values: Vec<Lit> = variants
+        .iter()
+        .filter(|variant| !variant.attrs.skip_serializing())
+        .map(|variant| {
+            Lit::Str(LitStr::new(
+                variant.attrs.name().serialize_name().as_ref(),
+                proc_macro2::Span::call_site(),
+            ))
+        })
+        .collect();
+
+    let mapping = get_mapping_from_attr(input)
+        .map(|mapping| quote!(#mapping))
+        .unwrap_or_else(|| quote!(#crate_root::__derive::DefaultKeywordMapping));
+
+    let ty = &input.ident;
+    let dummy_wrapper = quote::format_ident!("_IMPL_EASTIC_TYPE_FOR_{}", input.ident);
+
+    Ok(vec![quote!(
+        #[allow(non_upper_case_globals, dead_code, unused_variables)]
+        const #dummy_wrapper: () = {
+            impl #crate_root::__derive::KeywordFieldType<#mapping> for #ty { }
+
+            impl #crate_root::__derive::KeywordValues for #ty {
+                fn keyword_values() -> &'static [&'static str] {
+                    &[#(#values),*]
+                }
+            }
+        };
+    )])
+}
+
+// Get the mapping ident supplied by an #[elastic()] attribute
+fn get_mapping_from_attr(item: &DeriveInput) -> Option<syn::Ident> {
+    let val = get_elastic_meta_items(&item.attrs);
+
+    let val = val
+        .iter()
+        .filter_map(|meta| expect_name_value("mapping", meta))
+        .next();
+
+    val.and_then(|v| get_ident_from_lit(v).ok())
+}
@@ -22,6 +22,8 @@ use syn::{
     Visibility,
 };
 
+mod keyword;
+
 struct ElasticDocumentMapping {
     ident: Ident,
     definition: proc_macro2::TokenStream,
@@ -33,7 +35,8 @@ Derive `DocumentType` for the given input.
 
 The input must satisfy the following rules:
 
-- It must be a struct.
+- It must be a struct or an enum.
+  Enums are mapped as a `keyword` field instead of a document.
 - The structs field types must implement `FieldType` (or be ignored).
 - A mapping type supplied by `#[elastic(mapping="<ident>")]` must implement `DocumentMapping`,
 but not `PropertiesMapping`.
@@ -48,6 +51,7 @@ pub fn expand_derive(
             fields: Fields::Named(fields),
             ..
         }) => Ok(&fields.named),
+        Data::Enum(_) => return keyword::expand_derive(crate_root, input),
         _ => Err(DeriveElasticTypeError::InvalidInput),
     }?;
 
@@ -408,6 +412,9 @@ quick_error! {
         InvalidInput {
             display("deriving a document type is only valid for structs")
         }
+        InvalidEnumInput {
+            display("deriving a keyword type is only valid for externally tagged enums with unit variants")
+        }
     }
 }
 
@@ -49,4 +49,10 @@ impl DerivedDocument3 {
     }
 }
 
+#[derive(ElasticType)]
+pub enum DerivedKeyword {
+    Option1,
+    Option2,
+}
+
 fn main() {}
@@ -346,7 +346,7 @@ impl<'a> ReplaceBuilder<'a> {
                     let ident = ident(p);
 
                     syn::Stmt::Semi(Box::new(parse_expr(
-                        quote!(#url_ident.push_str(#ident.as_ref())),
+                        quote!(#url_ident.push_str(&encode_path_segment(#ident.as_ref()))),
                     )))
                 }
             })
@@ -421,9 +421,9 @@ mod tests {
         let expected = quote!({
             let mut url = String::with_capacity(10usize + index.len() + ty.len());
             url.push_str("/");
-            url.push_str(index.as_ref());
+            url.push_str(&encode_path_segment(index.as_ref()));
             url.push_str("/_search/");
-            url.push_str(ty.as_ref());
+            url.push_str(&encode_path_segment(ty.as_ref()));
 
             UrlPath::from(url)
         });
@@ -472,7 +472,7 @@ mod tests {
             IndicesExistsAliasUrlParams::Index(ref index) => {
                 let mut url = String::with_capacity(9usize + index.len());
                 url.push_str("/");
-                url.push_str(index.as_ref());
+                url.push_str(&encode_path_segment(index.as_ref()));
                 url.push_str("/_search");
 
                 UrlPath::from(url)
@@ -482,9 +482,9 @@ mod tests {
             IndicesExistsAliasUrlParams::IndexType(ref index, ref ty) => {
                 let mut url = String::with_capacity(10usize + index.len() + ty.len());
                 url.push_str("/");
-                url.push_str(index.as_ref());
+                url.push_str(&encode_path_segment(index.as_ref()));
                 url.push_str("/");
-                url.push_str(ty.as_ref());
+                url.push_str(&encode_path_segment(ty.as_ref()));
                 url.push_str("/_search");
 
                 UrlPath::from(url)